@@ -2,7 +2,8 @@ use crate::module::*;
 use frame_support::{log, pallet_prelude::*, weights::constants::WEIGHT_REF_TIME_PER_SECOND};
 use orml_traits::{
 	asset_registry::{
-		AssetMetadata, AssetProcessor, FixedConversionRateProvider, Inspect, Mutate, WeightToFeeConverter,
+		AssetMetadata, AssetProcessor, AssetStatus, AssetStatusProvider, FeeCurveProvider, FixedConversionRateProvider,
+		Inspect, Mutate, WeightToFeeConverter,
 	},
 	GetByKey,
 };
@@ -15,7 +16,10 @@ use sp_std::prelude::*;
 use xcm::v3::{prelude::*, Weight as XcmWeight};
 use xcm::VersionedMultiLocation;
 use xcm_builder::TakeRevenue;
-use xcm_executor::{traits::WeightTrader, Assets};
+use xcm_executor::{
+	traits::{TransactAsset, WeightTrader},
+	Assets,
+};
 
 /// Alias for AssetMetadata to improve readability (and to placate clippy)
 pub type DefaultAssetMetadata<T> =
@@ -62,6 +66,19 @@ impl<P: FixedConversionRateProvider> WeightToFeeConverter for FixedRateAssetRegi
 	}
 }
 
+/// A WeightToFeeConverter that prices weight with the fee curve of an asset
+/// from `C` if it has one, and with the fixed conversion rate from `P`
+/// otherwise.
+pub struct CurveRateAssetRegistryTrader<C: FeeCurveProvider, P: FixedConversionRateProvider>(PhantomData<(C, P)>);
+impl<C: FeeCurveProvider, P: FixedConversionRateProvider> WeightToFeeConverter for CurveRateAssetRegistryTrader<C, P> {
+	fn convert_weight_to_fee(location: &MultiLocation, weight: Weight) -> Option<u128> {
+		match C::get_fee_curve(location) {
+			Some(curve) => curve.fee(weight),
+			None => FixedRateAssetRegistryTrader::<P>::convert_weight_to_fee(location, weight),
+		}
+	}
+}
+
 /// Helper struct for the AssetRegistryTrader that stores the data about
 /// bought weight.
 pub struct BoughtWeight {
@@ -75,14 +92,17 @@ pub struct BoughtWeight {
 /// cover the weight. This asset is then "locked in" - later calls to
 /// `buy_weight` in the same xcm message only try the same asset.
 /// This is because only a single asset can be refunded due to the return type
-/// of `refund_weight`. This implementation assumes that `WeightToFeeConverter`
-/// implements a linear function, i.e. fee(x) + fee(y) = fee(x+y).
-pub struct AssetRegistryTrader<W: WeightToFeeConverter, R: TakeRevenue> {
+/// of `refund_weight`. Each purchase is charged the difference between the fee
+/// of all weight bought before and after it, so that non-linear
+/// `WeightToFeeConverter`s price the total weight of the message.
+///
+/// Assets that `S` reports as `Frozen` are never used to buy weight.
+pub struct AssetRegistryTrader<W: WeightToFeeConverter, R: TakeRevenue, S: AssetStatusProvider = ()> {
 	bought_weight: Option<BoughtWeight>,
-	_phantom: PhantomData<(W, R)>,
+	_phantom: PhantomData<(W, R, S)>,
 }
 
-impl<W: WeightToFeeConverter, R: TakeRevenue> WeightTrader for AssetRegistryTrader<W, R> {
+impl<W: WeightToFeeConverter, R: TakeRevenue, S: AssetStatusProvider> WeightTrader for AssetRegistryTrader<W, R, S> {
 	fn new() -> Self {
 		Self {
 			bought_weight: None,
@@ -105,7 +125,24 @@ impl<W: WeightToFeeConverter, R: TakeRevenue> WeightTrader for AssetRegistryTrad
 					continue;
 				}
 
-				if let Some(fee_increase) = W::convert_weight_to_fee(location, weight) {
+				match S::asset_status(location) {
+					Some(AssetStatus::Frozen) => continue,
+					Some(AssetStatus::Deprecated) => log::warn!(
+						target: "xcm::weight",
+						"AssetRegistryTrader::buy_weight paying with deprecated asset: {:?}",
+						location,
+					),
+					_ => {}
+				}
+
+				let (existing_weight, existing_fee) = match self.bought_weight {
+					Some(ref x) => (x.weight, x.amount),
+					None => (Weight::zero(), 0),
+				};
+				let total_weight = existing_weight.checked_add(&weight).ok_or(XcmError::Overflow)?;
+
+				if let Some(total_fee) = W::convert_weight_to_fee(location, total_weight) {
+					let fee_increase = total_fee.saturating_sub(existing_fee);
 					if fee_increase == 0 {
 						// if the fee is set very low it could lead to zero fees, in which case
 						// constructing the fee asset item to subtract from payment would fail.
@@ -114,14 +151,9 @@ impl<W: WeightToFeeConverter, R: TakeRevenue> WeightTrader for AssetRegistryTrad
 					}
 
 					if let Ok(unused) = payment.clone().checked_sub((*asset, fee_increase).into()) {
-						let (existing_weight, existing_fee) = match self.bought_weight {
-							Some(ref x) => (x.weight, x.amount),
-							None => (Weight::zero(), 0),
-						};
-
 						self.bought_weight = Some(BoughtWeight {
 							amount: existing_fee.checked_add(fee_increase).ok_or(XcmError::Overflow)?,
-							weight: existing_weight.checked_add(&weight).ok_or(XcmError::Overflow)?,
+							weight: total_weight,
 							asset_location: *location,
 						});
 						return Ok(unused);
@@ -151,7 +183,7 @@ impl<W: WeightToFeeConverter, R: TakeRevenue> WeightTrader for AssetRegistryTrad
 	}
 }
 
-impl<W: WeightToFeeConverter, R: TakeRevenue> Drop for AssetRegistryTrader<W, R> {
+impl<W: WeightToFeeConverter, R: TakeRevenue, S: AssetStatusProvider> Drop for AssetRegistryTrader<W, R, S> {
 	fn drop(&mut self) {
 		if let Some(ref bought) = self.bought_weight {
 			R::take_revenue((AssetId::Concrete(bought.asset_location), bought.amount).into());
@@ -159,6 +191,77 @@ impl<W: WeightToFeeConverter, R: TakeRevenue> Drop for AssetRegistryTrader<W, R>
 	}
 }
 
+/// A TransactAsset wrapper that refuses deposits of assets that `S` reports
+/// as `Frozen` and logs deposits of `Deprecated` ones. All other operations
+/// are forwarded to `Inner` unchanged.
+pub struct StatusCheckedTransactor<Inner, S>(PhantomData<(Inner, S)>);
+
+impl<Inner: TransactAsset, S: AssetStatusProvider> TransactAsset for StatusCheckedTransactor<Inner, S> {
+	fn can_check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_in(origin, what, context)
+	}
+
+	fn check_in(origin: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
+		Inner::check_in(origin, what, context)
+	}
+
+	fn can_check_out(dest: &MultiLocation, what: &MultiAsset, context: &XcmContext) -> XcmResult {
+		Inner::can_check_out(dest, what, context)
+	}
+
+	fn check_out(dest: &MultiLocation, what: &MultiAsset, context: &XcmContext) {
+		Inner::check_out(dest, what, context)
+	}
+
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, context: &XcmContext) -> XcmResult {
+		if let AssetId::Concrete(ref location) = what.id {
+			match S::asset_status(location) {
+				Some(AssetStatus::Frozen) => {
+					log::warn!(
+						target: "xcm::transactor",
+						"StatusCheckedTransactor::deposit_asset refused frozen asset: {:?}",
+						location,
+					);
+					return Err(XcmError::FailedToTransactAsset("AssetFrozen"));
+				}
+				Some(AssetStatus::Deprecated) => log::warn!(
+					target: "xcm::transactor",
+					"StatusCheckedTransactor::deposit_asset depositing deprecated asset: {:?}",
+					location,
+				),
+				_ => {}
+			}
+		}
+		Inner::deposit_asset(what, who, context)
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		maybe_context: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		Inner::withdraw_asset(what, who, maybe_context)
+	}
+
+	fn internal_transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		Inner::internal_transfer_asset(asset, from, to, context)
+	}
+
+	fn transfer_asset(
+		asset: &MultiAsset,
+		from: &MultiLocation,
+		to: &MultiLocation,
+		context: &XcmContext,
+	) -> Result<Assets, XcmError> {
+		Inner::transfer_asset(asset, from, to, context)
+	}
+}
+
 pub struct ExistentialDeposits<T: Config>(PhantomData<T>);
 
 // Return Existential deposit of an asset. Implementing this trait allows the
@@ -227,3 +330,9 @@ impl<T: Config> Mutate for Pallet<T> {
 		)
 	}
 }
+
+impl<T: Config> AssetStatusProvider for Pallet<T> {
+	fn asset_status(location: &MultiLocation) -> Option<AssetStatus> {
+		Pallet::<T>::location_to_asset_id(location).map(AssetStatuses::<T>::get)
+	}
+}
@@ -8,6 +8,7 @@ use frame_support::{pallet_prelude::*, traits::EnsureOriginWithArg};
 use frame_system::pallet_prelude::*;
 pub use orml_traits::asset_registry::AssetMetadata;
 use orml_traits::asset_registry::AssetProcessor;
+pub use orml_traits::asset_registry::AssetStatus;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, Member},
@@ -94,6 +95,10 @@ pub mod module {
 			asset_id: T::AssetId,
 			metadata: AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
 		},
+		AssetStatusUpdated {
+			asset_id: T::AssetId,
+			status: AssetStatus,
+		},
 	}
 
 	/// The metadata of an asset, indexed by asset id.
@@ -118,6 +123,12 @@ pub mod module {
 	#[pallet::getter(fn last_asset_id)]
 	pub(crate) type LastAssetId<T: Config> = StorageValue<_, T::AssetId, ValueQuery>;
 
+	/// The status of an asset, indexed by asset id. Assets without an entry
+	/// are `Live`.
+	#[pallet::storage]
+	#[pallet::getter(fn asset_status)]
+	pub type AssetStatuses<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, AssetStatus, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub assets: Vec<(T::AssetId, Vec<u8>)>,
@@ -166,6 +177,9 @@ pub mod module {
 			Self::do_register_asset(metadata, asset_id)
 		}
 
+		/// Update the given fields of the metadata of an asset, the fields
+		/// which are `None` are left unchanged. Updating an asset to its
+		/// current metadata is a no-op and emits no event.
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::update_asset())]
 		pub fn update_asset(
@@ -192,6 +206,48 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Set the status of an asset. `Frozen` assets are refused by the
+		/// status aware XCM transactor and trader, `Deprecated` assets are
+		/// still accepted but logged.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_asset_status())]
+		pub fn set_asset_status(origin: OriginFor<T>, asset_id: T::AssetId, status: AssetStatus) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin, &Some(asset_id.clone()))?;
+
+			ensure!(Metadata::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
+
+			if status == AssetStatus::Live {
+				AssetStatuses::<T>::remove(&asset_id);
+			} else {
+				AssetStatuses::<T>::insert(&asset_id, status);
+			}
+
+			Self::deposit_event(Event::<T>::AssetStatusUpdated { asset_id, status });
+
+			Ok(())
+		}
+
+		/// Register several assets like `register_asset`, in order. The
+		/// origin has to be authorized for each asset id, and no asset is
+		/// registered if any of them fails.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::register_assets(assets.len() as u32))]
+		pub fn register_assets(
+			origin: OriginFor<T>,
+			assets: Vec<(
+				Option<T::AssetId>,
+				AssetMetadata<T::Balance, T::CustomMetadata, T::StringLimit>,
+			)>,
+		) -> DispatchResult {
+			for (asset_id, metadata) in assets {
+				T::AuthorityOrigin::ensure_origin(origin.clone(), &asset_id)?;
+
+				Self::do_register_asset(metadata, asset_id)?;
+			}
+
+			Ok(())
+		}
 	}
 }
 
@@ -247,6 +303,7 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		Metadata::<T>::try_mutate(&asset_id, |maybe_metadata| -> DispatchResult {
 			let metadata = maybe_metadata.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+			let old_metadata = metadata.clone();
 			if let Some(decimals) = decimals {
 				metadata.decimals = decimals;
 			}
@@ -272,6 +329,11 @@ impl<T: Config> Pallet<T> {
 				metadata.additional = additional;
 			}
 
+			// nothing to do if the update does not change the metadata
+			if *metadata == old_metadata {
+				return Ok(());
+			}
+
 			Self::deposit_event(Event::<T>::UpdatedAsset {
 				asset_id: asset_id.clone(),
 				metadata: metadata.clone(),
@@ -190,6 +190,7 @@ decl_test_parachain! {
 				location: None,
 				additional: para::CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					fee_curve: None,
 				},
 			})),
 			(
@@ -202,6 +203,7 @@ decl_test_parachain! {
 				location: None,
 				additional: para::CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					fee_curve: None,
 				},
 			}))], 5
 		))),
@@ -250,6 +252,7 @@ pub fn para_ext(para_id: u32, asset_data: Option<(Vec<(u32, Vec<u8>)>, u32)>) ->
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -7,15 +7,16 @@ use cumulus_primitives_core::{ChannelStatus, GetChannelInfo, ParaId};
 use frame_support::traits::{EnsureOrigin, EnsureOriginWithArg};
 use frame_support::{
 	construct_runtime, match_types, ord_parameter_types, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, GetDefault, Nothing},
 	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
-use orml_asset_registry::{AssetRegistryTrader, FixedRateAssetRegistryTrader};
+use orml_asset_registry::{AssetRegistryTrader, CurveRateAssetRegistryTrader, StatusCheckedTransactor};
 use orml_traits::{
+	asset_registry::FeeCurve,
 	location::{AbsoluteReserveProvider, RelativeReserveProvider},
-	parameter_type_with_key, FixedConversionRateProvider, MultiCurrency,
+	parameter_type_with_key, FeeCurveProvider, FixedConversionRateProvider, MultiCurrency,
 };
 use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
 use pallet_xcm::XcmPassthrough;
@@ -95,15 +96,28 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
+	type OnBalanceMovement = ();
 	type ReserveIdentifier = [u8; 8];
 	type MaxReserves = ();
 	type MaxLocks = ConstU32<50>;
 	type DustRemovalWhitelist = Nothing;
+	type DustHandlingMode = GetDefault;
+	type MaxDustQueueLen = ();
+	type MaxDustPerBlock = ();
+	type MaxReapPerBlock = ();
+	type MaxIssuanceSnapshots = ();
+	type MaxSnapshotCurrencies = ();
+	type AccountCreation = GetDefault;
+	type AccountCreationWhitelist = Nothing;
+	type AssetAdminOrigin = EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<50>;
 }
 
 #[derive(scale_info::TypeInfo, Encode, Decode, Clone, Eq, PartialEq, Debug, MaxEncodedLen)]
 pub struct CustomMetadata {
 	pub fee_per_second: u128,
+	pub fee_curve: Option<FeeCurve<ConstU32<4>>>,
 }
 
 const ADMIN_ASSET_TWO: AccountId = AccountId32::new([42u8; 32]);
@@ -182,15 +196,18 @@ pub type XcmOriginToCallOrigin = (
 	XcmPassthrough<RuntimeOrigin>,
 );
 
-pub type LocalAssetTransactor = MultiCurrencyAdapter<
-	Tokens,
-	(),
-	IsNativeConcrete<CurrencyId, CurrencyIdConvert>,
-	AccountId,
-	LocationToAccountId,
-	CurrencyId,
-	CurrencyIdConvert,
-	(),
+pub type LocalAssetTransactor = StatusCheckedTransactor<
+	MultiCurrencyAdapter<
+		Tokens,
+		(),
+		IsNativeConcrete<CurrencyId, CurrencyIdConvert>,
+		AccountId,
+		LocationToAccountId,
+		CurrencyId,
+		CurrencyIdConvert,
+		(),
+	>,
+	AssetRegistry,
 >;
 
 pub type XcmRouter = ParachainXcmRouter<ParachainInfo>;
@@ -215,8 +232,13 @@ impl TakeRevenue for ToTreasury {
 	}
 }
 
-pub type AssetRegistryWeightTrader =
-	(AssetRegistryTrader<FixedRateAssetRegistryTrader<MyFixedConversionRateProvider>, ToTreasury>,);
+pub type AssetRegistryWeightTrader = (
+	AssetRegistryTrader<
+		CurveRateAssetRegistryTrader<MyFeeCurveProvider, MyFixedConversionRateProvider>,
+		ToTreasury,
+		AssetRegistry,
+	>,
+);
 
 pub struct MyFixedConversionRateProvider;
 impl FixedConversionRateProvider for MyFixedConversionRateProvider {
@@ -226,6 +248,16 @@ impl FixedConversionRateProvider for MyFixedConversionRateProvider {
 	}
 }
 
+pub struct MyFeeCurveProvider;
+impl FeeCurveProvider for MyFeeCurveProvider {
+	type MaxSegments = ConstU32<4>;
+
+	fn get_fee_curve(location: &MultiLocation) -> Option<FeeCurve<Self::MaxSegments>> {
+		let metadata = AssetRegistry::fetch_metadata_by_location(location)?;
+		metadata.additional.fee_curve
+	}
+}
+
 parameter_types! {
 	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
 	pub const BaseXcmWeight: Weight = Weight::from_parts(100_000_000, 100_000_000);
@@ -382,6 +414,23 @@ impl orml_xtokens::Config for Runtime {
 	type UniversalLocation = UniversalLocation;
 	type MaxAssetsForTransfer = MaxAssetsForTransfer;
 	type ReserveProvider = RelativeReserveProvider;
+	type MaxXcmEncodedSize = ConstU32<{ 64 * 1024 }>;
+	type MaxTrackedTransfers = ConstU32<0>;
+	type MaxTrackedTransfersPerAccount = ConstU32<0>;
+	type TransferStatusTimeout = ConstU64<10>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type NftClassId = u32;
+	type NftInstanceId = u32;
+	type NftLocationMapper = NoNftLocations;
+	type NativeCurrencyId = ();
+	type NativeAssetLocation = ();
+}
+
+pub struct NoNftLocations;
+impl Convert<(u32, u32), Option<(MultiLocation, AssetInstance)>> for NoNftLocations {
+	fn convert(_: (u32, u32)) -> Option<(MultiLocation, AssetInstance)> {
+		None
+	}
 }
 
 impl orml_xcm::Config for Runtime {
@@ -10,13 +10,17 @@ use frame_support::{
 	StorageHasher,
 };
 use mock::{para::RuntimeCall, *};
-use orml_traits::MultiCurrency;
+use orml_traits::{
+	asset_registry::{FeeCurve, FeeCurveSegment},
+	MultiCurrency,
+};
 use polkadot_parachain::primitives::Sibling;
 
 use sp_runtime::{
 	traits::{AccountIdConversion, BadOrigin, Dispatchable},
 	AccountId32,
 };
+use xcm_executor::traits::TransactAsset;
 use xcm_simulator::TestExt;
 
 type OldMultiLocation = xcm::v2::MultiLocation;
@@ -68,6 +72,7 @@ fn dummy_metadata() -> AssetMetadata<
 		),
 		additional: CustomMetadata {
 			fee_per_second: 1_000_000_000_000,
+			fee_curve: None,
 		},
 	}
 }
@@ -85,6 +90,7 @@ fn genesis_issuance_should_work() {
 			location: None,
 			additional: CustomMetadata {
 				fee_per_second: 1_000_000_000_000,
+				fee_curve: None,
 			},
 		};
 		let metadata2 = AssetMetadata {
@@ -95,6 +101,7 @@ fn genesis_issuance_should_work() {
 			location: None,
 			additional: CustomMetadata {
 				fee_per_second: 1_000_000_000_000,
+				fee_curve: None,
 			},
 		};
 		assert_eq!(AssetRegistry::metadata(4).unwrap(), metadata1);
@@ -370,6 +377,7 @@ fn test_fixed_rate_asset_trader() {
 			None,
 			Some(CustomMetadata {
 				fee_per_second: metadata.additional.fee_per_second * 2,
+				fee_curve: None,
 			}),
 		)
 		.unwrap();
@@ -413,6 +421,209 @@ fn test_fixed_rate_asset_trader() {
 	});
 }
 
+#[test]
+/// tests CurveRateAssetRegistryTrader
+fn test_curve_rate_asset_trader() {
+	TestNet::reset();
+
+	let curve: FeeCurve<ConstU32<4>> = FeeCurve {
+		segments: vec![
+			FeeCurveSegment {
+				from_ref_time: 0,
+				fee_per_second: 1_000_000_000_000,
+			},
+			FeeCurveSegment {
+				from_ref_time: 20,
+				fee_per_second: 3_000_000_000_000,
+			},
+		]
+		.try_into()
+		.unwrap(),
+	};
+	assert_eq!(curve.fee(Weight::from_parts(10, 0)), Some(10));
+	assert_eq!(curve.fee(Weight::from_parts(40, 0)), Some(80));
+	let unsorted: FeeCurve<ConstU32<4>> = FeeCurve {
+		segments: curve
+			.segments
+			.iter()
+			.rev()
+			.cloned()
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap(),
+	};
+	assert_eq!(unsorted.fee(Weight::from_parts(40, 0)), None);
+
+	let metadata = dummy_metadata();
+
+	ParaB::execute_with(|| {
+		let para_b_metadata = AssetMetadata {
+			additional: CustomMetadata {
+				fee_curve: Some(curve.clone()),
+				..metadata.additional.clone()
+			},
+			..metadata.clone()
+		};
+		AssetRegistry::register_asset(RuntimeOrigin::root(), para_b_metadata, None).unwrap();
+	});
+
+	ParaA::execute_with(|| {
+		let para_a_metadata = AssetMetadata {
+			location: Some(MultiLocation::new(0, X1(Junction::from(BoundedVec::try_from(vec![0]).unwrap()))).into()),
+			..metadata.clone()
+		};
+		AssetRegistry::register_asset(RuntimeOrigin::root(), para_a_metadata, None).unwrap();
+
+		assert_ok!(ParaTokens::deposit(CurrencyId::RegisteredAsset(1), &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::RegisteredAsset(1),
+			500,
+			Box::new(
+				MultiLocation::new(
+					1,
+					X2(
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					)
+				)
+				.into()
+			),
+			WeightLimit::Unlimited,
+		));
+	});
+
+	// the weight of 40 bought at the fixed rate would cost 40, the curve charges
+	// three times the rate above 20
+	let expected_fee = 80;
+	ParaB::execute_with(|| {
+		assert_eq!(
+			ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &BOB),
+			500 - expected_fee
+		);
+		assert_eq!(
+			ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &treasury_account()),
+			expected_fee
+		);
+	});
+}
+
+#[test]
+fn frozen_assets_are_refused_by_trader_and_transactor() {
+	TestNet::reset();
+
+	let metadata = dummy_metadata();
+
+	ParaB::execute_with(|| {
+		AssetRegistry::register_asset(RuntimeOrigin::root(), metadata.clone(), None).unwrap();
+
+		// only the asset authority can change the status
+		assert_noop!(
+			AssetRegistry::set_asset_status(RuntimeOrigin::signed(ALICE), 1, AssetStatus::Frozen),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::set_asset_status(RuntimeOrigin::root(), 2, AssetStatus::Frozen),
+			Error::<para::Runtime>::AssetNotFound
+		);
+		assert_ok!(AssetRegistry::set_asset_status(
+			RuntimeOrigin::root(),
+			1,
+			AssetStatus::Frozen
+		));
+		assert_eq!(AssetRegistry::asset_status(1), AssetStatus::Frozen);
+
+		let location: MultiLocation = metadata.location.clone().unwrap().try_into().unwrap();
+		let who = MultiLocation::new(
+			0,
+			X1(Junction::AccountId32 {
+				network: None,
+				id: BOB.into(),
+			}),
+		);
+		assert_eq!(
+			<para::LocalAssetTransactor as TransactAsset>::deposit_asset(
+				&(location, 100).into(),
+				&who,
+				&XcmContext::with_message_id([0; 32]),
+			),
+			Err(XcmError::FailedToTransactAsset("AssetFrozen"))
+		);
+	});
+
+	ParaA::execute_with(|| {
+		let para_a_metadata = AssetMetadata {
+			location: Some(MultiLocation::new(0, X1(Junction::from(BoundedVec::try_from(vec![0]).unwrap()))).into()),
+			..metadata.clone()
+		};
+		AssetRegistry::register_asset(RuntimeOrigin::root(), para_a_metadata, None).unwrap();
+
+		assert_ok!(ParaTokens::deposit(CurrencyId::RegisteredAsset(1), &ALICE, 1_000));
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::RegisteredAsset(1),
+			500,
+			Box::new(
+				MultiLocation::new(
+					1,
+					X2(
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					)
+				)
+				.into()
+			),
+			WeightLimit::Unlimited,
+		));
+	});
+
+	ParaB::execute_with(|| {
+		// the frozen asset can't pay for execution, so nothing arrives
+		assert_eq!(ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &BOB), 0);
+
+		assert_ok!(AssetRegistry::set_asset_status(
+			RuntimeOrigin::root(),
+			1,
+			AssetStatus::Deprecated
+		));
+	});
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::RegisteredAsset(1),
+			500,
+			Box::new(
+				MultiLocation::new(
+					1,
+					X2(
+						Parachain(2),
+						Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						}
+					)
+				)
+				.into()
+			),
+			WeightLimit::Unlimited,
+		));
+	});
+
+	ParaB::execute_with(|| {
+		// deprecated assets are still accepted
+		assert_eq!(ParaTokens::free_balance(CurrencyId::RegisteredAsset(1), &BOB), 460);
+	});
+}
+
 #[test]
 fn test_register_duplicate_location_returns_error() {
 	TestNet::reset();
@@ -479,6 +690,7 @@ fn test_update_metadata_works() {
 			),
 			additional: CustomMetadata {
 				fee_per_second: 2_000_000_000_000,
+				fee_curve: None,
 			},
 		};
 		assert_ok!(AssetRegistry::update_asset(
@@ -503,6 +715,71 @@ fn test_update_metadata_works() {
 	});
 }
 
+#[test]
+fn test_update_metadata_without_changes_is_noop() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata = dummy_metadata();
+		assert_ok!(AssetRegistry::register_asset(
+			RuntimeOrigin::root(),
+			metadata.clone(),
+			None
+		));
+		frame_system::Pallet::<para::Runtime>::reset_events();
+
+		assert_ok!(AssetRegistry::update_asset(
+			RuntimeOrigin::root(),
+			1,
+			Some(metadata.decimals),
+			None,
+			Some(metadata.symbol.clone()),
+			None,
+			Some(metadata.location.clone()),
+			None
+		));
+		assert_eq!(frame_system::Pallet::<para::Runtime>::events(), vec![]);
+		assert_eq!(AssetRegistry::metadata(1).unwrap(), metadata);
+	});
+}
+
+#[test]
+fn test_register_assets_works() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let metadata1 = dummy_metadata();
+		let metadata2 = AssetMetadata {
+			location: None,
+			..dummy_metadata()
+		};
+		// the origin has to be authorized for each asset id
+		assert_noop!(
+			AssetRegistry::register_assets(
+				RuntimeOrigin::root(),
+				vec![(None, metadata1.clone()), (Some(2), metadata2.clone())]
+			),
+			BadOrigin
+		);
+
+		assert_ok!(AssetRegistry::register_assets(
+			RuntimeOrigin::root(),
+			vec![(None, metadata1.clone()), (None, metadata2.clone())]
+		));
+		assert_eq!(AssetRegistry::metadata(1).unwrap(), metadata1);
+		assert_eq!(AssetRegistry::metadata(2).unwrap(), metadata2);
+
+		// no asset is registered if one of them fails
+		assert_noop!(
+			AssetRegistry::register_assets(
+				RuntimeOrigin::root(),
+				vec![(None, metadata2.clone()), (Some(5), metadata2)]
+			),
+			Error::<para::Runtime>::InvalidAssetId
+		);
+	});
+}
+
 #[test]
 fn test_update_metadata_fails_with_unknown_asset() {
 	TestNet::reset();
@@ -759,6 +1036,7 @@ fn test_decode_bounded_vec() {
 				location: None,
 				additional: CustomMetadata {
 					fee_per_second: 1_000_000_000_000,
+					fee_curve: None,
 				},
 			},
 		);
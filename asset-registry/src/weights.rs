@@ -13,6 +13,8 @@ pub trait WeightInfo {
 	fn register_asset() -> Weight;
 	fn update_asset() -> Weight;
 	fn set_asset_location() -> Weight;
+	fn set_asset_status() -> Weight;
+	fn register_assets(n: u32) -> Weight;
 }
 
 /// Default weights.
@@ -26,4 +28,10 @@ impl WeightInfo for () {
 	fn set_asset_location() -> Weight {
 		Weight::zero()
 	}
+	fn set_asset_status() -> Weight {
+		Weight::zero()
+	}
+	fn register_assets(_n: u32) -> Weight {
+		Weight::zero()
+	}
 }
@@ -0,0 +1,17 @@
+//! Runtime API definition for orml auction pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionApi<AuctionId> where
+		AuctionId: Codec,
+	{
+		/// The encoded subject attached to `auction_id` at creation, if any.
+		fn auction_subject(auction_id: AuctionId) -> Option<Vec<u8>>;
+	}
+}
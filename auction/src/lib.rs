@@ -6,7 +6,27 @@
 //! feature.
 //!
 //! The auction logic can be customized by implement and supplying
-//! `AuctionHandler` trait.
+//! `AuctionHandler` trait. Auctions can be created with a category, which is
+//! passed to the handler so one instance can host auctions of different kinds.
+//!
+//! Auctions created with `new_auction_with_deposit` reserve `CreationDeposit`
+//! from the creator, which is refunded when the auction ends or is removed.
+//!
+//! Auctions created with `new_auction_with_hidden_reserve` only commit to the
+//! hash of their reserve price. Bids below the reserve are accepted as usual,
+//! but the auction only concludes with a winner if the reserve is revealed
+//! with `reveal_reserve` before it ends and the final bid meets it. Otherwise
+//! the `ReserveNotMetHandler` of the handler refunds the last bid.
+//!
+//! Auctions created with `new_auction_with_subject` carry an encoded subject,
+//! e.g. the id of the auctioned item, which is stored in the auction info and
+//! emitted in `AuctionCreated`.
+//!
+//! An auction can be restricted to an allowlist of eligible bidders, set by
+//! the runtime with `update_bidder_allowlist` or by the creator of an
+//! auction created with `new_auction_with_deposit` through
+//! `set_bidder_allowlist`. Bids from accounts
+//! not on the allowlist are rejected.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Disable the following two lints since they originate from an external macro (namely decl_storage)
@@ -14,21 +34,40 @@
 #![allow(clippy::unused_unit)]
 
 use codec::MaxEncodedLen;
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ReservableCurrency},
+};
 use frame_system::{ensure_signed, pallet_prelude::*};
-use orml_traits::{Auction, AuctionHandler, AuctionInfo, Change};
+use orml_traits::{
+	Auction, AuctionHandler, AuctionInfo, AuctionWithCategory, AuctionWithSubject, Change, ReserveNotMetHandler,
+};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, MaybeSerializeDeserialize, Member, One, Zero},
+	traits::{AtLeast32BitUnsigned, Bounded, CheckedAdd, Hash, MaybeSerializeDeserialize, Member, One, Zero},
 	DispatchError, DispatchResult,
 };
+use sp_std::vec::Vec;
 
+mod migrations;
 mod mock;
 mod tests;
 mod weights;
 
+pub use migrations::Migration;
 pub use module::*;
 pub use weights::WeightInfo;
 
+type DepositBalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type AuctionSubjectOf<T> = BoundedVec<u8, <T as Config>::MaxSubjectLength>;
+pub type BidderAllowlistOf<T> = BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxBidders>;
+pub type AuctionInfoOf<T> = AuctionInfo<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::Balance,
+	BlockNumberFor<T>,
+	<T as Config>::AuctionCategory,
+	AuctionSubjectOf<T>,
+>;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -57,9 +96,31 @@ pub mod module {
 			+ codec::FullCodec
 			+ codec::MaxEncodedLen;
 
+		/// The category attached to an auction at creation.
+		type AuctionCategory: Parameter + Member + MaxEncodedLen;
+
 		/// The `AuctionHandler` that allow custom bidding logic and handles
-		/// auction result.
-		type Handler: AuctionHandler<Self::AccountId, Self::Balance, BlockNumberFor<Self>, Self::AuctionId>;
+		/// auction result, including the refund of the last bid of auctions
+		/// whose hidden reserve was not met.
+		type Handler: AuctionHandler<Self::AccountId, Self::Balance, BlockNumberFor<Self>, Self::AuctionId, Self::AuctionCategory>
+			+ ReserveNotMetHandler<Self::AccountId, Self::Balance, Self::AuctionId, Self::AuctionCategory>;
+
+		/// The currency the creation deposit is reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from the creator of an auction until it is
+		/// concluded or removed.
+		#[pallet::constant]
+		type CreationDeposit: Get<DepositBalanceOf<Self>>;
+
+		/// The maximum length of the subject attached to an auction.
+		#[pallet::constant]
+		type MaxSubjectLength: Get<u32>;
+
+		/// The maximum number of accounts on the bidder allowlist of an
+		/// auction.
+		#[pallet::constant]
+		type MaxBidders: Get<u32>;
 
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
@@ -72,6 +133,11 @@ pub mod module {
 		BidNotAccepted,
 		InvalidBidPrice,
 		NoAvailableAuctionId,
+		NoHiddenReserve,
+		ReserveAlreadyRevealed,
+		InvalidReserveReveal,
+		BidderNotAllowed,
+		NotAuctionCreator,
 	}
 
 	#[pallet::event]
@@ -83,18 +149,29 @@ pub mod module {
 			bidder: T::AccountId,
 			amount: T::Balance,
 		},
+		/// The hidden reserve of an auction is revealed
+		ReserveRevealed {
+			auction_id: T::AuctionId,
+			reserve: T::Balance,
+		},
+		/// An auction with a hidden reserve ended without a bid meeting it
+		ReserveNotMet { auction_id: T::AuctionId },
+		/// An auction is created
+		AuctionCreated {
+			auction_id: T::AuctionId,
+			subject: Option<AuctionSubjectOf<T>>,
+		},
+		/// The bidder allowlist of an auction is set or cleared
+		BidderAllowlistUpdated {
+			auction_id: T::AuctionId,
+			allowlist: Option<BidderAllowlistOf<T>>,
+		},
 	}
 
 	/// Stores on-going and future auctions. Closed auction are removed.
 	#[pallet::storage]
 	#[pallet::getter(fn auctions)]
-	pub type Auctions<T: Config> = StorageMap<
-		_,
-		Twox64Concat,
-		T::AuctionId,
-		AuctionInfo<T::AccountId, T::Balance, BlockNumberFor<T>>,
-		OptionQuery,
-	>;
+	pub type Auctions<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, AuctionInfoOf<T>, OptionQuery>;
 
 	/// Track the next auction ID.
 	#[pallet::storage]
@@ -107,7 +184,31 @@ pub mod module {
 	pub type AuctionEndTime<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Blake2_128Concat, T::AuctionId, (), OptionQuery>;
 
+	/// The creator and reserved creation deposit of an auction created with
+	/// a deposit. The entry is kept even if the deposit is zero, so the
+	/// creator can still manage the auction.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_deposits)]
+	pub type AuctionDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AuctionId, (T::AccountId, DepositBalanceOf<T>), OptionQuery>;
+
+	/// The committed hash of `(reserve, salt)` of an auction with a hidden
+	/// reserve, and the reserve once revealed.
+	#[pallet::storage]
+	#[pallet::getter(fn hidden_reserves)]
+	pub type HiddenReserves<T: Config> =
+		StorageMap<_, Twox64Concat, T::AuctionId, (T::Hash, Option<T::Balance>), OptionQuery>;
+
+	/// The accounts allowed to bid on an auction. Auctions without an
+	/// allowlist accept bids from anyone.
+	#[pallet::storage]
+	#[pallet::getter(fn bidder_allowlist)]
+	pub type BidderAllowlists<T: Config> = StorageMap<_, Twox64Concat, T::AuctionId, BidderAllowlistOf<T>, OptionQuery>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
@@ -119,7 +220,16 @@ pub mod module {
 		fn on_finalize(now: BlockNumberFor<T>) {
 			for (auction_id, _) in AuctionEndTime::<T>::drain_prefix(now) {
 				if let Some(auction) = Auctions::<T>::take(auction_id) {
-					T::Handler::on_auction_ended(auction_id, auction.bid);
+					Self::refund_deposit(auction_id);
+					BidderAllowlists::<T>::remove(auction_id);
+					let reserve = HiddenReserves::<T>::take(auction_id).map(|(_, revealed)| revealed);
+					match (reserve, auction.bid) {
+						(Some(revealed), Some(bid)) if revealed.map_or(true, |reserve| bid.1 < reserve) => {
+							T::Handler::on_reserve_not_met(auction_id, auction.category, bid);
+							Self::deposit_event(Event::ReserveNotMet { auction_id });
+						}
+						(_, bid) => T::Handler::on_auction_ended_with_category(auction_id, auction.category, bid),
+					}
 				}
 			}
 		}
@@ -136,6 +246,10 @@ pub mod module {
 		pub fn bid(origin: OriginFor<T>, id: T::AuctionId, #[pallet::compact] value: T::Balance) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 
+			if let Some(allowlist) = BidderAllowlists::<T>::get(id) {
+				ensure!(allowlist.contains(&from), Error::<T>::BidderNotAllowed);
+			}
+
 			Auctions::<T>::try_mutate_exists(id, |auction| -> DispatchResult {
 				let auction = auction.as_mut().ok_or(Error::<T>::AuctionNotExist)?;
 
@@ -149,7 +263,13 @@ pub mod module {
 				} else {
 					ensure!(!value.is_zero(), Error::<T>::InvalidBidPrice);
 				}
-				let bid_result = T::Handler::on_new_bid(block_number, id, (from.clone(), value), auction.bid.clone());
+				let bid_result = T::Handler::on_new_bid_with_category(
+					block_number,
+					id,
+					auction.category.clone(),
+					(from.clone(), value),
+					auction.bid.clone(),
+				);
 
 				ensure!(bid_result.accept_bid, Error::<T>::BidNotAccepted);
 				match bid_result.auction_end_change {
@@ -176,6 +296,129 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Reveal the hidden reserve of an auction.
+		///
+		/// The reserve and salt must hash to the commitment given at
+		/// creation. The reserve must be revealed before the auction ends
+		/// for it to conclude with a winner.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::reveal_reserve())]
+		pub fn reveal_reserve(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			reserve: T::Balance,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			HiddenReserves::<T>::try_mutate(id, |hidden| -> DispatchResult {
+				let (commitment, revealed) = hidden.as_mut().ok_or(Error::<T>::NoHiddenReserve)?;
+				ensure!(revealed.is_none(), Error::<T>::ReserveAlreadyRevealed);
+				ensure!(
+					T::Hashing::hash_of(&(reserve, salt)) == *commitment,
+					Error::<T>::InvalidReserveReveal
+				);
+				*revealed = Some(reserve);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ReserveRevealed {
+				auction_id: id,
+				reserve,
+			});
+			Ok(())
+		}
+
+		/// Set or clear the bidder allowlist of an auction.
+		///
+		/// The dispatch origin for this call must be `Signed` by the account
+		/// that created the auction with `new_auction_with_deposit`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_bidder_allowlist())]
+		pub fn set_bidder_allowlist(
+			origin: OriginFor<T>,
+			id: T::AuctionId,
+			allowlist: Option<BidderAllowlistOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (creator, _) = Self::auction_deposits(id).ok_or(Error::<T>::NotAuctionCreator)?;
+			ensure!(who == creator, Error::<T>::NotAuctionCreator);
+
+			Self::update_bidder_allowlist(id, allowlist)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn refund_deposit(id: T::AuctionId) {
+		if let Some((creator, deposit)) = AuctionDeposits::<T>::take(id) {
+			if !deposit.is_zero() {
+				T::Currency::unreserve(&creator, deposit);
+			}
+		}
+	}
+
+	/// Create an auction whose reserve price is hidden behind
+	/// `reserve_hash`, the hash of `(reserve, salt)`.
+	pub fn new_auction_with_hidden_reserve(
+		start: BlockNumberFor<T>,
+		end: Option<BlockNumberFor<T>>,
+		category: Option<T::AuctionCategory>,
+		reserve_hash: T::Hash,
+	) -> sp_std::result::Result<T::AuctionId, DispatchError> {
+		let auction_id = Self::new_auction_with_category(start, end, category)?;
+		HiddenReserves::<T>::insert(auction_id, (reserve_hash, None::<T::Balance>));
+		Ok(auction_id)
+	}
+
+	/// Set the bidder allowlist of auction `id`, or clear it to accept bids
+	/// from anyone.
+	pub fn update_bidder_allowlist(id: T::AuctionId, allowlist: Option<BidderAllowlistOf<T>>) -> DispatchResult {
+		ensure!(Auctions::<T>::contains_key(id), Error::<T>::AuctionNotExist);
+		BidderAllowlists::<T>::set(id, allowlist.clone());
+
+		Self::deposit_event(Event::BidderAllowlistUpdated {
+			auction_id: id,
+			allowlist,
+		});
+		Ok(())
+	}
+
+	/// The encoded subject of auction `id`, if any.
+	pub fn auction_subject(id: T::AuctionId) -> Option<Vec<u8>> {
+		Self::auctions(id)
+			.and_then(|auction| auction.subject)
+			.map(|subject| subject.into_inner())
+	}
+
+	fn do_new_auction(
+		start: BlockNumberFor<T>,
+		end: Option<BlockNumberFor<T>>,
+		category: Option<T::AuctionCategory>,
+		subject: Option<AuctionSubjectOf<T>>,
+	) -> sp_std::result::Result<T::AuctionId, DispatchError> {
+		let auction = AuctionInfo {
+			bid: None,
+			start,
+			end,
+			category,
+			subject: subject.clone(),
+		};
+		let auction_id = <AuctionsIndex<T>>::try_mutate(|n| -> sp_std::result::Result<T::AuctionId, DispatchError> {
+			let id = *n;
+			*n = n.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableAuctionId)?;
+			Ok(id)
+		})?;
+		Auctions::<T>::insert(auction_id, auction);
+		if let Some(end_block) = end {
+			AuctionEndTime::<T>::insert(end_block, auction_id, ());
+		}
+
+		Self::deposit_event(Event::AuctionCreated { auction_id, subject });
+		Ok(auction_id)
 	}
 }
 
@@ -183,49 +426,98 @@ impl<T: Config> Auction<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
 	type AuctionId = T::AuctionId;
 	type Balance = T::Balance;
 
+	/// The auction info of `id`, only telling whether it has a category and a
+	/// subject. Use `auctions` for the full auction info.
 	fn auction_info(id: Self::AuctionId) -> Option<AuctionInfo<T::AccountId, Self::Balance, BlockNumberFor<T>>> {
-		Self::auctions(id)
+		Self::auctions(id).map(|auction| AuctionInfo {
+			bid: auction.bid,
+			start: auction.start,
+			end: auction.end,
+			category: auction.category.map(|_| ()),
+			subject: auction.subject.map(|_| ()),
+		})
 	}
 
+	/// Update the bid, start and end of auction `id` with `info`, keeping its
+	/// category and subject.
 	fn update_auction(
 		id: Self::AuctionId,
 		info: AuctionInfo<T::AccountId, Self::Balance, BlockNumberFor<T>>,
 	) -> DispatchResult {
-		let auction = Auctions::<T>::get(id).ok_or(Error::<T>::AuctionNotExist)?;
-		if let Some(old_end) = auction.end {
-			AuctionEndTime::<T>::remove(old_end, id);
-		}
-		if let Some(new_end) = info.end {
-			AuctionEndTime::<T>::insert(new_end, id, ());
-		}
-		Auctions::<T>::insert(id, info);
-		Ok(())
+		Auctions::<T>::try_mutate(id, |maybe_auction| -> DispatchResult {
+			let auction = maybe_auction.as_mut().ok_or(Error::<T>::AuctionNotExist)?;
+			if let Some(old_end) = auction.end {
+				AuctionEndTime::<T>::remove(old_end, id);
+			}
+			if let Some(new_end) = info.end {
+				AuctionEndTime::<T>::insert(new_end, id, ());
+			}
+			auction.bid = info.bid;
+			auction.start = info.start;
+			auction.end = info.end;
+			Ok(())
+		})
 	}
 
 	fn new_auction(
 		start: BlockNumberFor<T>,
 		end: Option<BlockNumberFor<T>>,
 	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
-		let auction = AuctionInfo { bid: None, start, end };
-		let auction_id =
-			<AuctionsIndex<T>>::try_mutate(|n| -> sp_std::result::Result<Self::AuctionId, DispatchError> {
-				let id = *n;
-				*n = n.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableAuctionId)?;
-				Ok(id)
-			})?;
-		Auctions::<T>::insert(auction_id, auction);
-		if let Some(end_block) = end {
-			AuctionEndTime::<T>::insert(end_block, auction_id, ());
-		}
-
-		Ok(auction_id)
+		Self::do_new_auction(start, end, None, None)
 	}
 
 	fn remove_auction(id: Self::AuctionId) {
 		if let Some(auction) = Auctions::<T>::take(id) {
+			Self::refund_deposit(id);
+			HiddenReserves::<T>::remove(id);
+			BidderAllowlists::<T>::remove(id);
 			if let Some(end_block) = auction.end {
 				AuctionEndTime::<T>::remove(end_block, id);
 			}
 		}
 	}
 }
+
+impl<T: Config> AuctionWithCategory<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+	type AuctionCategory = T::AuctionCategory;
+
+	fn new_auction_with_category(
+		start: BlockNumberFor<T>,
+		end: Option<BlockNumberFor<T>>,
+		category: Option<Self::AuctionCategory>,
+	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
+		Self::do_new_auction(start, end, category, None)
+	}
+
+	fn new_auction_with_deposit(
+		creator: &T::AccountId,
+		start: BlockNumberFor<T>,
+		end: Option<BlockNumberFor<T>>,
+		category: Option<Self::AuctionCategory>,
+	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
+		frame_support::storage::with_storage_layer(|| {
+			let auction_id = Self::new_auction_with_category(start, end, category)?;
+
+			let deposit = T::CreationDeposit::get();
+			if !deposit.is_zero() {
+				T::Currency::reserve(creator, deposit)?;
+			}
+			AuctionDeposits::<T>::insert(auction_id, (creator.clone(), deposit));
+
+			Ok(auction_id)
+		})
+	}
+}
+
+impl<T: Config> AuctionWithSubject<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+	type AuctionSubject = AuctionSubjectOf<T>;
+
+	fn new_auction_with_subject(
+		start: BlockNumberFor<T>,
+		end: Option<BlockNumberFor<T>>,
+		category: Option<Self::AuctionCategory>,
+		subject: Self::AuctionSubject,
+	) -> sp_std::result::Result<Self::AuctionId, DispatchError> {
+		Self::do_new_auction(start, end, category, Some(subject))
+	}
+}
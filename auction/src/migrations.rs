@@ -0,0 +1,86 @@
+use crate::{AuctionInfoOf, Auctions, Config, Pallet};
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// Migrates the stored auctions to the current layout.
+///
+/// Each step translates the auctions of its version straight into the
+/// current layout, so at most one of them runs.
+pub struct Migration<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 1 {
+			weight.saturating_accrue(v1::migrate::<T>());
+		} else if onchain_version < 2 {
+			weight.saturating_accrue(v2::migrate::<T>());
+		}
+		if onchain_version < Pallet::<T>::current_storage_version() {
+			Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+		weight
+	}
+}
+
+mod v1 {
+	use super::*;
+
+	/// The auction info before auctions had a category.
+	#[derive(Decode)]
+	struct OldAuctionInfo<AccountId, Balance, BlockNumber> {
+		bid: Option<(AccountId, Balance)>,
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+	}
+
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		Auctions::<T>::translate::<OldAuctionInfo<T::AccountId, T::Balance, BlockNumberFor<T>>, _>(|_, old| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			Some(AuctionInfoOf::<T> {
+				bid: old.bid,
+				start: old.start,
+				end: old.end,
+				category: None,
+				subject: None,
+			})
+		});
+
+		weight
+	}
+}
+
+mod v2 {
+	use super::*;
+
+	/// The auction info before auctions had a subject.
+	#[derive(Decode)]
+	struct OldAuctionInfo<AccountId, Balance, BlockNumber, AuctionCategory> {
+		bid: Option<(AccountId, Balance)>,
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+		category: Option<AuctionCategory>,
+	}
+
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		Auctions::<T>::translate::<OldAuctionInfo<T::AccountId, T::Balance, BlockNumberFor<T>, T::AuctionCategory>, _>(
+			|_, old| {
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				Some(AuctionInfoOf::<T> {
+					bid: old.bid,
+					start: old.start,
+					end: old.end,
+					category: old.category,
+					subject: None,
+				})
+			},
+		);
+
+		weight
+	}
+}
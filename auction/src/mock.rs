@@ -4,8 +4,8 @@
 
 use super::*;
 use frame_support::{
-	construct_runtime,
-	traits::{ConstU64, Everything},
+	construct_runtime, parameter_types,
+	traits::{ConstU32, ConstU64, Everything},
 };
 use orml_traits::OnNewBidResult;
 use sp_core::H256;
@@ -17,6 +17,7 @@ pub type AccountId = u128;
 pub type Balance = u64;
 pub type BlockNumber = u64;
 pub type AuctionId = u64;
+pub type AuctionCategory = u8;
 
 impl frame_system::Config for Runtime {
 	type RuntimeOrigin = RuntimeOrigin;
@@ -33,7 +34,7 @@ impl frame_system::Config for Runtime {
 	type BlockLength = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type DbWeight = ();
@@ -44,16 +45,57 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = ConstU32<16>;
 }
 
+impl pallet_balances::Config for Runtime {
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type FreezeIdentifier = [u8; 8];
+	type MaxHolds = ();
+	type MaxFreezes = ();
+}
+
+parameter_types! {
+	pub static EndedAuctions: Vec<(AuctionId, Option<AuctionCategory>)> = vec![];
+	pub static ReserveNotMetAuctions: Vec<(AuctionId, (AccountId, Balance))> = vec![];
+	pub static CreationDeposit: Balance = CREATION_DEPOSIT;
+}
+
 pub struct Handler;
 
-impl AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for Handler {
+impl AuctionHandler<AccountId, Balance, BlockNumber, AuctionId, AuctionCategory> for Handler {
 	fn on_new_bid(
+		now: BlockNumber,
+		id: AuctionId,
+		new_bid: (AccountId, Balance),
+		last_bid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumber> {
+		Self::on_new_bid_with_category(now, id, None, new_bid, last_bid)
+	}
+
+	fn on_auction_ended(id: AuctionId, winner: Option<(AccountId, Balance)>) {
+		Self::on_auction_ended_with_category(id, None, winner)
+	}
+
+	fn on_new_bid_with_category(
 		now: BlockNumber,
 		_id: AuctionId,
+		category: Option<AuctionCategory>,
 		new_bid: (AccountId, Balance),
 		_last_bid: Option<(AccountId, Balance)>,
 	) -> OnNewBidResult<BlockNumber> {
-		if new_bid.0 == ALICE {
+		if category == Some(OPEN_SALE) {
+			OnNewBidResult {
+				accept_bid: true,
+				auction_end_change: Change::NoChange,
+			}
+		} else if new_bid.0 == ALICE {
 			OnNewBidResult {
 				accept_bid: true,
 				auction_end_change: Change::NewValue(Some(now + BID_EXTEND_BLOCK)),
@@ -66,14 +108,31 @@ impl AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> for Handler {
 		}
 	}
 
-	fn on_auction_ended(_id: AuctionId, _winner: Option<(AccountId, Balance)>) {}
+	fn on_auction_ended_with_category(
+		id: AuctionId,
+		category: Option<AuctionCategory>,
+		_winner: Option<(AccountId, Balance)>,
+	) {
+		EndedAuctions::mutate(|ended| ended.push((id, category)));
+	}
+}
+
+impl ReserveNotMetHandler<AccountId, Balance, AuctionId, AuctionCategory> for Handler {
+	fn on_reserve_not_met(id: AuctionId, _category: Option<AuctionCategory>, last_bid: (AccountId, Balance)) {
+		ReserveNotMetAuctions::mutate(|not_met| not_met.push((id, last_bid)));
+	}
 }
 
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Balance = Balance;
 	type AuctionId = AuctionId;
+	type AuctionCategory = AuctionCategory;
 	type Handler = Handler;
+	type Currency = PalletBalances;
+	type CreationDeposit = CreationDeposit;
+	type MaxSubjectLength = ConstU32<32>;
+	type MaxBidders = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -82,6 +141,7 @@ type Block = frame_system::mocking::MockBlock<Runtime>;
 construct_runtime!(
 	pub enum Runtime {
 		System: frame_system,
+		PalletBalances: pallet_balances,
 		AuctionModule: auction,
 	}
 );
@@ -89,6 +149,9 @@ construct_runtime!(
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const BID_EXTEND_BLOCK: BlockNumber = 10;
+pub const CREATION_DEPOSIT: Balance = 10;
+/// Auctions of this category accept bids from anyone without extending.
+pub const OPEN_SALE: AuctionCategory = 1;
 
 pub struct ExtBuilder;
 
@@ -100,10 +163,16 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::<Runtime>::default()
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
 			.build_storage()
 			.unwrap();
 
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, 100), (BOB, 5)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -5,6 +5,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use sp_runtime::traits::BlakeTwo256;
 
 #[test]
 fn new_auction_should_work() {
@@ -23,7 +24,9 @@ fn update_auction_should_work() {
 				AuctionInfo {
 					bid: Some((ALICE, 100)),
 					start: 10,
-					end: Some(100)
+					end: Some(100),
+					category: None,
+					subject: None
 				}
 			),
 			Error::<Runtime>::AuctionNotExist,
@@ -33,7 +36,9 @@ fn update_auction_should_work() {
 			AuctionInfo {
 				bid: Some((ALICE, 100)),
 				start: 10,
-				end: Some(100)
+				end: Some(100),
+				category: None,
+				subject: None
 			}
 		));
 	});
@@ -48,7 +53,9 @@ fn auction_info_should_work() {
 			Some(AuctionInfo {
 				bid: None,
 				start: 10,
-				end: Some(100)
+				end: Some(100),
+				category: None,
+				subject: None
 			})
 		);
 	});
@@ -64,7 +71,9 @@ fn bid_should_work() {
 			Some(AuctionInfo {
 				bid: None,
 				start: 0,
-				end: Some(5)
+				end: Some(5),
+				category: None,
+				subject: None
 			})
 		);
 		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(ALICE), 0, 20));
@@ -78,7 +87,9 @@ fn bid_should_work() {
 			Some(AuctionInfo {
 				bid: Some((ALICE, 20)),
 				start: 0,
-				end: Some(11)
+				end: Some(11),
+				category: None,
+				subject: None
 			})
 		);
 	});
@@ -157,3 +168,321 @@ fn cannot_add_new_auction_when_no_available_id() {
 		);
 	});
 }
+
+#[test]
+fn auction_category_is_passed_to_handler() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionModule::new_auction(0, Some(5)), 0);
+		assert_ok!(AuctionModule::new_auction_with_category(0, Some(5), Some(OPEN_SALE)), 1);
+		assert_eq!(
+			AuctionModule::auctions(1),
+			Some(AuctionInfo {
+				bid: None,
+				start: 0,
+				end: Some(5),
+				category: Some(OPEN_SALE),
+				subject: None
+			})
+		);
+		assert_eq!(
+			AuctionModule::auction_info(1),
+			Some(AuctionInfo {
+				bid: None,
+				start: 0,
+				end: Some(5),
+				category: Some(()),
+				subject: None
+			})
+		);
+
+		// updating the auction keeps its category
+		assert_ok!(AuctionModule::update_auction(
+			1,
+			AuctionInfo {
+				bid: None,
+				start: 0,
+				end: Some(6),
+				category: None,
+				subject: None
+			}
+		));
+		assert_eq!(AuctionModule::auctions(1).unwrap().category, Some(OPEN_SALE));
+		assert_ok!(AuctionModule::update_auction(
+			1,
+			AuctionInfo {
+				bid: None,
+				start: 0,
+				end: Some(5),
+				category: None,
+				subject: None
+			}
+		));
+
+		// only the open sale accepts bids from BOB, without extending the end
+		assert_noop!(
+			AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 20),
+			Error::<Runtime>::BidNotAccepted,
+		);
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 1, 20));
+		assert_eq!(AuctionModule::auction_info(1).unwrap().end, Some(5));
+
+		AuctionModule::on_finalize(5);
+		assert_eq!(EndedAuctions::get(), vec![(0, None), (1, Some(OPEN_SALE))]);
+	});
+}
+
+#[test]
+fn creation_deposit_is_refunded_when_auction_concludes() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionModule::new_auction_with_deposit(&BOB, 0, Some(5), None),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(AuctionModule::new_auction_with_deposit(&ALICE, 0, Some(5), None), 0);
+		assert_ok!(AuctionModule::new_auction_with_deposit(&ALICE, 0, Some(100), None), 1);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 2 * CREATION_DEPOSIT);
+		assert_eq!(AuctionModule::auction_deposits(0), Some((ALICE, CREATION_DEPOSIT)));
+
+		// refunded when the auction ends
+		AuctionModule::on_finalize(5);
+		assert_eq!(AuctionModule::auction_deposits(0), None);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), CREATION_DEPOSIT);
+
+		// refunded when the auction is removed
+		AuctionModule::remove_auction(1);
+		assert_eq!(AuctionModule::auction_deposits(1), None);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+
+		// auctions created without a creator take no deposit
+		assert_ok!(AuctionModule::new_auction(0, Some(10)), 2);
+		assert_eq!(AuctionModule::auction_deposits(2), None);
+	});
+}
+
+#[test]
+fn hidden_reserve_only_concludes_once_met() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let salt = [7u8; 32];
+		let reserve_hash = BlakeTwo256::hash_of(&(50u64, salt));
+		assert_ok!(
+			AuctionModule::new_auction_with_hidden_reserve(0, Some(5), Some(OPEN_SALE), reserve_hash),
+			0
+		);
+		assert_ok!(
+			AuctionModule::new_auction_with_hidden_reserve(0, Some(5), Some(OPEN_SALE), reserve_hash),
+			1
+		);
+		assert_ok!(
+			AuctionModule::new_auction_with_hidden_reserve(0, Some(5), Some(OPEN_SALE), reserve_hash),
+			2
+		);
+
+		// bids below the reserve are accepted
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 20));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 1, 60));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 2, 60));
+
+		assert_noop!(
+			AuctionModule::reveal_reserve(RuntimeOrigin::signed(BOB), 0, 40, salt),
+			Error::<Runtime>::InvalidReserveReveal
+		);
+		assert_ok!(AuctionModule::reveal_reserve(RuntimeOrigin::signed(BOB), 0, 50, salt));
+		System::assert_last_event(RuntimeEvent::AuctionModule(crate::Event::ReserveRevealed {
+			auction_id: 0,
+			reserve: 50,
+		}));
+		assert_noop!(
+			AuctionModule::reveal_reserve(RuntimeOrigin::signed(BOB), 0, 50, salt),
+			Error::<Runtime>::ReserveAlreadyRevealed
+		);
+		assert_ok!(AuctionModule::reveal_reserve(RuntimeOrigin::signed(ALICE), 1, 50, salt));
+		assert_eq!(AuctionModule::hidden_reserves(1), Some((reserve_hash, Some(50))));
+
+		// auction 0 bid is below the reserve, auction 1 meets it and auction 2 was
+		// never revealed
+		AuctionModule::on_finalize(5);
+		assert_eq!(EndedAuctions::get(), vec![(1, Some(OPEN_SALE))]);
+		assert_eq!(ReserveNotMetAuctions::get(), vec![(0, (BOB, 20)), (2, (BOB, 60))]);
+		System::assert_has_event(RuntimeEvent::AuctionModule(crate::Event::ReserveNotMet {
+			auction_id: 2,
+		}));
+		assert_eq!(AuctionModule::hidden_reserves(0), None);
+
+		assert_noop!(
+			AuctionModule::reveal_reserve(RuntimeOrigin::signed(BOB), 2, 50, salt),
+			Error::<Runtime>::NoHiddenReserve
+		);
+	});
+}
+
+#[test]
+fn auction_subject_is_stored_and_emitted() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let subject: AuctionSubjectOf<Runtime> = b"nft:1".to_vec().try_into().unwrap();
+		assert_ok!(
+			AuctionModule::new_auction_with_subject(0, Some(5), None, subject.clone()),
+			0
+		);
+		System::assert_last_event(RuntimeEvent::AuctionModule(crate::Event::AuctionCreated {
+			auction_id: 0,
+			subject: Some(subject.clone()),
+		}));
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(AuctionInfo {
+				bid: None,
+				start: 0,
+				end: Some(5),
+				category: None,
+				subject: Some(subject)
+			})
+		);
+		assert_eq!(AuctionModule::auction_subject(0), Some(b"nft:1".to_vec()));
+
+		assert_ok!(AuctionModule::new_auction(0, Some(5)), 1);
+		System::assert_last_event(RuntimeEvent::AuctionModule(crate::Event::AuctionCreated {
+			auction_id: 1,
+			subject: None,
+		}));
+		assert_eq!(AuctionModule::auction_subject(1), None);
+	});
+}
+
+#[test]
+fn bidder_allowlist_restricts_bids() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(
+			AuctionModule::new_auction_with_deposit(&ALICE, 0, Some(10), Some(OPEN_SALE)),
+			0
+		);
+		let allowlist: BidderAllowlistOf<Runtime> = vec![ALICE].try_into().unwrap();
+
+		assert_noop!(
+			AuctionModule::set_bidder_allowlist(RuntimeOrigin::signed(BOB), 0, Some(allowlist.clone())),
+			Error::<Runtime>::NotAuctionCreator
+		);
+		assert_ok!(AuctionModule::set_bidder_allowlist(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			Some(allowlist.clone())
+		));
+		System::assert_last_event(RuntimeEvent::AuctionModule(crate::Event::BidderAllowlistUpdated {
+			auction_id: 0,
+			allowlist: Some(allowlist.clone()),
+		}));
+		assert_eq!(AuctionModule::bidder_allowlist(0), Some(allowlist));
+
+		assert_noop!(
+			AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 10),
+			Error::<Runtime>::BidderNotAllowed
+		);
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(ALICE), 0, 10));
+
+		assert_ok!(AuctionModule::update_bidder_allowlist(0, None));
+		assert_ok!(AuctionModule::bid(RuntimeOrigin::signed(BOB), 0, 20));
+
+		// auctions without a creation deposit can only be restricted by the runtime
+		assert_ok!(AuctionModule::new_auction(0, Some(10)), 1);
+		assert_noop!(
+			AuctionModule::set_bidder_allowlist(RuntimeOrigin::signed(ALICE), 1, None),
+			Error::<Runtime>::NotAuctionCreator
+		);
+		assert_noop!(
+			AuctionModule::update_bidder_allowlist(2, None),
+			Error::<Runtime>::AuctionNotExist
+		);
+
+		assert_ok!(AuctionModule::update_bidder_allowlist(
+			1,
+			Some(vec![BOB].try_into().unwrap())
+		));
+		AuctionModule::on_finalize(10);
+		assert_eq!(AuctionModule::bidder_allowlist(0), None);
+		assert_eq!(AuctionModule::bidder_allowlist(1), None);
+	});
+}
+
+#[test]
+fn bidder_allowlist_works_without_creation_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		CreationDeposit::set(0);
+		assert_ok!(
+			AuctionModule::new_auction_with_deposit(&ALICE, 0, Some(10), Some(OPEN_SALE)),
+			0
+		);
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(AuctionModule::auction_deposits(0), Some((ALICE, 0)));
+
+		let allowlist: BidderAllowlistOf<Runtime> = vec![ALICE].try_into().unwrap();
+		assert_noop!(
+			AuctionModule::set_bidder_allowlist(RuntimeOrigin::signed(BOB), 0, Some(allowlist.clone())),
+			Error::<Runtime>::NotAuctionCreator
+		);
+		assert_ok!(AuctionModule::set_bidder_allowlist(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			Some(allowlist.clone())
+		));
+		assert_eq!(AuctionModule::bidder_allowlist(0), Some(allowlist));
+
+		AuctionModule::on_finalize(10);
+		assert_eq!(AuctionModule::auction_deposits(0), None);
+		assert_eq!(AuctionModule::bidder_allowlist(0), None);
+	});
+}
+
+#[test]
+fn migration_works() {
+	use codec::Encode;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::default().build().execute_with(|| {
+		let key = Auctions::<Runtime>::hashed_key_for(0);
+
+		// an auction stored before auctions had a category
+		StorageVersion::new(0).put::<AuctionModule>();
+		frame_support::storage::unhashed::put_raw(&key, &(Some((ALICE, 100u64)), 10u64, Some(100u64)).encode());
+		crate::Migration::<Runtime>::on_runtime_upgrade();
+		assert_eq!(
+			AuctionModule::on_chain_storage_version(),
+			AuctionModule::current_storage_version()
+		);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(AuctionInfo {
+				bid: Some((ALICE, 100)),
+				start: 10,
+				end: Some(100),
+				category: None,
+				subject: None,
+			})
+		);
+
+		// an auction stored before auctions had a subject
+		StorageVersion::new(1).put::<AuctionModule>();
+		frame_support::storage::unhashed::put_raw(
+			&key,
+			&(Some((ALICE, 100u64)), 10u64, Some(100u64), Some(3u8)).encode(),
+		);
+		crate::Migration::<Runtime>::on_runtime_upgrade();
+		assert_eq!(
+			AuctionModule::on_chain_storage_version(),
+			AuctionModule::current_storage_version()
+		);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(AuctionInfo {
+				bid: Some((ALICE, 100)),
+				start: 10,
+				end: Some(100),
+				category: Some(3),
+				subject: None,
+			})
+		);
+	});
+}
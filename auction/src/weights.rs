@@ -32,13 +32,15 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn bid_collateral_auction() -> Weight;
 	fn on_finalize(c: u32, ) -> Weight;
+	fn reveal_reserve() -> Weight;
+	fn set_bidder_allowlist() -> Weight;
 }
 
 /// Default weights.
 impl WeightInfo for () {
 	fn bid_collateral_auction() -> Weight {
 		Weight::from_parts(108_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
 			.saturating_add(RocksDbWeight::get().writes(9 as u64))
 	}
 	fn on_finalize(c: u32, ) -> Weight {
@@ -46,8 +48,18 @@ impl WeightInfo for () {
 			// Standard Error: 13_000
 			.saturating_add(Weight::from_parts(57_962_000, 0).saturating_mul(c as u64))
 			.saturating_add(RocksDbWeight::get().reads(10 as u64))
-			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(c as u64)))
 			.saturating_add(RocksDbWeight::get().writes(7 as u64))
-			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((7 as u64).saturating_mul(c as u64)))
+	}
+	fn reveal_reserve() -> Weight {
+		Weight::from_parts(21_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_bidder_allowlist() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 }
@@ -0,0 +1,29 @@
+//! Runtime API definition for authority module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuthorityApi<AsOriginId, Call, BlockNumber, ScheduledTask> where
+		AsOriginId: Codec,
+		Call: Codec,
+		BlockNumber: Codec,
+		ScheduledTask: Codec,
+	{
+		/// Whether the origin of `as_origin` is permitted to make the
+		/// `dispatch_as`, `schedule_dispatch`, `fast_track_scheduled_dispatch`
+		/// or `delay_scheduled_dispatch` call, `None` for other calls.
+		fn is_permitted(as_origin: AsOriginId, call: Call) -> Option<bool>;
+
+		/// Tasks scheduled by the authority module that are waiting to be
+		/// dispatched between blocks `from` and `to` inclusive, looking up at
+		/// most `MaxScheduledRange` blocks.
+		fn scheduled(from: BlockNumber, to: BlockNumber) -> Vec<ScheduledTask>;
+	}
+}
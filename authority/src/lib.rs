@@ -12,6 +12,9 @@
 //! - schedule a dispatchable
 //! - dispatch method with on behalf of other origins
 //!
+//! `cancel_all_scheduled_dispatches` cancels every scheduled task of an origin
+//! at once, for responding to a compromised origin.
+//!
 //! NOTE:
 //!
 //! In order to derive a feasible max encoded len for `DelayedOrigin`, it is
@@ -40,15 +43,17 @@ use frame_system::{pallet_prelude::*, EnsureRoot, EnsureSigned};
 use scale_info::TypeInfo;
 use sp_core::defer;
 use sp_runtime::{
-	traits::{CheckedSub, Dispatchable, Hash, Saturating},
+	traits::{CheckedSub, Dispatchable, Hash, One, Saturating, Zero},
 	ArithmeticError, DispatchError, DispatchResult, Either, RuntimeDebug,
 };
 use sp_std::prelude::*;
 
+mod migrations;
 mod mock;
 mod tests;
 mod weights;
 
+pub use migrations::Migration;
 pub use weights::WeightInfo;
 
 /// A delayed origin. Can only be dispatched via `dispatch_as` with a delay.
@@ -185,6 +190,24 @@ pub trait AsOriginId<Origin, PalletsOrigin> {
 /// The schedule task index type.
 pub type ScheduleTaskIndex = u32;
 
+/// A dispatch scheduled via `schedule_dispatch` that has not been
+/// dispatched or cancelled yet.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct ScheduledTask<BlockNumber, PalletsOrigin, Hash> {
+	/// The index of the task.
+	pub index: ScheduleTaskIndex,
+	/// The origin the call will be dispatched with.
+	pub origin: PalletsOrigin,
+	/// The block at which the call will be dispatched.
+	pub when: BlockNumber,
+	/// The delay requested when the task was scheduled.
+	pub delay: BlockNumber,
+	/// The priority of the task.
+	pub priority: Priority,
+	/// The hash of the scheduled call.
+	pub call_hash: Hash,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -195,6 +218,8 @@ pub mod module {
 	#[pallet::origin]
 	pub type Origin<T> = DelayedOrigin<BlockNumberFor<T>, <T as Config>::PalletsOrigin>;
 	pub(crate) type CallOf<T> = <T as Config>::RuntimeCall;
+	pub type ScheduledTaskOf<T> =
+		ScheduledTask<BlockNumberFor<T>, <T as Config>::PalletsOrigin, <T as frame_system::Config>::Hash>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -207,7 +232,9 @@ pub mod module {
 			+ OriginTrait<PalletsOrigin = Self::PalletsOrigin>;
 
 		/// The caller origin, overarching type of all pallets origins.
-		type PalletsOrigin: Parameter + Into<<Self as frame_system::Config>::RuntimeOrigin>;
+		type PalletsOrigin: Parameter
+			+ Into<<Self as frame_system::Config>::RuntimeOrigin>
+			+ TryInto<DelayedOrigin<BlockNumberFor<Self>, Self::PalletsOrigin>>;
 
 		/// The aggregated call type.
 		type RuntimeCall: Parameter
@@ -227,6 +254,19 @@ pub mod module {
 			BlockNumberFor<Self>,
 		>;
 
+		/// The maximum number of blocks `scheduled` looks up tasks for.
+		#[pallet::constant]
+		type MaxScheduledRange: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of accounts allowed to approve a call authorized
+		/// with an approval threshold.
+		#[pallet::constant]
+		type MaxCallTriggerers: Get<u32>;
+
+		/// The origin allowed to cancel every scheduled task of an origin at
+		/// once, e.g. in response to a compromised governance track.
+		type EmergencyCancelOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -247,6 +287,14 @@ pub mod module {
 		TriggerCallNotPermitted,
 		/// Call weight bound is wrong.
 		WrongCallWeightBound,
+		/// The approval threshold is zero or exceeds the number of triggerers.
+		InvalidApprovalThreshold,
+		/// Too many triggerers for a call.
+		TooManyCallTriggerers,
+		/// The call was already approved by the caller.
+		CallAlreadyApproved,
+		/// More tasks are scheduled than the given maximum.
+		TooManyScheduledTasks,
 	}
 
 	#[pallet::event]
@@ -285,6 +333,19 @@ pub mod module {
 		RemovedAuthorizedCall { hash: T::Hash },
 		/// An authorized call was triggered.
 		TriggeredCallBy { hash: T::Hash, caller: T::AccountId },
+		/// A call is authorized, requiring `threshold` of `triggerers` to
+		/// approve it before dispatch.
+		AuthorizedCallWithThreshold {
+			hash: T::Hash,
+			triggerers: Vec<T::AccountId>,
+			threshold: u32,
+		},
+		/// An authorized call was approved, but not yet by enough triggerers.
+		ApprovedCallBy {
+			hash: T::Hash,
+			caller: T::AccountId,
+			approvals: u32,
+		},
 	}
 
 	#[pallet::storage]
@@ -295,12 +356,68 @@ pub mod module {
 	#[pallet::getter(fn saved_calls)]
 	pub type SavedCalls<T: Config> = StorageMap<_, Identity, T::Hash, (CallOf<T>, Option<T::AccountId>), OptionQuery>;
 
+	/// Accounts allowed to trigger an authorized call and the number of them
+	/// that must approve it before dispatch.
+	///
+	/// CallApprovalThresholds: map Hash => Option<(BoundedVec<AccountId>, u32)>
+	#[pallet::storage]
+	#[pallet::getter(fn call_approval_thresholds)]
+	pub type CallApprovalThresholds<T: Config> =
+		StorageMap<_, Identity, T::Hash, (BoundedVec<T::AccountId, T::MaxCallTriggerers>, u32), OptionQuery>;
+
+	/// Triggerers that approved an authorized call so far.
+	///
+	/// CallApprovals: map Hash => BoundedVec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn call_approvals)]
+	pub type CallApprovals<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<T::AccountId, T::MaxCallTriggerers>, ValueQuery>;
+
+	/// Tasks scheduled by this module that are still waiting for dispatch.
+	///
+	/// ScheduledTasks: map ScheduleTaskIndex => Option<ScheduledTask>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_tasks)]
+	pub type ScheduledTasks<T: Config> =
+		StorageMap<_, Twox64Concat, ScheduleTaskIndex, ScheduledTaskOf<T>, OptionQuery>;
+
+	/// Index of scheduled tasks by the block they will be dispatched at.
+	///
+	/// TaskDispatchTimes: double_map BlockNumber, ScheduleTaskIndex => ()
+	#[pallet::storage]
+	pub type TaskDispatchTimes<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, ScheduleTaskIndex, (), OptionQuery>;
+
+	/// Index of scheduled tasks by the origin that scheduled them, the origin
+	/// of the task without `DelayedOrigin`.
+	///
+	/// TasksByOrigin: double_map PalletsOrigin, ScheduleTaskIndex => ()
+	#[pallet::storage]
+	pub type TasksByOrigin<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::PalletsOrigin, Twox64Concat, ScheduleTaskIndex, (), OptionQuery>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// tasks due in this block are dispatched by the scheduler, so they
+			// are no longer part of the queue
+			let mut count: u64 = 0;
+			for (index, _) in TaskDispatchTimes::<T>::drain_prefix(now) {
+				if let Some(task) = ScheduledTasks::<T>::take(index) {
+					TasksByOrigin::<T>::remove(Self::scheduling_origin(&task.origin), index);
+				}
+				count += 1;
+			}
+			T::DbWeight::get().reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_mul(3))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -356,6 +473,7 @@ pub mod module {
 				<T as Config>::RuntimeOrigin::from(origin)
 			};
 			let pallets_origin = schedule_origin.caller().clone();
+			let call_hash = T::Hashing::hash_of(&call);
 
 			T::Scheduler::schedule_named(
 				Encode::encode(&(&pallets_origin, id)),
@@ -367,6 +485,18 @@ pub mod module {
 			)
 			.map_err(|_| Error::<T>::FailedToSchedule)?;
 
+			let task = ScheduledTask {
+				index: id,
+				origin: pallets_origin.clone(),
+				when: Self::dispatch_block(when),
+				delay,
+				priority,
+				call_hash,
+			};
+			TaskDispatchTimes::<T>::insert(task.when, id, ());
+			TasksByOrigin::<T>::insert(Self::scheduling_origin(&task.origin), id, ());
+			ScheduledTasks::<T>::insert(id, task);
+
 			Self::deposit_event(Event::Scheduled {
 				origin: pallets_origin,
 				index: id,
@@ -396,6 +526,7 @@ pub mod module {
 			T::AuthorityConfig::check_fast_track_schedule(origin, &initial_origin, new_delay)?;
 			T::Scheduler::reschedule_named((&initial_origin, task_id).encode(), when)
 				.map_err(|_| Error::<T>::FailedToFastTrack)?;
+			Self::reschedule_task(task_id, Self::dispatch_block(when));
 
 			Self::deposit_event(Event::FastTracked {
 				origin: *initial_origin,
@@ -421,6 +552,7 @@ pub mod module {
 				DispatchTime::After(additional_delay),
 			)
 			.map_err(|_| Error::<T>::FailedToDelay)?;
+			Self::reschedule_task(task_id, Self::dispatch_block(DispatchTime::After(additional_delay)));
 
 			let now = frame_system::Pallet::<T>::block_number();
 			let dispatch_at = now.saturating_add(additional_delay);
@@ -443,6 +575,10 @@ pub mod module {
 		) -> DispatchResult {
 			T::AuthorityConfig::check_cancel_schedule(origin, &initial_origin)?;
 			T::Scheduler::cancel_named((&initial_origin, task_id).encode()).map_err(|_| Error::<T>::FailedToCancel)?;
+			if let Some(task) = ScheduledTasks::<T>::take(task_id) {
+				TaskDispatchTimes::<T>::remove(task.when, task_id);
+				TasksByOrigin::<T>::remove(Self::scheduling_origin(&task.origin), task_id);
+			}
 
 			Self::deposit_event(Event::Cancelled {
 				origin: *initial_origin,
@@ -461,6 +597,7 @@ pub mod module {
 			ensure_root(origin)?;
 			let hash = T::Hashing::hash_of(&call);
 			SavedCalls::<T>::insert(hash, (call, caller.clone()));
+			Self::remove_call_approvals(hash);
 			Self::deposit_event(Event::AuthorizedCall { hash, caller });
 			Ok(())
 		}
@@ -481,6 +618,7 @@ pub mod module {
 						ensure!(who == caller, Error::<T>::CallNotAuthorized);
 					}
 				}
+				Self::remove_call_approvals(hash);
 				Self::deposit_event(Event::RemovedAuthorizedCall { hash });
 				Ok(())
 			})
@@ -499,13 +637,36 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			SavedCalls::<T>::try_mutate_exists(hash, |maybe_call| {
 				let (call, maybe_caller) = maybe_call.take().ok_or(Error::<T>::CallNotAuthorized)?;
-				if let Some(caller) = maybe_caller {
-					ensure!(who == caller, Error::<T>::TriggerCallNotPermitted);
+				if let Some(caller) = &maybe_caller {
+					ensure!(who == *caller, Error::<T>::TriggerCallNotPermitted);
 				}
 				ensure!(
 					call_weight_bound.all_gte(call.get_dispatch_info().weight),
 					Error::<T>::WrongCallWeightBound
 				);
+				if let Some((triggerers, threshold)) = Self::call_approval_thresholds(hash) {
+					ensure!(triggerers.contains(&who), Error::<T>::TriggerCallNotPermitted);
+					let approvals = CallApprovals::<T>::try_mutate(hash, |approvals| -> Result<u32, DispatchError> {
+						ensure!(!approvals.contains(&who), Error::<T>::CallAlreadyApproved);
+						approvals
+							.try_push(who.clone())
+							.map_err(|_| Error::<T>::TooManyCallTriggerers)?;
+						Ok(approvals.len() as u32)
+					})?;
+					if approvals < threshold {
+						*maybe_call = Some((call, maybe_caller));
+						Self::deposit_event(Event::ApprovedCallBy {
+							hash,
+							caller: who,
+							approvals,
+						});
+						return Ok(PostDispatchInfo {
+							actual_weight: Some(T::WeightInfo::trigger_call()),
+							pays_fee: Pays::No,
+						});
+					}
+					Self::remove_call_approvals(hash);
+				}
 				let result = call.dispatch(OriginFor::<T>::root());
 				Self::deposit_event(Event::TriggeredCallBy { hash, caller: who });
 				Self::deposit_event(Event::Dispatched {
@@ -514,5 +675,176 @@ pub mod module {
 				Ok(Pays::No.into())
 			})
 		}
+
+		/// Authorize a call that is dispatched once `threshold` distinct
+		/// accounts of `triggerers` have triggered it with `trigger_call`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::authorize_call_with_threshold(triggerers.len() as u32))]
+		pub fn authorize_call_with_threshold(
+			origin: OriginFor<T>,
+			call: Box<CallOf<T>>,
+			mut triggerers: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			triggerers.sort();
+			triggerers.dedup();
+			ensure!(
+				threshold > 0 && threshold as usize <= triggerers.len(),
+				Error::<T>::InvalidApprovalThreshold
+			);
+			let bounded_triggerers: BoundedVec<T::AccountId, T::MaxCallTriggerers> = triggerers
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyCallTriggerers)?;
+
+			let hash = T::Hashing::hash_of(&call);
+			SavedCalls::<T>::insert(hash, (call, None));
+			CallApprovals::<T>::remove(hash);
+			CallApprovalThresholds::<T>::insert(hash, (bounded_triggerers, threshold));
+			Self::deposit_event(Event::AuthorizedCallWithThreshold {
+				hash,
+				triggerers,
+				threshold,
+			});
+			Ok(())
+		}
+
+		/// Cancel every scheduled task dispatched with the origin of
+		/// `as_origin`, with or without delay.
+		///
+		/// A `Cancelled` event is emitted for each cancelled task.
+		/// `max_tasks` must be at least the number of tasks scheduled by the
+		/// origin of `as_origin` and is used to weigh the call.
+		///
+		/// The dispatch origin for this call must be `EmergencyCancelOrigin`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::cancel_all_scheduled_dispatches(*max_tasks))]
+		pub fn cancel_all_scheduled_dispatches(
+			origin: OriginFor<T>,
+			as_origin: T::AsOriginId,
+			max_tasks: u32,
+		) -> DispatchResultWithPostInfo {
+			T::EmergencyCancelOrigin::ensure_origin(origin)?;
+
+			let pallets_origin = as_origin.into_origin();
+			let indexes = TasksByOrigin::<T>::iter_key_prefix(&pallets_origin)
+				.take(max_tasks.saturating_add(1) as usize)
+				.collect::<Vec<_>>();
+			ensure!(indexes.len() <= max_tasks as usize, Error::<T>::TooManyScheduledTasks);
+
+			for index in indexes.iter() {
+				TasksByOrigin::<T>::remove(&pallets_origin, index);
+				let Some(task) = ScheduledTasks::<T>::take(index) else {
+					continue;
+				};
+				TaskDispatchTimes::<T>::remove(task.when, index);
+				// the task may already be gone from the scheduler, only report
+				// the tasks which are actually cancelled
+				if T::Scheduler::cancel_named((&task.origin, task.index).encode()).is_ok() {
+					Self::deposit_event(Event::Cancelled {
+						origin: task.origin,
+						index: task.index,
+					});
+				}
+			}
+
+			Ok(Some(T::WeightInfo::cancel_all_scheduled_dispatches(indexes.len() as u32)).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether the origin of `as_origin` is permitted to make `call`.
+	///
+	/// Only `dispatch_as`, `schedule_dispatch`, `fast_track_scheduled_dispatch`
+	/// and `delay_scheduled_dispatch` are checked, `None` is returned for other
+	/// calls.
+	pub fn is_permitted(as_origin: T::AsOriginId, call: &Call<T>) -> Option<bool> {
+		let origin: <T as frame_system::Config>::RuntimeOrigin = as_origin.into_origin().into();
+		let result = match call {
+			Call::dispatch_as { as_origin, .. } => as_origin.check_dispatch_from(origin),
+			Call::schedule_dispatch { priority, .. } => T::AuthorityConfig::check_schedule_dispatch(origin, *priority),
+			Call::fast_track_scheduled_dispatch {
+				initial_origin, when, ..
+			} => {
+				let now = frame_system::Pallet::<T>::block_number();
+				let new_delay = match *when {
+					DispatchTime::At(x) => x
+						.checked_sub(&now)
+						.ok_or(DispatchError::from(ArithmeticError::Overflow)),
+					DispatchTime::After(x) => Ok(x),
+				};
+				new_delay.and_then(|new_delay| {
+					T::AuthorityConfig::check_fast_track_schedule(origin, initial_origin, new_delay)
+				})
+			}
+			Call::delay_scheduled_dispatch { initial_origin, .. } => {
+				T::AuthorityConfig::check_delay_schedule(origin, initial_origin)
+			}
+			_ => return None,
+		};
+		Some(result.is_ok())
+	}
+
+	/// Tasks waiting to be dispatched between blocks `from` and `to`
+	/// inclusive, ordered by dispatch block and index.
+	///
+	/// Each block of the range is looked up in `TaskDispatchTimes`, at most
+	/// `MaxScheduledRange` blocks starting at `from`.
+	pub fn scheduled(from: BlockNumberFor<T>, to: BlockNumberFor<T>) -> Vec<ScheduledTaskOf<T>> {
+		let max_range = T::MaxScheduledRange::get();
+		if max_range.is_zero() {
+			return Vec::new();
+		}
+		let to = to.min(from.saturating_add(max_range.saturating_sub(One::one())));
+
+		let mut tasks = Vec::new();
+		let mut block = from;
+		while block <= to {
+			let mut indexes: Vec<_> = TaskDispatchTimes::<T>::iter_key_prefix(block).collect();
+			indexes.sort();
+			tasks.extend(indexes.into_iter().filter_map(ScheduledTasks::<T>::get));
+			if block == to {
+				break;
+			}
+			block = block.saturating_add(One::one());
+		}
+		tasks
+	}
+
+	/// The block a task scheduled with `when` is dispatched at, following the
+	/// scheduler which has already serviced the current block.
+	fn dispatch_block(when: DispatchTime<BlockNumberFor<T>>) -> BlockNumberFor<T> {
+		match when {
+			DispatchTime::At(x) => x,
+			DispatchTime::After(x) => frame_system::Pallet::<T>::block_number()
+				.saturating_add(x)
+				.saturating_add(One::one()),
+		}
+	}
+
+	fn remove_call_approvals(hash: T::Hash) {
+		CallApprovalThresholds::<T>::remove(hash);
+		CallApprovals::<T>::remove(hash);
+	}
+
+	/// The origin that scheduled a task dispatched with `origin`, without
+	/// its `DelayedOrigin`.
+	fn scheduling_origin(origin: &T::PalletsOrigin) -> T::PalletsOrigin {
+		match TryInto::<DelayedOrigin<BlockNumberFor<T>, T::PalletsOrigin>>::try_into(origin.clone()) {
+			Ok(delayed) => *delayed.origin,
+			Err(_) => origin.clone(),
+		}
+	}
+
+	fn reschedule_task(index: ScheduleTaskIndex, when: BlockNumberFor<T>) {
+		ScheduledTasks::<T>::mutate(index, |maybe_task| {
+			if let Some(task) = maybe_task {
+				TaskDispatchTimes::<T>::remove(task.when, index);
+				TaskDispatchTimes::<T>::insert(when, index, ());
+				task.when = when;
+			}
+		});
 	}
 }
@@ -0,0 +1,73 @@
+use crate::{Config, Pallet, ScheduledTaskOf, ScheduledTasks, TaskDispatchTimes, TasksByOrigin};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{schedule::v1::Named as ScheduleNamed, OnRuntimeUpgrade},
+};
+use sp_std::vec::Vec;
+
+/// Populates `ScheduledTasks` and `TaskDispatchTimes` with the tasks
+/// scheduled before they were tracked, and indexes the tracked tasks by
+/// origin in `TasksByOrigin`.
+///
+/// The scheduler only knows tasks by the hash of their name, so the runtime
+/// lists the tasks in `PreUpgradeTasks`. Tasks which are no longer scheduled
+/// are skipped, and the dispatch block is taken from the scheduler.
+pub struct Migration<T, PreUpgradeTasks>(PhantomData<(T, PreUpgradeTasks)>);
+impl<T: Config, PreUpgradeTasks: Get<Vec<ScheduledTaskOf<T>>>> OnRuntimeUpgrade for Migration<T, PreUpgradeTasks> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 1 {
+			weight.saturating_accrue(v1::migrate::<T, PreUpgradeTasks>());
+		}
+		if onchain_version < 2 {
+			weight.saturating_accrue(v2::migrate::<T>());
+		}
+		if onchain_version < Pallet::<T>::current_storage_version() {
+			Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+		weight
+	}
+}
+
+mod v1 {
+	use super::*;
+
+	pub(crate) fn migrate<T: Config, PreUpgradeTasks: Get<Vec<ScheduledTaskOf<T>>>>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		for mut task in PreUpgradeTasks::get() {
+			weight.saturating_accrue(T::DbWeight::get().reads(2));
+			if ScheduledTasks::<T>::contains_key(task.index) {
+				continue;
+			}
+			let Ok(when) = T::Scheduler::next_dispatch_time((&task.origin, task.index).encode()) else {
+				continue;
+			};
+
+			task.when = when;
+			TaskDispatchTimes::<T>::insert(when, task.index, ());
+			ScheduledTasks::<T>::insert(task.index, task);
+			weight.saturating_accrue(T::DbWeight::get().writes(2));
+		}
+
+		weight
+	}
+}
+
+mod v2 {
+	use super::*;
+
+	/// Index the tracked tasks by the origin that scheduled them.
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		for (index, task) in ScheduledTasks::<T>::iter() {
+			TasksByOrigin::<T>::insert(Pallet::<T>::scheduling_origin(&task.origin), index, ());
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+		}
+
+		weight
+	}
+}
@@ -166,6 +166,9 @@ impl Config for Runtime {
 	type RuntimeCall = RuntimeCall;
 	type AsOriginId = MockAsOriginId;
 	type AuthorityConfig = AuthorityConfigImpl;
+	type MaxScheduledRange = ConstU64<5>;
+	type MaxCallTriggerers = ConstU32<3>;
+	type EmergencyCancelOrigin = EnsureRoot<u128>;
 	type WeightInfo = ();
 }
 
@@ -204,5 +207,6 @@ pub fn run_to_block(n: u64) {
 		Scheduler::on_finalize(System::block_number());
 		System::set_block_number(System::block_number() + 1);
 		Scheduler::on_initialize(System::block_number());
+		Authority::on_initialize(System::block_number());
 	}
 }
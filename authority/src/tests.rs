@@ -657,3 +657,411 @@ fn origin_max_encoded_len_works() {
 	assert_eq!(DelayedOrigin::<u32, OriginCaller>::max_encoded_len(), 22);
 	assert_eq!(OriginCaller::max_encoded_len(), 27);
 }
+
+#[test]
+fn is_permitted_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let remark_call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let dispatch_as = |as_origin| authority::Call::<Runtime>::dispatch_as {
+			as_origin,
+			call: Box::new(remark_call.clone()),
+		};
+		let schedule = authority::Call::<Runtime>::schedule_dispatch {
+			when: DispatchTime::After(1),
+			priority: 0,
+			with_delayed_origin: true,
+			call: Box::new(remark_call.clone()),
+		};
+		let fast_track = authority::Call::<Runtime>::fast_track_scheduled_dispatch {
+			initial_origin: Box::new(OriginCaller::system(RawOrigin::Signed(1))),
+			task_id: 0,
+			when: DispatchTime::After(0),
+		};
+		let delay = |initial_origin| authority::Call::<Runtime>::delay_scheduled_dispatch {
+			initial_origin: Box::new(initial_origin),
+			task_id: 0,
+			additional_delay: 2,
+		};
+
+		assert_eq!(
+			Authority::is_permitted(MockAsOriginId::Root, &dispatch_as(MockAsOriginId::Account2)),
+			Some(true)
+		);
+		assert_eq!(
+			Authority::is_permitted(MockAsOriginId::Account1, &dispatch_as(MockAsOriginId::Account1)),
+			Some(true)
+		);
+		assert_eq!(
+			Authority::is_permitted(MockAsOriginId::Account1, &dispatch_as(MockAsOriginId::Account2)),
+			Some(false)
+		);
+
+		assert_eq!(Authority::is_permitted(MockAsOriginId::Account1, &schedule), Some(true));
+
+		assert_eq!(Authority::is_permitted(MockAsOriginId::Root, &fast_track), Some(true));
+		assert_eq!(
+			Authority::is_permitted(MockAsOriginId::Account1, &fast_track),
+			Some(false)
+		);
+
+		assert_eq!(
+			Authority::is_permitted(
+				MockAsOriginId::Account1,
+				&delay(OriginCaller::system(RawOrigin::Signed(1)))
+			),
+			Some(true)
+		);
+		assert_eq!(
+			Authority::is_permitted(
+				MockAsOriginId::Account2,
+				&delay(OriginCaller::system(RawOrigin::Signed(1)))
+			),
+			Some(false)
+		);
+
+		assert_eq!(
+			Authority::is_permitted(
+				MockAsOriginId::Root,
+				&authority::Call::<Runtime>::remove_authorized_call {
+					hash: Default::default()
+				}
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn scheduled_tasks_are_tracked() {
+	ExtBuilder::default().build().execute_with(|| {
+		run_to_block(1);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let call_hash = <Runtime as frame_system::Config>::Hashing::hash_of(&call);
+		let delayed_root = OriginCaller::Authority(DelayedOrigin {
+			delay: 4,
+			origin: Box::new(OriginCaller::system(RawOrigin::Root)),
+		});
+
+		assert_ok!(Authority::schedule_dispatch(
+			RuntimeOrigin::root(),
+			DispatchTime::After(4),
+			1,
+			true,
+			Box::new(call.clone())
+		));
+		assert_ok!(Authority::schedule_dispatch(
+			RuntimeOrigin::root(),
+			DispatchTime::At(3),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+		assert_ok!(Authority::schedule_dispatch(
+			RuntimeOrigin::root(),
+			DispatchTime::At(10),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+
+		let task_0 = ScheduledTask {
+			index: 0,
+			origin: delayed_root.clone(),
+			when: 6,
+			delay: 4,
+			priority: 1,
+			call_hash,
+		};
+		let task_1 = ScheduledTask {
+			index: 1,
+			origin: OriginCaller::system(RawOrigin::Root),
+			when: 3,
+			delay: 2,
+			priority: 0,
+			call_hash,
+		};
+		assert_eq!(Authority::scheduled_tasks(0), Some(task_0.clone()));
+		assert_eq!(Authority::scheduled(2, 6), vec![task_1.clone(), task_0.clone()]);
+		assert_eq!(Authority::scheduled(4, 9), vec![task_0.clone()]);
+		// at most `MaxScheduledRange` blocks are looked up
+		assert_eq!(Authority::scheduled(0, 6), vec![task_1.clone()]);
+		assert_eq!(Authority::scheduled(6, BlockNumber::MAX), vec![task_0.clone()]);
+
+		assert_ok!(Authority::fast_track_scheduled_dispatch(
+			RuntimeOrigin::root(),
+			Box::new(delayed_root.clone()),
+			0,
+			DispatchTime::At(2),
+		));
+		assert_ok!(Authority::delay_scheduled_dispatch(
+			RuntimeOrigin::root(),
+			Box::new(OriginCaller::system(RawOrigin::Root)),
+			1,
+			3,
+		));
+		assert_ok!(Authority::cancel_scheduled_dispatch(
+			RuntimeOrigin::root(),
+			Box::new(OriginCaller::system(RawOrigin::Root)),
+			2,
+		));
+		assert_eq!(
+			Authority::scheduled(1, 10),
+			vec![
+				ScheduledTask { when: 2, ..task_0 },
+				ScheduledTask {
+					when: 5,
+					..task_1.clone()
+				},
+			]
+		);
+
+		run_to_block(2);
+		assert_eq!(Authority::scheduled_tasks(0), None);
+		assert_eq!(Authority::scheduled(1, 10), vec![ScheduledTask { when: 5, ..task_1 }]);
+
+		run_to_block(5);
+		assert_eq!(Authority::scheduled(1, 10), vec![]);
+		assert_eq!(TaskDispatchTimes::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn trigger_call_with_threshold_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		run_to_block(1);
+		let ensure_root_call = RuntimeCall::RootTesting(pallet_root_testing::Call::fill_block {
+			ratio: Perbill::from_percent(50),
+		});
+		let call = RuntimeCall::Authority(authority::Call::dispatch_as {
+			as_origin: MockAsOriginId::Root,
+			call: Box::new(ensure_root_call),
+		});
+		let hash = <Runtime as frame_system::Config>::Hashing::hash_of(&call);
+		let call_weight_bound = call.get_dispatch_info().weight;
+
+		assert_noop!(
+			Authority::authorize_call_with_threshold(RuntimeOrigin::root(), Box::new(call.clone()), vec![1, 2], 0),
+			Error::<Runtime>::InvalidApprovalThreshold
+		);
+		// duplicated triggerers are counted once
+		assert_noop!(
+			Authority::authorize_call_with_threshold(RuntimeOrigin::root(), Box::new(call.clone()), vec![1, 1], 2),
+			Error::<Runtime>::InvalidApprovalThreshold
+		);
+		assert_noop!(
+			Authority::authorize_call_with_threshold(
+				RuntimeOrigin::root(),
+				Box::new(call.clone()),
+				vec![1, 2, 3, 4],
+				2
+			),
+			Error::<Runtime>::TooManyCallTriggerers
+		);
+
+		assert_ok!(Authority::authorize_call_with_threshold(
+			RuntimeOrigin::root(),
+			Box::new(call.clone()),
+			vec![3, 1, 2],
+			2
+		));
+		System::assert_last_event(mock::RuntimeEvent::Authority(Event::AuthorizedCallWithThreshold {
+			hash,
+			triggerers: vec![1, 2, 3],
+			threshold: 2,
+		}));
+
+		assert_noop!(
+			Authority::trigger_call(RuntimeOrigin::signed(4), hash, call_weight_bound),
+			Error::<Runtime>::TriggerCallNotPermitted
+		);
+
+		// the first approval does not dispatch the call
+		assert_ok!(Authority::trigger_call(
+			RuntimeOrigin::signed(1),
+			hash,
+			call_weight_bound
+		));
+		System::assert_last_event(mock::RuntimeEvent::Authority(Event::ApprovedCallBy {
+			hash,
+			caller: 1,
+			approvals: 1,
+		}));
+		assert_eq!(Authority::saved_calls(&hash), Some((call.clone(), None)));
+		assert_eq!(Authority::call_approvals(&hash).into_inner(), vec![1]);
+		assert_noop!(
+			Authority::trigger_call(RuntimeOrigin::signed(1), hash, call_weight_bound),
+			Error::<Runtime>::CallAlreadyApproved
+		);
+
+		// the second approval dispatches it
+		assert_ok!(Authority::trigger_call(
+			RuntimeOrigin::signed(2),
+			hash,
+			call_weight_bound
+		));
+		System::assert_has_event(mock::RuntimeEvent::Authority(Event::TriggeredCallBy {
+			hash,
+			caller: 2,
+		}));
+		System::assert_last_event(mock::RuntimeEvent::Authority(Event::Dispatched { result: Ok(()) }));
+		assert_eq!(Authority::saved_calls(&hash), None);
+		assert_eq!(Authority::call_approval_thresholds(&hash), None);
+		assert!(Authority::call_approvals(&hash).is_empty());
+
+		// authorizing the call again without threshold drops the approvals
+		assert_ok!(Authority::authorize_call_with_threshold(
+			RuntimeOrigin::root(),
+			Box::new(call.clone()),
+			vec![1, 2],
+			2
+		));
+		assert_ok!(Authority::trigger_call(
+			RuntimeOrigin::signed(1),
+			hash,
+			call_weight_bound
+		));
+		assert_ok!(Authority::authorize_call(RuntimeOrigin::root(), Box::new(call), None));
+		assert_eq!(Authority::call_approval_thresholds(&hash), None);
+		assert!(Authority::call_approvals(&hash).is_empty());
+	});
+}
+
+#[test]
+fn cancel_all_scheduled_dispatches_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		run_to_block(1);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let account_1 = OriginCaller::system(RawOrigin::Signed(1));
+		let delayed_account_1 = OriginCaller::Authority(DelayedOrigin {
+			delay: 5,
+			origin: Box::new(account_1.clone()),
+		});
+
+		for with_delayed_origin in [false, true] {
+			assert_ok!(Authority::schedule_dispatch(
+				RuntimeOrigin::signed(1),
+				DispatchTime::After(5),
+				0,
+				with_delayed_origin,
+				Box::new(call.clone())
+			));
+		}
+		assert_ok!(Authority::schedule_dispatch(
+			RuntimeOrigin::root(),
+			DispatchTime::After(5),
+			0,
+			false,
+			Box::new(call.clone())
+		));
+
+		assert_noop!(
+			Authority::cancel_all_scheduled_dispatches(RuntimeOrigin::signed(1), MockAsOriginId::Account1, 3),
+			BadOrigin
+		);
+		assert_noop!(
+			Authority::cancel_all_scheduled_dispatches(RuntimeOrigin::root(), MockAsOriginId::Account1, 1),
+			Error::<Runtime>::TooManyScheduledTasks
+		);
+
+		// only the tasks of the origin count against `max_tasks`
+		assert_ok!(Authority::cancel_all_scheduled_dispatches(
+			RuntimeOrigin::root(),
+			MockAsOriginId::Account1,
+			2
+		));
+		System::assert_has_event(mock::RuntimeEvent::Authority(Event::Cancelled {
+			origin: account_1,
+			index: 0,
+		}));
+		System::assert_has_event(mock::RuntimeEvent::Authority(Event::Cancelled {
+			origin: delayed_account_1,
+			index: 1,
+		}));
+		assert_eq!(Authority::scheduled_tasks(0), None);
+		assert_eq!(Authority::scheduled_tasks(1), None);
+		assert_eq!(
+			Authority::scheduled_tasks(2).map(|task| task.origin),
+			Some(OriginCaller::system(RawOrigin::Root))
+		);
+
+		// nothing left to cancel
+		assert_ok!(Authority::cancel_all_scheduled_dispatches(
+			RuntimeOrigin::root(),
+			MockAsOriginId::Account1,
+			1
+		));
+		assert_eq!(TaskDispatchTimes::<Runtime>::iter().count(), 1);
+		assert_eq!(
+			TasksByOrigin::<Runtime>::iter_keys().collect::<Vec<_>>(),
+			vec![(OriginCaller::system(RawOrigin::Root), 2)]
+		);
+	});
+}
+
+#[test]
+fn migration_works() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	pub struct PreUpgradeTasks;
+	impl Get<Vec<ScheduledTaskOf<Runtime>>> for PreUpgradeTasks {
+		fn get() -> Vec<ScheduledTaskOf<Runtime>> {
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+			let task = ScheduledTask {
+				index: 0,
+				origin: OriginCaller::system(RawOrigin::Root),
+				when: 0,
+				delay: 9,
+				priority: 0,
+				call_hash: <Runtime as frame_system::Config>::Hashing::hash_of(&call),
+			};
+			vec![task.clone(), ScheduledTask { index: 1, ..task }]
+		}
+	}
+
+	ExtBuilder::default().build().execute_with(|| {
+		run_to_block(1);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		for _ in 0..2 {
+			assert_ok!(Authority::schedule_dispatch(
+				RuntimeOrigin::root(),
+				DispatchTime::At(10),
+				0,
+				false,
+				Box::new(call.clone())
+			));
+		}
+		assert_ok!(<Runtime as Config>::Scheduler::cancel_named(
+			(OriginCaller::system(RawOrigin::Root), 1u32).encode()
+		));
+
+		// tasks scheduled before they were tracked
+		let _ = ScheduledTasks::<Runtime>::clear(u32::MAX, None);
+		let _ = TaskDispatchTimes::<Runtime>::clear(u32::MAX, None);
+		let _ = TasksByOrigin::<Runtime>::clear(u32::MAX, None);
+		StorageVersion::new(0).put::<Authority>();
+		crate::Migration::<Runtime, PreUpgradeTasks>::on_runtime_upgrade();
+		assert_eq!(
+			Authority::on_chain_storage_version(),
+			Authority::current_storage_version()
+		);
+
+		let task = ScheduledTask {
+			when: 10,
+			..PreUpgradeTasks::get()[0].clone()
+		};
+		assert_eq!(Authority::scheduled(6, 10), vec![task]);
+		assert_eq!(Authority::scheduled_tasks(1), None);
+		let indexed = vec![(OriginCaller::system(RawOrigin::Root), 0)];
+		assert_eq!(TasksByOrigin::<Runtime>::iter_keys().collect::<Vec<_>>(), indexed);
+
+		// tracked tasks which are not indexed by origin yet
+		let _ = TasksByOrigin::<Runtime>::clear(u32::MAX, None);
+		StorageVersion::new(1).put::<Authority>();
+		crate::Migration::<Runtime, PreUpgradeTasks>::on_runtime_upgrade();
+		assert_eq!(
+			Authority::on_chain_storage_version(),
+			Authority::current_storage_version()
+		);
+		assert_eq!(TasksByOrigin::<Runtime>::iter_keys().collect::<Vec<_>>(), indexed);
+	});
+}
@@ -38,6 +38,8 @@ pub trait WeightInfo {
 	fn authorize_call() -> Weight;
 	fn remove_authorized_call() -> Weight;
 	fn trigger_call() -> Weight;
+	fn authorize_call_with_threshold(n: u32) -> Weight;
+	fn cancel_all_scheduled_dispatches(n: u32) -> Weight;
 }
 
 /// Default weights.
@@ -48,40 +50,51 @@ impl WeightInfo for () {
 	fn schedule_dispatch_without_delay() -> Weight {
 		Weight::from_parts(30_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
-			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
 	fn schedule_dispatch_with_delay() -> Weight {
 		Weight::from_parts(32_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
-			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
 	fn fast_track_scheduled_dispatch() -> Weight {
 		Weight::from_parts(42_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(3 as u64))
-			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
 	fn delay_scheduled_dispatch() -> Weight {
 		Weight::from_parts(42_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(3 as u64))
-			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
 	fn cancel_scheduled_dispatch() -> Weight {
 		Weight::from_parts(29_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(2 as u64))
-			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
 	}
 	fn authorize_call() -> Weight {
 		Weight::from_parts(14_000_000, 0)
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
 	fn remove_authorized_call() -> Weight {
 		Weight::from_parts(16_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
 	fn trigger_call() -> Weight {
 		Weight::from_parts(29_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn authorize_call_with_threshold(n: u32) -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn cancel_all_scheduled_dispatches(n: u32) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(29_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
 	}
 }
@@ -36,6 +36,15 @@ macro_rules! whitelist_account {
 	};
 }
 
+/// Whitelist the given storage key for the current benchmark, its reads and
+/// writes are not counted.
+#[macro_export]
+macro_rules! whitelist_key {
+	($key:expr) => {
+		$crate::benchmarking::add_to_whitelist($crate::TrackedStorageKey::new(AsRef::<[u8]>::as_ref(&$key).to_vec()));
+	};
+}
+
 /// Construct pallet benchmarks for weighing dispatchables.
 ///
 /// Works around the idea of complexity parameters, named by a single letter
@@ -161,6 +170,44 @@ macro_rules! whitelist_account {
 ///
 /// These `verify` blocks will not affect your benchmark results!
 ///
+/// Storage keys whose reads and writes should not be counted, e.g. storage
+/// that is always in the cache when the benchmarked code runs, can be
+/// whitelisted with `whitelist_key!` after the `let` statements of the setup
+/// block. The whitelist only applies to the benchmark it is declared in:
+///
+/// ```ignore
+/// set_value {
+///     let caller = account::<AccountId>("caller", 0, 0);
+///     whitelist_key!(Pallet::Value::<Runtime>::hashed_key());
+/// }: _(RuntimeOrigin::signed(caller), 1)
+/// verify {
+///     assert_eq!(Pallet::value(), Some(1));
+/// }
+/// ```
+///
+/// The proof size of each benchmark is recorded alongside its execution time.
+/// By default the CLI estimates the PoV size of storage accesses from the
+/// `MaxEncodedLen` of the accessed items. This can be overridden per benchmark
+/// with a `#[pov_mode]` attribute, either for all storage items or for specific
+/// ones:
+///
+/// ```ignore
+/// #[pov_mode = Measured]
+/// transfer {
+///     ...
+/// }: _(RuntimeOrigin::signed(caller), dest, amount)
+///
+/// #[pov_mode = MaxEncodedLen {
+///     Tokens::Accounts: Measured
+///     System::Account: Ignored
+/// }]
+/// transfer_all {
+///     ...
+/// }: _(RuntimeOrigin::signed(caller), dest, true)
+/// ```
+///
+/// A `#[pov_mode]` attribute must come before an `#[extra]` attribute.
+///
 /// You can construct benchmark tests like so:
 ///
 /// ```ignore
@@ -188,6 +235,7 @@ macro_rules! runtime_benchmarks {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -207,6 +255,7 @@ macro_rules! runtime_benchmarks_instance {
 			( )
 			( )
 			( )
+			( )
 			$( $rest )*
 		);
 	}
@@ -215,6 +264,36 @@ macro_rules! runtime_benchmarks_instance {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! benchmarks_iter {
+	// detect and extract `#[pov_mode = Mode { Pallet::Storage: Mode ... }]` tag:
+	(
+		{ $( $instance:ident )? }
+		$runtime:ident
+		$pallet:ident
+		( $( $names:tt )* )
+		( $( $names_extra:tt )* )
+		( $( $names_skip_meta:tt )* )
+		( $( $pov_name:ident: $( $storage:path = $pov_mode:ident )*; )* )
+		#[pov_mode = $mode:ident $( { $( $override_storage:path: $override_mode:ident )* } )?]
+		$( #[$extra:ident] )?
+		$name:ident
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance)? }
+			$runtime
+			$pallet
+			( $( $names )* )
+			( $( $names_extra )* )
+			( $( $names_skip_meta )* )
+			(
+				$( $pov_name: $( $storage = $pov_mode )*; )*
+				$name: ALL = $mode $( $( $override_storage = $override_mode )* )?;
+			)
+			$( #[$extra] )?
+			$name
+			$( $rest )*
+		}
+	};
 	// detect and extract `#[extra] tag:
 	(
 		{ $( $instance:ident )? }
@@ -223,6 +302,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		#[extra]
 		$name:ident
 		$( $rest:tt )*
@@ -234,6 +314,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* $name )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name
 			$( $rest )*
 		}
@@ -246,6 +327,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* ) // This contains $( $( { $instance } )? $name:ident )*
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: _ $(< $origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -257,6 +339,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $name $(< $origin_type >)? ( $origin $( , $arg )* )
 			verify $postcode
 			$( $rest )*
@@ -270,6 +353,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident $(< $origin_type:ty>)? ( $origin:expr $( , $arg:expr )* )
 		verify $postcode:block
 		$( $rest:tt )*
@@ -282,6 +366,7 @@ macro_rules! benchmarks_iter {
 				( $( $names )* )
 					( $( $names_extra )* )
 					( $( $names_skip_meta )* )
+					( $( $pov_modes )* )
 					$name {
 						$( $code )*
 							let __call =
@@ -315,6 +400,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
 		verify $postcode:block
 		$( $rest:tt )*
@@ -345,6 +431,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* { $( $instance )? } $name )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$( $rest )*
 		);
 	};
@@ -356,6 +443,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 	) => {
 		$crate::selected_benchmark!(
 			$runtime
@@ -370,6 +458,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra ),* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 		);
 	};
 	// add verify block to _() format
@@ -380,6 +469,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: _ ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -390,6 +480,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: _ ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -403,6 +494,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident ( $origin:expr $( , $arg:expr )* )
 		$( $rest:tt )*
 	) => {
@@ -413,6 +505,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $dispatch ( $origin $( , $arg )* )
 			verify { }
 			$( $rest )*
@@ -426,6 +519,7 @@ macro_rules! benchmarks_iter {
 		( $( $names:tt )* )
 		( $( $names_extra:tt )* )
 		( $( $names_skip_meta:tt )* )
+		( $( $pov_modes:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
 		$( $rest:tt )*
 	) => {
@@ -436,6 +530,7 @@ macro_rules! benchmarks_iter {
 			( $( $names )* )
 			( $( $names_extra )* )
 			( $( $names_skip_meta )* )
+			( $( $pov_modes )* )
 			$name { $( $code )* }: $eval
 			verify { }
 			$( $rest )*
@@ -719,6 +814,7 @@ macro_rules! impl_benchmark {
 		( $( { $( $name_inst:ident )? } $name:ident )* )
 		( $( $name_extra:ident ),* )
 		( $( $name_skip_meta:ident ),* )
+		( $( $pov_name:ident: $( $storage:path = $pov_mode:ident )*; )* )
 	) => {
 		pub struct Benchmark;
 
@@ -729,6 +825,17 @@ macro_rules! impl_benchmark {
 					let extra = [ $( stringify!($name_extra).as_ref() ),* ];
 					all_names.retain(|x| !extra.contains(x));
 				}
+				let pov_modes: $crate::Vec<(&str, $crate::Vec<($crate::Vec<u8>, $crate::Vec<u8>)>)> = $crate::vec![
+					$(
+						(stringify!($pov_name), $crate::vec![
+							// Stringify sometimes includes spaces, depending on the Rust version.
+							$( (
+								stringify!($storage).replace(" ", "").as_bytes().to_vec(),
+								stringify!($pov_mode).as_bytes().to_vec(),
+							), )*
+						]),
+					)*
+				];
 				all_names.into_iter().map(|benchmark| {
 					let selected_benchmark = match benchmark {
 						$( stringify!($name) => SelectedBenchmark::$name, )*
@@ -741,9 +848,11 @@ macro_rules! impl_benchmark {
 					$crate::BenchmarkMetadata {
 						name: benchmark.as_bytes().to_vec(),
 						components,
-						// TODO: Not supported by V2 syntax as of yet.
-						// https://github.com/paritytech/substrate/issues/13132
-						pov_modes: vec![],
+						pov_modes: pov_modes
+							.iter()
+							.find(|(name, _)| *name == benchmark)
+							.map(|(_, modes)| modes.clone())
+							.unwrap_or_default(),
 					}
 				}).collect::<$crate::Vec<_>>()
 			}
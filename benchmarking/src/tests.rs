@@ -103,6 +103,9 @@ fn new_test_ext() -> sp_io::TestExternalities {
 runtime_benchmarks! {
 	{ Test, test }
 
+	#[pov_mode = MaxEncodedLen {
+		Pallet::Value: Measured
+	}]
 	set_value {
 		let b in 1 .. 1000;
 		let caller = account::<AccountId>("caller", 0, 0);
@@ -111,6 +114,21 @@ runtime_benchmarks! {
 		assert_eq!(Pallet::value(), Some(b));
 	}
 
+	set_whitelisted_value {
+		let b in 1 .. 1000;
+		let caller = account::<AccountId>("caller", 0, 0);
+		whitelist_key!(Value::<Test>::hashed_key());
+	}: set_value (RawOrigin::Signed(caller), b)
+	verify {
+		assert_eq!(Pallet::value(), Some(b));
+	}
+
+	set_whitelisted_value_bad_origin {
+		let b in 1 .. 1000;
+		whitelist_key!(Value::<Test>::hashed_key());
+	}: set_value (RawOrigin::None, b)
+
+	#[pov_mode = Measured]
 	other_name {
 		let b in 1 .. 1000;
 	}: dummy (RawOrigin::None, b)
@@ -164,6 +182,29 @@ fn benchmarks_macro_works() {
 	});
 }
 
+#[test]
+fn benchmarks_macro_pov_mode_works() {
+	let benchmarks = Benchmark::benchmarks(false);
+	let pov_modes = |name: &str| {
+		benchmarks
+			.iter()
+			.find(|b| b.name == name.as_bytes())
+			.expect("benchmark exists")
+			.pov_modes
+			.clone()
+	};
+
+	assert_eq!(
+		pov_modes("set_value"),
+		vec![
+			(b"ALL".to_vec(), b"MaxEncodedLen".to_vec()),
+			(b"Pallet::Value".to_vec(), b"Measured".to_vec()),
+		]
+	);
+	assert_eq!(pov_modes("other_name"), vec![(b"ALL".to_vec(), b"Measured".to_vec())]);
+	assert_eq!(pov_modes("sort_vector"), vec![]);
+}
+
 #[test]
 fn benchmarks_macro_rename_works() {
 	// Check benchmark creation for `other_dummy`.
@@ -228,10 +269,31 @@ fn benchmarks_macro_verify_works() {
 	});
 }
 
+#[test]
+fn benchmarks_macro_whitelist_does_not_bypass_origin() {
+	// Whitelisting a storage key only affects DB tracking, an unsigned origin
+	// is still rejected by `set_value`.
+	let selected = SelectedBenchmark::set_whitelisted_value_bad_origin;
+
+	let closure =
+		<SelectedBenchmark as BenchmarkingSetup<Test>>::instance(&selected, &[(BenchmarkParameter::b, 1)], true)
+			.expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert!(closure().is_err());
+		assert_eq!(Pallet::value(), None);
+	});
+}
+
 #[test]
 fn benchmarks_generate_unit_tests() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(Benchmark::test_benchmark_set_value());
+		assert_ok!(Benchmark::test_benchmark_set_whitelisted_value());
+		assert_err!(
+			Benchmark::test_benchmark_set_whitelisted_value_bad_origin(),
+			"Bad origin"
+		);
 		assert_ok!(Benchmark::test_benchmark_other_name());
 		assert_ok!(Benchmark::test_benchmark_sort_vector());
 		assert_err!(Benchmark::test_benchmark_bad_origin(), "Bad origin");
@@ -0,0 +1,126 @@
+//! Adapter exposing ERC20 contracts as currencies.
+
+use super::*;
+use sp_runtime::traits::Convert;
+
+/// Access to ERC20 contracts, e.g. through the EVM of Frontier.
+pub trait Erc20Bridge<AccountId, Balance> {
+	/// The address of an ERC20 contract.
+	type Address;
+
+	/// The `totalSupply` of `contract`.
+	fn total_supply(contract: &Self::Address) -> Balance;
+
+	/// The `balanceOf` `who` in `contract`.
+	fn balance_of(contract: &Self::Address, who: &AccountId) -> Balance;
+
+	/// Call `transfer` of `contract` on behalf of `from`.
+	fn transfer(contract: &Self::Address, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+/// Adapt ERC20 contracts to `MultiCurrency`.
+///
+/// Currencies which `ContractOf` maps to a contract address are handled by
+/// `Bridge`, all others by `Inner`. ERC20 currencies can't be minted, burned
+/// or slashed through this adapter.
+pub struct Erc20Adapter<T, Inner, Bridge, ContractOf>(marker::PhantomData<(T, Inner, Bridge, ContractOf)>);
+
+impl<T, Inner, Bridge, ContractOf> MultiCurrency<T::AccountId> for Erc20Adapter<T, Inner, Bridge, ContractOf>
+where
+	T: Config,
+	Inner: MultiCurrency<T::AccountId>,
+	Bridge: Erc20Bridge<T::AccountId, Inner::Balance>,
+	ContractOf: Convert<Inner::CurrencyId, Option<Bridge::Address>>,
+{
+	type CurrencyId = Inner::CurrencyId;
+	type Balance = Inner::Balance;
+
+	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
+		match ContractOf::convert(currency_id) {
+			Some(_) => Zero::zero(),
+			None => Inner::minimum_balance(currency_id),
+		}
+	}
+
+	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
+		match ContractOf::convert(currency_id) {
+			Some(contract) => Bridge::total_supply(&contract),
+			None => Inner::total_issuance(currency_id),
+		}
+	}
+
+	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		match ContractOf::convert(currency_id) {
+			Some(contract) => Bridge::balance_of(&contract, who),
+			None => Inner::total_balance(currency_id, who),
+		}
+	}
+
+	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		match ContractOf::convert(currency_id) {
+			Some(contract) => Bridge::balance_of(&contract, who),
+			None => Inner::free_balance(currency_id, who),
+		}
+	}
+
+	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		match ContractOf::convert(currency_id) {
+			Some(contract) => {
+				ensure!(Bridge::balance_of(&contract, who) >= amount, Error::<T>::BalanceTooLow);
+				Ok(())
+			}
+			None => Inner::ensure_can_withdraw(currency_id, who, amount),
+		}
+	}
+
+	fn transfer(
+		currency_id: Self::CurrencyId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		match ContractOf::convert(currency_id) {
+			Some(contract) => {
+				if amount.is_zero() || from == to {
+					return Ok(());
+				}
+				Bridge::transfer(&contract, from, to, amount)
+			}
+			None => Inner::transfer(currency_id, from, to, amount),
+		}
+	}
+
+	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		match ContractOf::convert(currency_id) {
+			Some(_) => {
+				ensure!(amount.is_zero(), Error::<T>::Erc20OperationNotSupported);
+				Ok(())
+			}
+			None => Inner::deposit(currency_id, who, amount),
+		}
+	}
+
+	fn withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		match ContractOf::convert(currency_id) {
+			Some(_) => {
+				ensure!(amount.is_zero(), Error::<T>::Erc20OperationNotSupported);
+				Ok(())
+			}
+			None => Inner::withdraw(currency_id, who, amount),
+		}
+	}
+
+	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
+		match ContractOf::convert(currency_id) {
+			Some(_) => value.is_zero(),
+			None => Inner::can_slash(currency_id, who, value),
+		}
+	}
+
+	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
+		match ContractOf::convert(currency_id) {
+			Some(_) => amount,
+			None => Inner::slash(currency_id, who, amount),
+		}
+	}
+}
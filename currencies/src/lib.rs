@@ -9,6 +9,9 @@
 //! It also provides an adapter, to adapt `frame_support::traits::Currency`
 //! implementations into `BasicCurrencyExtended`.
 //!
+//! With the `erc20` feature, `Erc20Adapter` exposes ERC20 contracts through
+//! `MultiCurrency`, using an `Erc20Bridge` implementation to call them.
+//!
 //! The currencies module provides functionality of both `MultiCurrencyExtended`
 //! and `BasicCurrencyExtended`, via unified interfaces, and all calls would be
 //! delegated to the underlying multi-currency and base currency system.
@@ -23,6 +26,8 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `CurrencyMetadata` - Name, symbol and decimals of the native currency
+//!   from the config, and of other currencies from `ForeignCurrencyMetadata`.
 //!
 //! ## Interface
 //!
@@ -35,6 +40,14 @@
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//! - `slash` - Slash the balance of an account, in a given currency,
+//!   `SlashOrigin` required.
+//! - `slash_reserved` - Slash the reserved balance of an account, in a given
+//!   currency, `SlashOrigin` required.
+//!
+//! Slashing through the `MultiCurrency` and `MultiReservableCurrency`
+//! implementations runs the `OnSlash` hook and emits an event for any
+//! currency, like the calls.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -51,22 +64,26 @@ use frame_support::{
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use orml_traits::{
 	arithmetic::{Signed, SimpleArithmetic},
-	currency::TransferAll,
+	currency::{OnSlash, TransferAll},
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
-	NamedBasicReservableCurrency, NamedMultiReservableCurrency,
+	CurrencyMetadata, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReservableCurrency, NamedBasicReservableCurrency, NamedMultiReservableCurrency,
 };
 use orml_utilities::with_transaction_result;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
+	traits::{CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
 	DispatchError, DispatchResult,
 };
-use sp_std::{fmt::Debug, marker, result};
+use sp_std::{fmt::Debug, marker, prelude::Vec, result};
 
+#[cfg(any(test, feature = "erc20"))]
+mod erc20;
 mod mock;
 mod tests;
 mod weights;
 
+#[cfg(any(test, feature = "erc20"))]
+pub use erc20::{Erc20Adapter, Erc20Bridge};
 pub use module::*;
 pub use weights::WeightInfo;
 
@@ -86,6 +103,8 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
 		type MultiCurrency: TransferAll<Self::AccountId>
 			+ MultiCurrencyExtended<Self::AccountId>
 			+ MultiLockableCurrency<Self::AccountId>
@@ -100,6 +119,30 @@ pub mod module {
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The name of the native currency.
+		#[pallet::constant]
+		type NativeCurrencyName: Get<Vec<u8>>;
+
+		/// The symbol of the native currency.
+		#[pallet::constant]
+		type NativeCurrencySymbol: Get<Vec<u8>>;
+
+		/// The decimals of the native currency.
+		#[pallet::constant]
+		type NativeCurrencyDecimals: Get<u32>;
+
+		/// Metadata of the non-native currencies, e.g. from the asset
+		/// registry via `RegistryCurrencyMetadata`.
+		type ForeignCurrencyMetadata: CurrencyMetadata<CurrencyIdOf<Self>>;
+
+		/// The origin which may slash balances with the `slash` and
+		/// `slash_reserved` calls.
+		type SlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Hook to run after slashing an account, in any currency, with the
+		/// amount actually slashed. Not run if nothing was slashed.
+		type OnSlash: OnSlash<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -112,6 +155,25 @@ pub mod module {
 		BalanceTooLow,
 		/// Deposit result is not expected
 		DepositFailed,
+		/// The operation is not supported for ERC20 currencies
+		Erc20OperationNotSupported,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Balance was slashed from an account, free balance first.
+		Slashed {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Reserved balance was slashed from an account.
+		ReserveSlashed {
+			currency_id: CurrencyIdOf<T>,
+			who: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::pallet]
@@ -170,6 +232,70 @@ pub mod module {
 			let dest = T::Lookup::lookup(who)?;
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)
 		}
+
+		/// Slash `amount` from account `who` under `currency_id`, free
+		/// balance first. Slashes as much as possible if the balance is too
+		/// low.
+		///
+		/// The dispatch origin of this call must be `SlashOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::slash())]
+		pub fn slash(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::SlashOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as MultiCurrency<T::AccountId>>::slash(currency_id, &who, amount);
+			Ok(())
+		}
+
+		/// Slash `amount` from the reserved balance of account `who` under
+		/// `currency_id`. Slashes as much as possible if the reserved balance
+		/// is too low.
+		///
+		/// The dispatch origin of this call must be `SlashOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::slash_reserved())]
+		pub fn slash_reserved(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::SlashOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as MultiReservableCurrency<T::AccountId>>::slash_reserved(currency_id, &who, amount);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> CurrencyMetadata<CurrencyIdOf<T>> for Pallet<T> {
+	fn name(currency_id: &CurrencyIdOf<T>) -> Option<Vec<u8>> {
+		if *currency_id == T::GetNativeCurrencyId::get() {
+			Some(T::NativeCurrencyName::get())
+		} else {
+			T::ForeignCurrencyMetadata::name(currency_id)
+		}
+	}
+
+	fn symbol(currency_id: &CurrencyIdOf<T>) -> Option<Vec<u8>> {
+		if *currency_id == T::GetNativeCurrencyId::get() {
+			Some(T::NativeCurrencySymbol::get())
+		} else {
+			T::ForeignCurrencyMetadata::symbol(currency_id)
+		}
+	}
+
+	fn decimals(currency_id: &CurrencyIdOf<T>) -> Option<u32> {
+		if *currency_id == T::GetNativeCurrencyId::get() {
+			Some(T::NativeCurrencyDecimals::get())
+		} else {
+			T::ForeignCurrencyMetadata::decimals(currency_id)
+		}
 	}
 }
 
@@ -264,11 +390,24 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
+		if amount.is_zero() {
+			return amount;
+		}
+		let remaining = if currency_id == T::GetNativeCurrencyId::get() {
 			T::NativeCurrency::slash(who, amount)
 		} else {
 			T::MultiCurrency::slash(currency_id, who, amount)
-		}
+		};
+		let slashed = amount.saturating_sub(remaining);
+		if !slashed.is_zero() {
+			T::OnSlash::on_slash(currency_id, who, slashed);
+			Self::deposit_event(Event::Slashed {
+				currency_id,
+				who: who.clone(),
+				amount: slashed,
+			});
+		}
+		remaining
 	}
 }
 
@@ -332,11 +471,24 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
+		if value.is_zero() {
+			return value;
+		}
+		let remaining = if currency_id == T::GetNativeCurrencyId::get() {
 			T::NativeCurrency::slash_reserved(who, value)
 		} else {
 			T::MultiCurrency::slash_reserved(currency_id, who, value)
+		};
+		let slashed = value.saturating_sub(remaining);
+		if !slashed.is_zero() {
+			T::OnSlash::on_slash(currency_id, who, slashed);
+			Self::deposit_event(Event::ReserveSlashed {
+				currency_id,
+				who: who.clone(),
+				amount: slashed,
+			});
 		}
+		remaining
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
@@ -387,11 +539,24 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
+		if value.is_zero() {
+			return value;
+		}
+		let remaining = if currency_id == T::GetNativeCurrencyId::get() {
 			T::NativeCurrency::slash_reserved_named(id, who, value)
 		} else {
 			T::MultiCurrency::slash_reserved_named(id, currency_id, who, value)
-		}
+		};
+		let slashed = value.saturating_sub(remaining);
+		if !slashed.is_zero() {
+			T::OnSlash::on_slash(currency_id, who, slashed);
+			Self::deposit_event(Event::ReserveSlashed {
+				currency_id,
+				who: who.clone(),
+				amount: slashed,
+			});
+		}
+		remaining
 	}
 
 	fn reserved_balance_named(
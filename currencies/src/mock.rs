@@ -5,7 +5,7 @@
 use super::*;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU32, ConstU64, Everything, Nothing},
+	traits::{ConstU32, ConstU64, Everything, GetDefault, Nothing},
 	PalletId,
 };
 use orml_traits::{currency::MutationHooks, parameter_type_with_key};
@@ -98,10 +98,22 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = CurrencyHooks<Runtime>;
+	type OnBalanceMovement = ();
 	type MaxLocks = ConstU32<100_000>;
 	type MaxReserves = ConstU32<100_000>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = Nothing;
+	type DustHandlingMode = GetDefault;
+	type MaxDustQueueLen = ();
+	type MaxDustPerBlock = ();
+	type MaxReapPerBlock = ();
+	type MaxIssuanceSnapshots = ();
+	type MaxSnapshotCurrencies = ();
+	type AccountCreation = GetDefault;
+	type AccountCreationWhitelist = Nothing;
+	type AssetAdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<50>;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
@@ -109,12 +121,48 @@ pub const X_TOKEN_ID: CurrencyId = 2;
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+	pub NativeCurrencyName: Vec<u8> = b"Native".to_vec();
+	pub NativeCurrencySymbol: Vec<u8> = b"NAT".to_vec();
+	pub const NativeCurrencyDecimals: u32 = 12;
+}
+
+pub struct MockForeignCurrencyMetadata;
+impl CurrencyMetadata<CurrencyId> for MockForeignCurrencyMetadata {
+	fn name(currency_id: &CurrencyId) -> Option<Vec<u8>> {
+		(*currency_id == X_TOKEN_ID).then(|| b"X Token".to_vec())
+	}
+
+	fn symbol(currency_id: &CurrencyId) -> Option<Vec<u8>> {
+		(*currency_id == X_TOKEN_ID).then(|| b"XT".to_vec())
+	}
+
+	fn decimals(currency_id: &CurrencyId) -> Option<u32> {
+		(*currency_id == X_TOKEN_ID).then_some(18)
+	}
+}
+
+parameter_types! {
+	pub static SlashHookCalls: Vec<(CurrencyId, AccountId, Balance)> = vec![];
+}
+
+pub struct MockOnSlash;
+impl OnSlash<AccountId, CurrencyId, Balance> for MockOnSlash {
+	fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		SlashHookCalls::mutate(|calls| calls.push((currency_id, who.clone(), amount)));
+	}
 }
 
 impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type NativeCurrencyName = NativeCurrencyName;
+	type NativeCurrencySymbol = NativeCurrencySymbol;
+	type NativeCurrencyDecimals = NativeCurrencyDecimals;
+	type ForeignCurrencyMetadata = MockForeignCurrencyMetadata;
+	type SlashOrigin = frame_system::EnsureRoot<AccountId>;
+	type OnSlash = MockOnSlash;
 	type WeightInfo = ();
 }
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
@@ -131,6 +179,52 @@ construct_runtime!(
 	}
 );
 
+pub const ERC20_TOKEN_ID: CurrencyId = 1_000;
+
+parameter_types! {
+	pub static Erc20Balances: std::collections::BTreeMap<(u32, AccountId), Balance> = Default::default();
+}
+
+pub struct MockErc20;
+impl Erc20Bridge<AccountId, Balance> for MockErc20 {
+	type Address = u32;
+
+	fn total_supply(contract: &u32) -> Balance {
+		Erc20Balances::get()
+			.iter()
+			.filter(|((c, _), _)| c == contract)
+			.map(|(_, balance)| balance)
+			.sum()
+	}
+
+	fn balance_of(contract: &u32, who: &AccountId) -> Balance {
+		Erc20Balances::get()
+			.get(&(*contract, who.clone()))
+			.copied()
+			.unwrap_or_default()
+	}
+
+	fn transfer(contract: &u32, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		let from_balance = Self::balance_of(contract, from)
+			.checked_sub(amount)
+			.ok_or(DispatchError::Other("ERC20: transfer amount exceeds balance"))?;
+		Erc20Balances::mutate(|balances| {
+			balances.insert((*contract, from.clone()), from_balance);
+			*balances.entry((*contract, to.clone())).or_default() += amount;
+		});
+		Ok(())
+	}
+}
+
+pub struct ContractOf;
+impl sp_runtime::traits::Convert<CurrencyId, Option<u32>> for ContractOf {
+	fn convert(currency_id: CurrencyId) -> Option<u32> {
+		currency_id.checked_sub(ERC20_TOKEN_ID)
+	}
+}
+
+pub type Erc20Currencies = Erc20Adapter<Runtime, Currencies, MockErc20, ContractOf>;
+
 pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
 pub const BOB: AccountId = AccountId32::new([2u8; 32]);
 pub const EVA: AccountId = AccountId32::new([5u8; 32]);
@@ -181,6 +275,7 @@ impl ExtBuilder {
 				.into_iter()
 				.filter(|(_, currency_id, _)| *currency_id != NATIVE_CURRENCY_ID)
 				.collect::<Vec<_>>(),
+			..Default::default()
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -221,7 +221,10 @@ fn native_currency_should_work() {
 			assert_eq!(NativeCurrency::free_balance(&ALICE), 40);
 			assert_eq!(NativeCurrency::free_balance(&BOB), 160);
 
-			assert_eq!(Currencies::slash(NATIVE_CURRENCY_ID, &ALICE, 10), 0);
+			assert_eq!(
+				<Currencies as MultiCurrency<AccountId>>::slash(NATIVE_CURRENCY_ID, &ALICE, 10),
+				0
+			);
 			assert_eq!(NativeCurrency::free_balance(&ALICE), 30);
 			assert_eq!(NativeCurrency::total_issuance(), 190);
 		});
@@ -356,6 +359,61 @@ fn update_balance_call_fails_if_not_root_origin() {
 	});
 }
 
+#[test]
+fn slash_calls_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(Currencies::slash(Some(ALICE).into(), BOB, X_TOKEN_ID, 10), BadOrigin);
+
+			assert_ok!(Currencies::slash(RuntimeOrigin::root(), ALICE, X_TOKEN_ID, 10));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::Slashed {
+				currency_id: X_TOKEN_ID,
+				who: ALICE,
+				amount: 10,
+			}));
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 90);
+
+			// slashes as much as possible
+			assert_ok!(<Currencies as MultiReservableCurrency<AccountId>>::reserve(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				20
+			));
+			assert_ok!(Currencies::slash_reserved(
+				RuntimeOrigin::root(),
+				ALICE,
+				NATIVE_CURRENCY_ID,
+				30
+			));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::ReserveSlashed {
+				currency_id: NATIVE_CURRENCY_ID,
+				who: ALICE,
+				amount: 20,
+			}));
+			assert_eq!(PalletBalances::reserved_balance(&ALICE), 0);
+			assert_eq!(PalletBalances::free_balance(&ALICE), 80);
+
+			// nothing left to slash, no hook call or event
+			let events = System::events().len();
+			assert_ok!(Currencies::slash_reserved(
+				RuntimeOrigin::root(),
+				ALICE,
+				NATIVE_CURRENCY_ID,
+				10
+			));
+			assert_eq!(System::events().len(), events);
+
+			assert_eq!(
+				SlashHookCalls::get(),
+				vec![(X_TOKEN_ID, ALICE, 10), (NATIVE_CURRENCY_ID, ALICE, 20)]
+			);
+		});
+}
+
 #[test]
 fn call_event_should_work() {
 	ExtBuilder::default()
@@ -407,3 +465,61 @@ fn call_event_should_work() {
 			}));
 		});
 }
+
+#[test]
+fn erc20_adapter_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			Erc20Balances::mutate(|balances| {
+				balances.insert((0, ALICE), 100);
+			});
+
+			assert_eq!(Erc20Currencies::total_issuance(ERC20_TOKEN_ID), 100);
+			assert_eq!(Erc20Currencies::free_balance(ERC20_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Erc20Currencies::minimum_balance(ERC20_TOKEN_ID), 0);
+			assert_ok!(Erc20Currencies::ensure_can_withdraw(ERC20_TOKEN_ID, &ALICE, 100));
+			assert_noop!(
+				Erc20Currencies::ensure_can_withdraw(ERC20_TOKEN_ID, &ALICE, 101),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Erc20Currencies::transfer(ERC20_TOKEN_ID, &ALICE, &BOB, 40));
+			assert_eq!(Erc20Currencies::free_balance(ERC20_TOKEN_ID, &ALICE), 60);
+			assert_eq!(Erc20Currencies::total_balance(ERC20_TOKEN_ID, &BOB), 40);
+
+			assert_noop!(
+				Erc20Currencies::deposit(ERC20_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::Erc20OperationNotSupported
+			);
+			assert_noop!(
+				Erc20Currencies::withdraw(ERC20_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::Erc20OperationNotSupported
+			);
+			assert!(!Erc20Currencies::can_slash(ERC20_TOKEN_ID, &ALICE, 10));
+			assert_eq!(Erc20Currencies::slash(ERC20_TOKEN_ID, &ALICE, 10), 10);
+
+			// other currencies are handled by the inner implementation
+			assert_ok!(Erc20Currencies::transfer(X_TOKEN_ID, &ALICE, &BOB, 50));
+			assert_eq!(Erc20Currencies::free_balance(X_TOKEN_ID, &BOB), 150);
+			assert_eq!(Erc20Currencies::free_balance(NATIVE_CURRENCY_ID, &ALICE), 100);
+		});
+}
+
+#[test]
+fn currency_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Currencies::name(&NATIVE_CURRENCY_ID), Some(b"Native".to_vec()));
+		assert_eq!(Currencies::symbol(&NATIVE_CURRENCY_ID), Some(b"NAT".to_vec()));
+		assert_eq!(Currencies::decimals(&NATIVE_CURRENCY_ID), Some(12));
+
+		assert_eq!(Currencies::name(&X_TOKEN_ID), Some(b"X Token".to_vec()));
+		assert_eq!(Currencies::symbol(&X_TOKEN_ID), Some(b"XT".to_vec()));
+		assert_eq!(Currencies::decimals(&X_TOKEN_ID), Some(18));
+
+		assert_eq!(Currencies::name(&3), None);
+		assert_eq!(Currencies::symbol(&3), None);
+		assert_eq!(Currencies::decimals(&3), None);
+	});
+}
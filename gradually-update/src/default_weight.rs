@@ -23,4 +23,16 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(3 as u64))
 			.saturating_add(DbWeight::get().writes(3 as u64))
 	}
+	fn gradually_update_group(u: u32) -> Weight {
+		Weight::from_parts(57_922_000, 0)
+			.saturating_add(Weight::from_parts(20_890_000, 0).saturating_mul(u as u64))
+			.saturating_add(DbWeight::get().reads(3 as u64))
+			.saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(u as u64)))
+			.saturating_add(DbWeight::get().writes(2 as u64))
+	}
+	fn cancel_gradually_update_group() -> Weight {
+		Weight::from_parts(66_687_000, 0)
+			.saturating_add(DbWeight::get().reads(1 as u64))
+			.saturating_add(DbWeight::get().writes(1 as u64))
+	}
 }
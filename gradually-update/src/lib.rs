@@ -12,6 +12,10 @@
 //! smooth transition. It is also possible to cancel an update before it reaches
 //! to target value.
 //!
+//! Related values, such as the bounds of a parameter, can be scheduled as a
+//! group. All updates of a group advance in the same block, and if any of them
+//! cannot be applied none of them is written and the group is aborted.
+//!
 //! NOTE: Only unsigned integer value up to 128 bits are supported. But a
 //! "newtype" pattern struct that wraps an unsigned integer works too such as
 //! `Permill` and `FixedU128`.
@@ -35,6 +39,7 @@ use sp_runtime::{
 	traits::{SaturatedConversion, Saturating},
 	DispatchResult, RuntimeDebug,
 };
+use sp_std::vec::Vec;
 
 mod default_weight;
 mod mock;
@@ -60,12 +65,18 @@ pub mod module {
 		fn gradually_update() -> Weight;
 		fn cancel_gradually_update() -> Weight;
 		fn on_finalize(u: u32) -> Weight;
+		fn gradually_update_group(u: u32) -> Weight;
+		fn cancel_gradually_update_group() -> Weight;
 	}
 
 	pub(crate) type StorageKeyBytes<T> = BoundedVec<u8, <T as Config>::MaxStorageKeyBytes>;
 	pub(crate) type StorageValueBytes<T> = BoundedVec<u8, <T as Config>::MaxStorageValueBytes>;
 
 	type GraduallyUpdateOf<T> = GraduallyUpdate<StorageKeyBytes<T>, StorageValueBytes<T>>;
+	pub(crate) type GraduallyUpdateGroupOf<T> = BoundedVec<GraduallyUpdateOf<T>, <T as Config>::MaxGroupSize>;
+
+	/// The identifier of a group of updates.
+	pub type GroupId = u32;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -89,6 +100,9 @@ pub mod module {
 
 		/// Maximum size of storage value
 		type MaxStorageValueBytes: Get<u32>;
+
+		/// Maximum updates in a group
+		type MaxGroupSize: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -107,6 +121,12 @@ pub mod module {
 		MaxStorageKeyBytesExceeded,
 		/// Maximum value size exceeded
 		MaxStorageValueBytesExceeded,
+		/// A group has no updates or updates the same key twice.
+		InvalidGraduallyUpdateGroup,
+		/// No group exists to cancel.
+		GraduallyUpdateGroupNotFound,
+		/// No group ID available.
+		NoAvailableGroupId,
 	}
 
 	#[pallet::event]
@@ -126,6 +146,16 @@ pub mod module {
 			key: StorageKeyBytes<T>,
 			target_value: StorageValueBytes<T>,
 		},
+		/// Gradually update group added.
+		GraduallyUpdateGroupAdded { id: GroupId },
+		/// Gradually update group cancelled.
+		GraduallyUpdateGroupCancelled { id: GroupId },
+		/// Gradually update group aborted without writing any of its values,
+		/// because one of them could not be applied.
+		GraduallyUpdateGroupAborted { id: GroupId },
+		/// Gradually update aborted, because the stored value no longer
+		/// matches it.
+		GraduallyUpdateAborted { key: StorageKeyBytes<T> },
 	}
 
 	/// All the on-going updates
@@ -134,6 +164,17 @@ pub mod module {
 	pub(crate) type GraduallyUpdates<T: Config> =
 		StorageValue<_, BoundedVec<GraduallyUpdateOf<T>, T::MaxGraduallyUpdate>, ValueQuery>;
 
+	/// All the on-going groups of updates
+	#[pallet::storage]
+	#[pallet::getter(fn gradually_update_groups)]
+	pub(crate) type GraduallyUpdateGroups<T: Config> =
+		StorageValue<_, BoundedVec<(GroupId, GraduallyUpdateGroupOf<T>), T::MaxGraduallyUpdate>, ValueQuery>;
+
+	/// The ID of the next group of updates
+	#[pallet::storage]
+	#[pallet::getter(fn next_group_id)]
+	pub(crate) type NextGroupId<T: Config> = StorageValue<_, GroupId, ValueQuery>;
+
 	/// The last updated block number
 	#[pallet::storage]
 	#[pallet::getter(fn last_updated_at)]
@@ -147,7 +188,11 @@ pub mod module {
 		/// `on_initialize` to return the weight used in `on_finalize`.
 		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
 			if Self::_need_update(now) {
-				T::WeightInfo::on_finalize(GraduallyUpdates::<T>::get().len() as u32)
+				let grouped_updates: usize = GraduallyUpdateGroups::<T>::get()
+					.iter()
+					.map(|(_, group)| group.len())
+					.sum();
+				T::WeightInfo::on_finalize(GraduallyUpdates::<T>::get().len().saturating_add(grouped_updates) as u32)
 			} else {
 				Weight::zero()
 			}
@@ -167,19 +212,8 @@ pub mod module {
 		pub fn gradually_update(origin: OriginFor<T>, update: GraduallyUpdateOf<T>) -> DispatchResult {
 			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
 
-			// Support max value is u128, ensure per_block and target_value <= 16 bytes.
-			ensure!(
-				update.per_block.len() == update.target_value.len() && update.per_block.len() <= 16,
-				Error::<T>::InvalidPerBlockOrTargetValue
-			);
-
-			if storage::unhashed::exists(&update.key) {
-				let current_value = storage::unhashed::get::<StorageValueBytes<T>>(&update.key).unwrap();
-				ensure!(
-					current_value.len() == update.target_value.len(),
-					Error::<T>::InvalidTargetValue
-				);
-			}
+			Self::ensure_valid_update(&update)?;
+			ensure!(!Self::is_grouped(&update.key), Error::<T>::GraduallyUpdateHasExisted);
 
 			GraduallyUpdates::<T>::try_mutate(|gradually_updates| -> DispatchResult {
 				ensure!(
@@ -220,6 +254,64 @@ pub mod module {
 			Self::deposit_event(Event::GraduallyUpdateCancelled { key });
 			Ok(())
 		}
+
+		/// Add a group of gradually_updates that advance together.
+		///
+		/// The keys of the group must be distinct and not used by any other
+		/// scheduled update.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::gradually_update_group(updates.len() as u32))]
+		pub fn gradually_update_group(origin: OriginFor<T>, updates: GraduallyUpdateGroupOf<T>) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			ensure!(!updates.is_empty(), Error::<T>::InvalidGraduallyUpdateGroup);
+			let gradually_updates = GraduallyUpdates::<T>::get();
+			for (i, update) in updates.iter().enumerate() {
+				Self::ensure_valid_update(update)?;
+				ensure!(
+					updates.iter().skip(i + 1).all(|other| other.key != update.key),
+					Error::<T>::InvalidGraduallyUpdateGroup
+				);
+				ensure!(
+					!Self::is_grouped(&update.key) && !gradually_updates.iter().any(|item| item.key == update.key),
+					Error::<T>::GraduallyUpdateHasExisted
+				);
+			}
+
+			let id = NextGroupId::<T>::try_mutate(|next_id| -> Result<GroupId, DispatchError> {
+				let id = *next_id;
+				*next_id = next_id.checked_add(1).ok_or(Error::<T>::NoAvailableGroupId)?;
+				Ok(id)
+			})?;
+
+			GraduallyUpdateGroups::<T>::try_mutate(|groups| {
+				groups
+					.try_push((id, updates))
+					.map_err(|_| Error::<T>::MaxGraduallyUpdateExceeded)
+			})?;
+
+			Self::deposit_event(Event::GraduallyUpdateGroupAdded { id });
+			Ok(())
+		}
+
+		/// Cancel a group of gradually_updates.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::cancel_gradually_update_group())]
+		pub fn cancel_gradually_update_group(origin: OriginFor<T>, id: GroupId) -> DispatchResult {
+			T::DispatchOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+
+			GraduallyUpdateGroups::<T>::try_mutate(|groups| -> DispatchResult {
+				let old_len = groups.len();
+				groups.retain(|(group_id, _)| *group_id != id);
+
+				ensure!(groups.len() != old_len, Error::<T>::GraduallyUpdateGroupNotFound);
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::GraduallyUpdateGroupCancelled { id });
+			Ok(())
+		}
 	}
 }
 
@@ -237,52 +329,126 @@ impl<T: Config> Pallet<T> {
 		let initial_count = gradually_updates.len();
 
 		gradually_updates.retain(|update| {
-			let mut keep = true;
-			let current_value = storage::unhashed::get::<StorageValueBytes<T>>(&update.key).unwrap_or_default();
-			let current_value_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&current_value));
-
-			let frequency_u128: u128 = T::UpdateFrequency::get().saturated_into();
-
-			let step = u128::from_le_bytes(Self::convert_vec_to_u8(&update.per_block));
-			let step_u128 = step.checked_mul(frequency_u128).unwrap();
-
-			let target_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&update.target_value));
-
-			let new_value_u128 = if current_value_u128 > target_u128 {
-				(current_value_u128.checked_sub(step_u128).unwrap()).max(target_u128)
-			} else {
-				(current_value_u128.checked_add(step_u128).unwrap()).min(target_u128)
+			let Some((value, reached_target)) = Self::next_value(update) else {
+				Self::deposit_event(Event::GraduallyUpdateAborted {
+					key: update.key.clone(),
+				});
+				return false;
 			};
 
-			// current_value equal target_value, remove gradually_update
-			if new_value_u128 == target_u128 {
-				keep = false;
-			}
-
-			let mut value = new_value_u128.encode();
-			value.truncate(update.target_value.len());
-
 			storage::unhashed::put(&update.key, &value);
 
-			let bounded_value: StorageValueBytes<T> = value.to_vec().try_into().unwrap();
-
 			Self::deposit_event(Event::Updated {
 				block_number: now,
 				key: update.key.clone(),
-				target_value: bounded_value,
+				target_value: value,
 			});
 
-			keep
+			// current_value equal target_value, remove gradually_update
+			!reached_target
 		});
 
-		// gradually_update has finished. Remove it from GraduallyUpdates.
+		// gradually_update has finished or was aborted. Remove it from
+		// GraduallyUpdates.
 		if gradually_updates.len() < initial_count {
 			GraduallyUpdates::<T>::put(gradually_updates);
 		}
 
+		let mut groups = GraduallyUpdateGroups::<T>::get();
+		let initial_group_count = groups.len();
+
+		groups.retain(|(id, group)| Self::update_group(now, *id, group));
+
+		// groups that have finished or were aborted
+		if groups.len() < initial_group_count {
+			GraduallyUpdateGroups::<T>::put(groups);
+		}
+
 		LastUpdatedAt::<T>::put(now);
 	}
 
+	fn ensure_valid_update(update: &GraduallyUpdateOf<T>) -> DispatchResult {
+		// Support max value is u128, ensure per_block and target_value <= 16 bytes.
+		ensure!(
+			update.per_block.len() == update.target_value.len() && update.per_block.len() <= 16,
+			Error::<T>::InvalidPerBlockOrTargetValue
+		);
+
+		if storage::unhashed::exists(&update.key) {
+			let current_value = storage::unhashed::get::<StorageValueBytes<T>>(&update.key).unwrap();
+			ensure!(
+				current_value.len() == update.target_value.len(),
+				Error::<T>::InvalidTargetValue
+			);
+		}
+
+		Ok(())
+	}
+
+	fn is_grouped(key: &StorageKeyBytes<T>) -> bool {
+		GraduallyUpdateGroups::<T>::get()
+			.iter()
+			.any(|(_, group)| group.iter().any(|update| update.key == *key))
+	}
+
+	/// Advances all updates of the group, or none of them if any fails.
+	///
+	/// Returns whether the group should be kept.
+	fn update_group(now: BlockNumberFor<T>, id: GroupId, group: &GraduallyUpdateGroupOf<T>) -> bool {
+		let Some(new_values) = group
+			.iter()
+			.map(Self::next_value)
+			.collect::<Option<Vec<(StorageValueBytes<T>, bool)>>>()
+		else {
+			Self::deposit_event(Event::GraduallyUpdateGroupAborted { id });
+			return false;
+		};
+
+		let mut finished = true;
+		for (update, (value, reached_target)) in group.iter().zip(new_values) {
+			finished = finished && reached_target;
+			storage::unhashed::put(&update.key, &value);
+
+			Self::deposit_event(Event::Updated {
+				block_number: now,
+				key: update.key.clone(),
+				target_value: value,
+			});
+		}
+
+		!finished
+	}
+
+	/// The value after one step of `update` and whether it reaches the
+	/// target, or `None` if the stored value no longer matches the update.
+	fn next_value(update: &GraduallyUpdateOf<T>) -> Option<(StorageValueBytes<T>, bool)> {
+		let current_value = if storage::unhashed::exists(&update.key) {
+			storage::unhashed::get::<StorageValueBytes<T>>(&update.key)
+				.filter(|value| value.len() == update.target_value.len())?
+		} else {
+			Default::default()
+		};
+		let current_value_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&current_value));
+
+		let frequency_u128: u128 = T::UpdateFrequency::get().saturated_into();
+
+		let step = u128::from_le_bytes(Self::convert_vec_to_u8(&update.per_block));
+		let step_u128 = step.saturating_mul(frequency_u128);
+
+		let target_u128 = u128::from_le_bytes(Self::convert_vec_to_u8(&update.target_value));
+
+		let new_value_u128 = if current_value_u128 > target_u128 {
+			current_value_u128.saturating_sub(step_u128).max(target_u128)
+		} else {
+			current_value_u128.saturating_add(step_u128).min(target_u128)
+		};
+
+		let mut value = new_value_u128.encode();
+		value.truncate(update.target_value.len());
+
+		Some((value.try_into().ok()?, new_value_u128 == target_u128))
+	}
+
 	#[allow(clippy::ptr_arg)]
 	fn convert_vec_to_u8(input: &StorageValueBytes<T>) -> [u8; 16] {
 		let mut array: [u8; 16] = [0; 16];
@@ -48,6 +48,7 @@ impl Config for Runtime {
 	type MaxGraduallyUpdate = ConstU32<3>;
 	type MaxStorageKeyBytes = ConstU32<100_000>;
 	type MaxStorageValueBytes = ConstU32<100_000>;
+	type MaxGroupSize = ConstU32<2>;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -479,3 +479,167 @@ fn exceeding_max_gradually_updates_should_fail() {
 		assert_eq!(storage_get(&update4.key), vec![120]);
 	});
 }
+
+#[test]
+fn gradually_update_group_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let min: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		let max: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![2].try_into().unwrap(),
+			target_value: vec![60].try_into().unwrap(),
+			per_block: vec![2].try_into().unwrap(),
+		};
+
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_group(RuntimeOrigin::root(), vec![].try_into().unwrap()),
+			Error::<Runtime>::InvalidGraduallyUpdateGroup
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_group(
+				RuntimeOrigin::root(),
+				vec![min.clone(), min.clone()].try_into().unwrap()
+			),
+			Error::<Runtime>::InvalidGraduallyUpdateGroup
+		);
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_group(
+			RuntimeOrigin::root(),
+			vec![min.clone(), max.clone()].try_into().unwrap()
+		));
+		System::assert_last_event(RuntimeEvent::GraduallyUpdateModule(
+			crate::Event::GraduallyUpdateGroupAdded { id: 0 },
+		));
+
+		// grouped keys cannot be scheduled again
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update(RuntimeOrigin::root(), min.clone()),
+			Error::<Runtime>::GraduallyUpdateHasExisted
+		);
+		assert_noop!(
+			GraduallyUpdateModule::gradually_update_group(RuntimeOrigin::root(), vec![max.clone()].try_into().unwrap()),
+			Error::<Runtime>::GraduallyUpdateHasExisted
+		);
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(storage_get(&min.key), vec![10]);
+		assert_eq!(storage_get(&max.key), vec![20]);
+
+		GraduallyUpdateModule::on_finalize(20);
+		assert_eq!(storage_get(&min.key), vec![20]);
+		assert_eq!(storage_get(&max.key), vec![40]);
+
+		// the group finishes once all values reach their targets
+		GraduallyUpdateModule::on_finalize(30);
+		assert_eq!(storage_get(&min.key), vec![30]);
+		assert_eq!(storage_get(&max.key), vec![60]);
+		assert!(GraduallyUpdateModule::gradually_update_groups().is_empty());
+		System::assert_last_event(RuntimeEvent::GraduallyUpdateModule(crate::Event::Updated {
+			block_number: 30,
+			key: max.key,
+			target_value: vec![60].try_into().unwrap(),
+		}));
+	});
+}
+
+#[test]
+fn gradually_update_group_should_abort() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let min: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		let max: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![2].try_into().unwrap(),
+			target_value: vec![60].try_into().unwrap(),
+			per_block: vec![2].try_into().unwrap(),
+		};
+		assert_ok!(GraduallyUpdateModule::gradually_update_group(
+			RuntimeOrigin::root(),
+			vec![min.clone(), max.clone()].try_into().unwrap()
+		));
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(storage_get(&min.key), vec![10]);
+		assert_eq!(storage_get(&max.key), vec![20]);
+
+		// `max` can no longer be updated, so `min` is not written either
+		storage_set(&max.key, &20u32.encode());
+		GraduallyUpdateModule::on_finalize(20);
+		assert_eq!(storage_get(&min.key), vec![10]);
+		assert_eq!(storage_get(&max.key), vec![20, 0, 0, 0]);
+		assert!(GraduallyUpdateModule::gradually_update_groups().is_empty());
+		System::assert_last_event(RuntimeEvent::GraduallyUpdateModule(
+			crate::Event::GraduallyUpdateGroupAborted { id: 0 },
+		));
+	});
+}
+
+#[test]
+fn gradually_update_should_abort() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![30].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		assert_ok!(GraduallyUpdateModule::gradually_update(
+			RuntimeOrigin::root(),
+			update.clone()
+		));
+
+		GraduallyUpdateModule::on_finalize(10);
+		assert_eq!(storage_get(&update.key), vec![10]);
+
+		// the stored value no longer matches the update
+		storage_set(&update.key, &20u32.encode());
+		GraduallyUpdateModule::on_finalize(20);
+		assert_eq!(storage_get(&update.key), vec![20, 0, 0, 0]);
+		assert!(GraduallyUpdateModule::gradually_updates().is_empty());
+		System::assert_last_event(RuntimeEvent::GraduallyUpdateModule(
+			crate::Event::GraduallyUpdateAborted { key: update.key },
+		));
+	});
+}
+
+#[test]
+fn cancel_gradually_update_group_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let update: GraduallyUpdate<StorageKeyBytes<Runtime>, StorageValueBytes<Runtime>> = GraduallyUpdate {
+			key: vec![1].try_into().unwrap(),
+			target_value: vec![9].try_into().unwrap(),
+			per_block: vec![1].try_into().unwrap(),
+		};
+		assert_noop!(
+			GraduallyUpdateModule::cancel_gradually_update_group(RuntimeOrigin::root(), 0),
+			Error::<Runtime>::GraduallyUpdateGroupNotFound
+		);
+
+		assert_ok!(GraduallyUpdateModule::gradually_update_group(
+			RuntimeOrigin::root(),
+			vec![update.clone()].try_into().unwrap()
+		));
+		assert_ok!(GraduallyUpdateModule::cancel_gradually_update_group(
+			RuntimeOrigin::root(),
+			0
+		));
+		System::assert_last_event(RuntimeEvent::GraduallyUpdateModule(
+			crate::Event::GraduallyUpdateGroupCancelled { id: 0 },
+		));
+
+		// the key is free again
+		assert_ok!(GraduallyUpdateModule::gradually_update(RuntimeOrigin::root(), update));
+	});
+}
@@ -17,6 +17,43 @@
 //! - `mint` - Mint NFT(non fungible token)
 //! - `burn` - Burn NFT(non fungible token)
 //! - `destroy_class` - Destroy NFT(non fungible token) class
+//! - `start_destroy_class` - Destroy NFT(non fungible token) class with
+//!   remaining tokens, which are removed in pages by `on_idle`
+//! - `destroy_class_tokens` - Remove a page of tokens of a class being destroyed
+//! - `mint_as` / `burn_as` - Mint or burn on behalf of an account holding the
+//!   class `Issuer` role
+//! - `set_class_metadata` / `set_token_metadata` - Update metadata on behalf of
+//!   an account holding the class `Admin` role
+//! - `create_class_with_token_id_strategy` - Create NFT(non fungible token)
+//!   class whose token ids are assigned by the given `TokenIdStrategy`
+//! - `mint_with_id` - Mint NFT(non fungible token) with a caller-supplied id
+//!   into a class using the `CallerSupplied` strategy
+//!
+//! ### Dispatchable Functions
+//!
+//! - `transfer_class_role` - Hand a class role over to another account
+//! - `renounce_class_role` - Give up a delegated `Issuer` or `Admin` role
+//! - `mint_with_authorization` - Mint a token with a mint authorization signed
+//!   off-chain by an account holding the class `Issuer` role
+//! - `set_user` / `clear_user` - Set or clear the time-limited user of a token
+//! - `freeze_class_metadata` / `freeze_token_metadata` - Make class or token
+//!   metadata immutable
+//! - `set_class_royalty` - Set or clear the royalty of the tokens of a class
+//!
+//! ### Class Roles
+//!
+//! The class `Owner` is stored in the class info and implicitly holds every
+//! role. The `Issuer` role allows minting and burning tokens of the class and
+//! the `Admin` role allows updating class and token metadata. Both can be
+//! delegated to other accounts, so that minting rights can be split from
+//! administrative rights.
+//!
+//! ### Token Users
+//!
+//! The owner of a token can set a user of it, distinct from the owner, until
+//! an expiry block, e.g. to rent out a game item without transferring it. The
+//! user is exposed via the `NftUser` trait and no longer returned once
+//! expired. It is cleared when the token is transferred or burned.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -24,15 +61,22 @@
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{ensure, pallet_prelude::*, traits::Get, BoundedVec, Parameter};
 use frame_system::pallet_prelude::*;
+use orml_traits::NftUser;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One, Zero},
-	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
+	traits::{
+		AtLeast32BitUnsigned, CheckedAdd, CheckedSub, Hash, IdentifyAccount, MaybeSerializeDeserialize, Member, One,
+		TrailingZeroInput, Verify, Zero,
+	},
+	ArithmeticError, DispatchError, DispatchResult, PerThing, Perbill, RuntimeDebug,
 };
 use sp_std::vec::Vec;
 
 mod mock;
 mod tests;
+mod weights;
+
+pub use weights::WeightInfo;
 
 /// Class info
 #[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
@@ -58,6 +102,49 @@ pub struct TokenInfo<AccountId, Data, TokenMetadataOf> {
 	pub data: Data,
 }
 
+/// How the ids of the tokens minted into a class are assigned
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum TokenIdStrategy {
+	/// Ids are assigned sequentially per class
+	#[default]
+	Sequential,
+	/// Ids are derived from the hash of the token metadata
+	MetadataHash,
+	/// Ids are supplied by the caller of `mint_with_id`, e.g. to preserve the
+	/// ids of a collection migrated from another chain
+	CallerSupplied,
+}
+
+/// Class level roles
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum ClassRole {
+	/// Owns the class and implicitly holds every other role
+	Owner,
+	/// Can mint and burn tokens of the class
+	Issuer,
+	/// Can update class and token metadata
+	Admin,
+}
+
+/// An authorization to mint a token, signed off-chain by an account holding
+/// the `Issuer` role of the class
+#[derive(Encode, Decode, Clone, Eq, PartialEq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct MintAuthorization<ClassId, AccountId, Hash, BlockNumber> {
+	/// The class of the token
+	pub class_id: ClassId,
+	/// Hash of the token `(metadata, data)`
+	pub metadata_hash: Hash,
+	/// The owner of the minted token
+	pub beneficiary: AccountId,
+	/// The last block the authorization can be used at
+	pub expiry: BlockNumber,
+	/// Replay protection, each nonce can only be used once per class
+	pub nonce: u64,
+}
+
+/// The tag mint authorization payloads are prefixed with.
+pub const MINT_AUTHORIZATION_TAG: &[u8; 18] = b"orml-nft:mint-auth";
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -66,6 +153,7 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// The class ID type
 		type ClassId: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
 		/// The token ID type
@@ -78,10 +166,26 @@ pub mod module {
 		type MaxClassMetadata: Get<u32>;
 		/// The maximum size of a token's metadata
 		type MaxTokenMetadata: Get<u32>;
+		/// The maximum number of tokens of classes being destroyed, and of
+		/// used mint nonces of destroyed classes, removed in `on_idle` per
+		/// block
+		type MaxDestroyTokensPerBlock: Get<u32>;
+		/// The signature type of mint authorizations
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+		/// The public key type of mint authorization signers
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
 	pub type ClassMetadataOf<T> = BoundedVec<u8, <T as Config>::MaxClassMetadata>;
 	pub type TokenMetadataOf<T> = BoundedVec<u8, <T as Config>::MaxTokenMetadata>;
+	pub type MintAuthorizationOf<T> = MintAuthorization<
+		<T as Config>::ClassId,
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::Hash,
+		BlockNumberFor<T>,
+	>;
 	pub type ClassInfoOf<T> = ClassInfo<
 		<T as Config>::TokenId,
 		<T as frame_system::Config>::AccountId,
@@ -121,6 +225,76 @@ pub mod module {
 		CannotDestroyClass,
 		/// Failed because the Maximum amount of metadata was exceeded
 		MaxMetadataExceeded,
+		/// The class is being destroyed
+		ClassDestroying,
+		/// The class is not being destroyed
+		ClassNotDestroying,
+		/// The class owner can only transfer the `Owner` role
+		CannotRenounceOwner,
+		/// The mint authorization has expired
+		AuthorizationExpired,
+		/// The nonce of the mint authorization was already used
+		AuthorizationUsed,
+		/// The signature of the mint authorization is invalid
+		InvalidSignature,
+		/// The token metadata and data do not match the mint authorization
+		MetadataHashMismatch,
+		/// The metadata is frozen and can no longer be updated
+		MetadataFrozen,
+		/// A token with the same id already exists in the class
+		TokenAlreadyExists,
+		/// The token id strategy of the class does not support this way of
+		/// minting
+		TokenIdStrategyMismatch,
+		/// The expiry of a token user must be in the future
+		InvalidUserExpiry,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A class role was transferred to a new account.
+		ClassRoleTransferred {
+			class_id: T::ClassId,
+			role: ClassRole,
+			from: T::AccountId,
+			to: T::AccountId,
+		},
+		/// A class role was renounced.
+		ClassRoleRenounced {
+			class_id: T::ClassId,
+			role: ClassRole,
+			who: T::AccountId,
+		},
+		/// A token was minted with a mint authorization.
+		MintedWithAuthorization {
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			issuer: T::AccountId,
+			beneficiary: T::AccountId,
+		},
+		/// The metadata of a class was frozen.
+		ClassMetadataFrozen { class_id: T::ClassId, who: T::AccountId },
+		/// The metadata of a token was frozen.
+		TokenMetadataFrozen {
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			who: T::AccountId,
+		},
+		/// The user of a token was set.
+		TokenUserSet {
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			user: T::AccountId,
+			expires: BlockNumberFor<T>,
+		},
+		/// The user of a token was cleared.
+		TokenUserCleared { class_id: T::ClassId, token_id: T::TokenId },
+		/// The royalty of a class was set or cleared.
+		ClassRoyaltySet {
+			class_id: T::ClassId,
+			royalty: Option<(T::AccountId, Perbill)>,
+		},
 	}
 
 	/// Next available class ID.
@@ -148,6 +322,72 @@ pub mod module {
 	pub type Tokens<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, TokenInfoOf<T>>;
 
+	/// Classes being destroyed, whose tokens are removed in pages.
+	#[pallet::storage]
+	#[pallet::getter(fn destroying_classes)]
+	pub type DestroyingClasses<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, (), OptionQuery>;
+
+	/// Accounts the `Issuer` and `Admin` roles of a class are delegated to.
+	///
+	/// Returns `None` if the role is not delegated, in which case only the
+	/// class owner holds it.
+	#[pallet::storage]
+	#[pallet::getter(fn class_roles)]
+	pub type ClassRoles<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, ClassRole, T::AccountId, OptionQuery>;
+
+	/// Nonces of mint authorizations already used in a class.
+	#[pallet::storage]
+	#[pallet::getter(fn used_mint_nonces)]
+	pub type UsedMintNonces<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, u64, (), OptionQuery>;
+
+	/// Destroyed classes whose used mint nonces are removed in pages by
+	/// `on_idle`, with the cursor to continue from.
+	#[pallet::storage]
+	#[pallet::getter(fn clearing_mint_nonces)]
+	pub type ClearingMintNonces<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, Vec<u8>, OptionQuery>;
+
+	/// Classes whose metadata is frozen and can no longer be updated.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_class_metadata)]
+	pub type FrozenClassMetadata<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, (), OptionQuery>;
+
+	/// Tokens whose metadata is frozen and can no longer be updated.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_token_metadata)]
+	pub type FrozenTokenMetadata<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::ClassId, Twox64Concat, T::TokenId, (), OptionQuery>;
+
+	/// The royalty of the tokens of a class: the beneficiary and its part of
+	/// the sale price.
+	///
+	/// Returns `None` if the class has no royalty.
+	#[pallet::storage]
+	#[pallet::getter(fn class_royalty)]
+	pub type ClassRoyalties<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, (T::AccountId, Perbill), OptionQuery>;
+
+	/// The token id strategy of a class.
+	#[pallet::storage]
+	#[pallet::getter(fn token_id_strategy)]
+	pub type ClassTokenIdStrategies<T: Config> = StorageMap<_, Twox64Concat, T::ClassId, TokenIdStrategy, ValueQuery>;
+
+	/// The user of a token and the block it expires at.
+	///
+	/// Returns `None` if the token has no user. An expired user is kept until
+	/// it is replaced or cleared.
+	#[pallet::storage]
+	#[pallet::getter(fn token_users)]
+	pub type TokenUsers<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::ClassId,
+		Twox64Concat,
+		T::TokenId,
+		(T::AccountId, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
 	/// Token existence check by owner and class ID.
 	#[pallet::storage]
 	#[pallet::getter(fn tokens_by_owner)]
@@ -194,10 +434,202 @@ pub mod module {
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let weight = Self::idle_destroy_class_tokens(remaining_weight);
+			weight.saturating_add(Self::idle_clear_mint_nonces(remaining_weight.saturating_sub(weight)))
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove a page of tokens of a class being destroyed.
+		fn idle_destroy_class_tokens(remaining_weight: Weight) -> Weight {
+			// read the class being destroyed, write the class info, and remove its
+			// settings, royalty and roles with the last page
+			let base_weight = T::DbWeight::get().reads_writes(2, 9);
+			// read the token, remove it, its owner index, its frozen metadata flag
+			// and its user
+			let per_token_weight = T::DbWeight::get().reads_writes(1, 4);
+			if remaining_weight.any_lt(base_weight) {
+				return Weight::zero();
+			}
+			let class_id = match DestroyingClasses::<T>::iter_keys().next() {
+				Some(class_id) => class_id,
+				None => return T::DbWeight::get().reads(1),
+			};
+
+			let available = remaining_weight.saturating_sub(base_weight);
+			let mut limit = T::MaxDestroyTokensPerBlock::get() as u64;
+			if per_token_weight.ref_time() > 0 {
+				limit = limit.min(available.ref_time() / per_token_weight.ref_time());
+			}
+			if per_token_weight.proof_size() > 0 {
+				limit = limit.min(available.proof_size() / per_token_weight.proof_size());
+			}
+
+			match Self::destroy_class_tokens(class_id, limit as u32) {
+				Ok((removed, _)) => base_weight.saturating_add(per_token_weight.saturating_mul(removed.into())),
+				Err(_) => {
+					// the class can't be found, drop it so the next classes are processed
+					DestroyingClasses::<T>::remove(class_id);
+					base_weight
+				}
+			}
+		}
+
+		/// Remove a page of used mint nonces of a destroyed class.
+		fn idle_clear_mint_nonces(remaining_weight: Weight) -> Weight {
+			// read the cursor and update it
+			let base_weight = T::DbWeight::get().reads_writes(1, 1);
+			// read the nonce and remove it
+			let per_nonce_weight = T::DbWeight::get().reads_writes(1, 1);
+			if remaining_weight.any_lt(base_weight) {
+				return Weight::zero();
+			}
+			let (class_id, cursor) = match ClearingMintNonces::<T>::iter().next() {
+				Some(entry) => entry,
+				None => return T::DbWeight::get().reads(1),
+			};
+
+			let available = remaining_weight.saturating_sub(base_weight);
+			let mut limit = T::MaxDestroyTokensPerBlock::get() as u64;
+			if per_nonce_weight.ref_time() > 0 {
+				limit = limit.min(available.ref_time() / per_nonce_weight.ref_time());
+			}
+			if per_nonce_weight.proof_size() > 0 {
+				limit = limit.min(available.proof_size() / per_nonce_weight.proof_size());
+			}
+			if limit == 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let maybe_cursor = (!cursor.is_empty()).then_some(&cursor[..]);
+			let result = UsedMintNonces::<T>::clear_prefix(class_id, limit as u32, maybe_cursor);
+			match result.maybe_cursor {
+				Some(cursor) => ClearingMintNonces::<T>::insert(class_id, cursor),
+				None => ClearingMintNonces::<T>::remove(class_id),
+			}
+			base_weight.saturating_add(per_nonce_weight.saturating_mul(result.loops.into()))
+		}
+	}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Transfer a class role to `to`.
+		///
+		/// The `Owner` role can only be transferred by the class owner. The
+		/// `Issuer` and `Admin` roles can be transferred by the class owner or
+		/// by the account currently holding them.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::transfer_class_role())]
+		pub fn transfer_class_role(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			role: ClassRole,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_transfer_class_role(&who, class_id, role, &to)
+		}
+
+		/// Renounce a delegated `Issuer` or `Admin` role, leaving it to the
+		/// class owner.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::renounce_class_role())]
+		pub fn renounce_class_role(origin: OriginFor<T>, class_id: T::ClassId, role: ClassRole) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_renounce_class_role(&who, class_id, role)
+		}
+
+		/// Mint a token with a mint authorization `signer` signed off-chain,
+		/// over the payload returned by `mint_authorization_payload`.
+		///
+		/// `signer` must hold the `Issuer` role of the class, and `metadata`
+		/// and `data` must hash to the `metadata_hash` of the authorization.
+		/// Anyone can submit the authorization and pays for it.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::mint_with_authorization(metadata.len() as u32))]
+		pub fn mint_with_authorization(
+			origin: OriginFor<T>,
+			authorization: MintAuthorizationOf<T>,
+			signer: T::AccountId,
+			signature: T::OffchainSignature,
+			metadata: Vec<u8>,
+			data: T::TokenData,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_mint_with_authorization(authorization, signer, signature, metadata, data)?;
+			Ok(())
+		}
+
+		/// Freeze the metadata of a class, it can no longer be updated
+		/// thereafter.
+		///
+		/// The caller must hold the `Admin` role of the class.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::freeze_class_metadata())]
+		pub fn freeze_class_metadata(origin: OriginFor<T>, class_id: T::ClassId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_freeze_class_metadata(&who, class_id)
+		}
+
+		/// Freeze the metadata of a token, it can no longer be updated
+		/// thereafter.
+		///
+		/// The caller must hold the `Admin` role of the class.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::freeze_token_metadata())]
+		pub fn freeze_token_metadata(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_freeze_token_metadata(&who, (class_id, token_id))
+		}
+
+		/// Set `user` as the user of a token until the `expires` block.
+		///
+		/// The caller must own the token. Any previous user is replaced.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_user())]
+		pub fn set_user(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			token_id: T::TokenId,
+			user: T::AccountId,
+			expires: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_user(&who, (class_id, token_id), Some((user, expires)))
+		}
+
+		/// Clear the user of a token.
+		///
+		/// The caller must own the token.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::clear_user())]
+		pub fn clear_user(origin: OriginFor<T>, class_id: T::ClassId, token_id: T::TokenId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_user(&who, (class_id, token_id), None)
+		}
+
+		/// Set the royalty of the tokens of a class, paid to `beneficiary` on
+		/// sales, or clear it with `None`.
+		///
+		/// The caller must hold the `Admin` role of the class. The royalty
+		/// is frozen along with the class metadata.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_class_royalty())]
+		pub fn set_class_royalty(
+			origin: OriginFor<T>,
+			class_id: T::ClassId,
+			royalty: Option<(T::AccountId, Perbill)>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_class_royalty(&who, class_id, royalty)
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -227,8 +659,27 @@ impl<T: Config> Pallet<T> {
 		Ok(class_id)
 	}
 
+	/// Create NFT(non fungible token) class whose token ids are assigned by
+	/// `strategy`
+	pub fn create_class_with_token_id_strategy(
+		owner: &T::AccountId,
+		metadata: Vec<u8>,
+		data: T::ClassData,
+		strategy: TokenIdStrategy,
+	) -> Result<T::ClassId, DispatchError> {
+		let class_id = Self::create_class(owner, metadata, data)?;
+		if strategy != TokenIdStrategy::default() {
+			ClassTokenIdStrategies::<T>::insert(class_id, strategy);
+		}
+		Ok(class_id)
+	}
+
 	/// Transfer NFT(non fungible token) from `from` account to `to` account
 	pub fn transfer(from: &T::AccountId, to: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(token.0),
+			Error::<T>::ClassDestroying
+		);
 		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
 			let info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
 			ensure!(info.owner == *from, Error::<T>::NoPermission);
@@ -241,48 +692,112 @@ impl<T: Config> Pallet<T> {
 
 			TokensByOwner::<T>::remove((from, token.0, token.1));
 			TokensByOwner::<T>::insert((to, token.0, token.1), ());
+			TokenUsers::<T>::remove(token.0, token.1);
 
 			Ok(())
 		})
 	}
 
 	/// Mint NFT(non fungible token) to `owner`
+	///
+	/// The token id is assigned by the token id strategy of the class, which
+	/// must not be `CallerSupplied`.
 	pub fn mint(
 		owner: &T::AccountId,
 		class_id: T::ClassId,
 		metadata: Vec<u8>,
 		data: T::TokenData,
 	) -> Result<T::TokenId, DispatchError> {
-		NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
-			let bounded_metadata: BoundedVec<u8, T::MaxTokenMetadata> =
-				metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(class_id),
+			Error::<T>::ClassDestroying
+		);
+		let bounded_metadata: TokenMetadataOf<T> = metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
 
-			let token_id = *id;
-			*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
+		match Self::token_id_strategy(class_id) {
+			TokenIdStrategy::Sequential => {
+				NextTokenId::<T>::try_mutate(class_id, |id| -> Result<T::TokenId, DispatchError> {
+					let token_id = *id;
+					*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableTokenId)?;
 
-			Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
-				let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
-				info.total_issuance = info
-					.total_issuance
-					.checked_add(&One::one())
-					.ok_or(ArithmeticError::Overflow)?;
-				Ok(())
-			})?;
+					Self::do_mint(owner, class_id, token_id, bounded_metadata, data)?;
+					Ok(token_id)
+				})
+			}
+			TokenIdStrategy::MetadataHash => {
+				let hash = T::Hashing::hash(&bounded_metadata[..]);
+				let token_id = T::TokenId::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+					.map_err(|_| Error::<T>::NoAvailableTokenId)?;
 
-			let token_info = TokenInfo {
-				metadata: bounded_metadata,
-				owner: owner.clone(),
-				data,
-			};
-			Tokens::<T>::insert(class_id, token_id, token_info);
-			TokensByOwner::<T>::insert((owner, class_id, token_id), ());
+				Self::do_mint(owner, class_id, token_id, bounded_metadata, data)?;
+				Ok(token_id)
+			}
+			TokenIdStrategy::CallerSupplied => Err(Error::<T>::TokenIdStrategyMismatch.into()),
+		}
+	}
 
-			Ok(token_id)
-		})
+	/// Mint NFT(non fungible token) with id `token_id` to `owner`
+	///
+	/// The class must use the `CallerSupplied` token id strategy, and fails if
+	/// `token_id` is already in use.
+	pub fn mint_with_id(
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		metadata: Vec<u8>,
+		data: T::TokenData,
+	) -> DispatchResult {
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(class_id),
+			Error::<T>::ClassDestroying
+		);
+		ensure!(
+			Self::token_id_strategy(class_id) == TokenIdStrategy::CallerSupplied,
+			Error::<T>::TokenIdStrategyMismatch
+		);
+		let bounded_metadata: TokenMetadataOf<T> = metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+
+		Self::do_mint(owner, class_id, token_id, bounded_metadata, data)
+	}
+
+	fn do_mint(
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		token_id: T::TokenId,
+		metadata: TokenMetadataOf<T>,
+		data: T::TokenData,
+	) -> DispatchResult {
+		ensure!(
+			!Tokens::<T>::contains_key(class_id, token_id),
+			Error::<T>::TokenAlreadyExists
+		);
+
+		Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			info.total_issuance = info
+				.total_issuance
+				.checked_add(&One::one())
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+
+		let token_info = TokenInfo {
+			metadata,
+			owner: owner.clone(),
+			data,
+		};
+		Tokens::<T>::insert(class_id, token_id, token_info);
+		TokensByOwner::<T>::insert((owner, class_id, token_id), ());
+
+		Ok(())
 	}
 
 	/// Burn NFT(non fungible token) from `owner`
 	pub fn burn(owner: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(token.0),
+			Error::<T>::ClassDestroying
+		);
 		Tokens::<T>::try_mutate_exists(token.0, token.1, |token_info| -> DispatchResult {
 			let t = token_info.take().ok_or(Error::<T>::TokenNotFound)?;
 			ensure!(t.owner == *owner, Error::<T>::NoPermission);
@@ -297,6 +812,8 @@ impl<T: Config> Pallet<T> {
 			})?;
 
 			TokensByOwner::<T>::remove((owner, token.0, token.1));
+			FrozenTokenMetadata::<T>::remove(token.0, token.1);
+			TokenUsers::<T>::remove(token.0, token.1);
 
 			Ok(())
 		})
@@ -304,18 +821,395 @@ impl<T: Config> Pallet<T> {
 
 	/// Destroy NFT(non fungible token) class
 	pub fn destroy_class(owner: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(class_id),
+			Error::<T>::ClassDestroying
+		);
 		Classes::<T>::try_mutate_exists(class_id, |class_info| -> DispatchResult {
 			let info = class_info.take().ok_or(Error::<T>::ClassNotFound)?;
 			ensure!(info.owner == *owner, Error::<T>::NoPermission);
 			ensure!(info.total_issuance == Zero::zero(), Error::<T>::CannotDestroyClass);
 
 			NextTokenId::<T>::remove(class_id);
+			ClassTokenIdStrategies::<T>::remove(class_id);
+			Self::remove_class_roles(class_id);
+			ClearingMintNonces::<T>::insert(class_id, Vec::<u8>::new());
+			ClassRoyalties::<T>::remove(class_id);
+			FrozenClassMetadata::<T>::remove(class_id);
 
 			Ok(())
 		})
 	}
 
+	/// Destroy NFT(non fungible token) class even if tokens still exist.
+	///
+	/// A class without tokens is destroyed immediately. Otherwise the class
+	/// is marked as being destroyed, which prevents minting and transferring
+	/// its tokens, and the tokens are removed in pages by `on_idle` or
+	/// `destroy_class_tokens`. The class is removed along with its last token.
+	pub fn start_destroy_class(owner: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
+		let info = Classes::<T>::get(class_id).ok_or(Error::<T>::ClassNotFound)?;
+		ensure!(info.owner == *owner, Error::<T>::NoPermission);
+		ensure!(
+			!DestroyingClasses::<T>::contains_key(class_id),
+			Error::<T>::ClassDestroying
+		);
+
+		if info.total_issuance.is_zero() {
+			return Self::destroy_class(owner, class_id);
+		}
+
+		DestroyingClasses::<T>::insert(class_id, ());
+		Ok(())
+	}
+
+	/// Remove up to `limit` tokens of a class being destroyed.
+	///
+	/// Returns the number of removed tokens and whether the class is fully
+	/// destroyed.
+	pub fn destroy_class_tokens(class_id: T::ClassId, limit: u32) -> Result<(u32, bool), DispatchError> {
+		ensure!(
+			DestroyingClasses::<T>::contains_key(class_id),
+			Error::<T>::ClassNotDestroying
+		);
+		ensure!(Classes::<T>::contains_key(class_id), Error::<T>::ClassNotFound);
+
+		let mut removed: u32 = 0;
+		for (token_id, token_info) in Tokens::<T>::drain_prefix(class_id).take(limit as usize) {
+			TokensByOwner::<T>::remove((token_info.owner, class_id, token_id));
+			FrozenTokenMetadata::<T>::remove(class_id, token_id);
+			TokenUsers::<T>::remove(class_id, token_id);
+			removed += 1;
+		}
+
+		let finished = Classes::<T>::try_mutate_exists(class_id, |class_info| -> Result<bool, DispatchError> {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			let removed_issuance = T::TokenId::from(removed);
+			info.total_issuance = info
+				.total_issuance
+				.checked_sub(&removed_issuance)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			if info.total_issuance.is_zero() {
+				*class_info = None;
+				Ok(true)
+			} else {
+				Ok(false)
+			}
+		})?;
+
+		if finished {
+			NextTokenId::<T>::remove(class_id);
+			ClassTokenIdStrategies::<T>::remove(class_id);
+			Self::remove_class_roles(class_id);
+			ClearingMintNonces::<T>::insert(class_id, Vec::<u8>::new());
+			ClassRoyalties::<T>::remove(class_id);
+			FrozenClassMetadata::<T>::remove(class_id);
+			DestroyingClasses::<T>::remove(class_id);
+		}
+
+		Ok((removed, finished))
+	}
+
+	/// Remove the delegated roles of a class, at most one entry per role.
+	fn remove_class_roles(class_id: T::ClassId) {
+		for role in [ClassRole::Owner, ClassRole::Issuer, ClassRole::Admin] {
+			ClassRoles::<T>::remove(class_id, role);
+		}
+	}
+
 	pub fn is_owner(account: &T::AccountId, token: (T::ClassId, T::TokenId)) -> bool {
 		TokensByOwner::<T>::contains_key((account, token.0, token.1))
 	}
+
+	/// Returns whether `account` holds `role` in the class. The class owner
+	/// holds every role.
+	pub fn has_class_role(account: &T::AccountId, class_id: T::ClassId, role: ClassRole) -> bool {
+		match Classes::<T>::get(class_id) {
+			Some(info) if info.owner == *account => true,
+			Some(_) if role != ClassRole::Owner => ClassRoles::<T>::get(class_id, role).as_ref() == Some(account),
+			_ => false,
+		}
+	}
+
+	fn ensure_class_role(account: &T::AccountId, class_id: T::ClassId, role: ClassRole) -> DispatchResult {
+		ensure!(Classes::<T>::contains_key(class_id), Error::<T>::ClassNotFound);
+		ensure!(Self::has_class_role(account, class_id, role), Error::<T>::NoPermission);
+		Ok(())
+	}
+
+	/// Mint NFT(non fungible token) to `owner` on behalf of `issuer`, who
+	/// must hold the `Issuer` role of the class
+	pub fn mint_as(
+		issuer: &T::AccountId,
+		owner: &T::AccountId,
+		class_id: T::ClassId,
+		metadata: Vec<u8>,
+		data: T::TokenData,
+	) -> Result<T::TokenId, DispatchError> {
+		Self::ensure_class_role(issuer, class_id, ClassRole::Issuer)?;
+		Self::mint(owner, class_id, metadata, data)
+	}
+
+	/// Mint NFT(non fungible token) with a mint authorization signed by
+	/// `signer`, who must hold the `Issuer` role of the class
+	pub fn do_mint_with_authorization(
+		authorization: MintAuthorizationOf<T>,
+		signer: T::AccountId,
+		signature: T::OffchainSignature,
+		metadata: Vec<u8>,
+		data: T::TokenData,
+	) -> Result<T::TokenId, DispatchError> {
+		ensure!(
+			frame_system::Pallet::<T>::block_number() <= authorization.expiry,
+			Error::<T>::AuthorizationExpired
+		);
+		ensure!(
+			metadata.len() <= T::MaxTokenMetadata::get() as usize,
+			Error::<T>::MaxMetadataExceeded
+		);
+		ensure!(
+			T::Hashing::hash_of(&(&metadata, &data)) == authorization.metadata_hash,
+			Error::<T>::MetadataHashMismatch
+		);
+		ensure!(
+			signature.verify(&Self::mint_authorization_payload(&authorization)[..], &signer),
+			Error::<T>::InvalidSignature
+		);
+		let class_id = authorization.class_id;
+		ensure!(
+			!UsedMintNonces::<T>::contains_key(class_id, authorization.nonce),
+			Error::<T>::AuthorizationUsed
+		);
+
+		let token_id = Self::mint_as(&signer, &authorization.beneficiary, class_id, metadata, data)?;
+		UsedMintNonces::<T>::insert(class_id, authorization.nonce, ());
+
+		Self::deposit_event(Event::MintedWithAuthorization {
+			class_id,
+			token_id,
+			issuer: signer,
+			beneficiary: authorization.beneficiary,
+		});
+		Ok(token_id)
+	}
+
+	/// The payload a mint authorization is signed over: the authorization
+	/// prefixed with a tag of this pallet and the genesis hash of the chain,
+	/// so it can't be replayed on other chains or by other pallets.
+	pub fn mint_authorization_payload(authorization: &MintAuthorizationOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+		(MINT_AUTHORIZATION_TAG, genesis_hash, authorization).encode()
+	}
+
+	/// Burn NFT(non fungible token) on behalf of `who`, who must either own
+	/// the token or hold the `Issuer` role of the class
+	pub fn burn_as(who: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
+		let info = Tokens::<T>::get(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
+		if info.owner != *who {
+			Self::ensure_class_role(who, token.0, ClassRole::Issuer)?;
+		}
+		Self::burn(&info.owner, token)
+	}
+
+	/// Update the metadata of a class on behalf of `who`, who must hold the
+	/// `Admin` role of the class
+	pub fn set_class_metadata(who: &T::AccountId, class_id: T::ClassId, metadata: Vec<u8>) -> DispatchResult {
+		Self::ensure_class_role(who, class_id, ClassRole::Admin)?;
+		ensure!(
+			!FrozenClassMetadata::<T>::contains_key(class_id),
+			Error::<T>::MetadataFrozen
+		);
+		let bounded_metadata: BoundedVec<u8, T::MaxClassMetadata> =
+			metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+
+		Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			info.metadata = bounded_metadata;
+			Ok(())
+		})
+	}
+
+	/// Update the metadata of a token on behalf of `who`, who must hold the
+	/// `Admin` role of the class
+	pub fn set_token_metadata(
+		who: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		metadata: Vec<u8>,
+	) -> DispatchResult {
+		Self::ensure_class_role(who, token.0, ClassRole::Admin)?;
+		ensure!(
+			!FrozenTokenMetadata::<T>::contains_key(token.0, token.1),
+			Error::<T>::MetadataFrozen
+		);
+		let bounded_metadata: BoundedVec<u8, T::MaxTokenMetadata> =
+			metadata.try_into().map_err(|_| Error::<T>::MaxMetadataExceeded)?;
+
+		Tokens::<T>::try_mutate(token.0, token.1, |token_info| -> DispatchResult {
+			let info = token_info.as_mut().ok_or(Error::<T>::TokenNotFound)?;
+			info.metadata = bounded_metadata;
+			Ok(())
+		})
+	}
+
+	/// Set the royalty of a class on behalf of `who`, who must hold the
+	/// `Admin` role of the class
+	pub fn do_set_class_royalty(
+		who: &T::AccountId,
+		class_id: T::ClassId,
+		royalty: Option<(T::AccountId, Perbill)>,
+	) -> DispatchResult {
+		Self::ensure_class_role(who, class_id, ClassRole::Admin)?;
+		ensure!(
+			!FrozenClassMetadata::<T>::contains_key(class_id),
+			Error::<T>::MetadataFrozen
+		);
+
+		ClassRoyalties::<T>::set(class_id, royalty.clone());
+		Self::deposit_event(Event::ClassRoyaltySet { class_id, royalty });
+		Ok(())
+	}
+
+	/// The royalty due on a sale of a token of `class_id` at `price`, and
+	/// its beneficiary. Returns `None` if the class has no royalty.
+	pub fn royalty_info<Balance: AtLeast32BitUnsigned + Copy>(
+		class_id: T::ClassId,
+		price: Balance,
+	) -> Option<(T::AccountId, Balance)> {
+		ClassRoyalties::<T>::get(class_id).map(|(beneficiary, part)| (beneficiary, part.mul_floor(price)))
+	}
+
+	/// Freeze the metadata of a class on behalf of `who`, who must hold the
+	/// `Admin` role of the class
+	pub fn do_freeze_class_metadata(who: &T::AccountId, class_id: T::ClassId) -> DispatchResult {
+		Self::ensure_class_role(who, class_id, ClassRole::Admin)?;
+		ensure!(
+			!FrozenClassMetadata::<T>::contains_key(class_id),
+			Error::<T>::MetadataFrozen
+		);
+
+		FrozenClassMetadata::<T>::insert(class_id, ());
+		Self::deposit_event(Event::ClassMetadataFrozen {
+			class_id,
+			who: who.clone(),
+		});
+		Ok(())
+	}
+
+	/// Freeze the metadata of a token on behalf of `who`, who must hold the
+	/// `Admin` role of the class
+	pub fn do_freeze_token_metadata(who: &T::AccountId, token: (T::ClassId, T::TokenId)) -> DispatchResult {
+		Self::ensure_class_role(who, token.0, ClassRole::Admin)?;
+		ensure!(Tokens::<T>::contains_key(token.0, token.1), Error::<T>::TokenNotFound);
+		ensure!(
+			!FrozenTokenMetadata::<T>::contains_key(token.0, token.1),
+			Error::<T>::MetadataFrozen
+		);
+
+		FrozenTokenMetadata::<T>::insert(token.0, token.1, ());
+		Self::deposit_event(Event::TokenMetadataFrozen {
+			class_id: token.0,
+			token_id: token.1,
+			who: who.clone(),
+		});
+		Ok(())
+	}
+
+	/// Set or clear the user of a token owned by `owner`
+	pub fn do_set_user(
+		owner: &T::AccountId,
+		token: (T::ClassId, T::TokenId),
+		user: Option<(T::AccountId, BlockNumberFor<T>)>,
+	) -> DispatchResult {
+		let info = Tokens::<T>::get(token.0, token.1).ok_or(Error::<T>::TokenNotFound)?;
+		ensure!(info.owner == *owner, Error::<T>::NoPermission);
+
+		match user {
+			Some((user, expires)) => {
+				ensure!(
+					expires > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidUserExpiry
+				);
+				TokenUsers::<T>::insert(token.0, token.1, (user.clone(), expires));
+				Self::deposit_event(Event::TokenUserSet {
+					class_id: token.0,
+					token_id: token.1,
+					user,
+					expires,
+				});
+			}
+			None => {
+				TokenUsers::<T>::remove(token.0, token.1);
+				Self::deposit_event(Event::TokenUserCleared {
+					class_id: token.0,
+					token_id: token.1,
+				});
+			}
+		}
+		Ok(())
+	}
+
+	/// Transfer `role` of a class from `from` to `to`
+	pub fn do_transfer_class_role(
+		from: &T::AccountId,
+		class_id: T::ClassId,
+		role: ClassRole,
+		to: &T::AccountId,
+	) -> DispatchResult {
+		Classes::<T>::try_mutate(class_id, |class_info| -> DispatchResult {
+			let info = class_info.as_mut().ok_or(Error::<T>::ClassNotFound)?;
+			match role {
+				ClassRole::Owner => {
+					ensure!(info.owner == *from, Error::<T>::NoPermission);
+					info.owner = to.clone();
+				}
+				ClassRole::Issuer | ClassRole::Admin => {
+					ensure!(
+						info.owner == *from || ClassRoles::<T>::get(class_id, role).as_ref() == Some(from),
+						Error::<T>::NoPermission
+					);
+					ClassRoles::<T>::insert(class_id, role, to);
+				}
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ClassRoleTransferred {
+			class_id,
+			role,
+			from: from.clone(),
+			to: to.clone(),
+		});
+		Ok(())
+	}
+
+	/// Renounce a delegated `Issuer` or `Admin` role of a class held by `who`
+	pub fn do_renounce_class_role(who: &T::AccountId, class_id: T::ClassId, role: ClassRole) -> DispatchResult {
+		ensure!(role != ClassRole::Owner, Error::<T>::CannotRenounceOwner);
+		ensure!(Classes::<T>::contains_key(class_id), Error::<T>::ClassNotFound);
+		ClassRoles::<T>::try_mutate_exists(class_id, role, |holder| -> DispatchResult {
+			ensure!(holder.as_ref() == Some(who), Error::<T>::NoPermission);
+			*holder = None;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ClassRoleRenounced {
+			class_id,
+			role,
+			who: who.clone(),
+		});
+		Ok(())
+	}
+}
+
+impl<T: Config> NftUser<T::AccountId, T::ClassId, T::TokenId, BlockNumberFor<T>> for Pallet<T> {
+	fn user_of(class: T::ClassId, token: T::TokenId) -> Option<T::AccountId> {
+		TokenUsers::<T>::get(class, token)
+			.filter(|(_, expires)| *expires > frame_system::Pallet::<T>::block_number())
+			.map(|(user, _)| user)
+	}
+
+	fn user_expires(class: T::ClassId, token: T::TokenId) -> Option<BlockNumberFor<T>> {
+		TokenUsers::<T>::get(class, token).map(|(_, expires)| expires)
+	}
 }
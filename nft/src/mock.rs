@@ -41,13 +41,38 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = ConstU32<16>;
 }
 
+/// A signature of the message `1` by the account `0`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct MockSignature(pub AccountId, pub Vec<u8>);
+
+pub struct MockPublic(AccountId);
+
+impl IdentifyAccount for MockPublic {
+	type AccountId = AccountId;
+	fn into_account(self) -> AccountId {
+		self.0
+	}
+}
+
+impl Verify for MockSignature {
+	type Signer = MockPublic;
+	fn verify<L: sp_runtime::traits::Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId) -> bool {
+		self.0 == *signer && msg.get() == &self.1[..]
+	}
+}
+
 impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type ClassId = u64;
 	type TokenId = u64;
 	type ClassData = ();
 	type TokenData = ();
 	type MaxClassMetadata = ConstU32<1>;
 	type MaxTokenMetadata = ConstU32<1>;
+	type MaxDestroyTokensPerBlock = ConstU32<2>;
+	type OffchainSignature = MockSignature;
+	type OffchainPublic = MockPublic;
+	type WeightInfo = ();
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -61,6 +86,7 @@ construct_runtime!(
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
 pub const CLASS_ID: <Runtime as Config>::ClassId = 0;
 pub const CLASS_ID_NOT_EXIST: <Runtime as Config>::ClassId = 100;
 pub const TOKEN_ID: <Runtime as Config>::TokenId = 0;
@@ -66,6 +66,52 @@ fn mint_should_fail() {
 	});
 }
 
+#[test]
+fn token_id_strategies_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class_with_token_id_strategy(
+			&ALICE,
+			vec![1],
+			(),
+			TokenIdStrategy::MetadataHash
+		));
+		let token_id = NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()).unwrap();
+		assert_ne!(token_id, TOKEN_ID);
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, token_id)));
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()),
+			Error::<Runtime>::TokenAlreadyExists
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_id(&BOB, CLASS_ID, 100, vec![2], ()),
+			Error::<Runtime>::TokenIdStrategyMismatch
+		);
+
+		assert_ok!(NonFungibleTokenModule::create_class_with_token_id_strategy(
+			&ALICE,
+			vec![1],
+			(),
+			TokenIdStrategy::CallerSupplied
+		));
+		let class_id = CLASS_ID + 1;
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, class_id, vec![1], ()),
+			Error::<Runtime>::TokenIdStrategyMismatch
+		);
+		assert_ok!(NonFungibleTokenModule::mint_with_id(&BOB, class_id, 100, vec![1], ()));
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (class_id, 100)));
+		assert_eq!(NonFungibleTokenModule::classes(class_id).unwrap().total_issuance, 1);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_id(&ALICE, class_id, 100, vec![2], ()),
+			Error::<Runtime>::TokenAlreadyExists
+		);
+
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (class_id, 100)));
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, class_id));
+		assert!(!ClassTokenIdStrategies::<Runtime>::contains_key(class_id));
+	});
+}
+
 #[test]
 fn transfer_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -179,6 +225,94 @@ fn destroy_class_should_fail() {
 	});
 }
 
+#[test]
+fn start_destroy_class_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::start_destroy_class(&ALICE, CLASS_ID));
+		assert!(!Classes::<Runtime>::contains_key(CLASS_ID));
+		assert!(!DestroyingClasses::<Runtime>::contains_key(CLASS_ID));
+
+		let class_id = NonFungibleTokenModule::next_class_id();
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		for _ in 0..5 {
+			assert_ok!(NonFungibleTokenModule::mint(&BOB, class_id, vec![1], ()));
+		}
+		assert_noop!(
+			NonFungibleTokenModule::destroy_class_tokens(class_id, 1),
+			Error::<Runtime>::ClassNotDestroying
+		);
+		assert_noop!(
+			NonFungibleTokenModule::start_destroy_class(&BOB, class_id),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::start_destroy_class(&ALICE, class_id));
+		assert!(DestroyingClasses::<Runtime>::contains_key(class_id));
+		assert_noop!(
+			NonFungibleTokenModule::start_destroy_class(&ALICE, class_id),
+			Error::<Runtime>::ClassDestroying
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint(&BOB, class_id, vec![1], ()),
+			Error::<Runtime>::ClassDestroying
+		);
+		assert_noop!(
+			NonFungibleTokenModule::transfer(&BOB, &ALICE, (class_id, TOKEN_ID)),
+			Error::<Runtime>::ClassDestroying
+		);
+
+		assert_eq!(
+			NonFungibleTokenModule::destroy_class_tokens(class_id, 1),
+			Ok((1, false))
+		);
+		assert_eq!(NonFungibleTokenModule::classes(class_id).unwrap().total_issuance, 4);
+
+		// `on_idle` removes `MaxDestroyTokensPerBlock` tokens per block
+		NonFungibleTokenModule::on_idle(1, Weight::MAX);
+		assert_eq!(NonFungibleTokenModule::classes(class_id).unwrap().total_issuance, 2);
+		NonFungibleTokenModule::on_idle(2, Weight::MAX);
+
+		assert!(!Classes::<Runtime>::contains_key(class_id));
+		assert!(!NextTokenId::<Runtime>::contains_key(class_id));
+		assert!(!DestroyingClasses::<Runtime>::contains_key(class_id));
+		assert_eq!(Tokens::<Runtime>::iter_prefix(class_id).count(), 0);
+		assert_eq!(TokensByOwner::<Runtime>::iter_prefix((BOB,)).count(), 0);
+	});
+}
+
+#[test]
+fn destroying_class_tokens_can_not_be_burned() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::start_destroy_class(&ALICE, CLASS_ID));
+
+		// burning the last token must not allow destroying the class while its
+		// destruction is in progress
+		assert_noop!(
+			NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ClassDestroying
+		);
+		assert_noop!(
+			NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID),
+			Error::<Runtime>::ClassDestroying
+		);
+
+		NonFungibleTokenModule::on_idle(1, Weight::MAX);
+		assert!(!Classes::<Runtime>::contains_key(CLASS_ID));
+		assert!(!DestroyingClasses::<Runtime>::contains_key(CLASS_ID));
+	});
+}
+
+#[test]
+fn on_idle_drops_destroying_classes_not_found() {
+	ExtBuilder::default().build().execute_with(|| {
+		DestroyingClasses::<Runtime>::insert(CLASS_ID_NOT_EXIST, ());
+		NonFungibleTokenModule::on_idle(1, Weight::MAX);
+		assert!(!DestroyingClasses::<Runtime>::contains_key(CLASS_ID_NOT_EXIST));
+	});
+}
+
 #[test]
 fn exceeding_max_metadata_should_fail() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -193,3 +327,431 @@ fn exceeding_max_metadata_should_fail() {
 		);
 	});
 }
+
+#[test]
+fn class_roles_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+
+		// only the owner holds the roles initially
+		assert!(NonFungibleTokenModule::has_class_role(
+			&ALICE,
+			CLASS_ID,
+			ClassRole::Issuer
+		));
+		assert_noop!(
+			NonFungibleTokenModule::mint_as(&BOB, &BOB, CLASS_ID, vec![1], ()),
+			Error::<Runtime>::NoPermission
+		);
+
+		// delegate minting rights to BOB and administration to CHARLIE
+		assert_ok!(NonFungibleTokenModule::transfer_class_role(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			ClassRole::Issuer,
+			BOB
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::ClassRoleTransferred {
+			class_id: CLASS_ID,
+			role: ClassRole::Issuer,
+			from: ALICE,
+			to: BOB,
+		}));
+		assert_ok!(NonFungibleTokenModule::transfer_class_role(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			ClassRole::Admin,
+			CHARLIE
+		));
+
+		assert_ok!(NonFungibleTokenModule::mint_as(&BOB, &CHARLIE, CLASS_ID, vec![1], ()));
+		assert_noop!(
+			NonFungibleTokenModule::mint_as(&CHARLIE, &CHARLIE, CLASS_ID, vec![1], ()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_class_metadata(&BOB, CLASS_ID, vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::set_class_metadata(&CHARLIE, CLASS_ID, vec![2]));
+		assert_ok!(NonFungibleTokenModule::set_token_metadata(
+			&CHARLIE,
+			(CLASS_ID, TOKEN_ID),
+			vec![2]
+		));
+		assert_eq!(
+			NonFungibleTokenModule::classes(CLASS_ID).unwrap().metadata.into_inner(),
+			vec![2]
+		);
+		assert_eq!(
+			NonFungibleTokenModule::tokens(CLASS_ID, TOKEN_ID)
+				.unwrap()
+				.metadata
+				.into_inner(),
+			vec![2]
+		);
+
+		// the issuer can burn tokens it doesn't own
+		assert_ok!(NonFungibleTokenModule::burn_as(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert!(!NonFungibleTokenModule::is_owner(&CHARLIE, (CLASS_ID, TOKEN_ID)));
+
+		// the issuer hands the role back by renouncing it
+		assert_noop!(
+			NonFungibleTokenModule::renounce_class_role(RuntimeOrigin::signed(CHARLIE), CLASS_ID, ClassRole::Issuer),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::renounce_class_role(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			ClassRole::Issuer
+		));
+		assert!(!NonFungibleTokenModule::has_class_role(
+			&BOB,
+			CLASS_ID,
+			ClassRole::Issuer
+		));
+
+		// transferring ownership moves every implicit role
+		assert_noop!(
+			NonFungibleTokenModule::renounce_class_role(RuntimeOrigin::signed(ALICE), CLASS_ID, ClassRole::Owner),
+			Error::<Runtime>::CannotRenounceOwner
+		);
+		assert_noop!(
+			NonFungibleTokenModule::transfer_class_role(
+				RuntimeOrigin::signed(CHARLIE),
+				CLASS_ID,
+				ClassRole::Owner,
+				BOB
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::transfer_class_role(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			ClassRole::Owner,
+			BOB
+		));
+		assert!(NonFungibleTokenModule::has_class_role(
+			&BOB,
+			CLASS_ID,
+			ClassRole::Issuer
+		));
+		assert!(!NonFungibleTokenModule::has_class_role(
+			&ALICE,
+			CLASS_ID,
+			ClassRole::Issuer
+		));
+
+		// roles are cleared with the class
+		assert_ok!(NonFungibleTokenModule::destroy_class(&BOB, CLASS_ID));
+		assert_eq!(NonFungibleTokenModule::class_roles(CLASS_ID, ClassRole::Admin), None);
+	});
+}
+
+#[test]
+fn mint_with_authorization_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+
+		let authorization = MintAuthorization {
+			class_id: CLASS_ID,
+			metadata_hash: <Runtime as frame_system::Config>::Hashing::hash_of(&(vec![1u8], ())),
+			beneficiary: BOB,
+			expiry: 10,
+			nonce: 0,
+		};
+		let sign = |signer, authorization: &MintAuthorizationOf<Runtime>| {
+			MockSignature(
+				signer,
+				NonFungibleTokenModule::mint_authorization_payload(authorization),
+			)
+		};
+		let mint = |signer, authorization: MintAuthorizationOf<Runtime>, metadata: Vec<u8>| {
+			NonFungibleTokenModule::mint_with_authorization(
+				RuntimeOrigin::signed(CHARLIE),
+				authorization.clone(),
+				signer,
+				sign(signer, &authorization),
+				metadata,
+				(),
+			)
+		};
+
+		assert_noop!(
+			mint(ALICE, authorization.clone(), vec![2]),
+			Error::<Runtime>::MetadataHashMismatch
+		);
+		// oversized metadata is rejected before it is hashed
+		assert_noop!(
+			mint(ALICE, authorization.clone(), vec![1, 2]),
+			Error::<Runtime>::MaxMetadataExceeded
+		);
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_authorization(
+				RuntimeOrigin::signed(CHARLIE),
+				authorization.clone(),
+				ALICE,
+				sign(BOB, &authorization),
+				vec![1],
+				(),
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		// the bare authorization is not a valid payload
+		assert_noop!(
+			NonFungibleTokenModule::mint_with_authorization(
+				RuntimeOrigin::signed(CHARLIE),
+				authorization.clone(),
+				ALICE,
+				MockSignature(ALICE, authorization.encode()),
+				vec![1],
+				(),
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		// only accounts holding the `Issuer` role can authorize minting
+		assert_noop!(
+			mint(BOB, authorization.clone(), vec![1]),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(mint(ALICE, authorization.clone(), vec![1]));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::MintedWithAuthorization {
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+			issuer: ALICE,
+			beneficiary: BOB,
+		}));
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert!(NonFungibleTokenModule::used_mint_nonces(CLASS_ID, 0).is_some());
+
+		// the authorization can only be used once
+		assert_noop!(
+			mint(ALICE, authorization.clone(), vec![1]),
+			Error::<Runtime>::AuthorizationUsed
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			mint(
+				ALICE,
+				MintAuthorization {
+					nonce: 1,
+					..authorization
+				},
+				vec![1]
+			),
+			Error::<Runtime>::AuthorizationExpired
+		);
+	});
+}
+
+#[test]
+fn freeze_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::freeze_class_metadata(RuntimeOrigin::signed(BOB), CLASS_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::freeze_token_metadata(RuntimeOrigin::signed(ALICE), CLASS_ID, TOKEN_ID_NOT_EXIST),
+			Error::<Runtime>::TokenNotFound
+		);
+
+		assert_ok!(NonFungibleTokenModule::freeze_class_metadata(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::ClassMetadataFrozen {
+			class_id: CLASS_ID,
+			who: ALICE,
+		}));
+		assert_noop!(
+			NonFungibleTokenModule::set_class_metadata(&ALICE, CLASS_ID, vec![2]),
+			Error::<Runtime>::MetadataFrozen
+		);
+		assert_noop!(
+			NonFungibleTokenModule::freeze_class_metadata(RuntimeOrigin::signed(ALICE), CLASS_ID),
+			Error::<Runtime>::MetadataFrozen
+		);
+
+		// token metadata is frozen separately
+		assert_ok!(NonFungibleTokenModule::set_token_metadata(
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			vec![2]
+		));
+		assert_ok!(NonFungibleTokenModule::freeze_token_metadata(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			TOKEN_ID
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::TokenMetadataFrozen {
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+			who: ALICE,
+		}));
+		assert_noop!(
+			NonFungibleTokenModule::set_token_metadata(&ALICE, (CLASS_ID, TOKEN_ID), vec![3]),
+			Error::<Runtime>::MetadataFrozen
+		);
+
+		assert_ok!(NonFungibleTokenModule::burn(&BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::frozen_token_metadata(CLASS_ID, TOKEN_ID), None);
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert_eq!(NonFungibleTokenModule::frozen_class_metadata(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn token_users_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_ok!(NonFungibleTokenModule::mint(&BOB, CLASS_ID, vec![1], ()));
+
+		assert_noop!(
+			NonFungibleTokenModule::set_user(RuntimeOrigin::signed(ALICE), CLASS_ID, TOKEN_ID, CHARLIE, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_user(RuntimeOrigin::signed(BOB), CLASS_ID, TOKEN_ID_NOT_EXIST, CHARLIE, 10),
+			Error::<Runtime>::TokenNotFound
+		);
+		assert_noop!(
+			NonFungibleTokenModule::set_user(RuntimeOrigin::signed(BOB), CLASS_ID, TOKEN_ID, CHARLIE, 1),
+			Error::<Runtime>::InvalidUserExpiry
+		);
+
+		assert_ok!(NonFungibleTokenModule::set_user(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			TOKEN_ID,
+			CHARLIE,
+			10
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::TokenUserSet {
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+			user: CHARLIE,
+			expires: 10,
+		}));
+		assert_eq!(NonFungibleTokenModule::user_of(CLASS_ID, TOKEN_ID), Some(CHARLIE));
+		assert_eq!(NonFungibleTokenModule::user_expires(CLASS_ID, TOKEN_ID), Some(10));
+		// the owner is unchanged
+		assert!(NonFungibleTokenModule::is_owner(&BOB, (CLASS_ID, TOKEN_ID)));
+
+		// the user expires
+		System::set_block_number(10);
+		assert_eq!(NonFungibleTokenModule::user_of(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(NonFungibleTokenModule::user_expires(CLASS_ID, TOKEN_ID), Some(10));
+
+		assert_ok!(NonFungibleTokenModule::set_user(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			TOKEN_ID,
+			CHARLIE,
+			20
+		));
+		assert_ok!(NonFungibleTokenModule::clear_user(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			TOKEN_ID
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::TokenUserCleared {
+			class_id: CLASS_ID,
+			token_id: TOKEN_ID,
+		}));
+		assert_eq!(NonFungibleTokenModule::user_expires(CLASS_ID, TOKEN_ID), None);
+
+		// the user is cleared on transfer
+		assert_ok!(NonFungibleTokenModule::set_user(
+			RuntimeOrigin::signed(BOB),
+			CLASS_ID,
+			TOKEN_ID,
+			CHARLIE,
+			20
+		));
+		assert_ok!(NonFungibleTokenModule::transfer(&BOB, &ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::token_users(CLASS_ID, TOKEN_ID), None);
+
+		// and on burn
+		assert_ok!(NonFungibleTokenModule::set_user(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			TOKEN_ID,
+			CHARLIE,
+			20
+		));
+		assert_ok!(NonFungibleTokenModule::burn(&ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NonFungibleTokenModule::token_users(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn used_mint_nonces_are_removed_in_pages() {
+	let mut ext = ExtBuilder::default().build();
+	ext.execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		for nonce in 0..5 {
+			UsedMintNonces::<Runtime>::insert(CLASS_ID, nonce, ());
+		}
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert_eq!(UsedMintNonces::<Runtime>::iter_prefix(CLASS_ID).count(), 5);
+		assert!(ClearingMintNonces::<Runtime>::contains_key(CLASS_ID));
+	});
+	// the limit of `clear_prefix` only applies to committed keys
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		// `on_idle` removes `MaxDestroyTokensPerBlock` nonces per block
+		NonFungibleTokenModule::on_idle(1, Weight::MAX);
+		assert_eq!(UsedMintNonces::<Runtime>::iter_prefix(CLASS_ID).count(), 3);
+		NonFungibleTokenModule::on_idle(2, Weight::MAX);
+		NonFungibleTokenModule::on_idle(3, Weight::MAX);
+		assert_eq!(UsedMintNonces::<Runtime>::iter_prefix(CLASS_ID).count(), 0);
+		assert!(!ClearingMintNonces::<Runtime>::contains_key(CLASS_ID));
+	});
+}
+
+#[test]
+fn set_class_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NonFungibleTokenModule::create_class(&ALICE, vec![1], ()));
+		assert_eq!(NonFungibleTokenModule::royalty_info(CLASS_ID, 1_000u64), None);
+
+		let royalty = Some((CHARLIE, Perbill::from_percent(5)));
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(RuntimeOrigin::signed(BOB), CLASS_ID, royalty.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NonFungibleTokenModule::set_class_royalty(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID,
+			royalty.clone()
+		));
+		System::assert_last_event(RuntimeEvent::NonFungibleTokenModule(Event::ClassRoyaltySet {
+			class_id: CLASS_ID,
+			royalty: royalty.clone(),
+		}));
+		assert_eq!(
+			NonFungibleTokenModule::royalty_info(CLASS_ID, 1_000u64),
+			Some((CHARLIE, 50))
+		);
+
+		// the royalty is frozen along with the class metadata
+		assert_ok!(NonFungibleTokenModule::freeze_class_metadata(
+			RuntimeOrigin::signed(ALICE),
+			CLASS_ID
+		));
+		assert_noop!(
+			NonFungibleTokenModule::set_class_royalty(RuntimeOrigin::signed(ALICE), CLASS_ID, None),
+			Error::<Runtime>::MetadataFrozen
+		);
+
+		assert_ok!(NonFungibleTokenModule::destroy_class(&ALICE, CLASS_ID));
+		assert_eq!(NonFungibleTokenModule::class_royalty(CLASS_ID), None);
+	});
+}
@@ -18,4 +18,14 @@ sp_api::decl_runtime_apis! {
 		fn get_value(provider_id: ProviderId, key: Key) -> Option<Value>;
 		fn get_all_values(provider_id: ProviderId) -> Vec<(Key, Option<Value>)>;
 	}
+
+	pub trait OracleRawValuesApi<ProviderId, Key, Value, AccountId> where
+		ProviderId: Codec,
+		Key: Codec,
+		Value: Codec,
+		AccountId: Codec,
+	{
+		/// The latest raw value fed for `key` by each member.
+		fn raw_values(provider_id: ProviderId, key: Key) -> Vec<(AccountId, Value)>;
+	}
 }
@@ -1,6 +1,6 @@
 use crate::{Config, MomentOf, TimestampedValueOf};
 use frame_support::traits::{Get, Time};
-use orml_traits::CombineData;
+use orml_traits::{CombineData, GetByKey};
 use sp_runtime::traits::Saturating;
 use sp_std::{marker, prelude::*};
 
@@ -38,3 +38,40 @@ where
 		Some(value.clone())
 	}
 }
+
+/// Sort by value and returns median timestamped value, only taking into
+/// account values fed within the TTL of the key.
+/// Returns `None` if not enough fresh values. The oracle then keeps the
+/// last combined value, so readers should still check its timestamp.
+pub struct RecentCombineData<T, MinimumCount, Ttl, I = ()>(marker::PhantomData<(T, I, MinimumCount, Ttl)>);
+
+impl<T, I, MinimumCount, Ttl> CombineData<<T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>
+	for RecentCombineData<T, MinimumCount, Ttl, I>
+where
+	T: Config<I>,
+	I: 'static,
+	MinimumCount: Get<u32>,
+	Ttl: GetByKey<<T as Config<I>>::OracleKey, MomentOf<T, I>>,
+{
+	fn combine_data(
+		key: &<T as Config<I>>::OracleKey,
+		mut values: Vec<TimestampedValueOf<T, I>>,
+		_prev_value: Option<TimestampedValueOf<T, I>>,
+	) -> Option<TimestampedValueOf<T, I>> {
+		let ttl = Ttl::get(key);
+		let now = T::Time::now();
+
+		values.retain(|x| x.timestamp.saturating_add(ttl) > now);
+
+		let count = values.len() as u32;
+		let minimum_count = MinimumCount::get();
+		if count < minimum_count || count == 0 {
+			return None;
+		}
+
+		let mid_index = count / 2;
+		// Won't panic as `values` ensured not empty.
+		let (_, value, _) = values.select_nth_unstable_by(mid_index as usize, |a, b| a.value.cmp(&b.value));
+		Some(value.clone())
+	}
+}
@@ -13,6 +13,32 @@
 //!
 //! The data is valid only if feeded by an authorized operator.
 //! `pallet_membership` in FRAME can be used to as source of `T::Members`.
+//!
+//! Keys can be given metadata describing the kind of their value and its
+//! decimals, so one instance can serve prices, interest rates and plain
+//! integers through typed getters.
+//!
+//! A member whose last value of a key is older than `MaxMissedFeedingWindows`
+//! feeding windows is marked stale for the key when the key is fed, and
+//! optionally excluded from aggregation of the key until it feeds it again.
+//!
+//! In an emergency, root can set the combined value of a key directly with
+//! `force_feed_value`. The value is flagged as an override until the members
+//! feed enough values for it to be combined again.
+//!
+//! Keys can be given a challenge period with `set_challenge_period`. A newly
+//! combined value of such a key is pending until the period has passed, and
+//! the previous value is served in the meantime. Further values combined in
+//! the period replace the pending value without extending the period. A
+//! suspicious pending value can be discarded with `veto_pending_value`.
+//!
+//! A key can be deprecated with `deprecate_key` in favour of a replacement
+//! key, e.g. when currency ids are renumbered. Reads of the deprecated key
+//! return the value of the replacement key.
+//!
+//! The last `MaxValueHistory` effective values of each key are kept along with
+//! the moment they became effective, and exposed via `DataHistoryProvider`,
+//! e.g. to compute time weighted average prices with `DefaultTwapProvider`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Disable the following two lints since they originate from an external macro (namely decl_storage)
@@ -33,17 +59,22 @@ use frame_support::{
 	Parameter,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
-pub use orml_traits::{CombineData, DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+pub use orml_traits::{CombineData, DataFeeder, DataHistoryProvider, DataProvider, DataProviderExtended, OnNewData};
 use orml_utilities::OrderedSet;
 use scale_info::TypeInfo;
-use sp_runtime::{traits::Member, DispatchResult, RuntimeDebug};
+use sp_runtime::{
+	traits::{Member, Saturating, Zero},
+	DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
+};
 use sp_std::{prelude::*, vec};
 
-pub use crate::default_combine_data::DefaultCombineData;
+pub use crate::default_combine_data::{DefaultCombineData, RecentCombineData};
+pub use crate::value_codec::{FixedPointValue, IntegerValue, OracleValueCodec};
 
 mod default_combine_data;
 mod mock;
 mod tests;
+mod value_codec;
 mod weights;
 
 pub use module::*;
@@ -63,6 +94,28 @@ pub mod module {
 		pub timestamp: Moment,
 	}
 
+	/// The kind of value fed for a key.
+	#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum ValueKind {
+		/// A price, read with `get_price`.
+		Price,
+		/// An interest rate, read with `get_interest_rate`.
+		InterestRate,
+		/// An arbitrary integer, read with `get_u128`.
+		Integer,
+	}
+
+	/// Describes how the values of a key are interpreted.
+	#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct KeyMetadata {
+		/// The kind of value.
+		pub kind: ValueKind,
+		/// The number of decimals of the raw value. Ignored for integers.
+		pub decimals: u8,
+	}
+
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
 		type RuntimeEvent: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
@@ -100,6 +153,32 @@ pub mod module {
 		/// Maximum size the vector used for feed values
 		#[pallet::constant]
 		type MaxFeedValues: Get<u32>;
+
+		/// Exposes the raw integer representation of values for the typed
+		/// getters.
+		type ValueCodec: OracleValueCodec<Self::OracleValue>;
+
+		/// The origin which may update key metadata.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The period in which members are expected to feed each key they
+		/// feed.
+		#[pallet::constant]
+		type FeedingWindow: Get<MomentOf<Self, I>>;
+
+		/// The number of feeding windows a member can miss before being marked
+		/// stale, zero disables the check.
+		#[pallet::constant]
+		type MaxMissedFeedingWindows: Get<u32>;
+
+		/// Whether values of stale members are excluded from aggregation.
+		#[pallet::constant]
+		type SuspendStaleMembers: Get<bool>;
+
+		/// The number of effective values kept per key in the value history,
+		/// zero disables the history.
+		#[pallet::constant]
+		type MaxValueHistory: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -108,6 +187,13 @@ pub mod module {
 		NoPermission,
 		/// Feeder has already feeded at this block
 		AlreadyFeeded,
+		/// The key has no value in its challenge period
+		NoPendingValue,
+		/// The replacement key is the key itself, deprecated or a
+		/// replacement of another key
+		InvalidReplacementKey,
+		/// The key is not deprecated
+		KeyNotDeprecated,
 	}
 
 	#[pallet::event]
@@ -118,6 +204,37 @@ pub mod module {
 			sender: T::AccountId,
 			values: Vec<(T::OracleKey, T::OracleValue)>,
 		},
+		/// The metadata of a key is updated.
+		KeyMetadataUpdated {
+			key: T::OracleKey,
+			metadata: Option<KeyMetadata>,
+		},
+		/// A member missed too many feeding windows of a key.
+		MemberStale { who: T::AccountId, key: T::OracleKey },
+		/// A stale member fed a key again.
+		MemberResumed { who: T::AccountId, key: T::OracleKey },
+		/// The combined value of a key is overridden by root.
+		ValueOverridden { key: T::OracleKey, value: T::OracleValue },
+		/// The challenge period of a key is updated.
+		ChallengePeriodUpdated {
+			key: T::OracleKey,
+			period: Option<MomentOf<T, I>>,
+		},
+		/// A combined value is pending until its challenge period has passed.
+		ValuePending {
+			key: T::OracleKey,
+			value: T::OracleValue,
+			effective_at: MomentOf<T, I>,
+		},
+		/// A pending value is vetoed.
+		PendingValueVetoed { key: T::OracleKey, value: T::OracleValue },
+		/// A key is deprecated in favour of a replacement key.
+		KeyDeprecated {
+			key: T::OracleKey,
+			replacement: T::OracleKey,
+		},
+		/// The deprecation of a key is removed.
+		KeyUndeprecated { key: T::OracleKey },
 	}
 
 	/// Raw values for each oracle operators
@@ -132,6 +249,61 @@ pub mod module {
 	pub type Values<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, TimestampedValueOf<T, I>>;
 
+	/// Metadata describing how the values of a key are interpreted
+	#[pallet::storage]
+	#[pallet::getter(fn key_metadata)]
+	pub type KeyMetadatas<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, KeyMetadata>;
+
+	/// Members which missed too many feeding windows of a key
+	#[pallet::storage]
+	#[pallet::getter(fn stale_members)]
+	pub type StaleMembers<T: Config<I>, I: 'static = ()> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, <T as Config<I>>::OracleKey, ()>;
+
+	/// Keys whose combined value is overridden by root
+	#[pallet::storage]
+	#[pallet::getter(fn value_overrides)]
+	pub type ValueOverrides<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, ()>;
+
+	/// The delay between a value being combined and becoming effective
+	#[pallet::storage]
+	#[pallet::getter(fn challenge_periods)]
+	pub type ChallengePeriods<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, MomentOf<T, I>>;
+
+	/// Combined values in their challenge period, and the moment they become
+	/// effective
+	#[pallet::storage]
+	#[pallet::getter(fn pending_values)]
+	pub type PendingValues<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, (TimestampedValueOf<T, I>, MomentOf<T, I>)>;
+
+	/// The replacement keys of deprecated keys
+	#[pallet::storage]
+	#[pallet::getter(fn key_redirects)]
+	pub type KeyRedirects<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, <T as Config<I>>::OracleKey>;
+
+	/// The number of deprecated keys redirected to each replacement key
+	#[pallet::storage]
+	#[pallet::getter(fn redirected_keys)]
+	pub type RedirectedKeys<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, <T as Config<I>>::OracleKey, u32, ValueQuery>;
+
+	/// The last effective values of each key and the moments they became
+	/// effective at, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn value_history)]
+	pub type ValueHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		<T as Config<I>>::OracleKey,
+		BoundedVec<(MomentOf<T, I>, <T as Config<I>>::OracleValue), T::MaxValueHistory>,
+		ValueQuery,
+	>;
+
 	/// If an oracle operator has fed a value in this block
 	#[pallet::storage]
 	pub(crate) type HasDispatched<T: Config<I>, I: 'static = ()> =
@@ -159,7 +331,8 @@ pub mod module {
 		///
 		/// Require authorized operator.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::feed_values(values.len() as u32))]
+		#[pallet::weight(T::WeightInfo::feed_values(values.len() as u32)
+			.saturating_add(Pallet::<T, I>::check_heartbeats_weight(values.len() as u32)))]
 		pub fn feed_values(
 			origin: OriginFor<T>,
 			values: BoundedVec<(T::OracleKey, T::OracleValue), T::MaxFeedValues>,
@@ -179,31 +352,273 @@ pub mod module {
 			Self::do_feed_values(who, values.into())?;
 			Ok(Pays::No.into())
 		}
+
+		/// Set or remove the metadata of a key.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_key_metadata())]
+		pub fn set_key_metadata(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			metadata: Option<KeyMetadata>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			KeyMetadatas::<T, I>::set(&key, metadata);
+			Self::deposit_event(Event::KeyMetadataUpdated { key, metadata });
+			Ok(())
+		}
+
+		/// Set the combined value of a key directly, bypassing aggregation.
+		///
+		/// The value is flagged as an override until values fed by members
+		/// can be combined again.
+		///
+		/// The dispatch origin of this call must be `Root`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::force_feed_value())]
+		pub fn force_feed_value(origin: OriginFor<T>, key: T::OracleKey, value: T::OracleValue) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let now = T::Time::now();
+			let timestamped = TimestampedValue {
+				value: value.clone(),
+				timestamp: now,
+			};
+			Self::set_value(&key, timestamped, now);
+			PendingValues::<T, I>::remove(&key);
+			ValueOverrides::<T, I>::insert(&key, ());
+			Self::deposit_event(Event::ValueOverridden { key, value });
+			Ok(())
+		}
+
+		/// Set or remove the challenge period of a key.
+		///
+		/// Removing the challenge period makes a pending value effective
+		/// immediately.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_challenge_period())]
+		pub fn set_challenge_period(
+			origin: OriginFor<T>,
+			key: T::OracleKey,
+			period: Option<MomentOf<T, I>>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let period = period.filter(|period| !period.is_zero());
+			ChallengePeriods::<T, I>::set(&key, period);
+			if period.is_none() {
+				if let Some((pending, _)) = PendingValues::<T, I>::take(&key) {
+					Self::set_value(&key, pending, T::Time::now());
+				}
+			}
+			Self::deposit_event(Event::ChallengePeriodUpdated { key, period });
+			Ok(())
+		}
+
+		/// Discard the pending value of a key before its challenge period
+		/// has passed, the previous value stays effective.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::veto_pending_value())]
+		pub fn veto_pending_value(origin: OriginFor<T>, key: T::OracleKey) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let (pending, _) = PendingValues::<T, I>::get(&key)
+				.filter(|(_, effective_at)| T::Time::now() < *effective_at)
+				.ok_or(Error::<T, I>::NoPendingValue)?;
+			PendingValues::<T, I>::remove(&key);
+			Self::deposit_event(Event::PendingValueVetoed {
+				key,
+				value: pending.value,
+			});
+			Ok(())
+		}
+
+		/// Deprecate a key, redirecting reads of it to `replacement`.
+		///
+		/// The replacement can not be deprecated itself, and a key which is
+		/// the replacement of another key can not be deprecated.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::deprecate_key())]
+		pub fn deprecate_key(origin: OriginFor<T>, key: T::OracleKey, replacement: T::OracleKey) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				key != replacement
+					&& !KeyRedirects::<T, I>::contains_key(&replacement)
+					&& !RedirectedKeys::<T, I>::contains_key(&key),
+				Error::<T, I>::InvalidReplacementKey
+			);
+			if let Some(previous) = KeyRedirects::<T, I>::get(&key) {
+				Self::remove_redirected_key(&previous);
+			}
+			KeyRedirects::<T, I>::insert(&key, &replacement);
+			RedirectedKeys::<T, I>::mutate(&replacement, |count| *count = count.saturating_add(1));
+			Self::deposit_event(Event::KeyDeprecated { key, replacement });
+			Ok(())
+		}
+
+		/// Remove the deprecation of a key, reads of it return its own
+		/// value again.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::undeprecate_key())]
+		pub fn undeprecate_key(origin: OriginFor<T>, key: T::OracleKey) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			let replacement = KeyRedirects::<T, I>::take(&key).ok_or(Error::<T, I>::KeyNotDeprecated)?;
+			Self::remove_redirected_key(&replacement);
+			Self::deposit_event(Event::KeyUndeprecated { key });
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	fn remove_redirected_key(replacement: &T::OracleKey) {
+		RedirectedKeys::<T, I>::mutate_exists(replacement, |count| {
+			*count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+		});
+	}
+
 	pub fn read_raw_values(key: &T::OracleKey) -> Vec<TimestampedValueOf<T, I>> {
+		let suspend_stale = T::SuspendStaleMembers::get();
 		T::Members::sorted_members()
 			.iter()
 			.chain([T::RootOperatorAccountId::get()].iter())
+			.filter(|x| !(suspend_stale && StaleMembers::<T, I>::contains_key(x, key)))
 			.filter_map(|x| Self::raw_values(x, key))
 			.collect()
 	}
 
-	/// Fetch current combined value.
+	/// Latest raw value fed for `key` by each member and the root operator,
+	/// including stale members.
+	#[allow(clippy::complexity)]
+	pub fn get_raw_values(key: &T::OracleKey) -> Vec<(T::AccountId, TimestampedValueOf<T, I>)> {
+		T::Members::sorted_members()
+			.into_iter()
+			.chain([T::RootOperatorAccountId::get()])
+			.filter_map(|x| Self::raw_values(&x, key).map(|value| (x, value)))
+			.collect()
+	}
+
+	/// Fetch current combined value, which is the pending value once its
+	/// challenge period has passed.
+	///
+	/// Reads of a deprecated key return the value of its replacement.
 	pub fn get(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
-		Self::values(key)
+		match Self::key_redirects(key) {
+			Some(replacement) => Self::current_value(&replacement),
+			None => Self::current_value(key),
+		}
+	}
+
+	fn current_value(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
+		match Self::pending_values(key) {
+			Some((pending, effective_at)) if T::Time::now() >= effective_at => Some(pending),
+			_ => Self::values(key),
+		}
 	}
 
 	#[allow(clippy::complexity)]
 	pub fn get_all_values() -> Vec<(T::OracleKey, Option<TimestampedValueOf<T, I>>)> {
-		<Values<T, I>>::iter().map(|(k, v)| (k, Some(v))).collect()
+		<Values<T, I>>::iter_keys()
+			.chain(PendingValues::<T, I>::iter_keys().filter(|k| !<Values<T, I>>::contains_key(k)))
+			.filter_map(|k| Self::get(&k).map(|v| (k, Some(v))))
+			.collect()
+	}
+
+	/// Fetch current combined value of a price key.
+	pub fn get_price(key: &T::OracleKey) -> Option<FixedU128> {
+		Self::get_scaled(key, ValueKind::Price)
+	}
+
+	/// Fetch current combined value of an interest rate key.
+	pub fn get_interest_rate(key: &T::OracleKey) -> Option<FixedU128> {
+		Self::get_scaled(key, ValueKind::InterestRate)
+	}
+
+	/// Fetch current combined value of an integer key.
+	pub fn get_u128(key: &T::OracleKey) -> Option<u128> {
+		Self::get_raw(key, ValueKind::Integer).map(|(_, raw)| raw)
+	}
+
+	fn get_scaled(key: &T::OracleKey, kind: ValueKind) -> Option<FixedU128> {
+		let (metadata, raw) = Self::get_raw(key, kind)?;
+		let divisor = 10u128.checked_pow(metadata.decimals.into())?;
+		FixedU128::checked_from_rational(raw, divisor)
+	}
+
+	fn get_raw(key: &T::OracleKey, kind: ValueKind) -> Option<(KeyMetadata, u128)> {
+		let metadata = Self::key_metadata(key).filter(|metadata| metadata.kind == kind)?;
+		let raw = T::ValueCodec::to_raw(&Self::get(key)?.value)?;
+		Some((metadata, raw))
 	}
 
-	fn combined(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
+	/// The latest combined value of `key`, including a pending value.
+	fn latest_combined(key: &T::OracleKey) -> Option<TimestampedValueOf<T, I>> {
+		Self::pending_values(key)
+			.map(|(pending, _)| pending)
+			.or_else(|| Self::values(key))
+	}
+
+	fn combined(key: &T::OracleKey, prev_value: Option<TimestampedValueOf<T, I>>) -> Option<TimestampedValueOf<T, I>> {
 		let values = Self::read_raw_values(key);
-		T::CombineData::combine_data(key, values, Self::values(key))
+		T::CombineData::combine_data(key, values, prev_value)
+	}
+
+	/// Store the combined value of `key`, as a pending value if `key` has a
+	/// challenge period.
+	fn update_value(key: &T::OracleKey, combined: TimestampedValueOf<T, I>, now: MomentOf<T, I>) {
+		let period = match Self::challenge_periods(key) {
+			Some(period) => period,
+			None => return Self::set_value(key, combined, now),
+		};
+
+		if let Some((pending, effective_at)) = Self::pending_values(key) {
+			if now < effective_at {
+				PendingValues::<T, I>::insert(key, (combined, effective_at));
+				return;
+			}
+			// the pending value has passed its challenge period
+			Self::set_value(key, pending, effective_at);
+			PendingValues::<T, I>::remove(key);
+		}
+
+		if Self::values(key).as_ref() != Some(&combined) {
+			let effective_at = now.saturating_add(period);
+			Self::deposit_event(Event::ValuePending {
+				key: key.clone(),
+				value: combined.value.clone(),
+				effective_at,
+			});
+			PendingValues::<T, I>::insert(key, (combined, effective_at));
+		}
+	}
+
+	/// Store the effective value of `key` and record it in the value history
+	/// if it changed.
+	fn set_value(key: &T::OracleKey, value: TimestampedValueOf<T, I>, effective_at: MomentOf<T, I>) {
+		if !T::MaxValueHistory::get().is_zero() {
+			ValueHistory::<T, I>::mutate(key, |history| {
+				if history.last().map(|(_, last)| last) == Some(&value.value) {
+					return;
+				}
+				if history.is_full() {
+					history.remove(0);
+				}
+				let _ = history.try_push((effective_at, value.value.clone()));
+			});
+		}
+		<Values<T, I>>::insert(key, value);
 	}
 
 	fn ensure_account(who: Option<T::AccountId>) -> Result<T::AccountId, DispatchError> {
@@ -216,18 +631,70 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// The weight of resuming the feeder and checking the heartbeats of each
+	/// member for `c` keys.
+	fn check_heartbeats_weight(c: u32) -> Weight {
+		let resume = T::DbWeight::get().reads_writes(1, 1).saturating_mul(c as u64);
+		if T::MaxMissedFeedingWindows::get().is_zero() {
+			return resume;
+		}
+		let members = T::Members::count() as u64;
+		T::DbWeight::get()
+			.reads_writes(2, 1)
+			.saturating_mul(members.saturating_mul(c as u64))
+			.saturating_add(resume)
+	}
+
+	/// Mark members whose last value of `key` is older than
+	/// `MaxMissedFeedingWindows` feeding windows as stale.
+	fn check_heartbeats(key: &T::OracleKey, now: MomentOf<T, I>) {
+		let max_missed = T::MaxMissedFeedingWindows::get();
+		if max_missed.is_zero() {
+			return;
+		}
+		let max_age = T::FeedingWindow::get().saturating_mul(max_missed.into());
+
+		for member in T::Members::sorted_members() {
+			if StaleMembers::<T, I>::contains_key(&member, key) {
+				continue;
+			}
+			let is_stale =
+				Self::raw_values(&member, key).map_or(false, |raw| now.saturating_sub(raw.timestamp) > max_age);
+			if is_stale {
+				StaleMembers::<T, I>::insert(&member, key, ());
+				Self::deposit_event(Event::MemberStale {
+					who: member,
+					key: key.clone(),
+				});
+			}
+		}
+	}
+
 	fn do_feed_values(who: T::AccountId, values: Vec<(T::OracleKey, T::OracleValue)>) -> DispatchResult {
 		let now = T::Time::now();
 		for (key, value) in &values {
+			if StaleMembers::<T, I>::take(&who, key).is_some() {
+				Self::deposit_event(Event::MemberResumed {
+					who: who.clone(),
+					key: key.clone(),
+				});
+			}
 			let timestamped = TimestampedValue {
 				value: value.clone(),
 				timestamp: now,
 			};
 			RawValues::<T, I>::insert(&who, key, timestamped);
-
-			// Update `Values` storage if `combined` yielded result.
-			if let Some(combined) = Self::combined(key) {
-				<Values<T, I>>::insert(key, combined);
+			Self::check_heartbeats(key, now);
+
+			// Update `Values` storage if `combined` yielded result. Combining may
+			// return the previous value if there are not enough values, which
+			// keeps an override in place.
+			let prev_value = Self::latest_combined(key);
+			if let Some(combined) = Self::combined(key, prev_value.clone()) {
+				if prev_value.as_ref() != Some(&combined) {
+					ValueOverrides::<T, I>::remove(key);
+				}
+				Self::update_value(key, combined, now);
 			}
 
 			T::OnNewData::on_new_data(&who, key, value);
@@ -242,6 +709,7 @@ impl<T: Config<I>, I: 'static> ChangeMembers<T::AccountId> for Pallet<T, I> {
 		// remove values
 		for removed in outgoing {
 			let _ = RawValues::<T, I>::clear_prefix(removed, u32::MAX, None);
+			let _ = StaleMembers::<T, I>::clear_prefix(removed, u32::MAX, None);
 		}
 	}
 
@@ -250,6 +718,19 @@ impl<T: Config<I>, I: 'static> ChangeMembers<T::AccountId> for Pallet<T, I> {
 	}
 }
 
+impl<Value: Clone, Moment: Clone> orml_traits::Timestamped for TimestampedValue<Value, Moment> {
+	type Value = Value;
+	type Moment = Moment;
+
+	fn value(&self) -> Value {
+		self.value.clone()
+	}
+
+	fn timestamp(&self) -> Moment {
+		self.timestamp.clone()
+	}
+}
+
 impl<T: Config<I>, I: 'static> DataProvider<T::OracleKey, T::OracleValue> for Pallet<T, I> {
 	fn get(key: &T::OracleKey) -> Option<T::OracleValue> {
 		Self::get(key).map(|timestamped_value| timestamped_value.value)
@@ -266,6 +747,12 @@ impl<T: Config<I>, I: 'static> DataProviderExtended<T::OracleKey, TimestampedVal
 	}
 }
 
+impl<T: Config<I>, I: 'static> DataHistoryProvider<T::OracleKey, T::OracleValue, MomentOf<T, I>> for Pallet<T, I> {
+	fn get_history(key: &T::OracleKey) -> Vec<(MomentOf<T, I>, T::OracleValue)> {
+		Self::value_history(key).into_inner()
+	}
+}
+
 impl<T: Config<I>, I: 'static> DataFeeder<T::OracleKey, T::OracleValue, T::AccountId> for Pallet<T, I> {
 	fn feed_value(who: Option<T::AccountId>, key: T::OracleKey, value: T::OracleValue) -> DispatchResult {
 		Self::do_feed_values(Self::ensure_account(who)?, vec![(key, value)])
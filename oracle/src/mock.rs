@@ -70,6 +70,8 @@ impl Timestamp {
 parameter_types! {
 	pub const RootOperatorAccountId: AccountId = 4;
 	pub static OracleMembers: Vec<AccountId> = vec![1, 2, 3];
+	pub static MaxMissedFeedingWindows: u32 = 0;
+	pub static SuspendStaleMembers: bool = false;
 }
 
 pub struct Members;
@@ -92,6 +94,12 @@ impl Config for Test {
 	type WeightInfo = ();
 	type MaxHasDispatchedSize = ConstU32<100>;
 	type MaxFeedValues = ConstU32<5>;
+	type ValueCodec = IntegerValue<Value>;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type FeedingWindow = ConstU32<100>;
+	type MaxMissedFeedingWindows = MaxMissedFeedingWindows;
+	type SuspendStaleMembers = SuspendStaleMembers;
+	type MaxValueHistory = ConstU32<2>;
 }
 
 type Block = frame_system::mocking::MockBlock<Test>;
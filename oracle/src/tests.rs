@@ -354,3 +354,436 @@ fn values_are_updated_on_feed() {
 		);
 	});
 }
+
+#[test]
+fn typed_getters_use_key_metadata() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		for member in 1..=3 {
+			assert_ok!(ModuleOracle::feed_values(
+				RuntimeOrigin::signed(member),
+				vec![(1, 1_250), (2, 35), (3, 7)].try_into().unwrap()
+			));
+		}
+
+		// no metadata
+		assert_eq!(ModuleOracle::get_price(&1), None);
+		assert_eq!(ModuleOracle::get_u128(&3), None);
+
+		assert_noop!(
+			ModuleOracle::set_key_metadata(
+				RuntimeOrigin::signed(1),
+				1,
+				Some(KeyMetadata {
+					kind: ValueKind::Price,
+					decimals: 2
+				})
+			),
+			DispatchError::BadOrigin
+		);
+
+		let price = KeyMetadata {
+			kind: ValueKind::Price,
+			decimals: 2,
+		};
+		assert_ok!(ModuleOracle::set_key_metadata(RuntimeOrigin::root(), 1, Some(price)));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::KeyMetadataUpdated {
+			key: 1,
+			metadata: Some(price),
+		}));
+		assert_ok!(ModuleOracle::set_key_metadata(
+			RuntimeOrigin::root(),
+			2,
+			Some(KeyMetadata {
+				kind: ValueKind::InterestRate,
+				decimals: 3
+			})
+		));
+		assert_ok!(ModuleOracle::set_key_metadata(
+			RuntimeOrigin::root(),
+			3,
+			Some(KeyMetadata {
+				kind: ValueKind::Integer,
+				decimals: 0
+			})
+		));
+
+		assert_eq!(ModuleOracle::get_price(&1), Some(FixedU128::from_rational(125, 10)));
+		assert_eq!(
+			ModuleOracle::get_interest_rate(&2),
+			Some(FixedU128::from_rational(35, 1_000))
+		);
+		assert_eq!(ModuleOracle::get_u128(&3), Some(7));
+
+		// the kind must match
+		assert_eq!(ModuleOracle::get_interest_rate(&1), None);
+		assert_eq!(ModuleOracle::get_price(&3), None);
+		assert_eq!(ModuleOracle::get_u128(&1), None);
+
+		assert_ok!(ModuleOracle::set_key_metadata(RuntimeOrigin::root(), 1, None));
+		assert_eq!(ModuleOracle::key_metadata(1), None);
+		assert_eq!(ModuleOracle::get_price(&1), None);
+	});
+}
+
+#[test]
+fn stale_members_are_suspended_until_they_feed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxMissedFeedingWindows::set(2);
+		SuspendStaleMembers::set(true);
+
+		for member in 1..=3 {
+			assert_ok!(ModuleOracle::feed_values(
+				RuntimeOrigin::signed(member),
+				vec![(1, 1000)].try_into().unwrap()
+			));
+		}
+		assert_eq!(ModuleOracle::read_raw_values(&1).len(), 3);
+
+		ModuleOracle::on_finalize(1);
+		System::set_block_number(2);
+		Timestamp::set_timestamp(12345 + 201);
+
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(1, 1100)].try_into().unwrap()
+		));
+		System::assert_has_event(RuntimeEvent::ModuleOracle(crate::Event::MemberStale { who: 2, key: 1 }));
+		System::assert_has_event(RuntimeEvent::ModuleOracle(crate::Event::MemberStale { who: 3, key: 1 }));
+		assert_eq!(ModuleOracle::stale_members(1, 1), None);
+		assert_eq!(ModuleOracle::stale_members(3, 1), Some(()));
+		assert_eq!(ModuleOracle::read_raw_values(&1).len(), 1);
+
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(2),
+			vec![(1, 1200)].try_into().unwrap()
+		));
+		System::assert_has_event(RuntimeEvent::ModuleOracle(crate::Event::MemberResumed {
+			who: 2,
+			key: 1,
+		}));
+		assert_eq!(ModuleOracle::stale_members(2, 1), None);
+		assert_eq!(ModuleOracle::read_raw_values(&1).len(), 2);
+
+		// staleness is tracked per key, member 3 is not stale for a key it
+		// didn't feed
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(3),
+			vec![(2, 1000)].try_into().unwrap()
+		));
+		assert_eq!(ModuleOracle::stale_members(3, 1), Some(()));
+		assert_eq!(ModuleOracle::stale_members(3, 2), None);
+		assert_eq!(ModuleOracle::read_raw_values(&1).len(), 2);
+		assert_eq!(ModuleOracle::read_raw_values(&2).len(), 1);
+
+		// not suspended when only flagging stale members
+		SuspendStaleMembers::set(false);
+		assert_eq!(ModuleOracle::read_raw_values(&1).len(), 3);
+	});
+}
+
+#[test]
+fn recent_combine_data_ignores_stale_values() {
+	orml_traits::parameter_type_with_key! {
+		pub FeedTtl: |key: u32| -> u32 {
+			if *key == 1 { 100 } else { 1000 }
+		};
+	}
+	type Combine = RecentCombineData<Test, frame_support::traits::ConstU32<2>, FeedTtl>;
+
+	new_test_ext().execute_with(|| {
+		let fed_at = |value, timestamp| TimestampedValue { value, timestamp };
+		let values = vec![fed_at(1300, 12000), fed_at(1000, 12300), fed_at(1200, 12340)];
+		let prev_value = Some(fed_at(900, 11000));
+
+		// only the two latest values are fresh for key 1
+		assert_eq!(
+			Combine::combine_data(&1, values.clone(), prev_value),
+			Some(fed_at(1200, 12340))
+		);
+		// all values are fresh for key 2
+		assert_eq!(
+			Combine::combine_data(&2, values.clone(), prev_value),
+			Some(fed_at(1200, 12340))
+		);
+
+		Timestamp::set_timestamp(12420);
+		assert_eq!(Combine::combine_data(&1, values.clone(), prev_value), None);
+		assert_eq!(
+			Combine::combine_data(&2, values.clone(), prev_value),
+			Some(fed_at(1200, 12340))
+		);
+
+		Timestamp::set_timestamp(13320);
+		assert_eq!(Combine::combine_data(&2, values, prev_value), None);
+	});
+}
+
+#[test]
+fn force_feed_value_overrides_until_members_feed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			ModuleOracle::force_feed_value(RuntimeOrigin::signed(1), 50, 800),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ModuleOracle::force_feed_value(RuntimeOrigin::root(), 50, 800));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::ValueOverridden {
+			key: 50,
+			value: 800,
+		}));
+		assert_eq!(
+			ModuleOracle::values(50),
+			Some(TimestampedValue {
+				value: 800,
+				timestamp: 12345,
+			})
+		);
+		assert_eq!(ModuleOracle::value_overrides(50), Some(()));
+
+		// values which can not be combined yet keep the override
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(50, 900)].try_into().unwrap()
+		));
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(2),
+			vec![(50, 1000)].try_into().unwrap()
+		));
+		assert_eq!(ModuleOracle::values(50).map(|v| v.value), Some(800));
+		assert_eq!(ModuleOracle::value_overrides(50), Some(()));
+
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(3),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+		assert_eq!(ModuleOracle::values(50).map(|v| v.value), Some(1000));
+		assert_eq!(ModuleOracle::value_overrides(50), None);
+	});
+}
+
+#[test]
+fn challenge_period_delays_and_vetoes_combined_values() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			ModuleOracle::set_challenge_period(RuntimeOrigin::signed(1), 50, Some(100)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ModuleOracle::set_challenge_period(RuntimeOrigin::root(), 50, Some(100)));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::ChallengePeriodUpdated {
+			key: 50,
+			period: Some(100),
+		}));
+
+		for member in 1..=3 {
+			assert_ok!(ModuleOracle::feed_values(
+				RuntimeOrigin::signed(member),
+				vec![(50, 1000)].try_into().unwrap()
+			));
+		}
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::ValuePending {
+			key: 50,
+			value: 1000,
+			effective_at: 12445,
+		}));
+		assert_eq!(ModuleOracle::get(&50), None);
+		assert_eq!(ModuleOracle::get_all_values(), vec![]);
+
+		Timestamp::set_timestamp(12445);
+		assert_eq!(ModuleOracle::get(&50).map(|v| v.value), Some(1000));
+		assert_eq!(
+			ModuleOracle::get_all_values(),
+			vec![(
+				50,
+				Some(TimestampedValue {
+					value: 1000,
+					timestamp: 12345,
+				})
+			)]
+		);
+
+		// the previous value is served while the new value is pending
+		ModuleOracle::on_finalize(1);
+		for member in 1..=2 {
+			assert_ok!(ModuleOracle::feed_values(
+				RuntimeOrigin::signed(member),
+				vec![(50, 2000)].try_into().unwrap()
+			));
+		}
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::ValuePending {
+			key: 50,
+			value: 2000,
+			effective_at: 12545,
+		}));
+		assert_eq!(ModuleOracle::values(50).map(|v| v.value), Some(1000));
+		assert_eq!(ModuleOracle::get(&50).map(|v| v.value), Some(1000));
+
+		assert_noop!(
+			ModuleOracle::veto_pending_value(RuntimeOrigin::signed(1), 50),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ModuleOracle::veto_pending_value(RuntimeOrigin::root(), 50));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::PendingValueVetoed {
+			key: 50,
+			value: 2000,
+		}));
+		assert_eq!(ModuleOracle::pending_values(50), None);
+		assert_noop!(
+			ModuleOracle::veto_pending_value(RuntimeOrigin::root(), 50),
+			Error::<Test, _>::NoPendingValue
+		);
+
+		Timestamp::set_timestamp(12545);
+		assert_eq!(ModuleOracle::get(&50).map(|v| v.value), Some(1000));
+	});
+}
+
+#[test]
+fn deprecated_keys_are_redirected_to_replacement() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for member in 1..=3 {
+			assert_ok!(ModuleOracle::feed_values(
+				RuntimeOrigin::signed(member),
+				vec![(50, 1000), (60, 2000)].try_into().unwrap()
+			));
+		}
+
+		assert_noop!(
+			ModuleOracle::deprecate_key(RuntimeOrigin::signed(1), 50, 60),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ModuleOracle::deprecate_key(RuntimeOrigin::root(), 50, 50),
+			Error::<Test, _>::InvalidReplacementKey
+		);
+		assert_ok!(ModuleOracle::deprecate_key(RuntimeOrigin::root(), 50, 60));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::KeyDeprecated {
+			key: 50,
+			replacement: 60,
+		}));
+		assert_noop!(
+			ModuleOracle::deprecate_key(RuntimeOrigin::root(), 60, 70),
+			Error::<Test, _>::InvalidReplacementKey
+		);
+		assert_noop!(
+			ModuleOracle::deprecate_key(RuntimeOrigin::root(), 70, 50),
+			Error::<Test, _>::InvalidReplacementKey
+		);
+		assert_eq!(ModuleOracle::redirected_keys(60), 1);
+
+		// redirecting to another replacement moves the reverse index
+		assert_ok!(ModuleOracle::deprecate_key(RuntimeOrigin::root(), 50, 70));
+		assert_eq!(ModuleOracle::redirected_keys(60), 0);
+		assert_eq!(ModuleOracle::redirected_keys(70), 1);
+		assert_ok!(ModuleOracle::deprecate_key(RuntimeOrigin::root(), 50, 60));
+
+		let events = System::events().len();
+		assert_eq!(ModuleOracle::get(&50).map(|v| v.value), Some(2000));
+		// reads do not emit events
+		assert_eq!(System::events().len(), events);
+		assert_eq!(
+			<ModuleOracle as DataProviderExtended<_, _>>::get_no_op(&50).map(|v| v.value),
+			Some(2000)
+		);
+		assert_eq!(ModuleOracle::values(50).map(|v| v.value), Some(1000));
+
+		assert_ok!(ModuleOracle::undeprecate_key(RuntimeOrigin::root(), 50));
+		System::assert_last_event(RuntimeEvent::ModuleOracle(crate::Event::KeyUndeprecated { key: 50 }));
+		assert!(!RedirectedKeys::<Test, ()>::contains_key(60));
+		assert!(!RedirectedKeys::<Test, ()>::contains_key(70));
+		assert_ok!(ModuleOracle::deprecate_key(RuntimeOrigin::root(), 60, 50));
+		assert_ok!(ModuleOracle::undeprecate_key(RuntimeOrigin::root(), 60));
+		assert_eq!(ModuleOracle::get(&50).map(|v| v.value), Some(1000));
+		assert_noop!(
+			ModuleOracle::undeprecate_key(RuntimeOrigin::root(), 50),
+			Error::<Test, _>::KeyNotDeprecated
+		);
+	});
+}
+
+#[test]
+fn should_get_raw_values_by_member() {
+	new_test_ext().execute_with(|| {
+		let key: u32 = 50;
+		assert_eq!(ModuleOracle::get_raw_values(&key), vec![]);
+
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(2),
+			vec![(key, 1200)].try_into().unwrap()
+		));
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(key, 1000)].try_into().unwrap()
+		));
+
+		assert_eq!(
+			ModuleOracle::get_raw_values(&key),
+			vec![
+				(
+					1,
+					TimestampedValue {
+						value: 1000,
+						timestamp: 12345,
+					}
+				),
+				(
+					2,
+					TimestampedValue {
+						value: 1200,
+						timestamp: 12345,
+					}
+				),
+			]
+		);
+	});
+}
+
+#[test]
+fn value_history_keeps_last_effective_values() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(100);
+		assert_ok!(ModuleOracle::force_feed_value(RuntimeOrigin::root(), 50, 800));
+		// an unchanged value is not recorded again
+		Timestamp::set_timestamp(150);
+		assert_ok!(ModuleOracle::force_feed_value(RuntimeOrigin::root(), 50, 800));
+		Timestamp::set_timestamp(200);
+		assert_ok!(ModuleOracle::force_feed_value(RuntimeOrigin::root(), 50, 900));
+		assert_eq!(ModuleOracle::get_history(&50), vec![(100, 800), (200, 900)]);
+
+		// the oldest value is dropped
+		Timestamp::set_timestamp(300);
+		assert_ok!(ModuleOracle::force_feed_value(RuntimeOrigin::root(), 50, 1000));
+		assert_eq!(ModuleOracle::get_history(&50), vec![(200, 900), (300, 1000)]);
+
+		// a pending value is recorded at the moment it becomes effective
+		assert_ok!(ModuleOracle::set_challenge_period(RuntimeOrigin::root(), 50, Some(100)));
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(2),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(3),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+		assert_eq!(ModuleOracle::get_history(&50), vec![(200, 900), (300, 1000)]);
+		ModuleOracle::on_finalize(1);
+		Timestamp::set_timestamp(450);
+		assert_ok!(ModuleOracle::feed_values(
+			RuntimeOrigin::signed(1),
+			vec![(50, 1100)].try_into().unwrap()
+		));
+		assert_eq!(ModuleOracle::get_history(&50), vec![(300, 1000), (400, 1100)]);
+		assert_eq!(ModuleOracle::value_history(60).len(), 0);
+	});
+}
@@ -0,0 +1,41 @@
+use sp_runtime::FixedPointNumber;
+use sp_std::marker;
+
+/// Exposes the raw integer representation of oracle values, which is scaled
+/// by the decimals in the key's metadata to produce typed values.
+pub trait OracleValueCodec<Value> {
+	/// The raw integer representation of `value`, `None` if it has none.
+	fn to_raw(value: &Value) -> Option<u128>;
+}
+
+impl<Value> OracleValueCodec<Value> for () {
+	fn to_raw(_value: &Value) -> Option<u128> {
+		None
+	}
+}
+
+/// Uses integer values as is.
+pub struct IntegerValue<Value>(marker::PhantomData<Value>);
+
+impl<Value> OracleValueCodec<Value> for IntegerValue<Value>
+where
+	Value: Clone + TryInto<u128>,
+{
+	fn to_raw(value: &Value) -> Option<u128> {
+		value.clone().try_into().ok()
+	}
+}
+
+/// Uses the inner value of fixed point numbers. Keys with such values should
+/// have `FixedPointNumber::DIV` as decimals, e.g. 18 for `FixedU128`.
+pub struct FixedPointValue<Value>(marker::PhantomData<Value>);
+
+impl<Value> OracleValueCodec<Value> for FixedPointValue<Value>
+where
+	Value: FixedPointNumber,
+	Value::Inner: TryInto<u128>,
+{
+	fn to_raw(value: &Value) -> Option<u128> {
+		value.into_inner().try_into().ok()
+	}
+}
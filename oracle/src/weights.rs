@@ -32,6 +32,12 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn feed_values(c: u32, ) -> Weight;
 	fn on_finalize() -> Weight;
+	fn set_key_metadata() -> Weight;
+	fn force_feed_value() -> Weight;
+	fn set_challenge_period() -> Weight;
+	fn veto_pending_value() -> Weight;
+	fn deprecate_key() -> Weight;
+	fn undeprecate_key() -> Weight;
 }
 
 /// Default weights.
@@ -41,11 +47,41 @@ impl WeightInfo for () {
 			// Standard Error: 84_000
 			.saturating_add(Weight::from_parts(3_600_000, 0).saturating_mul(c as u64))
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads((3 as u64).saturating_mul(c as u64)))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((6 as u64).saturating_mul(c as u64)))
 	}
 	fn on_finalize() -> Weight {
 		Weight::from_parts(3_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn set_key_metadata() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn force_feed_value() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn set_challenge_period() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn veto_pending_value() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn deprecate_key() -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn undeprecate_key() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
 }
@@ -8,7 +8,7 @@ use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 
 use frame_support::traits::EnsureOriginWithArg;
-use orml_traits::parameters::{AggregratedKeyValue, Into2, Key, RuntimeParameterStore, TryInto2};
+use orml_traits::parameters::{AggregratedKeyValue, Into2, Key, RuntimeParameterStore, TryInto2, ValidateParameter};
 
 mod mock;
 mod tests;
@@ -27,11 +27,23 @@ pub mod module {
 
 		/// The key value type for parameters. Usually created by
 		/// orml_traits::parameters::define_aggregrated_parameters
-		type AggregratedKeyValue: AggregratedKeyValue;
+		type AggregratedKeyValue: AggregratedKeyValue + MaxEncodedLen;
 
 		/// The origin which may update the parameter.
+		///
+		/// It is checked against the key of each `set_parameter` call, so
+		/// different keys or key prefixes can require different origins, e.g.
+		/// by matching on the pallet variant of the aggregated key.
 		type AdminOrigin: EnsureOriginWithArg<Self::RuntimeOrigin, KeyOf<Self>>;
 
+		/// Validate the new parameter value before it is stored.
+		type Validate: ValidateParameter<Self::AggregratedKeyValue>;
+
+		/// The maximum number of parameter changes scheduled for a single
+		/// block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -40,13 +52,25 @@ pub mod module {
 	type ValueOf<T> = <<T as Config>::AggregratedKeyValue as AggregratedKeyValue>::AggregratedValue;
 
 	#[pallet::error]
-	pub enum Error<T> {}
+	pub enum Error<T> {
+		/// The activation block is not in the future.
+		InvalidActivationBlock,
+		/// Too many parameter changes are already scheduled for the block.
+		TooManyScheduled,
+	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// Parameter is updated
 		Updated { key_value: T::AggregratedKeyValue },
+		/// Parameter change is scheduled
+		ParameterScheduled {
+			key_value: T::AggregratedKeyValue,
+			activate_at: BlockNumberFor<T>,
+		},
+		/// Scheduled parameter change is applied
+		ParameterActivated { key_value: T::AggregratedKeyValue },
 	}
 
 	/// Stored parameters.
@@ -55,9 +79,37 @@ pub mod module {
 	#[pallet::storage]
 	pub type Parameters<T: Config> = StorageMap<_, Blake2_128Concat, KeyOf<T>, ValueOf<T>, OptionQuery>;
 
+	/// Parameter changes waiting for their activation block.
+	///
+	/// ScheduledParameters: map BlockNumber => Vec<AggregratedKeyValue>
+	#[pallet::storage]
+	pub type ScheduledParameters<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::AggregratedKeyValue, T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let scheduled = ScheduledParameters::<T>::take(now);
+			let count = scheduled.len() as u32;
+
+			for key_value in scheduled {
+				let (key, value) = key_value.clone().into_parts();
+				Parameters::<T>::mutate(key, |v| *v = value);
+				Self::deposit_event(Event::ParameterActivated { key_value });
+			}
+
+			T::WeightInfo::on_initialize(count)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set parameter
@@ -68,12 +120,47 @@ pub mod module {
 
 			T::AdminOrigin::ensure_origin(origin, &key)?;
 
+			T::Validate::validate(&key_value)?;
+
 			Parameters::<T>::mutate(key, |v| *v = value);
 
 			Self::deposit_event(Event::Updated { key_value });
 
 			Ok(())
 		}
+
+		/// Schedule a parameter change
+		///
+		/// The change is stored as pending and applied at the beginning of
+		/// block `activate_at`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::schedule_parameter())]
+		pub fn schedule_parameter(
+			origin: OriginFor<T>,
+			key_value: T::AggregratedKeyValue,
+			activate_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let (key, _) = key_value.clone().into_parts();
+
+			T::AdminOrigin::ensure_origin(origin, &key)?;
+
+			T::Validate::validate(&key_value)?;
+
+			ensure!(
+				activate_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidActivationBlock
+			);
+
+			ScheduledParameters::<T>::try_mutate(activate_at, |scheduled| {
+				scheduled
+					.try_push(key_value.clone())
+					.map_err(|_| Error::<T>::TooManyScheduled)
+			})?;
+
+			Self::deposit_event(Event::ParameterScheduled { key_value, activate_at });
+
+			Ok(())
+		}
 	}
 }
 
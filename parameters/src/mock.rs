@@ -90,10 +90,25 @@ impl EnsureOriginWithArg<RuntimeOrigin, RuntimeParametersKey> for EnsureOriginIm
 	}
 }
 
+pub struct ValidateImpl;
+
+impl ValidateParameter<RuntimeParameters> for ValidateImpl {
+	fn validate(key_value: &RuntimeParameters) -> DispatchResult {
+		match key_value {
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(_, Some(value))) if *value > 100 => {
+				Err(DispatchError::Other("value too large"))
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type AggregratedKeyValue = RuntimeParameters;
 	type AdminOrigin = EnsureOriginImpl;
+	type Validate = ValidateImpl;
+	type MaxScheduledPerBlock = ConstU32<2>;
 	type WeightInfo = ();
 }
 
@@ -72,3 +72,108 @@ fn set_parameters() {
 		);
 	});
 }
+
+#[test]
+fn scheduled_parameters() {
+	ExtBuilder::new().execute_with(|| {
+		assert_noop!(
+			ModuleParameters::schedule_parameter(
+				RuntimeOrigin::root(),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(123))),
+				1,
+			),
+			Error::<Runtime>::InvalidActivationBlock
+		);
+
+		assert_noop!(
+			ModuleParameters::schedule_parameter(
+				RuntimeOrigin::signed(1),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(123))),
+				10,
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(ModuleParameters::schedule_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(123))),
+			10,
+		));
+		System::assert_last_event(RuntimeEvent::ModuleParameters(crate::Event::ParameterScheduled {
+			key_value: RuntimeParameters::Pallet1(pallet1::Parameters::Key1(pallet1::Key1, Some(123))),
+			activate_at: 10,
+		}));
+
+		assert_ok!(ModuleParameters::schedule_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key2(pallet1::Key2(234), Some(345))),
+			10,
+		));
+
+		assert_noop!(
+			ModuleParameters::schedule_parameter(
+				RuntimeOrigin::root(),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key2(pallet1::Key2(235), Some(345))),
+				10,
+			),
+			Error::<Runtime>::TooManyScheduled
+		);
+
+		assert_eq!(
+			<ModuleParameters as RuntimeParameterStore>::get::<pallet1::Parameters, _>(pallet1::Key1),
+			None
+		);
+
+		ModuleParameters::on_initialize(9);
+		assert_eq!(
+			<ModuleParameters as RuntimeParameterStore>::get::<pallet1::Parameters, _>(pallet1::Key1),
+			None
+		);
+
+		ModuleParameters::on_initialize(10);
+		assert_eq!(
+			<ModuleParameters as RuntimeParameterStore>::get::<pallet1::Parameters, _>(pallet1::Key1),
+			Some(123)
+		);
+		assert_eq!(
+			<ModuleParameters as RuntimeParameterStore>::get::<pallet1::Parameters, _>(pallet1::Key2(234)),
+			Some(345)
+		);
+		System::assert_last_event(RuntimeEvent::ModuleParameters(crate::Event::ParameterActivated {
+			key_value: RuntimeParameters::Pallet1(pallet1::Parameters::Key2(pallet1::Key2(234), Some(345))),
+		}));
+		assert_eq!(ScheduledParameters::<Runtime>::get(10).len(), 0);
+	});
+}
+
+#[test]
+fn validate_parameters() {
+	ExtBuilder::new().execute_with(|| {
+		assert_noop!(
+			ModuleParameters::set_parameter(
+				RuntimeOrigin::root(),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3((1, 2)), Some(101))),
+			),
+			DispatchError::Other("value too large")
+		);
+
+		assert_noop!(
+			ModuleParameters::schedule_parameter(
+				RuntimeOrigin::root(),
+				RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3((1, 2)), Some(101))),
+				10,
+			),
+			DispatchError::Other("value too large")
+		);
+
+		assert_ok!(ModuleParameters::set_parameter(
+			RuntimeOrigin::root(),
+			RuntimeParameters::Pallet1(pallet1::Parameters::Key3(pallet1::Key3((1, 2)), Some(100))),
+		));
+
+		assert_eq!(
+			<ModuleParameters as RuntimeParameterStore>::get::<pallet1::Parameters, _>(pallet1::Key3((1, 2))),
+			Some(100)
+		);
+	});
+}
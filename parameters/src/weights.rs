@@ -8,10 +8,19 @@ use sp_std::marker::PhantomData;
 
 pub trait WeightInfo {
 	fn set_parameter() -> Weight;
+	fn schedule_parameter() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
 }
 
 impl WeightInfo for () {
 	fn set_parameter() -> Weight {
 		RocksDbWeight::get().reads_writes(2, 1)
 	}
+	fn schedule_parameter() -> Weight {
+		RocksDbWeight::get().reads_writes(2, 1)
+	}
+	fn on_initialize(n: u32) -> Weight {
+		RocksDbWeight::get().reads_writes(1, 1)
+			.saturating_add(RocksDbWeight::get().reads_writes((1 as u64).saturating_mul(n as u64), (1 as u64).saturating_mul(n as u64)))
+	}
 }
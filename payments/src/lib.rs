@@ -18,29 +18,67 @@
 //! - Remark: The pallet allows to create payments by optionally providing some
 //!   extra(limited) amount of bytes, this is reffered to as Remark. This can be
 //!   used by a marketplace to seperate/tag payments.
+//! - External reference: Payments can be created with a hash referencing them
+//!   in an external system, like an invoice or order. The remark and external
+//!   reference are stored until the payment is settled.
 //! - CancelBufferBlockLength: This is the time window where the recipient can
 //!   dispute a cancellation request from the payment creator.
+//! - AppealWindow: This is the time window where the creator or recipient can
+//!   appeal the ruling of a judge by reserving a bond. When zero, rulings are
+//!   settled immediately.
 
 //! Extrinsics
 //!
 //! - `pay` - Create an payment for the given currencyid/amount
 //! - `pay_with_remark` - Create a payment with a remark, can be used to tag
 //!   payments
+//! - `pay_with_expiry` - Create a payment that can be refunded to the creator
+//!   once it expires
+//! - `pay_with_splits` - Create a payment whose released amount is split
+//!   between the recipient and other accounts by fixed percentages
+//! - `pay_with_reference` - Create a payment with a remark and an external
+//!   reference, used to reconcile payments with off-chain orders
 //! - `release` - Release the payment amount to recipent
+//! - `release_partial` - Release a part of the payment amount to recipient,
+//!   the rest of the payment stays reserved
 //! - `cancel` - Allows the recipient to cancel the payment and release the
 //!   payment amount to creator
 //! - `resolve_release_payment` - Allows assigned judge to release a payment
 //! - `resolve_cancel_payment` - Allows assigned judge to cancel a payment
+//! - `appeal_ruling` - Allows the creator or recipient to appeal the ruling of
+//!   the judge within the appeal window
+//! - `resolve_appeal` - Allows the appeal origin to settle an appealed payment
+//! - `finalize_ruling` - Allows anyone to settle a ruled payment once the
+//!   appeal window has passed
 //! - `request_refund` - Allows the creator of the payment to trigger cancel
 //!   with a buffer time.
 //! - `claim_refund` - Allows the creator to claim payment refund after buffer
 //!   time
 //! - `dispute_refund` - Allows the recipient to dispute the payment request of
 //!   sender
+//! - `claim_expired_refund` - Allows anyone to refund an expired payment to its
+//!   creator
 //! - `request_payment` - Create a payment that can be completed by the sender
 //!   using the `accept_and_pay` extrinsic.
 //! - `accept_and_pay` - Allows the sender to fulfill a payment request created
 //!   by a recipient
+//! - `request_payment_in_reference` - Create a payment request for the
+//!   equivalent of an amount of a reference asset, the amount to pay is
+//!   converted using the price provider when the request is accepted
+//! - `set_fee_config` - Allows the fee origin to configure the fee of an asset
+//!   and payment kind, overriding the `FeeHandler`
+//! - `create_recurring` - Create a recurring payment of a number of
+//!   installments, reserving the first installment
+//! - `collect_installment` - Allows anyone to transfer a due installment of a
+//!   recurring payment to its recipient
+//! - `cancel_recurring` - Allows the payer to cancel a recurring payment
+//!   between installments
+//! - `propose_payment` - Propose a payment that is only reserved once the
+//!   recipient accepts it using the `accept_payment` extrinsic
+//! - `accept_payment` - Allows the recipient to accept a proposed payment or to
+//!   counter propose a different amount
+//! - `confirm_counter_proposal` - Allows the creator to accept the amount
+//!   counter proposed by the recipient
 
 //! Types
 //!
@@ -62,13 +100,19 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod migrations;
 pub mod types;
 pub mod weights;
 
+pub use migrations::Migration;
+
 #[frame_support::pallet]
 pub mod pallet {
 	pub use crate::{
-		types::{DisputeResolver, FeeHandler, PaymentDetail, PaymentHandler, PaymentState, ScheduledTask, Task},
+		types::{
+			DisputeResolver, FeeConfig, FeeHandler, PaymentDetail, PaymentHandler, PaymentKind, PaymentMetadata,
+			PaymentState, RecurringPayment, ScheduledTask, Task,
+		},
 		weights::WeightInfo,
 	};
 	use frame_support::{
@@ -76,16 +120,19 @@ pub mod pallet {
 		storage::bounded_btree_map::BoundedBTreeMap, traits::tokens::BalanceStatus,
 	};
 	use frame_system::pallet_prelude::*;
+	use orml_traits::PriceProvider;
 	use orml_traits::{MultiCurrency, MultiReservableCurrency};
 	use sp_runtime::{
-		traits::{CheckedAdd, Saturating},
-		Percent,
+		traits::{CheckedAdd, Saturating, Zero},
+		FixedPointNumber, FixedU128, Perbill, Percent,
 	};
 	use sp_std::vec::Vec;
 
 	pub type BalanceOf<T> = <<T as Config>::Asset as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
 	pub type AssetIdOf<T> = <<T as Config>::Asset as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 	pub type BoundedDataOf<T> = BoundedVec<u8, <T as Config>::MaxRemarkLength>;
+	/// list of accounts and their share of a released payment
+	pub type SplitsOf<T> = BoundedVec<(<T as frame_system::Config>::AccountId, Percent), <T as Config>::MaxSplits>;
 	/// type of ScheduledTask used by the pallet
 	pub type ScheduledTaskOf<T> = ScheduledTask<BlockNumberFor<T>>;
 	/// list of ScheduledTasks, stored as a BoundedBTreeMap
@@ -123,11 +170,31 @@ pub mod pallet {
 		/// canceled payment
 		#[pallet::constant]
 		type MaxScheduledTaskListLength: Get<u32>;
+		/// Maximum number of split recipients of a payment
+		#[pallet::constant]
+		type MaxSplits: Get<u32>;
+		/// Appeal period - number of blocks the ruling of a judge can be
+		/// appealed before it can be finalized
+		#[pallet::constant]
+		type AppealWindow: Get<BlockNumberFor<Self>>;
+		/// Appeal bond percentage - share of the payment amount reserved from
+		/// the appellant
+		#[pallet::constant]
+		type AppealBondPercentage: Get<Percent>;
+		/// The origin which may resolve appealed payments
+		type AppealOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Price provider used to convert amounts of a reference asset
+		type PriceProvider: PriceProvider<AssetIdOf<Self>, FixedU128>;
+		/// The origin which may configure fees
+		type FeeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 		//// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -147,6 +214,53 @@ pub mod pallet {
 		PaymentDetail<T>,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn payment_reference)]
+	/// The reference asset and amount of payment requests that are
+	/// denominated in a reference asset, the payment amount is computed when
+	/// the request is accepted.
+	pub(super) type PaymentReferences<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // payment creator
+		Blake2_128Concat,
+		T::AccountId, // payment recipient
+		(AssetIdOf<T>, BalanceOf<T>),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn payment_metadata)]
+	/// The remark and external reference of payments created with either,
+	/// removed when the payment is settled.
+	pub(super) type PaymentMetadatas<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // payment creator
+		Blake2_128Concat,
+		T::AccountId, // payment recipient
+		PaymentMetadata<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fee_config)]
+	/// The fee of payments of an asset and kind, payments without a
+	/// configured fee use the `FeeHandler`.
+	pub(super) type FeeConfigs<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AssetIdOf<T>, Twox64Concat, PaymentKind, FeeConfig<T::AccountId>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn recurring_payment)]
+	/// Recurring payments, keyed by payer and recipient. Only the next
+	/// installment is reserved from the payer.
+	pub(super) type RecurringPayments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId, // payer
+		Blake2_128Concat,
+		T::AccountId, // recipient
+		RecurringPayment<T>,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn tasks)]
 	/// Store the list of tasks to be executed in the on_idle function
@@ -161,9 +275,16 @@ pub mod pallet {
 			asset: AssetIdOf<T>,
 			amount: BalanceOf<T>,
 			remark: Option<BoundedDataOf<T>>,
+			external_reference: Option<T::Hash>,
 		},
 		/// Payment amount released to the recipient
 		PaymentReleased { from: T::AccountId, to: T::AccountId },
+		/// Part of the payment amount released to the recipient
+		PaymentPartiallyReleased {
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 		/// Payment has been cancelled by the creator
 		PaymentCancelled { from: T::AccountId, to: T::AccountId },
 		/// A payment that NeedsReview has been resolved by Judge
@@ -172,6 +293,28 @@ pub mod pallet {
 			to: T::AccountId,
 			recipient_share: Percent,
 		},
+		/// A judge has ruled on a payment that can be appealed until
+		/// `appeal_deadline`
+		PaymentRuled {
+			from: T::AccountId,
+			to: T::AccountId,
+			recipient_share: Percent,
+			appeal_deadline: BlockNumberFor<T>,
+		},
+		/// The ruling of a payment has been appealed
+		RulingAppealed {
+			from: T::AccountId,
+			to: T::AccountId,
+			appellant: T::AccountId,
+			bond: BalanceOf<T>,
+		},
+		/// An appealed payment has been resolved by the appeal origin
+		AppealResolved {
+			from: T::AccountId,
+			to: T::AccountId,
+			recipient_share: Percent,
+			bond_slashed: bool,
+		},
 		/// the payment creator has created a refund request
 		PaymentCreatorRequestedRefund {
 			from: T::AccountId,
@@ -184,6 +327,56 @@ pub mod pallet {
 		PaymentRequestCreated { from: T::AccountId, to: T::AccountId },
 		/// Payment request was completed by sender
 		PaymentRequestCompleted { from: T::AccountId, to: T::AccountId },
+		/// An expired payment has been refunded to the creator
+		PaymentExpiredRefunded { from: T::AccountId, to: T::AccountId },
+		/// The fee configuration of an asset and payment kind was updated
+		FeeConfigUpdated {
+			asset: AssetIdOf<T>,
+			kind: PaymentKind,
+			config: Option<FeeConfig<T::AccountId>>,
+		},
+		/// A recurring payment has been created
+		RecurringPaymentCreated {
+			from: T::AccountId,
+			to: T::AccountId,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			interval: BlockNumberFor<T>,
+			count: u32,
+		},
+		/// An installment of a recurring payment was transferred to the
+		/// recipient
+		InstallmentCollected {
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+			remaining: u32,
+		},
+		/// A recurring payment has been cancelled by the payer
+		RecurringPaymentCancelled { from: T::AccountId, to: T::AccountId },
+		/// A recurring payment stopped because the next installment could not
+		/// be reserved from the payer
+		RecurringPaymentStopped { from: T::AccountId, to: T::AccountId },
+		/// A payment was proposed by the creator
+		PaymentProposed {
+			from: T::AccountId,
+			to: T::AccountId,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// The recipient of a proposed payment counter proposed a different
+		/// amount
+		PaymentCounterProposed {
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// A proposed payment was accepted and its amount reserved
+		PaymentAccepted {
+			from: T::AccountId,
+			to: T::AccountId,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -206,6 +399,26 @@ pub mod pallet {
 		DisputePeriodNotPassed,
 		/// The automatic cancelation queue cannot accept
 		RefundQueueFull,
+		/// The amount is zero or exceeds the remaining payment amount
+		InvalidAmount,
+		/// The expiry block is not in the future
+		InvalidExpiry,
+		/// The payment has no expiry or has not expired yet
+		PaymentNotExpired,
+		/// The split shares add up to more than 100%
+		InvalidSplits,
+		/// The appeal window of the ruling has passed
+		AppealWindowPassed,
+		/// The appeal window of the ruling has not passed yet
+		AppealWindowNotPassed,
+		/// The price of the reference asset is not available
+		PriceUnavailable,
+		/// The interval or installment count of a recurring payment is zero
+		InvalidRecurringSchedule,
+		/// The next installment of the recurring payment is not due yet
+		InstallmentNotDue,
+		/// The next installment of the recurring payment is already due
+		InstallmentDue,
 	}
 
 	#[pallet::hooks]
@@ -291,25 +504,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 
-			// create PaymentDetail and add to storage
-			let payment_detail = <Self as PaymentHandler<T>>::create_payment(
-				&who,
-				&recipient,
-				asset,
-				amount,
-				PaymentState::Created,
-				T::IncentivePercentage::get(),
-				remark.as_ref().map(|x| x.as_slice()),
-			)?;
-			// reserve funds for payment
-			<Self as PaymentHandler<T>>::reserve_payment_amount(&who, &recipient, payment_detail)?;
-			// emit paymentcreated event
-			Self::deposit_event(Event::PaymentCreated {
-				from: who,
-				asset,
-				amount,
-				remark,
-			});
+			Self::do_pay(who, recipient, asset, amount, remark, None, None, Default::default())?;
 			Ok(().into())
 		}
 
@@ -345,8 +540,12 @@ pub mod pallet {
 						<Self as PaymentHandler<T>>::settle_payment(&creator, &who, Percent::from_percent(0))?;
 						Self::deposit_event(Event::PaymentCancelled { from: creator, to: who });
 					}
-					// if the payment is in state PaymentRequested, remove from storage
-					PaymentState::PaymentRequested => Payment::<T>::remove(&creator, &who),
+					// if the payment is requested or proposed, remove from storage
+					PaymentState::PaymentRequested | PaymentState::Proposed | PaymentState::CounterProposed { .. } => {
+						Payment::<T>::remove(&creator, &who);
+						PaymentReferences::<T>::remove(&creator, &who);
+						PaymentMetadatas::<T>::remove(&creator, &who);
+					}
 					_ => fail!(Error::<T>::InvalidAction),
 				}
 			}
@@ -356,7 +555,9 @@ pub mod pallet {
 		/// This extrinsic is used to resolve disputes between the creator and
 		/// recipient of the payment.
 		/// This extrinsic allows the assigned judge to
-		/// cancel/release/partial_release the payment.
+		/// cancel/release/partial_release the payment. If an appeal window is
+		/// configured, the ruling is only settled once the window has passed
+		/// without appeal.
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::resolve_payment())]
 		pub fn resolve_payment(
@@ -371,7 +572,14 @@ pub mod pallet {
 			if let Some(payment) = Payment::<T>::get(&account_pair.0, &account_pair.1) {
 				ensure!(who == payment.resolver_account, Error::<T>::InvalidAction);
 				ensure!(
-					payment.state != PaymentState::PaymentRequested,
+					!matches!(
+						payment.state,
+						PaymentState::PaymentRequested
+							| PaymentState::Ruled { .. }
+							| PaymentState::Appealed { .. }
+							| PaymentState::Proposed
+							| PaymentState::CounterProposed { .. }
+					),
 					Error::<T>::InvalidAction
 				);
 				if matches!(payment.state, PaymentState::RefundRequested { .. }) {
@@ -380,6 +588,29 @@ pub mod pallet {
 					})
 				}
 			}
+
+			let appeal_window = T::AppealWindow::get();
+			if !appeal_window.is_zero() {
+				let appeal_deadline = frame_system::Pallet::<T>::block_number()
+					.checked_add(&appeal_window)
+					.ok_or(Error::<T>::MathError)?;
+				Payment::<T>::try_mutate(&account_pair.0, &account_pair.1, |maybe_payment| -> DispatchResult {
+					let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+					payment.state = PaymentState::Ruled {
+						recipient_share,
+						appeal_deadline,
+					};
+					Ok(())
+				})?;
+				Self::deposit_event(Event::PaymentRuled {
+					from: account_pair.0,
+					to: account_pair.1,
+					recipient_share,
+					appeal_deadline,
+				});
+				return Ok(().into());
+			}
+
 			// try to update the payment to new state
 			<Self as PaymentHandler<T>>::settle_payment(&account_pair.0, &account_pair.1, recipient_share)?;
 			Self::deposit_event(Event::PaymentResolved {
@@ -522,13 +753,27 @@ pub mod pallet {
 		pub fn accept_and_pay(origin: OriginFor<T>, to: T::AccountId) -> DispatchResultWithPostInfo {
 			let from = ensure_signed(origin)?;
 
-			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			let mut payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
 
 			ensure!(
 				payment.state == PaymentState::PaymentRequested,
 				Error::<T>::InvalidAction
 			);
 
+			// compute the amount of requests denominated in a reference asset
+			if let Some((reference_asset, reference_amount)) = PaymentReferences::<T>::get(&from, &to) {
+				let amount = Self::convert_reference_amount(reference_asset, payment.asset, reference_amount)?;
+				payment = <Self as PaymentHandler<T>>::create_payment(
+					&from,
+					&to,
+					payment.asset,
+					amount,
+					PaymentState::PaymentRequested,
+					Percent::from_percent(0),
+					None,
+				)?;
+			}
+
 			// reserve all the fees from the sender
 			<Self as PaymentHandler<T>>::reserve_payment_amount(&from, &to, payment)?;
 
@@ -539,6 +784,716 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Release a part of a created payment, this will transfer `amount`
+		/// from the reserved payment amount to the recipient. The incentive
+		/// and fee amounts are settled in the same proportion, the rest of
+		/// the payment stays reserved. Releasing the whole remaining amount
+		/// completes the payment like `release`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::release_partial())]
+		pub fn release_partial(
+			origin: OriginFor<T>,
+			to: T::AccountId,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+
+			// ensure the payment is in Created state
+			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+			ensure!(!amount.is_zero() && amount <= payment.amount, Error::<T>::InvalidAmount);
+
+			if amount == payment.amount {
+				<Self as PaymentHandler<T>>::settle_payment(&from, &to, Percent::from_percent(100))?;
+				Self::deposit_event(Event::PaymentReleased { from, to });
+			} else {
+				Self::do_release_partial(&from, &to, amount)?;
+				Self::deposit_event(Event::PaymentPartiallyReleased { from, to, amount });
+			}
+
+			Ok(().into())
+		}
+
+		/// Create a payment like `pay` that expires at the `expiry` block. If
+		/// the payment is not released or disputed by then, anyone can refund
+		/// it to the creator using `claim_expired_refund`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::pay_with_expiry(T::MaxRemarkLength::get()))]
+		pub fn pay_with_expiry(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			expiry: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			Self::do_pay(
+				who,
+				recipient,
+				asset,
+				amount,
+				remark,
+				None,
+				Some(expiry),
+				Default::default(),
+			)?;
+			Ok(().into())
+		}
+
+		/// Refund an expired payment to its creator. Only payments that are
+		/// still in created state can be refunded, this extrinsic can be
+		/// called by anyone.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::claim_expired_refund())]
+		pub fn claim_expired_refund(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::Created, Error::<T>::InvalidAction);
+			let expiry = payment.expiry.ok_or(Error::<T>::PaymentNotExpired)?;
+			ensure!(
+				expiry <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::PaymentNotExpired
+			);
+
+			// refund is a settle_payment with 0% recipient_share
+			<Self as PaymentHandler<T>>::settle_payment(&from, &to, Percent::from_percent(0))?;
+
+			Self::deposit_event(Event::PaymentExpiredRefunded { from, to });
+			Ok(().into())
+		}
+
+		/// Create a payment like `pay` where each account in `splits`
+		/// receives its share of the amount released to the recipient, the
+		/// recipient keeps the remainder. The shares can add up to at most
+		/// 100%.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::pay_with_splits(T::MaxRemarkLength::get()))]
+		pub fn pay_with_splits(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			splits: SplitsOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let total_share: u32 = splits.iter().map(|(_, share)| share.deconstruct() as u32).sum();
+			ensure!(
+				total_share <= Percent::one().deconstruct() as u32,
+				Error::<T>::InvalidSplits
+			);
+
+			Self::do_pay(who, recipient, asset, amount, remark, None, None, splits)?;
+			Ok(().into())
+		}
+
+		/// Allow the creator or recipient of a ruled payment to appeal the
+		/// ruling before the appeal window passes. A share of the payment
+		/// amount is reserved from the appellant as bond, the bond is slashed
+		/// if the appeal origin upholds the ruling.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::appeal_ruling())]
+		pub fn appeal_ruling(origin: OriginFor<T>, from: T::AccountId, to: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(who == from || who == to, Error::<T>::InvalidAction);
+
+			Payment::<T>::try_mutate(&from, &to, |maybe_payment| -> DispatchResult {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				match payment.state {
+					PaymentState::Ruled {
+						recipient_share,
+						appeal_deadline,
+					} => {
+						ensure!(
+							appeal_deadline > frame_system::Pallet::<T>::block_number(),
+							Error::<T>::AppealWindowPassed
+						);
+
+						let bond = T::AppealBondPercentage::get().mul_floor(payment.amount);
+						T::Asset::reserve(payment.asset, &who, bond)?;
+
+						payment.state = PaymentState::Appealed {
+							recipient_share,
+							appellant: who.clone(),
+							bond,
+						};
+
+						Self::deposit_event(Event::RulingAppealed {
+							from: from.clone(),
+							to: to.clone(),
+							appellant: who,
+							bond,
+						});
+					}
+					_ => fail!(Error::<T>::InvalidAction),
+				}
+				Ok(())
+			})?;
+
+			Ok(().into())
+		}
+
+		/// Allow the appeal origin to settle an appealed payment with a new
+		/// recipient_share. The bond of the appellant is returned if the
+		/// ruling is changed, otherwise it is slashed.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::resolve_appeal())]
+		pub fn resolve_appeal(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+			recipient_share: Percent,
+		) -> DispatchResultWithPostInfo {
+			T::AppealOrigin::ensure_origin(origin)?;
+
+			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			let (ruled_share, appellant, bond) = match payment.state {
+				PaymentState::Appealed {
+					recipient_share,
+					appellant,
+					bond,
+				} => (recipient_share, appellant, bond),
+				_ => fail!(Error::<T>::InvalidAction),
+			};
+
+			<Self as PaymentHandler<T>>::settle_payment(&from, &to, recipient_share)?;
+
+			let bond_slashed = recipient_share == ruled_share;
+			if bond_slashed {
+				T::Asset::slash_reserved(payment.asset, &appellant, bond);
+			} else {
+				T::Asset::unreserve(payment.asset, &appellant, bond);
+			}
+
+			Self::deposit_event(Event::AppealResolved {
+				from,
+				to,
+				recipient_share,
+				bond_slashed,
+			});
+			Ok(().into())
+		}
+
+		/// Settle a ruled payment once the appeal window has passed without
+		/// appeal, this extrinsic can be called by anyone.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::finalize_ruling())]
+		pub fn finalize_ruling(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let payment = Payment::<T>::get(&from, &to).ok_or(Error::<T>::InvalidPayment)?;
+			match payment.state {
+				PaymentState::Ruled {
+					recipient_share,
+					appeal_deadline,
+				} => {
+					ensure!(
+						appeal_deadline <= frame_system::Pallet::<T>::block_number(),
+						Error::<T>::AppealWindowNotPassed
+					);
+
+					<Self as PaymentHandler<T>>::settle_payment(&from, &to, recipient_share)?;
+
+					Self::deposit_event(Event::PaymentResolved {
+						from,
+						to,
+						recipient_share,
+					});
+				}
+				_ => fail!(Error::<T>::InvalidAction),
+			}
+			Ok(().into())
+		}
+
+		/// Create a payment request like `request_payment` for the equivalent
+		/// of `reference_amount` of `reference_asset`. The amount of `asset`
+		/// to pay is computed with the price provider when the sender accepts
+		/// the request using the `accept_and_pay` extrinsic.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::request_payment_in_reference())]
+		pub fn request_payment_in_reference(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			asset: AssetIdOf<T>,
+			reference_asset: AssetIdOf<T>,
+			#[pallet::compact] reference_amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let to = ensure_signed(origin)?;
+
+			// the payment amount is only known when the request is accepted
+			<Self as PaymentHandler<T>>::create_payment(
+				&from,
+				&to,
+				asset,
+				Zero::zero(),
+				PaymentState::PaymentRequested,
+				Percent::from_percent(0),
+				None,
+			)?;
+			PaymentReferences::<T>::insert(&from, &to, (reference_asset, reference_amount));
+
+			Self::deposit_event(Event::PaymentRequestCreated { from, to });
+
+			Ok(().into())
+		}
+
+		/// Set or remove the fee configuration of payments of `asset` and
+		/// `kind`. Payments without a fee configuration use the
+		/// `FeeHandler`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_fee_config())]
+		pub fn set_fee_config(
+			origin: OriginFor<T>,
+			asset: AssetIdOf<T>,
+			kind: PaymentKind,
+			config: Option<FeeConfig<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
+			T::FeeOrigin::ensure_origin(origin)?;
+
+			FeeConfigs::<T>::set(asset, kind, config.clone());
+
+			Self::deposit_event(Event::FeeConfigUpdated { asset, kind, config });
+
+			Ok(().into())
+		}
+
+		/// Create a recurring payment of `count` installments of `amount` to
+		/// `recipient`, one every `interval` blocks. The first installment is
+		/// reserved now and can be collected after `interval` blocks, each
+		/// following installment is reserved when the previous one is
+		/// collected.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::create_recurring())]
+		pub fn create_recurring(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			interval: BlockNumberFor<T>,
+			count: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(
+				!interval.is_zero() && !count.is_zero(),
+				Error::<T>::InvalidRecurringSchedule
+			);
+			ensure!(
+				!RecurringPayments::<T>::contains_key(&who, &recipient),
+				Error::<T>::PaymentAlreadyInProcess
+			);
+
+			let next_due = frame_system::Pallet::<T>::block_number()
+				.checked_add(&interval)
+				.ok_or(Error::<T>::MathError)?;
+			T::Asset::reserve(asset, &who, amount)?;
+			RecurringPayments::<T>::insert(
+				&who,
+				&recipient,
+				RecurringPayment {
+					asset,
+					amount,
+					interval,
+					remaining: count,
+					next_due,
+				},
+			);
+
+			Self::deposit_event(Event::RecurringPaymentCreated {
+				from: who,
+				to: recipient,
+				asset,
+				amount,
+				interval,
+				count,
+			});
+			Ok(().into())
+		}
+
+		/// Transfer the due installment of a recurring payment to its
+		/// recipient and reserve the next one. If the next installment can
+		/// not be reserved the recurring payment stops. This extrinsic can be
+		/// called by anyone.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::collect_installment())]
+		pub fn collect_installment(
+			origin: OriginFor<T>,
+			from: T::AccountId,
+			to: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			Self::do_collect_installment(&from, &to)?;
+			Ok(().into())
+		}
+
+		/// Cancel a recurring payment to `recipient` and unreserve the next
+		/// installment. This extrinsic can only be called by the payer while
+		/// no installment is due.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::cancel_recurring())]
+		pub fn cancel_recurring(origin: OriginFor<T>, recipient: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let recurring = RecurringPayments::<T>::get(&who, &recipient).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(
+				recurring.next_due > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InstallmentDue
+			);
+
+			T::Asset::unreserve(recurring.asset, &who, recurring.amount);
+			RecurringPayments::<T>::remove(&who, &recipient);
+
+			Self::deposit_event(Event::RecurringPaymentCancelled {
+				from: who,
+				to: recipient,
+			});
+			Ok(().into())
+		}
+
+		/// Create a payment like `pay` with a hash referencing it in an
+		/// external system, like an invoice or order. The remark and the
+		/// reference are stored until the payment is settled.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::pay_with_reference(T::MaxRemarkLength::get()))]
+		pub fn pay_with_reference(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			external_reference: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Self::do_pay(
+				who,
+				recipient,
+				asset,
+				amount,
+				remark,
+				Some(external_reference),
+				None,
+				Default::default(),
+			)?;
+			Ok(().into())
+		}
+
+		/// Propose a payment of `amount` to `recipient`. Nothing is reserved
+		/// until the recipient accepts the payment using the `accept_payment`
+		/// extrinsic, after which it is a created payment like one made with
+		/// `pay`. The creator can replace the proposal by creating another
+		/// payment to the recipient.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::propose_payment(T::MaxRemarkLength::get()))]
+		pub fn propose_payment(
+			origin: OriginFor<T>,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+
+			<Self as PaymentHandler<T>>::create_payment(
+				&who,
+				&recipient,
+				asset,
+				amount,
+				PaymentState::Proposed,
+				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+			if remark.is_some() {
+				PaymentMetadatas::<T>::insert(
+					&who,
+					&recipient,
+					PaymentMetadata {
+						remark,
+						external_reference: None,
+					},
+				);
+			}
+
+			Self::deposit_event(Event::PaymentProposed {
+				from: who,
+				to: recipient,
+				asset,
+				amount,
+			});
+			Ok(().into())
+		}
+
+		/// Accept a payment proposed by `creator`, reserving the proposed
+		/// amount from the creator. If `counter_amount` differs from the
+		/// proposed amount, nothing is reserved and the creator has to
+		/// confirm the new amount using the `confirm_counter_proposal`
+		/// extrinsic. This extrinsic can only be called by the recipient.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::accept_payment())]
+		pub fn accept_payment(
+			origin: OriginFor<T>,
+			creator: T::AccountId,
+			counter_amount: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut payment = Payment::<T>::get(&creator, &who).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(payment.state == PaymentState::Proposed, Error::<T>::InvalidAction);
+
+			match counter_amount.filter(|amount| *amount != payment.amount) {
+				Some(amount) => {
+					ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+					payment.state = PaymentState::CounterProposed { amount };
+					Payment::<T>::insert(&creator, &who, payment);
+					Self::deposit_event(Event::PaymentCounterProposed {
+						from: creator,
+						to: who,
+						amount,
+					});
+				}
+				None => {
+					payment.state = PaymentState::Created;
+					Self::do_accept_payment(creator, who, payment)?;
+				}
+			}
+			Ok(().into())
+		}
+
+		/// Confirm the amount counter proposed by the recipient of a proposed
+		/// payment, reserving it from the creator. This extrinsic can only be
+		/// called by the creator.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::confirm_counter_proposal())]
+		pub fn confirm_counter_proposal(origin: OriginFor<T>, recipient: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let payment = Payment::<T>::get(&who, &recipient).ok_or(Error::<T>::InvalidPayment)?;
+			let amount = match payment.state {
+				PaymentState::CounterProposed { amount } => amount,
+				_ => fail!(Error::<T>::InvalidAction),
+			};
+
+			// the fee and incentive amounts are computed again for the new amount
+			let metadata = PaymentMetadatas::<T>::get(&who, &recipient);
+			let payment = <Self as PaymentHandler<T>>::create_payment(
+				&who,
+				&recipient,
+				payment.asset,
+				amount,
+				PaymentState::Created,
+				T::IncentivePercentage::get(),
+				metadata.as_ref().and_then(|m| m.remark.as_ref()).map(|x| x.as_slice()),
+			)?;
+			if let Some(metadata) = metadata {
+				PaymentMetadatas::<T>::insert(&who, &recipient, metadata);
+			}
+			Self::do_accept_payment(who, recipient, payment)?;
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Convert `amount` of `reference_asset` to the equivalent amount of
+		/// `asset` using the configured price provider.
+		fn convert_reference_amount(
+			reference_asset: AssetIdOf<T>,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			if reference_asset == asset {
+				return Ok(amount);
+			}
+			let price = T::PriceProvider::get_price(reference_asset, asset).ok_or(Error::<T>::PriceUnavailable)?;
+			let amount: u128 = amount.try_into().map_err(|_| Error::<T>::MathError)?;
+			let converted = price.checked_mul_int(amount).ok_or(Error::<T>::MathError)?;
+			converted.try_into().map_err(|_| Error::<T>::MathError.into())
+		}
+
+		#[allow(clippy::too_many_arguments)]
+		fn do_pay(
+			who: T::AccountId,
+			recipient: T::AccountId,
+			asset: AssetIdOf<T>,
+			amount: BalanceOf<T>,
+			remark: Option<BoundedDataOf<T>>,
+			external_reference: Option<T::Hash>,
+			expiry: Option<BlockNumberFor<T>>,
+			splits: SplitsOf<T>,
+		) -> DispatchResult {
+			// create PaymentDetail and add to storage
+			let mut payment_detail = <Self as PaymentHandler<T>>::create_payment(
+				&who,
+				&recipient,
+				asset,
+				amount,
+				PaymentState::Created,
+				T::IncentivePercentage::get(),
+				remark.as_ref().map(|x| x.as_slice()),
+			)?;
+			if expiry.is_some() || !splits.is_empty() {
+				payment_detail.expiry = expiry;
+				payment_detail.splits = splits;
+				// the payment kind changed, so the fee has to be computed again
+				let remark = remark.as_ref().map(|x| x.as_slice());
+				payment_detail.fee_detail = Some(Self::fee_detail(&who, &recipient, &payment_detail, remark));
+				Payment::<T>::insert(&who, &recipient, &payment_detail);
+			}
+			// reserve funds for payment
+			<Self as PaymentHandler<T>>::reserve_payment_amount(&who, &recipient, payment_detail)?;
+			if remark.is_some() || external_reference.is_some() {
+				PaymentMetadatas::<T>::insert(
+					&who,
+					&recipient,
+					PaymentMetadata {
+						remark: remark.clone(),
+						external_reference,
+					},
+				);
+			}
+			// emit paymentcreated event
+			Self::deposit_event(Event::PaymentCreated {
+				from: who,
+				asset,
+				amount,
+				remark,
+				external_reference,
+			});
+			Ok(())
+		}
+
+		/// The fee recipient and amount of `detail`, taken from the fee
+		/// configuration of its asset and kind if there is one and from the
+		/// `FeeHandler` otherwise.
+		fn fee_detail(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			detail: &PaymentDetail<T>,
+			remark: Option<&[u8]>,
+		) -> (T::AccountId, BalanceOf<T>) {
+			let (fee_recipient, fee_percent) = match FeeConfigs::<T>::get(detail.asset, detail.kind()) {
+				Some(FeeConfig { recipient, percentage }) => (recipient, percentage),
+				None => T::FeeHandler::apply_fees(from, to, detail, remark),
+			};
+			(fee_recipient, fee_percent.mul_floor(detail.amount))
+		}
+
+		/// Release `amount` of the payment to the recipient, together with
+		/// the proportional share of the incentive and fee amounts.
+		#[require_transactional]
+		fn do_release_partial(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			Payment::<T>::try_mutate(from, to, |maybe_payment| -> DispatchResult {
+				let payment = maybe_payment.as_mut().ok_or(Error::<T>::InvalidPayment)?;
+				let ratio = Perbill::from_rational(amount, payment.amount);
+
+				// unreserve the share of the incentive amount to the owner account
+				let incentive_amount = ratio.mul_floor(payment.incentive_amount);
+				T::Asset::unreserve(payment.asset, from, incentive_amount);
+				payment.incentive_amount = payment.incentive_amount.saturating_sub(incentive_amount);
+
+				// transfer the share of the fee to the fee recipient
+				if let Some((fee_recipient, fee_amount)) = payment.fee_detail.as_mut() {
+					let released_fee = ratio.mul_floor(*fee_amount);
+					T::Asset::unreserve(payment.asset, from, released_fee);
+					T::Asset::transfer(payment.asset, from, fee_recipient, released_fee)?;
+					*fee_amount = fee_amount.saturating_sub(released_fee);
+				}
+
+				// unreserve the released amount to the recipient
+				T::Asset::unreserve(payment.asset, to, amount);
+				Self::distribute_splits(payment.asset, &payment.splits, to, amount)?;
+				payment.amount = payment.amount.saturating_sub(amount);
+
+				Ok(())
+			})
+		}
+
+		/// Store an accepted proposal as a created payment and reserve its
+		/// amount from the creator.
+		#[require_transactional]
+		fn do_accept_payment(from: T::AccountId, to: T::AccountId, payment: PaymentDetail<T>) -> DispatchResult {
+			let amount = payment.amount;
+			Payment::<T>::insert(&from, &to, &payment);
+			<Self as PaymentHandler<T>>::reserve_payment_amount(&from, &to, payment)?;
+
+			Self::deposit_event(Event::PaymentAccepted { from, to, amount });
+			Ok(())
+		}
+
+		/// Transfer the reserved installment of a due recurring payment and
+		/// reserve the next one. Installments missed in earlier intervals can
+		/// be collected one after the other.
+		#[require_transactional]
+		fn do_collect_installment(from: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+			let mut recurring = RecurringPayments::<T>::get(from, to).ok_or(Error::<T>::InvalidPayment)?;
+			ensure!(
+				recurring.next_due <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InstallmentNotDue
+			);
+
+			let unpaid =
+				T::Asset::repatriate_reserved(recurring.asset, from, to, recurring.amount, BalanceStatus::Free)?;
+			let collected = recurring.amount.saturating_sub(unpaid);
+			recurring.remaining = recurring.remaining.saturating_sub(1);
+			let remaining = recurring.remaining;
+
+			let stopped = if remaining.is_zero() {
+				RecurringPayments::<T>::remove(from, to);
+				false
+			} else if T::Asset::reserve(recurring.asset, from, recurring.amount).is_err() {
+				RecurringPayments::<T>::remove(from, to);
+				true
+			} else {
+				recurring.next_due = recurring.next_due.saturating_add(recurring.interval);
+				RecurringPayments::<T>::insert(from, to, recurring);
+				false
+			};
+
+			Self::deposit_event(Event::InstallmentCollected {
+				from: from.clone(),
+				to: to.clone(),
+				amount: collected,
+				remaining,
+			});
+			if stopped {
+				Self::deposit_event(Event::RecurringPaymentStopped {
+					from: from.clone(),
+					to: to.clone(),
+				});
+			}
+			Ok(())
+		}
+
+		/// Transfer the share of each split account from the `amount`
+		/// released to the recipient.
+		fn distribute_splits(
+			asset: AssetIdOf<T>,
+			splits: &SplitsOf<T>,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			for (account, share) in splits.iter() {
+				T::Asset::transfer(asset, to, account, share.mul_floor(amount))?;
+			}
+			Ok(())
+		}
 	}
 
 	impl<T: Config> PaymentHandler<T> for Pallet<T> {
@@ -559,12 +1514,18 @@ pub mod pallet {
 				from,
 				recipient,
 				|maybe_payment| -> Result<PaymentDetail<T>, sp_runtime::DispatchError> {
-					// only payment requests can be overwritten
+					// only payment requests and proposals can be overwritten
 					if let Some(payment) = maybe_payment {
 						ensure!(
-							payment.state == PaymentState::PaymentRequested,
+							matches!(
+								payment.state,
+								PaymentState::PaymentRequested
+									| PaymentState::Proposed | PaymentState::CounterProposed { .. }
+							),
 							Error::<T>::PaymentAlreadyInProcess
 						);
+						PaymentReferences::<T>::remove(from, recipient);
+						PaymentMetadatas::<T>::remove(from, recipient);
 					}
 
 					// Calculate incentive amount - this is to insentivise the user to release
@@ -578,13 +1539,13 @@ pub mod pallet {
 						state: payment_state,
 						resolver_account: T::DisputeResolver::get_resolver_account(),
 						fee_detail: None,
+						expiry: None,
+						splits: Default::default(),
 					};
 
-					// Calculate fee amount - this will be implemented based on the custom
-					// implementation of the fee provider
-					let (fee_recipient, fee_percent) = T::FeeHandler::apply_fees(from, recipient, &new_payment, remark);
-					let fee_amount = fee_percent.mul_floor(amount);
-					new_payment.fee_detail = Some((fee_recipient, fee_amount));
+					// Calculate fee amount - this will be taken from the on-chain fee configuration
+					// or the custom implementation of the fee provider
+					new_payment.fee_detail = Some(Self::fee_detail(from, recipient, &new_payment, remark));
 
 					*maybe_payment = Some(new_payment.clone());
 
@@ -647,6 +1608,9 @@ pub mod pallet {
 				let amount_to_sender = payment.amount.saturating_sub(amount_to_recipient);
 				// send share to recipient
 				T::Asset::transfer(payment.asset, to, from, amount_to_sender)?;
+				// send the split shares of the released amount
+				Self::distribute_splits(payment.asset, &payment.splits, to, amount_to_recipient)?;
+				PaymentMetadatas::<T>::remove(from, to);
 
 				Ok(())
 			})?;
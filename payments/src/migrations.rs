@@ -0,0 +1,115 @@
+use crate::{pallet::Payment, Config, Pallet, PaymentDetail, PaymentState};
+use codec::HasCompact;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// Migrates the stored payments to the current layout.
+///
+/// Each step translates the payments of its version straight into the
+/// current layout, so at most one of them runs.
+pub struct Migration<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 1 {
+			weight.saturating_accrue(v1::migrate::<T>());
+		} else if onchain_version < 2 {
+			weight.saturating_accrue(v2::migrate::<T>());
+		}
+		// version 3 appended the `Ruled` and `Appealed` payment states, the
+		// payments stored on version 2 decode unchanged
+		if onchain_version < Pallet::<T>::current_storage_version() {
+			Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+		weight
+	}
+}
+
+mod v1 {
+	use super::*;
+
+	/// The payment detail before payments could expire.
+	#[derive(Decode)]
+	struct OldPaymentDetail<AssetId, Balance: HasCompact, AccountId, State> {
+		asset: AssetId,
+		#[codec(compact)]
+		amount: Balance,
+		#[codec(compact)]
+		incentive_amount: Balance,
+		state: State,
+		resolver_account: AccountId,
+		fee_detail: Option<(AccountId, Balance)>,
+	}
+
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		Payment::<T>::translate::<
+			OldPaymentDetail<crate::AssetIdOf<T>, crate::BalanceOf<T>, T::AccountId, PaymentState<T>>,
+			_,
+		>(|_, _, old| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			Some(PaymentDetail {
+				asset: old.asset,
+				amount: old.amount,
+				incentive_amount: old.incentive_amount,
+				state: old.state,
+				resolver_account: old.resolver_account,
+				fee_detail: old.fee_detail,
+				expiry: None,
+				splits: Default::default(),
+			})
+		});
+
+		weight
+	}
+}
+
+mod v2 {
+	use super::*;
+
+	/// The payment detail before payments could be split.
+	#[derive(Decode)]
+	struct OldPaymentDetail<AssetId, Balance: HasCompact, AccountId, State, BlockNumber> {
+		asset: AssetId,
+		#[codec(compact)]
+		amount: Balance,
+		#[codec(compact)]
+		incentive_amount: Balance,
+		state: State,
+		resolver_account: AccountId,
+		fee_detail: Option<(AccountId, Balance)>,
+		expiry: Option<BlockNumber>,
+	}
+
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		Payment::<T>::translate::<
+			OldPaymentDetail<
+				crate::AssetIdOf<T>,
+				crate::BalanceOf<T>,
+				T::AccountId,
+				PaymentState<T>,
+				BlockNumberFor<T>,
+			>,
+			_,
+		>(|_, _, old| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			Some(PaymentDetail {
+				asset: old.asset,
+				amount: old.amount,
+				incentive_amount: old.incentive_amount,
+				state: old.state,
+				resolver_account: old.resolver_account,
+				fee_detail: old.fee_detail,
+				expiry: old.expiry,
+				splits: Default::default(),
+			})
+		});
+
+		weight
+	}
+}
@@ -3,14 +3,14 @@ use crate::PaymentDetail;
 use frame_support::{
 	dispatch::DispatchClass,
 	parameter_types,
-	traits::{ConstU32, Contains, Everything, Hooks, OnFinalize},
+	traits::{ConstU32, Contains, Everything, GetDefault, Hooks, Nothing, OnFinalize},
 };
 use frame_system as system;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage, Percent,
+	BuildStorage, FixedPointNumber, FixedU128, Percent,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -22,6 +22,7 @@ pub const PAYMENT_RECIPENT: AccountId = 11;
 pub const PAYMENT_CREATOR_TWO: AccountId = 30;
 pub const PAYMENT_RECIPENT_TWO: AccountId = 31;
 pub const CURRENCY_ID: u32 = 1;
+pub const REFERENCE_CURRENCY_ID: u32 = 2;
 pub const RESOLVER_ACCOUNT: AccountId = 12;
 pub const FEE_RECIPIENT_ACCOUNT: AccountId = 20;
 pub const PAYMENT_RECIPENT_FEE_CHARGED: AccountId = 21;
@@ -92,9 +93,21 @@ impl orml_tokens::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
+	type OnBalanceMovement = ();
 	type WeightInfo = ();
 	type MaxLocks = MaxLocks;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type DustHandlingMode = GetDefault;
+	type MaxDustQueueLen = ();
+	type MaxDustPerBlock = ();
+	type MaxReapPerBlock = ();
+	type MaxIssuanceSnapshots = ();
+	type MaxSnapshotCurrencies = ();
+	type AccountCreation = GetDefault;
+	type AccountCreationWhitelist = Nothing;
+	type AssetAdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<50>;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
 }
@@ -121,11 +134,24 @@ impl crate::types::FeeHandler<Test> for MockFeeHandler {
 	}
 }
 
+pub struct MockPriceProvider;
+impl orml_traits::PriceProvider<u32, FixedU128> for MockPriceProvider {
+	fn get_price(base: u32, quote: u32) -> Option<FixedU128> {
+		match (base, quote) {
+			(REFERENCE_CURRENCY_ID, CURRENCY_ID) => Some(FixedU128::saturating_from_rational(5, 2)),
+			_ => None,
+		}
+	}
+}
+
 parameter_types! {
 	pub const IncentivePercentage: Percent = Percent::from_percent(INCENTIVE_PERCENTAGE);
 	pub const MaxRemarkLength: u32 = 50;
 	pub const CancelBufferBlockLength: u64 = CANCEL_BLOCK_BUFFER;
 	pub const MaxScheduledTaskListLength : u32 = 5;
+	pub const MaxSplits: u32 = 3;
+	pub static AppealWindow: u64 = 0;
+	pub const AppealBondPercentage: Percent = Percent::from_percent(10);
 }
 
 impl payment::Config for Test {
@@ -137,6 +163,12 @@ impl payment::Config for Test {
 	type MaxRemarkLength = MaxRemarkLength;
 	type CancelBufferBlockLength = CancelBufferBlockLength;
 	type MaxScheduledTaskListLength = MaxScheduledTaskListLength;
+	type MaxSplits = MaxSplits;
+	type AppealWindow = AppealWindow;
+	type AppealBondPercentage = AppealBondPercentage;
+	type AppealOrigin = frame_system::EnsureRoot<AccountId>;
+	type PriceProvider = MockPriceProvider;
+	type FeeOrigin = frame_system::EnsureRoot<AccountId>;
 	type WeightInfo = ();
 }
 
@@ -149,6 +181,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(PAYMENT_CREATOR, CURRENCY_ID, 100),
 			(PAYMENT_CREATOR_TWO, CURRENCY_ID, 100),
 		],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -1,11 +1,12 @@
 use crate::{
 	mock::*,
-	types::{PaymentDetail, PaymentState},
+	types::{FeeConfig, PaymentDetail, PaymentKind, PaymentMetadata, PaymentState},
 	weights::WeightInfo,
-	Payment as PaymentStore, PaymentHandler, ScheduledTask, ScheduledTasks, Task,
+	BoundedDataOf, Payment as PaymentStore, PaymentHandler, PaymentReferences, ScheduledTask, ScheduledTasks, Task,
 };
 use frame_support::{assert_noop, assert_ok, storage::with_transaction, traits::OnIdle, weights::Weight};
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use sp_core::H256;
 use sp_runtime::{Percent, TransactionOutcome};
 
 type Error = crate::Error<Test>;
@@ -42,7 +43,8 @@ fn test_pay_works() {
 				from: PAYMENT_CREATOR,
 				asset: CURRENCY_ID,
 				amount: payment_amount,
-				remark: None
+				remark: None,
+				external_reference: None
 			}
 			.into()
 		);
@@ -56,6 +58,8 @@ fn test_pay_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved correctly
@@ -94,6 +98,8 @@ fn test_pay_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 	});
@@ -124,6 +130,8 @@ fn test_cancel_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved
@@ -182,6 +190,8 @@ fn test_release_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved
@@ -346,6 +356,8 @@ fn test_charging_fee_payment_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved
@@ -405,6 +417,8 @@ fn test_charging_fee_payment_works_when_canceled() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved
@@ -454,6 +468,8 @@ fn test_pay_with_remark_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 		// the payment amount should be reserved correctly
@@ -489,7 +505,8 @@ fn test_pay_with_remark_works() {
 				from: PAYMENT_CREATOR,
 				asset: CURRENCY_ID,
 				amount: payment_amount,
-				remark: Some(vec![1u8; 10].try_into().unwrap())
+				remark: Some(vec![1u8; 10].try_into().unwrap()),
+				external_reference: None
 			}
 			.into()
 		);
@@ -532,6 +549,8 @@ fn test_do_not_overwrite_logic_works() {
 				state: PaymentState::NeedsReview,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			},
 		);
 
@@ -592,6 +611,8 @@ fn test_request_refund() {
 				},
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -657,6 +678,8 @@ fn test_dispute_refund() {
 				state: PaymentState::NeedsReview,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -702,6 +725,8 @@ fn test_request_payment() {
 				state: PaymentState::PaymentRequested,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -781,6 +806,8 @@ fn test_accept_and_pay() {
 				state: PaymentState::PaymentRequested,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -852,6 +879,8 @@ fn test_accept_and_pay_should_charge_fee_correctly() {
 				state: PaymentState::PaymentRequested,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -929,6 +958,8 @@ fn test_create_payment_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -957,6 +988,8 @@ fn test_create_payment_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 	});
@@ -997,6 +1030,8 @@ fn test_reserve_payment_amount_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -1047,6 +1082,8 @@ fn test_reserve_payment_amount_works() {
 				state: PaymentState::Created,
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 	});
@@ -1284,6 +1321,8 @@ fn test_automatic_refund_works() {
 				},
 				resolver_account: RESOLVER_ACCOUNT,
 				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
 			})
 		);
 
@@ -1448,3 +1487,1167 @@ fn on_idle_works() {
 		);
 	});
 }
+
+#[test]
+fn test_release_partial_works() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+		let payment_amount = 40;
+		let expected_incentive_amount = payment_amount / INCENTIVE_PERCENTAGE as u128;
+		let expected_fee_amount = payment_amount / MARKETPLACE_FEE_PERCENTAGE as u128;
+
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT_FEE_CHARGED,
+			CURRENCY_ID,
+			payment_amount,
+			None
+		));
+
+		// should fail for zero or excessive amounts
+		assert_noop!(
+			Payment::release_partial(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT_FEE_CHARGED, 0),
+			Error::InvalidAmount
+		);
+		assert_noop!(
+			Payment::release_partial(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_RECIPENT_FEE_CHARGED,
+				payment_amount + 1
+			),
+			Error::InvalidAmount
+		);
+		// should fail for non existent payment
+		assert_noop!(
+			Payment::release_partial(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT, 10),
+			Error::InvalidPayment
+		);
+
+		// release a quarter of the payment
+		assert_ok!(Payment::release_partial(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT_FEE_CHARGED,
+			10
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentPartiallyReleased {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT_FEE_CHARGED,
+				amount: 10,
+			}
+			.into()
+		);
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			Some(PaymentDetail {
+				asset: CURRENCY_ID,
+				amount: 30,
+				incentive_amount: expected_incentive_amount - 1,
+				state: PaymentState::Created,
+				resolver_account: RESOLVER_ACCOUNT,
+				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, expected_fee_amount - 1)),
+				expiry: None,
+				splits: Default::default(),
+			})
+		);
+		// a quarter of the incentive is unreserved and a quarter of the fee is paid
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - (expected_incentive_amount - 1) - expected_fee_amount
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_FEE_CHARGED), 10);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_RECIPENT_FEE_CHARGED), 30);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &FEE_RECIPIENT_ACCOUNT), 1);
+
+		// releasing the remaining amount completes the payment
+		assert_ok!(Payment::release_partial(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT_FEE_CHARGED,
+			30
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentReleased {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT_FEE_CHARGED,
+			}
+			.into()
+		);
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT_FEE_CHARGED),
+			None
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - expected_fee_amount
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_FEE_CHARGED),
+			payment_amount
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &FEE_RECIPIENT_ACCOUNT),
+			expected_fee_amount
+		);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 0);
+	});
+}
+
+#[test]
+fn test_claim_expired_refund_works() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+		let payment_amount = 20;
+		let expected_incentive_amount = payment_amount / INCENTIVE_PERCENTAGE as u128;
+
+		// expiry must be in the future
+		assert_noop!(
+			Payment::pay_with_expiry(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_RECIPENT,
+				CURRENCY_ID,
+				payment_amount,
+				None,
+				1
+			),
+			Error::InvalidExpiry
+		);
+
+		assert_ok!(Payment::pay_with_expiry(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None,
+			10
+		));
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(PaymentDetail {
+				asset: CURRENCY_ID,
+				amount: payment_amount,
+				incentive_amount: expected_incentive_amount,
+				state: PaymentState::Created,
+				resolver_account: RESOLVER_ACCOUNT,
+				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: Some(10),
+				splits: Default::default(),
+			})
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - expected_incentive_amount
+		);
+
+		// cannot claim before expiry
+		assert_noop!(
+			Payment::claim_expired_refund(
+				RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::PaymentNotExpired
+		);
+
+		System::set_block_number(10);
+
+		// anyone can claim the refund after expiry
+		assert_ok!(Payment::claim_expired_refund(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentExpiredRefunded {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT
+			}
+			.into()
+		);
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance
+		);
+		assert_eq!(Tokens::total_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 0);
+	});
+}
+
+#[test]
+fn test_claim_expired_refund_fails_for_disputed_payment() {
+	new_test_ext().execute_with(|| {
+		// payments without expiry cannot be refunded
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_RECIPENT_TWO,
+			CURRENCY_ID,
+			20,
+			None
+		));
+		assert_noop!(
+			Payment::claim_expired_refund(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_CREATOR_TWO,
+				PAYMENT_RECIPENT_TWO
+			),
+			Error::PaymentNotExpired
+		);
+
+		assert_ok!(Payment::pay_with_expiry(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			20,
+			None,
+			10
+		));
+		assert_ok!(Payment::request_refund(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_ok!(Payment::dispute_refund(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR
+		));
+
+		System::set_block_number(10);
+
+		// disputed payments are not refunded after expiry
+		assert_noop!(
+			Payment::claim_expired_refund(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::InvalidAction
+		);
+	});
+}
+
+#[test]
+fn test_pay_with_splits_works() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+		let payment_amount = 40;
+		let expected_incentive_amount = payment_amount / INCENTIVE_PERCENTAGE as u128;
+
+		// shares cannot exceed 100%
+		assert_noop!(
+			Payment::pay_with_splits(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_RECIPENT,
+				CURRENCY_ID,
+				payment_amount,
+				None,
+				vec![
+					(PAYMENT_RECIPENT_TWO, Percent::from_percent(60)),
+					(FEE_RECIPIENT_ACCOUNT, Percent::from_percent(50))
+				]
+				.try_into()
+				.unwrap()
+			),
+			Error::InvalidSplits
+		);
+
+		assert_ok!(Payment::pay_with_splits(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None,
+			vec![
+				(PAYMENT_RECIPENT_TWO, Percent::from_percent(25)),
+				(FEE_RECIPIENT_ACCOUNT, Percent::from_percent(10))
+			]
+			.try_into()
+			.unwrap()
+		));
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - expected_incentive_amount
+		);
+
+		// partial release is split as well
+		assert_ok!(Payment::release_partial(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			20
+		));
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 13);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_TWO), 5);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &FEE_RECIPIENT_ACCOUNT), 2);
+
+		assert_ok!(Payment::release(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 26);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_TWO), 10);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &FEE_RECIPIENT_ACCOUNT), 4);
+	});
+}
+
+#[test]
+fn test_cancel_payment_with_splits_refunds_creator() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+
+		assert_ok!(Payment::pay_with_splits(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			40,
+			None,
+			vec![(PAYMENT_RECIPENT_TWO, Percent::from_percent(25))]
+				.try_into()
+				.unwrap()
+		));
+
+		assert_ok!(Payment::cancel(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR
+		));
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 0);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_TWO), 0);
+	});
+}
+
+#[test]
+fn test_ruling_can_be_appealed() {
+	new_test_ext().execute_with(|| {
+		AppealWindow::set(10);
+		let creator_initial_balance = 100;
+		let payment_amount = 40;
+		let expected_incentive_amount = payment_amount / INCENTIVE_PERCENTAGE as u128;
+		let expected_bond = payment_amount / 10;
+
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None
+		));
+
+		assert_ok!(Payment::resolve_payment(
+			RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			Percent::from_percent(50)
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentRuled {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				recipient_share: Percent::from_percent(50),
+				appeal_deadline: 11,
+			}
+			.into()
+		);
+		// the ruling is not settled yet
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT)
+				.unwrap()
+				.state,
+			PaymentState::Ruled {
+				recipient_share: Percent::from_percent(50),
+				appeal_deadline: 11,
+			}
+		);
+		assert_noop!(
+			Payment::release(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::InvalidAction
+		);
+		assert_noop!(
+			Payment::resolve_payment(
+				RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT,
+				Percent::from_percent(100)
+			),
+			Error::InvalidAction
+		);
+		assert_noop!(
+			Payment::finalize_ruling(
+				RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::AppealWindowNotPassed
+		);
+
+		// only the creator or recipient can appeal
+		assert_noop!(
+			Payment::appeal_ruling(
+				RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::InvalidAction
+		);
+		assert_ok!(Payment::appeal_ruling(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::RulingAppealed {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				appellant: PAYMENT_CREATOR,
+				bond: expected_bond,
+			}
+			.into()
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - expected_incentive_amount - expected_bond
+		);
+		assert_noop!(
+			Payment::appeal_ruling(
+				RuntimeOrigin::signed(PAYMENT_RECIPENT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::InvalidAction
+		);
+
+		// the appeal origin settles the payment, the bond is returned since the
+		// ruling changed
+		assert_noop!(
+			Payment::resolve_appeal(
+				RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT,
+				Percent::from_percent(0)
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Payment::resolve_appeal(
+			RuntimeOrigin::root(),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			Percent::from_percent(0)
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::AppealResolved {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				recipient_share: Percent::from_percent(0),
+				bond_slashed: false,
+			}
+			.into()
+		);
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance
+		);
+		assert_eq!(Tokens::total_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 0);
+	});
+}
+
+#[test]
+fn test_upheld_appeal_slashes_bond() {
+	new_test_ext().execute_with(|| {
+		AppealWindow::set(10);
+		let creator_initial_balance = 100;
+		let payment_amount = 40;
+		let expected_bond = payment_amount / 10;
+
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None
+		));
+		assert_ok!(Payment::resolve_payment(
+			RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			Percent::from_percent(100)
+		));
+		assert_ok!(Payment::appeal_ruling(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_ok!(Payment::resolve_appeal(
+			RuntimeOrigin::root(),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			Percent::from_percent(100)
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::AppealResolved {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				recipient_share: Percent::from_percent(100),
+				bond_slashed: true,
+			}
+			.into()
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount - expected_bond
+		);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 0);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), payment_amount);
+	});
+}
+
+#[test]
+fn test_ruling_is_finalized_after_appeal_window() {
+	new_test_ext().execute_with(|| {
+		AppealWindow::set(10);
+		let creator_initial_balance = 100;
+		let payment_amount = 40;
+
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None
+		));
+		assert_ok!(Payment::resolve_payment(
+			RuntimeOrigin::signed(RESOLVER_ACCOUNT),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT,
+			Percent::from_percent(50)
+		));
+
+		System::set_block_number(11);
+
+		assert_noop!(
+			Payment::appeal_ruling(
+				RuntimeOrigin::signed(PAYMENT_RECIPENT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::AppealWindowPassed
+		);
+		assert_ok!(Payment::finalize_ruling(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentResolved {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				recipient_share: Percent::from_percent(50),
+			}
+			.into()
+		);
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - payment_amount / 2
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), payment_amount / 2);
+	});
+}
+
+#[test]
+fn test_request_payment_in_reference_works() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+		let reference_amount = 10;
+		// the mock price of the reference currency is 2.5
+		let expected_amount = 25;
+
+		assert_ok!(Payment::request_payment_in_reference(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			CURRENCY_ID,
+			REFERENCE_CURRENCY_ID,
+			reference_amount,
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentRequestCreated {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+			}
+			.into()
+		);
+		assert_eq!(
+			PaymentReferences::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some((REFERENCE_CURRENCY_ID, reference_amount))
+		);
+
+		assert_ok!(Payment::accept_and_pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentRequestCompleted {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+			}
+			.into()
+		);
+
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - expected_amount
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), expected_amount);
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentReferences::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+	});
+}
+
+#[test]
+fn test_request_payment_in_reference_fails_without_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payment::request_payment_in_reference(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			CURRENCY_ID,
+			3,
+			10,
+		));
+		assert_noop!(
+			Payment::accept_and_pay(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::PriceUnavailable
+		);
+
+		// cancelling the request removes the reference
+		assert_ok!(Payment::cancel(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR
+		));
+		assert_eq!(PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(PaymentReferences::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+	});
+}
+
+#[test]
+fn test_request_payment_in_reference_fails_on_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payment::request_payment_in_reference(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			CURRENCY_ID,
+			REFERENCE_CURRENCY_ID,
+			u128::MAX,
+		));
+		assert_noop!(
+			Payment::accept_and_pay(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::MathError
+		);
+	});
+}
+
+#[test]
+fn test_set_fee_config_works() {
+	new_test_ext().execute_with(|| {
+		let payment_amount = 20;
+		let config = FeeConfig {
+			recipient: RESOLVER_ACCOUNT,
+			percentage: Percent::from_percent(20),
+		};
+
+		assert_noop!(
+			Payment::set_fee_config(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				CURRENCY_ID,
+				PaymentKind::Standard,
+				Some(config.clone())
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Payment::set_fee_config(
+			RuntimeOrigin::root(),
+			CURRENCY_ID,
+			PaymentKind::Standard,
+			Some(config.clone())
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::FeeConfigUpdated {
+				asset: CURRENCY_ID,
+				kind: PaymentKind::Standard,
+				config: Some(config.clone()),
+			}
+			.into()
+		);
+		assert_eq!(Payment::fee_config(CURRENCY_ID, PaymentKind::Standard), Some(config));
+
+		// the configured fee overrides the fee handler
+		assert_ok!(Payment::pay(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			None
+		));
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT)
+				.unwrap()
+				.fee_detail,
+			Some((RESOLVER_ACCOUNT, 4))
+		);
+		assert_ok!(Payment::release(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &RESOLVER_ACCOUNT), 4);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			100 - payment_amount - 4
+		);
+
+		// other payment kinds still use the fee handler
+		assert_ok!(Payment::pay_with_expiry(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_RECIPENT_FEE_CHARGED,
+			CURRENCY_ID,
+			payment_amount,
+			None,
+			10
+		));
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR_TWO, PAYMENT_RECIPENT_FEE_CHARGED)
+				.unwrap()
+				.fee_detail,
+			Some((FEE_RECIPIENT_ACCOUNT, 2))
+		);
+
+		assert_ok!(Payment::set_fee_config(
+			RuntimeOrigin::root(),
+			CURRENCY_ID,
+			PaymentKind::Standard,
+			None
+		));
+		assert_eq!(Payment::fee_config(CURRENCY_ID, PaymentKind::Standard), None);
+	});
+}
+
+#[test]
+fn test_recurring_payment_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Payment::create_recurring(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_RECIPENT,
+				CURRENCY_ID,
+				30,
+				0,
+				3
+			),
+			Error::InvalidRecurringSchedule
+		);
+		assert_ok!(Payment::create_recurring(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			30,
+			10,
+			3
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::RecurringPaymentCreated {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				asset: CURRENCY_ID,
+				amount: 30,
+				interval: 10,
+				count: 3,
+			}
+			.into()
+		);
+		// the first installment is reserved
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR), 70);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 30);
+		assert_noop!(
+			Payment::create_recurring(
+				RuntimeOrigin::signed(PAYMENT_CREATOR),
+				PAYMENT_RECIPENT,
+				CURRENCY_ID,
+				30,
+				10,
+				3
+			),
+			Error::PaymentAlreadyInProcess
+		);
+
+		assert_noop!(
+			Payment::collect_installment(
+				RuntimeOrigin::signed(PAYMENT_RECIPENT),
+				PAYMENT_CREATOR,
+				PAYMENT_RECIPENT
+			),
+			Error::InstallmentNotDue
+		);
+
+		System::set_block_number(11);
+		assert_ok!(Payment::collect_installment(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::InstallmentCollected {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				amount: 30,
+				remaining: 2,
+			}
+			.into()
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 30);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR), 40);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 30);
+
+		// missed installments can still be collected, by anyone
+		System::set_block_number(31);
+		assert_noop!(
+			Payment::cancel_recurring(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::InstallmentDue
+		);
+		assert_ok!(Payment::collect_installment(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_ok!(Payment::collect_installment(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_CREATOR,
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), 90);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR), 10);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 0);
+		assert_eq!(Payment::recurring_payment(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+	});
+}
+
+#[test]
+fn test_recurring_payment_cancel_and_stop_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Payment::create_recurring(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			30,
+			10,
+			3
+		));
+		// the payer can cancel between installments
+		assert_ok!(Payment::cancel_recurring(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::RecurringPaymentCancelled {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+			}
+			.into()
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR), 100);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_CREATOR), 0);
+
+		// the payment stops when the next installment can not be reserved
+		assert_ok!(Payment::create_recurring(
+			RuntimeOrigin::signed(PAYMENT_CREATOR_TWO),
+			PAYMENT_RECIPENT_TWO,
+			CURRENCY_ID,
+			40,
+			10,
+			5
+		));
+		System::set_block_number(11);
+		assert_ok!(Payment::collect_installment(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT_TWO),
+			PAYMENT_CREATOR_TWO,
+			PAYMENT_RECIPENT_TWO
+		));
+		System::set_block_number(21);
+		assert_ok!(Payment::collect_installment(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT_TWO),
+			PAYMENT_CREATOR_TWO,
+			PAYMENT_RECIPENT_TWO
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::RecurringPaymentStopped {
+				from: PAYMENT_CREATOR_TWO,
+				to: PAYMENT_RECIPENT_TWO,
+			}
+			.into()
+		);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT_TWO), 80);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR_TWO), 20);
+		assert_eq!(
+			Payment::recurring_payment(PAYMENT_CREATOR_TWO, PAYMENT_RECIPENT_TWO),
+			None
+		);
+	});
+}
+
+#[test]
+fn test_pay_with_reference_works() {
+	new_test_ext().execute_with(|| {
+		let payment_amount = 20;
+		let remark: BoundedDataOf<Test> = vec![1u8; 10].try_into().unwrap();
+		let external_reference = H256::repeat_byte(7);
+
+		assert_ok!(Payment::pay_with_reference(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			payment_amount,
+			Some(remark.clone()),
+			external_reference
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentCreated {
+				from: PAYMENT_CREATOR,
+				asset: CURRENCY_ID,
+				amount: payment_amount,
+				remark: Some(remark.clone()),
+				external_reference: Some(external_reference)
+			}
+			.into()
+		);
+		assert_eq!(
+			Payment::payment_metadata(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(PaymentMetadata {
+				remark: Some(remark),
+				external_reference: Some(external_reference)
+			})
+		);
+
+		// the metadata is removed when the payment is settled
+		assert_ok!(Payment::release(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(Payment::payment_metadata(PAYMENT_CREATOR, PAYMENT_RECIPENT), None);
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), payment_amount);
+	});
+}
+
+#[test]
+fn test_propose_and_accept_payment_works() {
+	new_test_ext().execute_with(|| {
+		let creator_initial_balance = 100;
+		let proposed_amount = 20;
+		let counter_amount = 30;
+
+		assert_ok!(Payment::propose_payment(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			proposed_amount,
+			None
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentProposed {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				asset: CURRENCY_ID,
+				amount: proposed_amount,
+			}
+			.into()
+		);
+		// nothing is reserved until the proposal is accepted
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance
+		);
+		assert_noop!(
+			Payment::release(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::InvalidAction
+		);
+		assert_noop!(
+			Payment::confirm_counter_proposal(RuntimeOrigin::signed(PAYMENT_CREATOR), PAYMENT_RECIPENT),
+			Error::InvalidAction
+		);
+
+		// the recipient counter proposes a different amount
+		assert_ok!(Payment::accept_payment(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			Some(counter_amount)
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentCounterProposed {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				amount: counter_amount,
+			}
+			.into()
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance
+		);
+		assert_noop!(
+			Payment::accept_payment(RuntimeOrigin::signed(PAYMENT_RECIPENT), PAYMENT_CREATOR, None),
+			Error::InvalidAction
+		);
+
+		// the creator confirms the counter proposal
+		assert_ok!(Payment::confirm_counter_proposal(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentAccepted {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				amount: counter_amount,
+			}
+			.into()
+		);
+		let expected_incentive_amount = counter_amount / INCENTIVE_PERCENTAGE as u128;
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(PaymentDetail {
+				asset: CURRENCY_ID,
+				amount: counter_amount,
+				incentive_amount: expected_incentive_amount,
+				state: PaymentState::Created,
+				resolver_account: RESOLVER_ACCOUNT,
+				fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 0)),
+				expiry: None,
+				splits: Default::default(),
+			})
+		);
+		assert_eq!(
+			Tokens::free_balance(CURRENCY_ID, &PAYMENT_CREATOR),
+			creator_initial_balance - counter_amount - expected_incentive_amount
+		);
+		assert_eq!(Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_RECIPENT), counter_amount);
+
+		assert_ok!(Payment::release(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT
+		));
+		assert_eq!(Tokens::free_balance(CURRENCY_ID, &PAYMENT_RECIPENT), counter_amount);
+
+		// accepting the proposed amount reserves it right away
+		assert_ok!(Payment::propose_payment(
+			RuntimeOrigin::signed(PAYMENT_CREATOR),
+			PAYMENT_RECIPENT,
+			CURRENCY_ID,
+			proposed_amount,
+			None
+		));
+		assert_ok!(Payment::accept_payment(
+			RuntimeOrigin::signed(PAYMENT_RECIPENT),
+			PAYMENT_CREATOR,
+			Some(proposed_amount)
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::<Test>::PaymentAccepted {
+				from: PAYMENT_CREATOR,
+				to: PAYMENT_RECIPENT,
+				amount: proposed_amount,
+			}
+			.into()
+		);
+		assert_eq!(
+			Payment::payment(PAYMENT_CREATOR, PAYMENT_RECIPENT).map(|payment| payment.state),
+			Some(PaymentState::Created)
+		);
+		assert_eq!(
+			Tokens::reserved_balance(CURRENCY_ID, &PAYMENT_RECIPENT),
+			proposed_amount
+		);
+	});
+}
+
+#[test]
+fn migration_works() {
+	use codec::{Compact, Encode};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let old_fields = (
+			CURRENCY_ID,
+			Compact(20u128),
+			Compact(2u128),
+			PaymentState::<Test>::Created,
+			RESOLVER_ACCOUNT,
+			Some((FEE_RECIPIENT_ACCOUNT, 1u128)),
+		);
+		let expected = PaymentDetail {
+			asset: CURRENCY_ID,
+			amount: 20,
+			incentive_amount: 2,
+			state: PaymentState::Created,
+			resolver_account: RESOLVER_ACCOUNT,
+			fee_detail: Some((FEE_RECIPIENT_ACCOUNT, 1)),
+			expiry: None,
+			splits: Default::default(),
+		};
+		let key = PaymentStore::<Test>::hashed_key_for(PAYMENT_CREATOR, PAYMENT_RECIPENT);
+
+		// a payment stored before payments could expire
+		StorageVersion::new(0).put::<Payment>();
+		frame_support::storage::unhashed::put_raw(&key, &old_fields.encode());
+		crate::Migration::<Test>::on_runtime_upgrade();
+		assert_eq!(Payment::on_chain_storage_version(), Payment::current_storage_version());
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(expected.clone())
+		);
+
+		// a payment stored before payments could be split
+		StorageVersion::new(1).put::<Payment>();
+		frame_support::storage::unhashed::put_raw(&key, &(old_fields, Some(5u64)).encode());
+		crate::Migration::<Test>::on_runtime_upgrade();
+		assert_eq!(Payment::on_chain_storage_version(), Payment::current_storage_version());
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(PaymentDetail {
+				expiry: Some(5),
+				..expected.clone()
+			})
+		);
+
+		// a payment stored before rulings could be appealed decodes unchanged
+		StorageVersion::new(2).put::<Payment>();
+		crate::Migration::<Test>::on_runtime_upgrade();
+		assert_eq!(Payment::on_chain_storage_version(), 3);
+		assert_eq!(
+			PaymentStore::<Test>::get(PAYMENT_CREATOR, PAYMENT_RECIPENT),
+			Some(PaymentDetail {
+				expiry: Some(5),
+				..expected
+			})
+		);
+	});
+}
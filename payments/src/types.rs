@@ -1,5 +1,5 @@
 #![allow(unused_qualifications)]
-use crate::{pallet, AssetIdOf, BalanceOf};
+use crate::{pallet, AssetIdOf, BalanceOf, BoundedDataOf, SplitsOf};
 use codec::{Decode, Encode, HasCompact, MaxEncodedLen};
 use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
@@ -30,6 +30,65 @@ pub struct PaymentDetail<T: pallet::Config> {
 	pub resolver_account: T::AccountId,
 	/// fee charged and recipient account details
 	pub fee_detail: Option<(T::AccountId, BalanceOf<T>)>,
+	/// block after which the creator can claim a refund if the payment is
+	/// still not released or disputed
+	pub expiry: Option<BlockNumberFor<T>>,
+	/// accounts receiving a fixed share of the amount released to the
+	/// recipient
+	pub splits: SplitsOf<T>,
+}
+
+impl<T: pallet::Config> PaymentDetail<T> {
+	/// The kind of the payment, used to look up its fee configuration
+	pub fn kind(&self) -> PaymentKind {
+		if self.state == PaymentState::PaymentRequested {
+			PaymentKind::Request
+		} else if !self.splits.is_empty() {
+			PaymentKind::Split
+		} else if self.expiry.is_some() {
+			PaymentKind::Expiring
+		} else {
+			PaymentKind::Standard
+		}
+	}
+}
+
+/// The remark and external reference attached to a payment at creation,
+/// kept until the payment is settled
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: pallet::Config))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PaymentMetadata<T: pallet::Config> {
+	/// remark given by the creator
+	pub remark: Option<BoundedDataOf<T>>,
+	/// hash referencing the payment in an external system, like an invoice
+	/// or order
+	pub external_reference: Option<T::Hash>,
+}
+
+/// The kinds of payments that can be given their own fee configuration
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PaymentKind {
+	/// A payment created with `pay`
+	Standard,
+	/// A payment created with `pay_with_expiry`
+	Expiring,
+	/// A payment created with `pay_with_splits`
+	Split,
+	/// A payment requested by the recipient
+	Request,
+}
+
+/// The on-chain fee configuration of an asset and payment kind
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeConfig<AccountId> {
+	/// account receiving the fee
+	pub recipient: AccountId,
+	/// share of the payment amount charged as fee
+	pub percentage: Percent,
 }
 
 /// The `PaymentState` enum tracks the possible states that a payment can be in.
@@ -48,6 +107,25 @@ pub enum PaymentState<T: pallet::Config> {
 	RefundRequested { cancel_block: BlockNumberFor<T> },
 	/// The recipient of this transaction has created a request
 	PaymentRequested,
+	/// A judge has ruled on the payment, the ruling can be appealed until
+	/// `appeal_deadline`
+	Ruled {
+		recipient_share: Percent,
+		appeal_deadline: BlockNumberFor<T>,
+	},
+	/// The ruling has been appealed by `appellant` who reserved `bond`, and
+	/// awaits the decision of the appeal origin
+	Appealed {
+		recipient_share: Percent,
+		appellant: T::AccountId,
+		bond: BalanceOf<T>,
+	},
+	/// The creator has proposed a payment that is reserved once the recipient
+	/// accepts it
+	Proposed,
+	/// The recipient has proposed a different `amount` that is reserved once
+	/// the creator confirms it
+	CounterProposed { amount: BalanceOf<T> },
 }
 
 /// trait that defines how to create/release payments for users
@@ -94,7 +172,8 @@ pub trait DisputeResolver<Account> {
 }
 
 /// Fee Handler trait that defines how to handle marketplace fees to every
-/// payment/swap
+/// payment/swap, used when no fee is configured on-chain for the asset and
+/// payment kind
 pub trait FeeHandler<T: pallet::Config> {
 	/// Get the distribution of fees to marketplace participants
 	fn apply_fees(
@@ -120,3 +199,24 @@ pub struct ScheduledTask<Time: HasCompact> {
 	/// the 'time' at which the task should be executed
 	pub when: Time,
 }
+
+/// The details of a recurring payment, installments of `amount` can be
+/// collected every `interval` blocks
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: pallet::Config))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecurringPayment<T: pallet::Config> {
+	/// type of asset used for the installments
+	pub asset: AssetIdOf<T>,
+	/// amount of each installment
+	#[codec(compact)]
+	pub amount: BalanceOf<T>,
+	/// number of blocks between two installments
+	pub interval: BlockNumberFor<T>,
+	/// number of installments left to collect, the next one is reserved
+	/// from the payer
+	pub remaining: u32,
+	/// block from which the next installment can be collected
+	pub next_due: BlockNumberFor<T>,
+}
@@ -41,6 +41,22 @@ pub trait WeightInfo {
 	fn request_payment() -> Weight;
 	fn accept_and_pay() -> Weight;
 	fn remove_task() -> Weight;
+	fn release_partial() -> Weight;
+	fn pay_with_expiry(x: u32) -> Weight;
+	fn claim_expired_refund() -> Weight;
+	fn pay_with_splits(x: u32) -> Weight;
+	fn appeal_ruling() -> Weight;
+	fn resolve_appeal() -> Weight;
+	fn finalize_ruling() -> Weight;
+	fn request_payment_in_reference() -> Weight;
+	fn set_fee_config() -> Weight;
+	fn create_recurring() -> Weight;
+	fn collect_installment() -> Weight;
+	fn cancel_recurring() -> Weight;
+	fn pay_with_reference(x: u32) -> Weight;
+	fn propose_payment(x: u32) -> Weight;
+	fn accept_payment() -> Weight;
+	fn confirm_counter_proposal() -> Weight;
 }
 
 /// Weights for virto_payment using the Substrate node and recommended hardware.
@@ -112,6 +128,125 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn release_partial() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_expiry(_x: u32) -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn claim_expired_refund() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_splits(_x: u32) -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn appeal_ruling() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:3 w:3)
+	fn resolve_appeal() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn finalize_ruling() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentReferences (r:0 w:1)
+	// Storage: Sudo Key (r:1 w:0)
+	fn request_payment_in_reference() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment FeeConfigs (r:0 w:1)
+	fn set_fee_config() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn create_recurring() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn collect_installment() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn cancel_recurring() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:0 w:1)
+	// Storage: Sudo Key (r:1 w:0)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_reference(_x: u32) -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:0 w:1)
+	fn propose_payment(_x: u32) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn accept_payment() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:1 w:0)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn confirm_counter_proposal() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -182,4 +317,123 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn release_partial() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_expiry(_x: u32) -> Weight {
+		Weight::from_parts(56_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	fn claim_expired_refund() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_splits(_x: u32) -> Weight {
+		Weight::from_parts(58_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn appeal_ruling() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:3 w:3)
+	fn resolve_appeal() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn finalize_ruling() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentReferences (r:0 w:1)
+	// Storage: Sudo Key (r:1 w:0)
+	fn request_payment_in_reference() -> Weight {
+		Weight::from_parts(18_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment FeeConfigs (r:0 w:1)
+	fn set_fee_config() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn create_recurring() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn collect_installment() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	// Storage: Payment RecurringPayments (r:1 w:1)
+	// Storage: Assets Accounts (r:1 w:1)
+	fn cancel_recurring() -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:0 w:1)
+	// Storage: Sudo Key (r:1 w:0)
+	// Storage: Assets Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn pay_with_reference(_x: u32) -> Weight {
+		Weight::from_parts(57_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:0 w:1)
+	fn propose_payment(_x: u32) -> Weight {
+		Weight::from_parts(30_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn accept_payment() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	// Storage: Payment Payment (r:1 w:1)
+	// Storage: Payment PaymentMetadatas (r:1 w:0)
+	// Storage: Assets Accounts (r:2 w:2)
+	fn confirm_counter_proposal() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
 }
@@ -7,9 +7,10 @@ mod tests;
 
 use codec::{FullCodec, HasCompact};
 use frame_support::pallet_prelude::*;
-use orml_traits::RewardHandler;
+use frame_system::pallet_prelude::BlockNumberFor;
+use orml_traits::{PenaltyDestination, RewardHandler, RewardPoolPause, WithdrawPenalty, YieldSource};
+use orml_utilities::{mul_div, FixedAccumulator, Rounding};
 use scale_info::TypeInfo;
-use sp_core::U256;
 use sp_runtime::{
 	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, Saturating, UniqueSaturatedInto, Zero},
 	FixedPointOperand, RuntimeDebug, SaturatedConversion,
@@ -39,6 +40,15 @@ where
 	}
 }
 
+/// The paused share changes of a pool.
+#[derive(Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PoolPauseStatus {
+	/// Adding shares is paused
+	pub share_additions: bool,
+	/// Removing shares is paused
+	pub share_removals: bool,
+}
+
 pub use module::*;
 
 #[frame_support::pallet]
@@ -75,16 +85,64 @@ pub mod module {
 
 		/// The `RewardHandler`
 		type Handler: RewardHandler<Self::AccountId, Self::CurrencyId, Balance = Self::Balance, PoolId = Self::PoolId>;
+
+		/// The number of blocks between two share checkpoints. Zero disables
+		/// checkpoints.
+		#[pallet::constant]
+		type CheckpointInterval: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of checkpoints kept per pool, older checkpoints
+		/// are dropped.
+		#[pallet::constant]
+		type MaxCheckpoints: Get<u32>;
+
+		/// The penalty deducted from rewards claimed, directly or by removing
+		/// shares, before the minimum staking duration of their pool.
+		type WithdrawPenalty: WithdrawPenalty<
+			Self::AccountId,
+			Self::PoolId,
+			Self::CurrencyId,
+			Self::Balance,
+			BlockNumberFor<Self>,
+		>;
+
+		/// The source of rewards accumulated into pools enabled with
+		/// `enable_yield_source` at the start of each block.
+		type YieldSource: YieldSource<Self::PoolId, Self::CurrencyId, Self::Balance>;
+
+		/// The maximum number of pools enabled with `enable_yield_source`,
+		/// which bounds the work done at the start of each block.
+		#[pallet::constant]
+		type MaxYieldSourcePools: Get<u32>;
 	}
 
 	type WithdrawnRewards<T> = BTreeMap<<T as Config>::CurrencyId, <T as Config>::Balance>;
 
+	/// Shares recorded at the start of checkpoints, oldest first.
+	pub type ShareHistory<T> = BoundedVec<(BlockNumberFor<T>, <T as Config>::Share), <T as Config>::MaxCheckpoints>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Pool does not exist
 		PoolDoesNotExist,
 		ShareDoesNotExist,
 		CanSplitOnlyLessThanShare,
+		/// Pool already exists
+		PoolAlreadyExists,
+		/// Shares of the pool do not add up to its total shares
+		InconsistentPoolShares,
+		/// Checkpoints are disabled by `CheckpointInterval`
+		CheckpointsDisabled,
+		/// Adding shares to the pool is paused
+		ShareAdditionsPaused,
+		/// Removing shares from the pool is paused
+		ShareRemovalsPaused,
+		/// `MaxYieldSourcePools` pools are already enabled with
+		/// `enable_yield_source`
+		TooManyYieldSourcePools,
+		/// The share checkpoints of the pool are still being removed after
+		/// `disable_checkpoints`
+		CheckpointsBeingCleared,
 	}
 
 	/// Record reward pool info.
@@ -111,9 +169,91 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The first checkpoint of pools with share checkpoints enabled.
+	///
+	/// map PoolId => BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn checkpoints_enabled_since)]
+	pub type CheckpointsEnabledSince<T: Config> =
+		StorageMap<_, Twox64Concat, T::PoolId, BlockNumberFor<T>, OptionQuery>;
+
+	/// Total shares of a pool at the start of the checkpoints in which they
+	/// changed.
+	///
+	/// map PoolId => ShareHistory
+	#[pallet::storage]
+	#[pallet::getter(fn total_share_checkpoints)]
+	pub type TotalShareCheckpoints<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, ShareHistory<T>, ValueQuery>;
+
+	/// Share of an account at the start of the checkpoints in which it
+	/// changed.
+	///
+	/// double_map (PoolId, AccountId) => ShareHistory
+	#[pallet::storage]
+	#[pallet::getter(fn share_checkpoints)]
+	pub type ShareCheckpoints<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, ShareHistory<T>, ValueQuery>;
+
+	/// Pools whose share checkpoints are removed in `on_idle` after
+	/// `disable_checkpoints`, with the cursor to continue from.
+	///
+	/// map PoolId => Vec<u8>
+	#[pallet::storage]
+	#[pallet::getter(fn clearing_checkpoints)]
+	pub type ClearingCheckpoints<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, Vec<u8>, OptionQuery>;
+
+	/// The block at which shares were last added by an account, the minimum
+	/// staking duration is counted from it.
+	///
+	/// double_map (PoolId, AccountId) => BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn share_added_at)]
+	pub type ShareAddedAt<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::PoolId, Twox64Concat, T::AccountId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The paused share changes of a pool, absent if none is paused.
+	///
+	/// map PoolId => PoolPauseStatus
+	#[pallet::storage]
+	#[pallet::getter(fn pool_pause_status)]
+	pub type PoolPauses<T: Config> = StorageMap<_, Twox64Concat, T::PoolId, PoolPauseStatus, ValueQuery>;
+
+	/// Pools whose rewards are accumulated from `YieldSource` each block.
+	///
+	/// map PoolId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn yield_source_enabled)]
+	pub type YieldSourceEnabled<T: Config> = CountedStorageMap<_, Twox64Concat, T::PoolId, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+			let mut weight = Weight::zero();
+			let mut writes: u64 = 0;
+			for pool in YieldSourceEnabled::<T>::iter_keys().take(T::MaxYieldSourcePools::get() as usize) {
+				weight.saturating_accrue(T::DbWeight::get().reads(2));
+				// pools without shares are skipped so the yield is kept by the source
+				if !PoolInfos::<T>::contains_key(&pool) {
+					continue;
+				}
+				weight.saturating_accrue(T::YieldSource::take_yield_weight());
+				for (reward_currency, reward_increment) in T::YieldSource::take_yield(&pool) {
+					if Self::accumulate_reward(&pool, reward_currency, reward_increment).is_ok() {
+						writes = writes.saturating_add(1);
+					}
+				}
+			}
+			weight.saturating_add(T::DbWeight::get().writes(writes))
+		}
+
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::idle_clear_checkpoints(remaining_weight)
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -140,10 +280,24 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Like `add_share`, but fails if adding shares to `pool` is paused.
+	pub fn try_add_share(who: &T::AccountId, pool: &T::PoolId, add_amount: T::Share) -> DispatchResult {
+		ensure!(
+			!Self::pool_pause_status(pool).share_additions,
+			Error::<T>::ShareAdditionsPaused
+		);
+		Self::add_share(who, pool, add_amount);
+		Ok(())
+	}
+
+	/// Adds `add_amount` shares of `who` to `pool`, regardless of the pause
+	/// status of `pool`.
 	pub fn add_share(who: &T::AccountId, pool: &T::PoolId, add_amount: T::Share) {
 		if add_amount.is_zero() {
 			return;
 		}
+		Self::checkpoint_share(who, pool);
+		ShareAddedAt::<T>::insert(pool, who, frame_system::Pallet::<T>::block_number());
 
 		PoolInfos::<T>::mutate(pool, |pool_info| {
 			let initial_total_shares = pool_info.total_shares;
@@ -158,12 +312,15 @@ impl<T: Config> Pallet<T> {
 					let reward_inflation = if initial_total_shares.is_zero() {
 						Zero::zero()
 					} else {
-						U256::from(add_amount.to_owned().saturated_into::<u128>())
-							.saturating_mul(total_reward.to_owned().saturated_into::<u128>().into())
-							.checked_div(initial_total_shares.to_owned().saturated_into::<u128>().into())
-							.unwrap_or_default()
-							.as_u128()
-							.saturated_into()
+						mul_div(
+							add_amount.saturated_into(),
+							total_reward.to_owned().saturated_into(),
+							initial_total_shares.saturated_into(),
+							Rounding::Down,
+						)
+						// saturate instead of diluting the rewards of existing shares
+						.unwrap_or(u128::MAX)
+						.saturated_into()
 					};
 					*total_reward = total_reward.saturating_add(reward_inflation);
 					*total_withdrawn_reward = total_withdrawn_reward.saturating_add(reward_inflation);
@@ -188,13 +345,28 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// Like `remove_share`, but fails if removing shares from `pool` is
+	/// paused.
+	pub fn try_remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) -> DispatchResult {
+		ensure!(
+			!Self::pool_pause_status(pool).share_removals,
+			Error::<T>::ShareRemovalsPaused
+		);
+		Self::remove_share(who, pool, remove_amount);
+		Ok(())
+	}
+
+	/// Removes `remove_amount` shares of `who` from `pool`, claiming their
+	/// rewards first, regardless of the pause status of `pool`.
 	pub fn remove_share(who: &T::AccountId, pool: &T::PoolId, remove_amount: T::Share) {
 		if remove_amount.is_zero() {
 			return;
 		}
+		Self::checkpoint_share(who, pool);
 
-		// claim rewards firstly
-		Self::claim_rewards(who, pool);
+		// claim rewards firstly, penalized if the shares are removed early
+		let penalized = Self::is_early_withdrawal(who, pool);
+		let penalties = Self::do_claim_rewards(who, pool, penalized);
 
 		SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |share_info| {
 			if let Some((mut share, mut withdrawn_rewards)) = share_info.take() {
@@ -206,20 +378,20 @@ impl<T: Config> Pallet<T> {
 
 				PoolInfos::<T>::mutate_exists(pool, |maybe_pool_info| {
 					if let Some(mut pool_info) = maybe_pool_info.take() {
-						let removing_share = U256::from(remove_amount.saturated_into::<u128>());
-
 						pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
 
 						// update withdrawn rewards for each reward currency
 						withdrawn_rewards
 							.iter_mut()
 							.for_each(|(reward_currency, withdrawn_reward)| {
-								let withdrawn_reward_to_remove: T::Balance = removing_share
-									.saturating_mul(withdrawn_reward.to_owned().saturated_into::<u128>().into())
-									.checked_div(share.saturated_into::<u128>().into())
-									.unwrap_or_default()
-									.as_u128()
-									.saturated_into();
+								let withdrawn_reward_to_remove: T::Balance = mul_div(
+									remove_amount.saturated_into(),
+									withdrawn_reward.to_owned().saturated_into(),
+									share.saturated_into(),
+									Rounding::Down,
+								)
+								.unwrap_or_default()
+								.saturated_into();
 
 								if let Some((total_reward, total_withdrawn_reward)) =
 									pool_info.rewards.get_mut(reward_currency)
@@ -245,9 +417,13 @@ impl<T: Config> Pallet<T> {
 				share = share.saturating_sub(remove_amount);
 				if !share.is_zero() {
 					*share_info = Some((share, withdrawn_rewards));
+				} else {
+					ShareAddedAt::<T>::remove(pool, who);
 				}
 			}
 		});
+
+		Self::route_penalties(who, pool, penalties);
 	}
 
 	pub fn set_share(who: &T::AccountId, pool: &T::PoolId, new_share: T::Share) {
@@ -260,7 +436,29 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Like `set_share`, but fails if the needed share change of `pool` is
+	/// paused.
+	pub fn try_set_share(who: &T::AccountId, pool: &T::PoolId, new_share: T::Share) -> DispatchResult {
+		let (share, _) = Self::shares_and_withdrawn_rewards(pool, who);
+
+		if new_share > share {
+			Self::try_add_share(who, pool, new_share.saturating_sub(share))
+		} else {
+			Self::try_remove_share(who, pool, share.saturating_sub(new_share))
+		}
+	}
+
+	/// Claims all rewards of `who`, less the withdraw penalty if claimed
+	/// before the minimum staking duration of `pool`.
 	pub fn claim_rewards(who: &T::AccountId, pool: &T::PoolId) {
+		let penalties = Self::do_claim_rewards(who, pool, Self::is_early_withdrawal(who, pool));
+		Self::route_penalties(who, pool, penalties);
+	}
+
+	/// Claims all rewards of `who`, returning the penalty deducted for each
+	/// reward currency if `penalized`.
+	fn do_claim_rewards(who: &T::AccountId, pool: &T::PoolId, penalized: bool) -> Vec<(T::CurrencyId, T::Balance)> {
+		let mut penalties = Vec::new();
 		SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |maybe_share_withdrawn| {
 			if let Some((share, withdrawn_rewards)) = maybe_share_withdrawn {
 				if share.is_zero() {
@@ -269,10 +467,10 @@ impl<T: Config> Pallet<T> {
 
 				PoolInfos::<T>::mutate_exists(pool, |maybe_pool_info| {
 					if let Some(pool_info) = maybe_pool_info {
-						let total_shares = U256::from(pool_info.total_shares.to_owned().saturated_into::<u128>());
+						let total_shares: u128 = pool_info.total_shares.saturated_into();
 						pool_info.rewards.iter_mut().for_each(
 							|(reward_currency, (total_reward, total_withdrawn_reward))| {
-								Self::claim_one(
+								let penalty = Self::claim_one(
 									withdrawn_rewards,
 									*reward_currency,
 									share.to_owned(),
@@ -281,16 +479,25 @@ impl<T: Config> Pallet<T> {
 									total_withdrawn_reward,
 									who,
 									pool,
+									penalized,
 								);
+								if !penalty.is_zero() {
+									penalties.push((*reward_currency, penalty));
+								}
 							},
 						);
 					}
 				});
 			}
 		});
+		penalties
 	}
 
+	/// Claims the `reward_currency` rewards of `who`, less the withdraw
+	/// penalty if claimed before the minimum staking duration of `pool`.
 	pub fn claim_reward(who: &T::AccountId, pool: &T::PoolId, reward_currency: T::CurrencyId) {
+		let penalized = Self::is_early_withdrawal(who, pool);
+		let mut penalty = Zero::zero();
 		SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |maybe_share_withdrawn| {
 			if let Some((share, withdrawn_rewards)) = maybe_share_withdrawn {
 				if share.is_zero() {
@@ -298,9 +505,9 @@ impl<T: Config> Pallet<T> {
 				}
 
 				PoolInfos::<T>::mutate(pool, |pool_info| {
-					let total_shares = U256::from(pool_info.total_shares.to_owned().saturated_into::<u128>());
+					let total_shares: u128 = pool_info.total_shares.saturated_into();
 					if let Some((total_reward, total_withdrawn_reward)) = pool_info.rewards.get_mut(&reward_currency) {
-						Self::claim_one(
+						penalty = Self::claim_one(
 							withdrawn_rewards,
 							reward_currency,
 							share.to_owned(),
@@ -309,11 +516,15 @@ impl<T: Config> Pallet<T> {
 							total_withdrawn_reward,
 							who,
 							pool,
+							penalized,
 						);
 					}
 				});
 			}
 		});
+		if !penalty.is_zero() {
+			Self::route_penalties(who, pool, sp_std::vec![(reward_currency, penalty)]);
+		}
 	}
 
 	/// Splits share into two parts.
@@ -323,25 +534,33 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Similar too claim and add 2 shares later, but does not requires pool
 	/// inflation and is more efficient.
+	///
+	/// `other` keeps the later of both staking starts, so moving shares does
+	/// not shorten the minimum staking duration.
+	///
+	/// Does not check the pause status of `pool`.
 	pub fn transfer_share_and_rewards(
 		who: &T::AccountId,
 		pool: &T::PoolId,
 		move_share: T::Share,
 		other: &T::AccountId,
 	) -> DispatchResult {
+		Self::checkpoint_share(who, pool);
+		Self::checkpoint_share(other, pool);
 		SharesAndWithdrawnRewards::<T>::mutate(pool, other, |increased_share| {
 			let (increased_share, increased_rewards) = increased_share;
 			SharesAndWithdrawnRewards::<T>::mutate_exists(pool, who, |share| {
 				let (share, rewards) = share.as_mut().ok_or(Error::<T>::ShareDoesNotExist)?;
 				ensure!(move_share < *share, Error::<T>::CanSplitOnlyLessThanShare);
 				for (reward_currency, balance) in rewards {
-					// u128 * u128 is always less than u256
 					// move_share / share always less then 1 and share > 0
-					// so final results is computable and is always less or equal than u128
-					let move_balance = U256::from(balance.to_owned().saturated_into::<u128>())
-						* U256::from(move_share.to_owned().saturated_into::<u128>())
-						/ U256::from(share.to_owned().saturated_into::<u128>());
-					let move_balance: Option<u128> = move_balance.try_into().ok();
+					// so final results is always less or equal than u128
+					let move_balance = mul_div(
+						balance.to_owned().saturated_into(),
+						move_share.saturated_into(),
+						share.to_owned().saturated_into(),
+						Rounding::Down,
+					);
 					if let Some(move_balance) = move_balance {
 						let move_balance: T::Balance = move_balance.unique_saturated_into();
 						*balance = balance.saturating_sub(move_balance);
@@ -355,22 +574,359 @@ impl<T: Config> Pallet<T> {
 				}
 				*share = share.saturating_sub(move_share);
 				*increased_share = increased_share.saturating_add(move_share);
-				Ok(())
+				Ok::<_, DispatchError>(())
 			})
+		})?;
+
+		if let Some(added_at) = Self::share_added_at(pool, who) {
+			ShareAddedAt::<T>::mutate(pool, other, |other_added_at| {
+				*other_added_at = Some(other_added_at.map_or(added_at, |other_added_at| other_added_at.max(added_at)));
+			});
+		}
+		Ok(())
+	}
+
+	/// Like `transfer_share_and_rewards`, but fails if adding or removing
+	/// shares of `pool` is paused.
+	pub fn try_transfer_share_and_rewards(
+		who: &T::AccountId,
+		pool: &T::PoolId,
+		move_share: T::Share,
+		other: &T::AccountId,
+	) -> DispatchResult {
+		let pause_status = Self::pool_pause_status(pool);
+		ensure!(!pause_status.share_additions, Error::<T>::ShareAdditionsPaused);
+		ensure!(!pause_status.share_removals, Error::<T>::ShareRemovalsPaused);
+		Self::transfer_share_and_rewards(who, pool, move_share, other)
+	}
+
+	/// Moves the pool `old_id` with all its shares and withdrawn rewards to
+	/// `new_id`, so pending rewards are preserved.
+	///
+	/// Fails without changes if `new_id` is in use or the shares of the pool do
+	/// not add up to its total shares.
+	pub fn migrate_pool(old_id: &T::PoolId, new_id: &T::PoolId) -> DispatchResult {
+		frame_support::storage::with_storage_layer(|| Self::do_migrate_pool(old_id, new_id))
+	}
+
+	/// Migrates all `(old_id, new_id)` pairs in order, either all of them or
+	/// none.
+	pub fn migrate_pools(pools: &[(T::PoolId, T::PoolId)]) -> DispatchResult {
+		frame_support::storage::with_storage_layer(|| {
+			pools
+				.iter()
+				.try_for_each(|(old_id, new_id)| Self::do_migrate_pool(old_id, new_id))
 		})
 	}
 
+	fn do_migrate_pool(old_id: &T::PoolId, new_id: &T::PoolId) -> DispatchResult {
+		ensure!(PoolInfos::<T>::contains_key(old_id), Error::<T>::PoolDoesNotExist);
+		if old_id == new_id {
+			return Ok(());
+		}
+		ensure!(
+			!PoolInfos::<T>::contains_key(new_id)
+				&& SharesAndWithdrawnRewards::<T>::iter_prefix(new_id).next().is_none(),
+			Error::<T>::PoolAlreadyExists
+		);
+		ensure!(
+			!ClearingCheckpoints::<T>::contains_key(new_id),
+			Error::<T>::CheckpointsBeingCleared
+		);
+
+		let pool_info = PoolInfos::<T>::take(old_id);
+		let mut total_shares = T::Share::zero();
+		for (who, share_info) in SharesAndWithdrawnRewards::<T>::drain_prefix(old_id) {
+			total_shares = total_shares.saturating_add(share_info.0);
+			SharesAndWithdrawnRewards::<T>::insert(new_id, who, share_info);
+		}
+		ensure!(
+			total_shares == pool_info.total_shares,
+			Error::<T>::InconsistentPoolShares
+		);
+		PoolInfos::<T>::insert(new_id, pool_info);
+		for (who, added_at) in ShareAddedAt::<T>::drain_prefix(old_id) {
+			ShareAddedAt::<T>::insert(new_id, who, added_at);
+		}
+		if PoolPauses::<T>::contains_key(old_id) {
+			PoolPauses::<T>::insert(new_id, PoolPauses::<T>::take(old_id));
+		}
+		if YieldSourceEnabled::<T>::take(old_id) {
+			YieldSourceEnabled::<T>::insert(new_id, true);
+		}
+
+		if let Some(since) = CheckpointsEnabledSince::<T>::take(old_id) {
+			CheckpointsEnabledSince::<T>::insert(new_id, since);
+			TotalShareCheckpoints::<T>::insert(new_id, TotalShareCheckpoints::<T>::take(old_id));
+			for (who, history) in ShareCheckpoints::<T>::drain_prefix(old_id) {
+				ShareCheckpoints::<T>::insert(new_id, who, history);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Starts recording share checkpoints for `pool`, beginning with the
+	/// next checkpoint.
+	///
+	/// Fails if the checkpoints recorded before they were last disabled are
+	/// not removed yet.
+	pub fn enable_checkpoints(pool: &T::PoolId) -> DispatchResult {
+		let interval = T::CheckpointInterval::get();
+		ensure!(!interval.is_zero(), Error::<T>::CheckpointsDisabled);
+		ensure!(
+			!ClearingCheckpoints::<T>::contains_key(pool),
+			Error::<T>::CheckpointsBeingCleared
+		);
+		if !CheckpointsEnabledSince::<T>::contains_key(pool) {
+			let since = Self::current_checkpoint(interval).saturating_add(interval);
+			CheckpointsEnabledSince::<T>::insert(pool, since);
+		}
+		Ok(())
+	}
+
+	/// Stops recording share checkpoints for `pool` and removes its
+	/// recorded history, the share checkpoints of its accounts in pages by
+	/// `on_idle`.
+	pub fn disable_checkpoints(pool: &T::PoolId) {
+		if CheckpointsEnabledSince::<T>::take(pool).is_some() {
+			TotalShareCheckpoints::<T>::remove(pool);
+			ClearingCheckpoints::<T>::insert(pool, Vec::<u8>::new());
+		}
+	}
+
+	/// Remove a page of share checkpoints of a pool whose checkpoints were
+	/// disabled.
+	fn idle_clear_checkpoints(remaining_weight: Weight) -> Weight {
+		// read the cursor and update it
+		let base_weight = T::DbWeight::get().reads_writes(1, 1);
+		// read the share checkpoints and remove them
+		let per_account_weight = T::DbWeight::get().reads_writes(1, 1);
+		if remaining_weight.any_lt(base_weight) {
+			return Weight::zero();
+		}
+		let Some((pool, cursor)) = ClearingCheckpoints::<T>::iter().next() else {
+			return T::DbWeight::get().reads(1);
+		};
+
+		let available = remaining_weight.saturating_sub(base_weight);
+		let mut limit = u32::MAX as u64;
+		if per_account_weight.ref_time() > 0 {
+			limit = limit.min(available.ref_time() / per_account_weight.ref_time());
+		}
+		if per_account_weight.proof_size() > 0 {
+			limit = limit.min(available.proof_size() / per_account_weight.proof_size());
+		}
+		if limit == 0 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let maybe_cursor = (!cursor.is_empty()).then_some(&cursor[..]);
+		let result = ShareCheckpoints::<T>::clear_prefix(&pool, limit as u32, maybe_cursor);
+		match result.maybe_cursor {
+			Some(cursor) => ClearingCheckpoints::<T>::insert(&pool, cursor),
+			None => ClearingCheckpoints::<T>::remove(&pool),
+		}
+		base_weight.saturating_add(per_account_weight.saturating_mul(result.loops.into()))
+	}
+
+	/// Starts accumulating the rewards of `pool` from `YieldSource` at the
+	/// start of each block.
+	///
+	/// Fails if `MaxYieldSourcePools` pools are already enabled.
+	pub fn enable_yield_source(pool: &T::PoolId) -> DispatchResult {
+		if !YieldSourceEnabled::<T>::contains_key(pool) {
+			ensure!(
+				YieldSourceEnabled::<T>::count() < T::MaxYieldSourcePools::get(),
+				Error::<T>::TooManyYieldSourcePools
+			);
+			YieldSourceEnabled::<T>::insert(pool, true);
+		}
+		Ok(())
+	}
+
+	/// Stops accumulating the rewards of `pool` from `YieldSource`, rewards
+	/// can still be accumulated with `accumulate_reward`.
+	pub fn disable_yield_source(pool: &T::PoolId) {
+		YieldSourceEnabled::<T>::remove(pool);
+	}
+
+	/// The share of `who` in `pool` at the start of the checkpoint
+	/// containing block `at`.
+	///
+	/// Returns `None` if the checkpoint is in the future, was not recorded
+	/// or has been dropped from the history.
+	pub fn share_at(pool: &T::PoolId, who: &T::AccountId, at: BlockNumberFor<T>) -> Option<T::Share> {
+		let checkpoint = Self::queryable_checkpoint(pool, at)?;
+		let current_share = Self::shares_and_withdrawn_rewards(pool, who).0;
+		Some(Self::value_at(
+			&ShareCheckpoints::<T>::get(pool, who),
+			checkpoint,
+			current_share,
+		))
+	}
+
+	/// The total shares of `pool` at the start of the checkpoint containing
+	/// block `at`.
+	///
+	/// Returns `None` under the same conditions as `share_at`.
+	pub fn total_shares_at(pool: &T::PoolId, at: BlockNumberFor<T>) -> Option<T::Share> {
+		let checkpoint = Self::queryable_checkpoint(pool, at)?;
+		let current_total_shares = Self::pool_infos(pool).total_shares;
+		Some(Self::value_at(
+			&TotalShareCheckpoints::<T>::get(pool),
+			checkpoint,
+			current_total_shares,
+		))
+	}
+
+	fn current_checkpoint(interval: BlockNumberFor<T>) -> BlockNumberFor<T> {
+		let now = frame_system::Pallet::<T>::block_number();
+		now.saturating_sub(now % interval)
+	}
+
+	/// The oldest checkpoint still kept in the history.
+	fn oldest_checkpoint(interval: BlockNumberFor<T>) -> BlockNumberFor<T> {
+		let kept: BlockNumberFor<T> = T::MaxCheckpoints::get().saturating_sub(1).into();
+		Self::current_checkpoint(interval).saturating_sub(kept.saturating_mul(interval))
+	}
+
+	fn queryable_checkpoint(pool: &T::PoolId, at: BlockNumberFor<T>) -> Option<BlockNumberFor<T>> {
+		let interval = T::CheckpointInterval::get();
+		if interval.is_zero() || T::MaxCheckpoints::get().is_zero() {
+			return None;
+		}
+		let since = Self::checkpoints_enabled_since(pool)?;
+		let checkpoint = at.saturating_sub(at % interval);
+		let queryable = checkpoint >= since
+			&& checkpoint >= Self::oldest_checkpoint(interval)
+			&& checkpoint <= Self::current_checkpoint(interval);
+		queryable.then_some(checkpoint)
+	}
+
+	/// Entries are only recorded on the first change within a checkpoint,
+	/// so the value at `checkpoint` is the first entry at or after it, or
+	/// the current value if nothing changed since.
+	fn value_at(history: &ShareHistory<T>, checkpoint: BlockNumberFor<T>, current: T::Share) -> T::Share {
+		history
+			.iter()
+			.find(|(recorded_at, _)| *recorded_at >= checkpoint)
+			.map_or(current, |(_, share)| *share)
+	}
+
+	/// Records the share of `who` and the total shares of `pool` before
+	/// they change, if checkpoints are enabled for `pool`.
+	fn checkpoint_share(who: &T::AccountId, pool: &T::PoolId) {
+		let interval = T::CheckpointInterval::get();
+		if interval.is_zero() {
+			return;
+		}
+		let Some(since) = Self::checkpoints_enabled_since(pool) else {
+			return;
+		};
+		let checkpoint = Self::current_checkpoint(interval);
+		if checkpoint < since {
+			return;
+		}
+		let oldest = Self::oldest_checkpoint(interval);
+
+		let share = Self::shares_and_withdrawn_rewards(pool, who).0;
+		ShareCheckpoints::<T>::mutate(pool, who, |history| {
+			Self::record_checkpoint(history, checkpoint, oldest, share)
+		});
+		let total_shares = Self::pool_infos(pool).total_shares;
+		TotalShareCheckpoints::<T>::mutate(pool, |history| {
+			Self::record_checkpoint(history, checkpoint, oldest, total_shares)
+		});
+	}
+
+	fn record_checkpoint(
+		history: &mut ShareHistory<T>,
+		checkpoint: BlockNumberFor<T>,
+		oldest: BlockNumberFor<T>,
+		share: T::Share,
+	) {
+		if history
+			.last()
+			.map_or(false, |(recorded_at, _)| *recorded_at == checkpoint)
+		{
+			return;
+		}
+		// entries older than the oldest kept checkpoint can no longer be queried
+		history.retain(|(recorded_at, _)| *recorded_at >= oldest);
+		// at most `MaxCheckpoints - 1` older entries remain, so this only fails
+		// when `MaxCheckpoints` is zero
+		let _ = history.try_push((checkpoint, share));
+	}
+
+	/// Whether claiming rewards or removing shares of `who` now is before the
+	/// minimum staking duration of `pool`.
+	fn is_early_withdrawal(who: &T::AccountId, pool: &T::PoolId) -> bool {
+		Self::share_added_at(pool, who).map_or(false, |added_at| {
+			frame_system::Pallet::<T>::block_number()
+				< added_at.saturating_add(T::WithdrawPenalty::minimum_duration(pool))
+		})
+	}
+
+	/// Routes the penalties deducted from `who` to the other stakers of
+	/// `pool` or to the account given by `WithdrawPenalty`. Penalties routed
+	/// to stakers are dropped if no shares remain in `pool`.
+	fn route_penalties(who: &T::AccountId, pool: &T::PoolId, penalties: Vec<(T::CurrencyId, T::Balance)>) {
+		if penalties.is_empty() {
+			return;
+		}
+		match T::WithdrawPenalty::destination(pool) {
+			PenaltyDestination::Stakers => {
+				let (share, _) = Self::shares_and_withdrawn_rewards(pool, who);
+				for (reward_currency, penalty) in penalties {
+					if Self::accumulate_reward(pool, reward_currency, penalty).is_err() || share.is_zero() {
+						continue;
+					}
+					// `who` does not earn a part of its own penalty, like shares added
+					// after rewards were accumulated
+					let total_shares: u128 = Self::pool_infos(pool).total_shares.saturated_into();
+					let excluded: T::Balance = mul_div(
+						share.saturated_into(),
+						penalty.saturated_into(),
+						total_shares,
+						Rounding::Up,
+					)
+					.unwrap_or(u128::MAX)
+					.saturated_into();
+					let excluded = excluded.min(penalty);
+					PoolInfos::<T>::mutate(pool, |pool_info| {
+						if let Some((_, total_withdrawn_reward)) = pool_info.rewards.get_mut(&reward_currency) {
+							*total_withdrawn_reward = total_withdrawn_reward.saturating_add(excluded);
+						}
+					});
+					SharesAndWithdrawnRewards::<T>::mutate(pool, who, |(_, withdrawn_rewards)| {
+						let withdrawn_reward = withdrawn_rewards.entry(reward_currency).or_default();
+						*withdrawn_reward = withdrawn_reward.saturating_add(excluded);
+					});
+				}
+			}
+			PenaltyDestination::Account(account) => {
+				for (reward_currency, penalty) in penalties {
+					T::Handler::payout(&account, pool, reward_currency, penalty);
+				}
+			}
+		}
+	}
+
+	/// Pays out the pending reward of `who`, less the penalty if `penalized`,
+	/// and returns the deducted penalty.
 	#[allow(clippy::too_many_arguments)] // just we need to have all these to do the stuff
 	fn claim_one(
 		withdrawn_rewards: &mut BTreeMap<T::CurrencyId, T::Balance>,
 		reward_currency: T::CurrencyId,
 		share: T::Share,
 		total_reward: T::Balance,
-		total_shares: U256,
+		total_shares: u128,
 		total_withdrawn_reward: &mut T::Balance,
 		who: &T::AccountId,
 		pool: &T::PoolId,
-	) {
+		penalized: bool,
+	) -> T::Balance {
 		let withdrawn_reward = withdrawn_rewards.get(&reward_currency).copied().unwrap_or_default();
 		let reward_to_withdraw = Self::reward_to_withdraw(
 			share,
@@ -383,26 +939,79 @@ impl<T: Config> Pallet<T> {
 			*total_withdrawn_reward = total_withdrawn_reward.saturating_add(reward_to_withdraw);
 			withdrawn_rewards.insert(reward_currency, withdrawn_reward.saturating_add(reward_to_withdraw));
 
+			let penalty = if penalized {
+				T::WithdrawPenalty::penalty(who, pool, reward_currency, reward_to_withdraw).min(reward_to_withdraw)
+			} else {
+				Zero::zero()
+			};
+
 			// pay reward to `who`
-			T::Handler::payout(who, pool, reward_currency, reward_to_withdraw);
+			let payout = reward_to_withdraw.saturating_sub(penalty);
+			if !payout.is_zero() {
+				T::Handler::payout(who, pool, reward_currency, payout);
+			}
+			return penalty;
 		}
+		Zero::zero()
+	}
+
+	fn update_pool_pause_status(pool: &T::PoolId, f: impl FnOnce(&mut PoolPauseStatus)) {
+		PoolPauses::<T>::mutate_exists(pool, |maybe_status| {
+			let mut status = maybe_status.unwrap_or_default();
+			f(&mut status);
+			*maybe_status = (status != PoolPauseStatus::default()).then_some(status);
+		});
+	}
+
+	/// The rewards of `reward_currency` accumulated per share of `pool`,
+	/// including the inflation added for later shares.
+	///
+	/// `None` if the pool has no shares or the value overflows.
+	pub fn reward_per_share(pool: &T::PoolId, reward_currency: T::CurrencyId) -> Option<FixedAccumulator<18>> {
+		let pool_info = Self::pool_infos(pool);
+		let (total_reward, _) = pool_info.rewards.get(&reward_currency).copied().unwrap_or_default();
+		FixedAccumulator::from_rational(
+			total_reward.saturated_into(),
+			pool_info.total_shares.saturated_into(),
+			Rounding::Down,
+		)
 	}
 
 	fn reward_to_withdraw(
 		share: T::Share,
 		total_reward: T::Balance,
-		total_shares: U256,
+		total_shares: u128,
 		withdrawn_reward: T::Balance,
 		total_withdrawn_reward: T::Balance,
 	) -> T::Balance {
-		let total_reward_proportion: T::Balance = U256::from(share.saturated_into::<u128>())
-			.saturating_mul(U256::from(total_reward.saturated_into::<u128>()))
-			.checked_div(total_shares)
-			.unwrap_or_default()
-			.as_u128()
-			.unique_saturated_into();
+		let total_reward_proportion: T::Balance = mul_div(
+			share.saturated_into(),
+			total_reward.saturated_into(),
+			total_shares,
+			Rounding::Down,
+		)
+		.unwrap_or_default()
+		.unique_saturated_into();
 		total_reward_proportion
 			.saturating_sub(withdrawn_reward)
 			.min(total_reward.saturating_sub(total_withdrawn_reward))
 	}
 }
+
+impl<T: Config> RewardPoolPause<T::PoolId> for Pallet<T> {
+	fn set_share_additions_paused(pool: &T::PoolId, paused: bool) {
+		Self::update_pool_pause_status(pool, |status| status.share_additions = paused);
+	}
+
+	fn set_share_removals_paused(pool: &T::PoolId, paused: bool) {
+		Self::update_pool_pause_status(pool, |status| status.share_removals = paused);
+	}
+
+	fn share_additions_paused(pool: &T::PoolId) -> bool {
+		Self::pool_pause_status(pool).share_additions
+	}
+
+	fn share_removals_paused(pool: &T::PoolId) -> bool {
+		Self::pool_pause_status(pool).share_removals
+	}
+}
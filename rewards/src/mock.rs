@@ -4,7 +4,7 @@
 
 use super::*;
 use frame_support::{
-	construct_runtime,
+	construct_runtime, parameter_types,
 	traits::{ConstU64, Everything},
 	weights::constants::RocksDbWeight,
 };
@@ -24,6 +24,7 @@ pub type CurrencyId = u32;
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CAROL: AccountId = 3;
+pub const TREASURY: AccountId = 4;
 pub const DOT_POOL: PoolId = 1;
 pub const NATIVE_COIN: CurrencyId = 0;
 pub const STABLE_COIN: CurrencyId = 1;
@@ -77,12 +78,57 @@ impl RewardHandler<AccountId, CurrencyId> for Handler {
 	}
 }
 
+parameter_types! {
+	pub static MinimumStakingDuration: u64 = 0;
+	pub static PenaltyPercent: Balance = 0;
+	pub static PenaltyToTreasury: bool = false;
+}
+
+pub struct Penalty;
+impl WithdrawPenalty<AccountId, PoolId, CurrencyId, Balance, u64> for Penalty {
+	fn minimum_duration(_pool: &PoolId) -> u64 {
+		MinimumStakingDuration::get()
+	}
+
+	fn penalty(_who: &AccountId, _pool: &PoolId, _currency_id: CurrencyId, reward: Balance) -> Balance {
+		reward * PenaltyPercent::get() / 100
+	}
+
+	fn destination(_pool: &PoolId) -> PenaltyDestination<AccountId> {
+		if PenaltyToTreasury::get() {
+			PenaltyDestination::Account(TREASURY)
+		} else {
+			PenaltyDestination::Stakers
+		}
+	}
+}
+
+parameter_types! {
+	pub static PoolYields: Vec<(PoolId, CurrencyId, Balance)> = vec![];
+}
+
+pub struct MockYieldSource;
+impl YieldSource<PoolId, CurrencyId, Balance> for MockYieldSource {
+	fn take_yield(pool: &PoolId) -> Vec<(CurrencyId, Balance)> {
+		PoolYields::get()
+			.into_iter()
+			.filter(|(yield_pool, _, _)| yield_pool == pool)
+			.map(|(_, currency_id, amount)| (currency_id, amount))
+			.collect()
+	}
+}
+
 impl Config for Runtime {
 	type Share = Share;
 	type Balance = Balance;
 	type PoolId = PoolId;
 	type CurrencyId = CurrencyId;
 	type Handler = Handler;
+	type CheckpointInterval = ConstU64<10>;
+	type MaxCheckpoints = ConstU32<3>;
+	type WithdrawPenalty = Penalty;
+	type YieldSource = MockYieldSource;
+	type MaxYieldSourcePools = ConstU32<2>;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -166,6 +166,15 @@ fn claim_rewards_should_work() {
 				rewards: vec![(NATIVE_COIN, (10_000, 5_000))].into_iter().collect()
 			}
 		);
+		assert_eq!(
+			RewardsModule::reward_per_share(&DOT_POOL, NATIVE_COIN),
+			Some(FixedAccumulator::from_mantissa(25_000_000_000_000_000_000))
+		);
+		assert_eq!(
+			RewardsModule::reward_per_share(&DOT_POOL, STABLE_COIN),
+			Some(FixedAccumulator::default())
+		);
+		assert_eq!(RewardsModule::reward_per_share(&2, NATIVE_COIN), None);
 
 		assert_eq!(
 			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
@@ -593,3 +602,332 @@ fn transfer_share_and_rewards() {
 		);
 	});
 }
+
+#[test]
+fn migrate_pool_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		const NEW_POOL: PoolId = 2;
+		const OTHER_POOL: PoolId = 3;
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 50);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 300));
+		RewardsModule::add_share(&CAROL, &OTHER_POOL, 10);
+
+		assert_noop!(
+			RewardsModule::migrate_pool(&NEW_POOL, &OTHER_POOL),
+			Error::<Runtime>::PoolDoesNotExist
+		);
+		assert_noop!(
+			RewardsModule::migrate_pool(&DOT_POOL, &OTHER_POOL),
+			Error::<Runtime>::PoolAlreadyExists
+		);
+
+		SharesAndWithdrawnRewards::<Runtime>::mutate(DOT_POOL, CAROL, |(share, _)| *share = 1);
+		assert_noop!(
+			RewardsModule::migrate_pool(&DOT_POOL, &NEW_POOL),
+			Error::<Runtime>::InconsistentPoolShares
+		);
+		SharesAndWithdrawnRewards::<Runtime>::remove(DOT_POOL, CAROL);
+
+		assert_ok!(RewardsModule::enable_yield_source(&DOT_POOL));
+		let pool_info = RewardsModule::pool_infos(DOT_POOL);
+		assert_ok!(RewardsModule::migrate_pool(&DOT_POOL, &NEW_POOL));
+		assert!(!PoolInfos::<Runtime>::contains_key(DOT_POOL));
+		assert!(!RewardsModule::yield_source_enabled(DOT_POOL));
+		assert!(RewardsModule::yield_source_enabled(NEW_POOL));
+		assert_eq!(RewardsModule::pool_infos(NEW_POOL), pool_info);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, ALICE),
+			Default::default()
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(NEW_POOL, ALICE),
+			(100, Default::default())
+		);
+
+		// pending rewards are kept
+		RewardsModule::claim_rewards(&ALICE, &NEW_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(NEW_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			200
+		);
+
+		// bulk migration is all or nothing
+		assert_noop!(
+			RewardsModule::migrate_pools(&[(NEW_POOL, DOT_POOL), (OTHER_POOL, DOT_POOL)]),
+			Error::<Runtime>::PoolAlreadyExists
+		);
+		assert_ok!(RewardsModule::migrate_pools(&[
+			(NEW_POOL, DOT_POOL),
+			(OTHER_POOL, NEW_POOL)
+		]));
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(DOT_POOL, BOB),
+			(50, Default::default())
+		);
+		assert_eq!(
+			RewardsModule::shares_and_withdrawn_rewards(NEW_POOL, CAROL),
+			(10, Default::default())
+		);
+	});
+}
+
+#[test]
+fn share_checkpoints_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(5);
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 5), None);
+
+		assert_ok!(RewardsModule::enable_checkpoints(&DOT_POOL));
+		assert_eq!(RewardsModule::checkpoints_enabled_since(DOT_POOL), Some(10));
+		// changes before the first checkpoint are not recorded
+		RewardsModule::add_share(&BOB, &DOT_POOL, 50);
+		assert_eq!(RewardsModule::share_checkpoints(DOT_POOL, BOB), vec![]);
+
+		System::set_block_number(12);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 10), Some(100));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 5), None);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 20), None);
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::remove_share(&BOB, &DOT_POOL, 20);
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		assert_eq!(RewardsModule::share_checkpoints(DOT_POOL, ALICE), vec![(10, 100)]);
+		// the checkpoint holds the shares at its start
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 19), Some(100));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &BOB, 10), Some(50));
+		assert_eq!(RewardsModule::total_shares_at(&DOT_POOL, 10), Some(150));
+
+		System::set_block_number(35);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 20), Some(300));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &BOB, 30), Some(30));
+		assert_eq!(RewardsModule::total_shares_at(&DOT_POOL, 30), Some(330));
+		assert_ok!(RewardsModule::transfer_share_and_rewards(&ALICE, &DOT_POOL, 100, &BOB));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 30), Some(300));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &BOB, 30), Some(30));
+
+		// only the last `MaxCheckpoints` checkpoints are kept
+		System::set_block_number(40);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 10), None);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 20), Some(300));
+		RewardsModule::set_share(&ALICE, &DOT_POOL, 0);
+		assert_eq!(
+			RewardsModule::share_checkpoints(DOT_POOL, ALICE),
+			vec![(30, 300), (40, 200)]
+		);
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 20), Some(300));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 40), Some(200));
+
+		// history moves with the pool
+		const NEW_POOL: PoolId = 2;
+		assert_ok!(RewardsModule::migrate_pool(&DOT_POOL, &NEW_POOL));
+		assert_eq!(RewardsModule::share_at(&NEW_POOL, &ALICE, 40), Some(200));
+		assert_eq!(RewardsModule::share_at(&DOT_POOL, &ALICE, 40), None);
+
+		RewardsModule::disable_checkpoints(&NEW_POOL);
+		assert_eq!(RewardsModule::share_at(&NEW_POOL, &ALICE, 40), None);
+		assert_eq!(RewardsModule::total_share_checkpoints(NEW_POOL), vec![]);
+		assert!(RewardsModule::clearing_checkpoints(NEW_POOL).is_some());
+		assert_noop!(
+			RewardsModule::enable_checkpoints(&NEW_POOL),
+			Error::<Runtime>::CheckpointsBeingCleared
+		);
+		assert_ok!(RewardsModule::migrate_pool(&NEW_POOL, &DOT_POOL));
+		assert_noop!(
+			RewardsModule::migrate_pool(&DOT_POOL, &NEW_POOL),
+			Error::<Runtime>::CheckpointsBeingCleared
+		);
+
+		// the share checkpoints of the accounts are removed in `on_idle`, one
+		// account at a time here
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let one_account = db_weight.reads_writes(2, 2);
+		assert_eq!(RewardsModule::on_idle(40, one_account), one_account);
+		assert_eq!(ShareCheckpoints::<Runtime>::iter_prefix(NEW_POOL).count(), 1);
+		assert_eq!(RewardsModule::on_idle(40, Weight::MAX), db_weight.reads_writes(2, 2));
+		assert_eq!(ShareCheckpoints::<Runtime>::iter_prefix(NEW_POOL).count(), 0);
+		assert_eq!(RewardsModule::clearing_checkpoints(NEW_POOL), None);
+		assert_eq!(RewardsModule::on_idle(40, Weight::MAX), db_weight.reads(1));
+
+		assert_ok!(RewardsModule::enable_checkpoints(&NEW_POOL));
+	});
+}
+
+#[test]
+fn early_withdrawal_should_be_penalized() {
+	ExtBuilder::default().build().execute_with(|| {
+		MinimumStakingDuration::set(10);
+		PenaltyPercent::set(50);
+		let payout = |who| RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, who, NATIVE_COIN)).unwrap_or(&0));
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_eq!(RewardsModule::share_added_at(DOT_POOL, ALICE), Some(0));
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 100));
+
+		// half of the pending reward of ALICE goes to BOB
+		System::set_block_number(9);
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 100);
+		assert_eq!(payout(ALICE), 25);
+		assert_eq!(RewardsModule::share_added_at(DOT_POOL, ALICE), None);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (75, 0))].into_iter().collect(),
+			}
+		);
+
+		// no penalty once the minimum duration has passed
+		System::set_block_number(10);
+		RewardsModule::remove_share(&BOB, &DOT_POOL, 100);
+		assert_eq!(payout(BOB), 75);
+	});
+}
+
+#[test]
+fn early_withdrawal_penalty_can_go_to_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		MinimumStakingDuration::set(10);
+		PenaltyPercent::set(50);
+		PenaltyToTreasury::set(true);
+		let payout = |who| RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, who, NATIVE_COIN)).unwrap_or(&0));
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 100));
+
+		// moving shares keeps the later staking start
+		System::set_block_number(5);
+		RewardsModule::add_share(&CAROL, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::transfer_share_and_rewards(&CAROL, &DOT_POOL, 50, &BOB));
+		assert_eq!(RewardsModule::share_added_at(DOT_POOL, BOB), Some(5));
+
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 50);
+		assert_eq!(payout(ALICE), 25);
+		assert_eq!(payout(TREASURY), 25);
+
+		// claiming once the minimum duration has passed is not penalized
+		System::set_block_number(15);
+		RewardsModule::claim_rewards(&BOB, &DOT_POOL);
+		assert_eq!(payout(BOB), 50);
+	});
+}
+
+#[test]
+fn early_claim_should_be_penalized() {
+	ExtBuilder::default().build().execute_with(|| {
+		MinimumStakingDuration::set(10);
+		PenaltyPercent::set(50);
+		let payout = |who| RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, who, NATIVE_COIN)).unwrap_or(&0));
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 100));
+
+		// claiming before removing the shares does not avoid the penalty, and
+		// ALICE earns no part of it
+		System::set_block_number(9);
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(payout(ALICE), 25);
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 100);
+		assert_eq!(payout(ALICE), 25);
+
+		System::set_block_number(10);
+		RewardsModule::remove_share(&BOB, &DOT_POOL, 100);
+		assert_eq!(payout(BOB), 62);
+	});
+}
+
+#[test]
+fn paused_share_changes_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::add_share(&BOB, &DOT_POOL, 100);
+
+		RewardsModule::set_share_additions_paused(&DOT_POOL, true);
+		assert!(RewardsModule::share_additions_paused(&DOT_POOL));
+		assert_noop!(
+			RewardsModule::try_add_share(&ALICE, &DOT_POOL, 100),
+			Error::<Runtime>::ShareAdditionsPaused
+		);
+		assert_noop!(
+			RewardsModule::try_transfer_share_and_rewards(&ALICE, &DOT_POOL, 50, &CAROL),
+			Error::<Runtime>::ShareAdditionsPaused
+		);
+		assert_ok!(RewardsModule::try_remove_share(&BOB, &DOT_POOL, 50));
+
+		RewardsModule::set_share_removals_paused(&DOT_POOL, true);
+		assert_noop!(
+			RewardsModule::try_remove_share(&ALICE, &DOT_POOL, 50),
+			Error::<Runtime>::ShareRemovalsPaused
+		);
+		assert_noop!(
+			RewardsModule::try_set_share(&ALICE, &DOT_POOL, 50),
+			Error::<Runtime>::ShareRemovalsPaused
+		);
+		// share changes of the unchecked functions are not paused
+		RewardsModule::add_share(&CAROL, &DOT_POOL, 100);
+		RewardsModule::remove_share(&CAROL, &DOT_POOL, 100);
+
+		// rewards keep accumulating and can be claimed while paused
+		assert_ok!(RewardsModule::accumulate_reward(&DOT_POOL, NATIVE_COIN, 150));
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| *v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).unwrap_or(&0)),
+			100
+		);
+
+		RewardsModule::set_share_additions_paused(&DOT_POOL, false);
+		RewardsModule::set_share_removals_paused(&DOT_POOL, false);
+		assert!(!PoolPauses::<Runtime>::contains_key(DOT_POOL));
+		RewardsModule::remove_share(&ALICE, &DOT_POOL, 50);
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 50);
+	});
+}
+
+#[test]
+fn yield_source_accumulates_rewards_of_enabled_pools() {
+	ExtBuilder::default().build().execute_with(|| {
+		PoolYields::set(vec![(DOT_POOL, NATIVE_COIN, 100), (DOT_POOL, STABLE_COIN, 0)]);
+		assert_ok!(RewardsModule::enable_yield_source(&DOT_POOL));
+		assert_ok!(RewardsModule::enable_yield_source(&DOT_POOL));
+		assert!(RewardsModule::yield_source_enabled(DOT_POOL));
+		assert_ok!(RewardsModule::enable_yield_source(&2));
+		assert_noop!(
+			RewardsModule::enable_yield_source(&3),
+			Error::<Runtime>::TooManyYieldSourcePools
+		);
+		RewardsModule::disable_yield_source(&2);
+
+		// skipped while the pool has no shares
+		RewardsModule::on_initialize(1);
+		assert_eq!(RewardsModule::pool_infos(DOT_POOL), Default::default());
+
+		RewardsModule::add_share(&ALICE, &DOT_POOL, 100);
+		RewardsModule::on_initialize(2);
+		RewardsModule::on_initialize(3);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL),
+			PoolInfo {
+				total_shares: 100,
+				rewards: vec![(NATIVE_COIN, (200, 0))].into_iter().collect(),
+			}
+		);
+
+		RewardsModule::disable_yield_source(&DOT_POOL);
+		assert!(!RewardsModule::yield_source_enabled(DOT_POOL));
+		RewardsModule::on_initialize(4);
+		assert_eq!(
+			RewardsModule::pool_infos(DOT_POOL).rewards,
+			vec![(NATIVE_COIN, (200, 0))].into_iter().collect()
+		);
+
+		RewardsModule::claim_rewards(&ALICE, &DOT_POOL);
+		assert_eq!(
+			RECEIVED_PAYOUT.with(|v| v.borrow().get(&(DOT_POOL, ALICE, NATIVE_COIN)).copied()),
+			Some(200)
+		);
+	});
+}
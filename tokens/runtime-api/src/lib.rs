@@ -15,4 +15,16 @@ sp_api::decl_runtime_apis! {
 	{
 		fn query_existential_deposit(currency_id: CurrencyId) -> Balance;
 	}
+
+	pub trait TokensAccountApi<AccountId, CurrencyId, Balance> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// The maximum amount of `currency_id` that `account` can transfer,
+		/// taking frozen and reserved balances into account. If `keep_alive`
+		/// is true, the amount leaves enough balance to keep the account
+		/// alive.
+		fn reducible_balance(account: AccountId, currency_id: CurrencyId, keep_alive: bool) -> Balance;
+	}
 }
@@ -1,11 +1,14 @@
-use frame_support::dispatch::DispatchError;
+use crate::{Config, Pallet};
+use frame_support::dispatch::{DispatchClass, DispatchError};
 use frame_support::traits::tokens::{Fortitude, Precision, Preservation, Provenance};
 use frame_support::traits::{
 	fungible, fungibles,
 	tokens::{Balance as BalanceT, DepositConsequence, WithdrawConsequence},
 	Contains, Get,
 };
+use pallet_session::SessionManager;
 use sp_arithmetic::{traits::Bounded, ArithmeticError};
+use sp_std::prelude::Vec;
 
 pub struct Combiner<AccountId, TestKey, A, B>(sp_std::marker::PhantomData<(AccountId, TestKey, A, B)>);
 
@@ -344,3 +347,31 @@ where
 		T::set_total_issuance(GetCurrencyId::get(), amount)
 	}
 }
+
+/// Records a total issuance snapshot with `snapshot_total_issuance` whenever a
+/// session starts, and forwards to `Inner`. Configured as the
+/// `SessionManager` of `pallet_session` in place of `Inner`.
+pub struct SnapshotIssuanceOnSessionStart<T, Inner>(sp_std::marker::PhantomData<(T, Inner)>);
+impl<T, Inner, ValidatorId> SessionManager<ValidatorId> for SnapshotIssuanceOnSessionStart<T, Inner>
+where
+	T: Config,
+	Inner: SessionManager<ValidatorId>,
+{
+	fn new_session(new_index: u32) -> Option<Vec<ValidatorId>> {
+		Inner::new_session(new_index)
+	}
+
+	fn new_session_genesis(new_index: u32) -> Option<Vec<ValidatorId>> {
+		Inner::new_session_genesis(new_index)
+	}
+
+	fn end_session(end_index: u32) {
+		Inner::end_session(end_index)
+	}
+
+	fn start_session(start_index: u32) {
+		let weight = Pallet::<T>::snapshot_total_issuance(start_index);
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Mandatory);
+		Inner::start_session(start_index)
+	}
+}
@@ -21,6 +21,8 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `fungibles::InspectFreeze` and `fungibles::MutateFreeze` - Named freezes,
+//!   bounded by `MaxFreezes` and kept separately from locks.
 //!
 //! ## Interface
 //!
@@ -28,6 +30,17 @@
 //!
 //! - `transfer` - Transfer some balance to another account.
 //! - `transfer_all` - Transfer all balance to another account.
+//! - `transfer_everything` - Transfer all balance of every currency to another
+//!   account.
+//!
+//! ### Issuance Snapshots
+//!
+//! `snapshot_total_issuance` records the total issuance of up to
+//! `MaxSnapshotCurrencies` currencies, to be called by the runtime at session
+//! boundaries, e.g. by configuring `SnapshotIssuanceOnSessionStart` as the
+//! `SessionManager` of `pallet_session`. The last `MaxIssuanceSnapshots`
+//! snapshots are kept per currency and can be queried with
+//! `total_issuance_at`, e.g. to compute supply growth on-chain.
 //!
 //! ### Genesis Config
 //!
@@ -69,13 +82,15 @@ use sp_std::{cmp, convert::Infallible, marker, prelude::*, vec::Vec};
 
 use orml_traits::{
 	arithmetic::{self, Signed},
-	currency::{MutationHooks, OnDeposit, OnDust, OnSlash, OnTransfer, TransferAll},
+	currency::{MutationHooks, OnBalanceMovement, OnDeposit, OnDust, OnSlash, OnTransfer, TransferAll},
 	BalanceStatus, GetByKey, Happened, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
 	MultiReservableCurrency, NamedMultiReservableCurrency,
 };
+use orml_utilities::{PagerError, StorageDoubleMapPager};
 
 mod imbalances;
 mod impls;
+mod migrations;
 mod mock;
 mod tests;
 mod tests_currency_adapter;
@@ -86,6 +101,7 @@ mod tests_multicurrency;
 mod weights;
 
 pub use impls::*;
+pub use migrations::Migration;
 pub use weights::WeightInfo;
 
 pub struct TransferDust<T, GetAccountId>(marker::PhantomData<(T, GetAccountId)>);
@@ -137,6 +153,52 @@ pub struct ReserveData<ReserveIdentifier, Balance> {
 	pub amount: Balance,
 }
 
+/// A named freeze on a balance, set through `fungibles::MutateFreeze`. Like
+/// locks, freezes overlap with each other and with locks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct FreezeData<FreezeIdentifier, Balance> {
+	/// The identifier for the freeze.
+	pub id: FreezeIdentifier,
+	/// The amount which the free balance may not drop below when this freeze
+	/// is in effect.
+	pub amount: Balance,
+}
+
+/// How dust of accounts below the existential deposit is handled.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum DustHandling {
+	/// `OnDust` is called as soon as the dust is detected.
+	#[default]
+	Inline,
+	/// The account is queued and `OnDust` is called in `on_idle`, at most
+	/// `MaxDustPerBlock` accounts per block. Falls back to `Inline` if the
+	/// queue is full.
+	Deferred,
+}
+
+/// Who can get a new token account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum AccountCreation {
+	/// Any account receiving at least the existential deposit.
+	#[default]
+	Permissionless,
+	/// Only accounts which already have a provider reference in
+	/// `frame_system` not held by their token accounts, e.g. from holding
+	/// the native existential deposit, or are in `AccountCreationWhitelist`.
+	/// This prevents bloating storage by sending junk currencies to many fresh
+	/// accounts.
+	RequireProvider,
+}
+
+/// The counters of account entries `Migration` is populating in `on_idle`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PendingCounts {
+	/// Count the `ProviderReferences` of each account.
+	pub provider_references: bool,
+	/// Count the `AccountsCount` of each currency.
+	pub accounts: bool,
+}
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 pub struct AccountData<Balance> {
@@ -223,6 +285,10 @@ pub mod module {
 		/// For example: OnDust, OnNewTokenAccount
 		type CurrencyHooks: MutationHooks<Self::AccountId, Self::CurrencyId, Self::Balance>;
 
+		/// Hook to run after balance was moved from an account to another,
+		/// into an account or out of an account.
+		type OnBalanceMovement: OnBalanceMovement<Self::AccountId, Self::CurrencyId, Self::Balance>;
+
 		#[pallet::constant]
 		type MaxLocks: Get<u32>;
 
@@ -233,9 +299,55 @@ pub mod module {
 		/// The id type for named reserves.
 		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
 
+		/// The id type for freezes.
+		type FreezeIdentifier: Parameter + Member + MaxEncodedLen + Copy;
+
+		/// The maximum number of freezes that can exist on an account.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
 		// The whitelist of accounts that will not be reaped even if its total
 		// is zero or below ED.
 		type DustRemovalWhitelist: Contains<Self::AccountId>;
+
+		/// Whether dust is handled inline or deferred to `on_idle`.
+		#[pallet::constant]
+		type DustHandlingMode: Get<DustHandling>;
+
+		/// The maximum number of accounts waiting for their dust to be
+		/// handled.
+		#[pallet::constant]
+		type MaxDustQueueLen: Get<u32>;
+
+		/// The maximum number of queued dust accounts handled per block.
+		#[pallet::constant]
+		type MaxDustPerBlock: Get<u32>;
+
+		/// The maximum number of account entries checked per block in
+		/// `on_idle` for being empty and reaped. Zero disables the sweep.
+		#[pallet::constant]
+		type MaxReapPerBlock: Get<u32>;
+
+		/// Who can get a new token account.
+		#[pallet::constant]
+		type AccountCreation: Get<AccountCreation>;
+
+		/// The accounts which can get a new token account without a provider
+		/// reference when `AccountCreation` requires one.
+		type AccountCreationWhitelist: Contains<Self::AccountId>;
+
+		/// The origin which can freeze and thaw accounts.
+		type AssetAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of total issuance snapshots kept per currency,
+		/// older snapshots are dropped. Zero disables snapshots.
+		#[pallet::constant]
+		type MaxIssuanceSnapshots: Get<u32>;
+
+		/// The maximum number of currencies recorded in one total issuance
+		/// snapshot, further currencies of `TotalIssuance` are skipped.
+		#[pallet::constant]
+		type MaxSnapshotCurrencies: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -256,6 +368,21 @@ pub mod module {
 		DeadAccount,
 		// Number of named reserves exceed `T::MaxReserves`
 		TooManyReserves,
+		/// The account is not allowed to get a new token account
+		CannotCreateAccount,
+		/// Number of freezes exceed `T::MaxFreezes`
+		TooManyFreezes,
+		/// The account entry still holds some balance, locks, reserves or
+		/// freezes
+		AccountNotDead,
+		/// The account is frozen for the currency and cannot send funds
+		AccountFrozen,
+		/// The account is not frozen for the currency
+		AccountNotFrozen,
+		/// The account is already frozen for the currency
+		AlreadyFrozen,
+		/// The account holds more currencies than the given maximum
+		TooManyCurrencies,
 	}
 
 	#[pallet::event]
@@ -358,6 +485,18 @@ pub mod module {
 			who: T::AccountId,
 			amount: T::Balance,
 		},
+		/// Some balance was frozen.
+		Frozen {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Some frozen balance was thawed.
+		Thawed {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
 		Issued {
 			currency_id: T::CurrencyId,
 			amount: T::Balance,
@@ -366,6 +505,21 @@ pub mod module {
 			currency_id: T::CurrencyId,
 			amount: T::Balance,
 		},
+		/// An empty account entry was removed.
+		DeadAccountReaped {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// An account was frozen for a currency, blocking outgoing transfers.
+		AccountFrozen {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// A frozen account was thawed.
+		AccountThawed {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
 	}
 
 	/// The total issuance of a token type.
@@ -418,14 +572,84 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// The number of token accounts of an account, each holding a provider
+	/// reference on it.
+	#[pallet::storage]
+	#[pallet::getter(fn provider_references)]
+	pub type ProviderReferences<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The number of accounts holding an entry of a token type.
+	#[pallet::storage]
+	#[pallet::getter(fn accounts_count)]
+	pub type AccountsCount<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, u32, ValueQuery>;
+
+	/// Freezes on some account balances.
+	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
+	pub type Freezes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<FreezeData<T::FreezeIdentifier, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
+	/// Accounts frozen for a currency, which cannot send funds of it.
+	///
+	/// double_map AccountId, CurrencyId => ()
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_accounts)]
+	pub type FrozenAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// Accounts whose dust is waiting to be handled in `on_idle`.
+	#[pallet::storage]
+	#[pallet::getter(fn dust_queue)]
+	pub type DustQueue<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, T::CurrencyId), T::MaxDustQueueLen>, ValueQuery>;
+
+	/// The raw storage key of `Accounts` that the `on_idle` sweep of empty
+	/// account entries continues from.
+	#[pallet::storage]
+	pub type ReapCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// The counters `Migration` is populating in `on_idle`. The counters of
+	/// account entries not counted yet are left to the migration.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_counts)]
+	pub type PendingCountMigration<T: Config> = StorageValue<_, PendingCounts, OptionQuery>;
+
+	/// The raw storage key of the last entry of `Accounts` counted by
+	/// `Migration`.
+	#[pallet::storage]
+	pub type CountMigrationCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// The total issuance of a token type at the sessions it was recorded
+	/// at, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn issuance_snapshots)]
+	pub type IssuanceSnapshots<T: Config> =
+		StorageMap<_, Twox64Concat, T::CurrencyId, BoundedVec<(u32, T::Balance), T::MaxIssuanceSnapshots>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub balances: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
+		/// Amounts moved from the free to the reserved balance of endowed
+		/// accounts.
+		pub reserves: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
+		/// Locks set on the balance of endowed accounts, after `reserves`.
+		pub locks: Vec<(T::AccountId, T::CurrencyId, LockIdentifier, T::Balance)>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			GenesisConfig { balances: vec![] }
+			GenesisConfig {
+				balances: vec![],
+				reserves: vec![],
+				locks: vec![],
+			}
 		}
 	}
 
@@ -459,14 +683,48 @@ pub mod module {
 							.expect("total issuance cannot overflow when building genesis")
 					});
 				});
+
+			self.reserves.iter().for_each(|(account_id, currency_id, amount)| {
+				assert!(
+					unique_endowed_accounts.contains(&(account_id, currency_id)),
+					"reserves can only be set on endowed accounts in genesis."
+				);
+				<Pallet<T> as MultiReservableCurrency<_>>::reserve(*currency_id, account_id, *amount)
+					.expect("reserved amount cannot exceed the free balance in genesis");
+			});
+
+			self.locks
+				.iter()
+				.for_each(|(account_id, currency_id, lock_id, amount)| {
+					assert!(
+						unique_endowed_accounts.contains(&(account_id, currency_id)),
+						"locks can only be set on endowed accounts in genesis."
+					);
+					<Pallet<T> as MultiLockableCurrency<_>>::set_lock(*lock_id, *currency_id, account_id, *amount)
+						.expect("locks cannot exceed MaxLocks in genesis");
+				});
 		}
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut used_weight = Self::count_account_entries(remaining_weight);
+			used_weight.saturating_accrue(Self::handle_dust_queue(remaining_weight.saturating_sub(used_weight)));
+			used_weight.saturating_add(Self::sweep_dead_accounts(remaining_weight.saturating_sub(used_weight)))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -629,6 +887,12 @@ pub mod module {
 							.ok_or(ArithmeticError::Overflow)?;
 						Ok(())
 					})?;
+					Self::on_balance_movement(
+						currency_id,
+						None,
+						Some(&who),
+						new_total.defensive_saturating_sub(old_total),
+					);
 				} else if new_total < old_total {
 					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
 						*t = t
@@ -636,6 +900,12 @@ pub mod module {
 							.ok_or(ArithmeticError::Underflow)?;
 						Ok(())
 					})?;
+					Self::on_balance_movement(
+						currency_id,
+						Some(&who),
+						None,
+						old_total.defensive_saturating_sub(new_total),
+					);
 				}
 
 				Self::deposit_event(Event::BalanceSet {
@@ -649,12 +919,404 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Remove account entries of `currency_id` which hold no balance,
+		/// locks, reserves or freezes.
+		///
+		/// Fails if any of the `accounts` entries is not empty, accounts
+		/// without an entry are skipped.
+		///
+		/// The dispatch origin for this call must be `Signed`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::reap_dead_accounts(accounts.len() as u32))]
+		pub fn reap_dead_accounts(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			accounts: Vec<T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			for who in accounts {
+				// nothing to reap if there is no entry
+				if let Ok(account) = Accounts::<T>::try_get(&who, currency_id) {
+					ensure!(
+						Self::is_dead_account(&who, currency_id, &account),
+						Error::<T>::AccountNotDead
+					);
+					Self::do_reap_dead_account(&who, currency_id);
+				}
+			}
+			Ok(())
+		}
+
+		/// Freeze `who` for `currency_id`, blocking any transfer, withdrawal
+		/// or reserve of its funds until thawed.
+		///
+		/// The dispatch origin for this call must be `AssetAdminOrigin`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::AssetAdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				!FrozenAccounts::<T>::contains_key(&who, currency_id),
+				Error::<T>::AlreadyFrozen
+			);
+
+			FrozenAccounts::<T>::insert(&who, currency_id, ());
+			Self::deposit_event(Event::AccountFrozen { currency_id, who });
+			Ok(())
+		}
+
+		/// Thaw `who` for `currency_id`.
+		///
+		/// The dispatch origin for this call must be `AssetAdminOrigin`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::AssetAdminOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				FrozenAccounts::<T>::take(&who, currency_id).is_some(),
+				Error::<T>::AccountNotFrozen
+			);
+
+			Self::deposit_event(Event::AccountThawed { currency_id, who });
+			Ok(())
+		}
+
+		/// Transfer the transferable balance of every currency held by the
+		/// origin to the given account, e.g. to migrate an account.
+		///
+		/// Like `transfer_all`, only _transferable_ balances are transferred
+		/// for each currency. Currencies without transferable balance are
+		/// skipped.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `dest`: The recipient of the transfer.
+		/// - `keep_alive`: Keep at least the existential deposit of every
+		///   currency in the sender account (true), or allow the sender
+		///   account to be killed (false).
+		/// - `skip_frozen`: Skip currencies with a locked or frozen amount
+		///   instead of transferring their unfrozen balance.
+		/// - `max_currencies`: The maximum number of currencies the origin
+		///   holds, used to weigh the call.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::transfer_everything(*max_currencies))]
+		pub fn transfer_everything(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			keep_alive: bool,
+			skip_frozen: bool,
+			max_currencies: u32,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let preservation = if keep_alive {
+				Preservation::Protect
+			} else {
+				Preservation::Expendable
+			};
+
+			// read at most one account more than allowed, to fail early
+			let accounts = Accounts::<T>::iter_prefix(&from)
+				.take((max_currencies as usize).saturating_add(1))
+				.collect::<Vec<_>>();
+			ensure!(accounts.len() <= max_currencies as usize, Error::<T>::TooManyCurrencies);
+
+			for (currency_id, account) in accounts.iter() {
+				if skip_frozen && !account.frozen.is_zero() {
+					continue;
+				}
+				let reducible_balance = <Self as fungibles::Inspect<T::AccountId>>::reducible_balance(
+					*currency_id,
+					&from,
+					preservation,
+					Fortitude::Polite,
+				);
+				if reducible_balance.is_zero() {
+					continue;
+				}
+				<Self as fungibles::Mutate<_>>::transfer(*currency_id, &from, &to, reducible_balance, preservation)?;
+			}
+
+			Ok(Some(T::WeightInfo::transfer_everything(accounts.len() as u32)).into())
+		}
+	}
+}
+
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config> Pallet<T> {
+	/// Number of accounts read from storage at a time when checking the
+	/// invariants.
+	const TRY_STATE_PAGE_SIZE: usize = 1_000;
+
+	/// Check that the balances of all accounts add up to the total issuance of
+	/// their currency, that the frozen balance of every account matches its
+	/// largest lock or freeze and that named reserves do not exceed the
+	/// reserved balance.
+	pub(crate) fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		let mut issuance = sp_std::collections::btree_map::BTreeMap::<T::CurrencyId, T::Balance>::new();
+		let mut start_key: Option<Vec<u8>> = None;
+		loop {
+			let mut accounts = match start_key.take() {
+				Some(key) => Accounts::<T>::iter_from(key),
+				None => Accounts::<T>::iter(),
+			};
+			let mut count = 0;
+			for (who, currency_id, account) in accounts.by_ref().take(Self::TRY_STATE_PAGE_SIZE) {
+				count += 1;
+				let total = issuance.entry(currency_id).or_default();
+				*total = total.checked_add(&account.total()).ok_or("total issuance overflow")?;
+
+				let max_frozen = Self::max_frozen(&Self::locks(&who, currency_id), &Self::freezes(&who, currency_id));
+				ensure!(
+					account.frozen == max_frozen,
+					"frozen balance does not match the largest lock or freeze"
+				);
+
+				let named_reserved = Self::reserves(&who, currency_id)
+					.iter()
+					.fold(Zero::zero(), |sum: T::Balance, reserve| {
+						sum.saturating_add(reserve.amount)
+					});
+				ensure!(
+					named_reserved <= account.reserved,
+					"named reserves exceed the reserved balance"
+				);
+			}
+			if count < Self::TRY_STATE_PAGE_SIZE {
+				break;
+			}
+			start_key = Some(accounts.last_raw_key().to_vec());
+		}
+
+		for (who, currency_id, _) in Locks::<T>::iter() {
+			ensure!(
+				Accounts::<T>::contains_key(&who, currency_id),
+				"locks of a non-existent account"
+			);
+		}
+
+		for (who, currency_id, _) in Freezes::<T>::iter() {
+			ensure!(
+				Accounts::<T>::contains_key(&who, currency_id),
+				"freezes of a non-existent account"
+			);
+		}
+
+		for (currency_id, total_issuance) in TotalIssuance::<T>::iter() {
+			ensure!(
+				issuance.remove(&currency_id).unwrap_or_default() == total_issuance,
+				"balances do not add up to the total issuance"
+			);
+		}
+		ensure!(
+			issuance.values().all(|total| total.is_zero()),
+			"balances of a currency without total issuance"
+		);
+
+		Ok(())
 	}
 }
 
+/// Pages through the account entries counted by `Migration`.
+type CountMigrationPager<T> = StorageDoubleMapPager<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::CurrencyId,
+	AccountData<<T as Config>::Balance>,
+	Accounts<T>,
+	CountMigrationCursor<T>,
+	BoundedVec<u8, ConstU32<256>>,
+>;
+
+/// Pages through the account entries swept by `on_idle`.
+type ReapPager<T> = StorageDoubleMapPager<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::CurrencyId,
+	AccountData<<T as Config>::Balance>,
+	Accounts<T>,
+	ReapCursor<T>,
+	BoundedVec<u8, ConstU32<256>>,
+>;
+
 impl<T: Config> Pallet<T> {
+	/// Run the `OnBalanceMovement` hook if `amount` is not zero.
+	fn on_balance_movement(
+		currency_id: T::CurrencyId,
+		from: Option<&T::AccountId>,
+		to: Option<&T::AccountId>,
+		amount: T::Balance,
+	) {
+		if !amount.is_zero() {
+			T::OnBalanceMovement::on_balance_movement(currency_id, from, to, amount);
+		}
+	}
+
+	/// Record the total issuance of up to `MaxSnapshotCurrencies` currencies
+	/// at `session`, dropping the oldest snapshot of a currency once
+	/// `MaxIssuanceSnapshots` are kept. Returns the weight consumed.
+	///
+	/// Snapshots must be recorded with increasing session indexes.
+	pub fn snapshot_total_issuance(session: u32) -> Weight {
+		if T::MaxIssuanceSnapshots::get().is_zero() {
+			return Weight::zero();
+		}
+		let mut recorded = 0;
+		let max_currencies = T::MaxSnapshotCurrencies::get() as usize;
+		for (currency_id, total_issuance) in TotalIssuance::<T>::iter().take(max_currencies) {
+			recorded += 1;
+			IssuanceSnapshots::<T>::mutate(currency_id, |snapshots| {
+				if snapshots.last().map_or(false, |(last, _)| *last >= session) {
+					return;
+				}
+				if snapshots.is_full() {
+					snapshots.remove(0);
+				}
+				let _ = snapshots.try_push((session, total_issuance));
+			});
+		}
+		T::WeightInfo::snapshot_total_issuance(recorded)
+	}
+
+	/// The total issuance of `currency_id` recorded at `session`, `None` if
+	/// no snapshot was recorded at it or it has been dropped.
+	pub fn total_issuance_at(currency_id: T::CurrencyId, session: u32) -> Option<T::Balance> {
+		let snapshots = Self::issuance_snapshots(currency_id);
+		snapshots
+			.binary_search_by_key(&session, |(recorded_at, _)| *recorded_at)
+			.ok()
+			.map(|index| snapshots[index].1)
+	}
+
+	/// Whether a new token account can be created for `who`.
+	///
+	/// The provider references held by the token accounts of `who` don't
+	/// count, so holding a token doesn't allow receiving other tokens.
+	pub(crate) fn can_create_account(who: &T::AccountId) -> bool {
+		match T::AccountCreation::get() {
+			AccountCreation::Permissionless => true,
+			AccountCreation::RequireProvider => {
+				frame_system::Pallet::<T>::providers(who) > Self::provider_references(who)
+					|| T::AccountCreationWhitelist::contains(who)
+			}
+		}
+	}
+
+	/// Ensure a token account of `currency_id` exists for `who` or can be
+	/// created.
+	pub(crate) fn ensure_can_create_account(who: &T::AccountId, currency_id: T::CurrencyId) -> DispatchResult {
+		ensure!(
+			Accounts::<T>::contains_key(who, currency_id) || Self::can_create_account(who),
+			Error::<T>::CannotCreateAccount
+		);
+		Ok(())
+	}
+
+	/// The counters of the account entry of `who` and `currency_id` left to
+	/// `Migration`, as it did not count the entry yet.
+	fn counts_left_to_migration(who: &T::AccountId, currency_id: T::CurrencyId) -> PendingCounts {
+		let Some(pending) = PendingCountMigration::<T>::get() else {
+			return PendingCounts::default();
+		};
+		match CountMigrationCursor::<T>::get() {
+			Some(cursor) if Accounts::<T>::hashed_key_for(who, currency_id) <= cursor.into_inner() => {
+				PendingCounts::default()
+			}
+			_ => pending,
+		}
+	}
+
+	/// Count a new account entry of `who` and `currency_id`.
+	fn inc_account_counts(who: &T::AccountId, currency_id: T::CurrencyId) {
+		let left_to_migration = Self::counts_left_to_migration(who, currency_id);
+		if !left_to_migration.provider_references {
+			ProviderReferences::<T>::mutate(who, |count| *count = count.saturating_add(1));
+		}
+		if !left_to_migration.accounts {
+			AccountsCount::<T>::mutate(currency_id, |count| *count = count.saturating_add(1));
+		}
+	}
+
+	/// Uncount a removed account entry of `who` and `currency_id`.
+	fn dec_account_counts(who: &T::AccountId, currency_id: T::CurrencyId) {
+		let left_to_migration = Self::counts_left_to_migration(who, currency_id);
+		if !left_to_migration.provider_references {
+			ProviderReferences::<T>::mutate_exists(who, |maybe_count| {
+				*maybe_count = maybe_count
+					.and_then(|count| count.checked_sub(1))
+					.filter(|count| *count > 0);
+			});
+		}
+		if !left_to_migration.accounts {
+			AccountsCount::<T>::mutate(currency_id, |count| *count = count.saturating_sub(1));
+		}
+	}
+
+	/// Count a page of account entries while `Migration` is in progress.
+	fn count_account_entries(remaining_weight: Weight) -> Weight {
+		// read the pending counters and the cursor, and update them
+		let base_weight = T::DbWeight::get().reads_writes(2, 2);
+		let per_account_weight = T::WeightInfo::count_accounts(1);
+		if remaining_weight.any_lt(base_weight) {
+			return Weight::zero();
+		}
+		let Some(pending) = PendingCountMigration::<T>::get() else {
+			return T::DbWeight::get().reads(1);
+		};
+
+		let available = remaining_weight.saturating_sub(base_weight);
+		let mut limit = u32::MAX as u64;
+		if per_account_weight.ref_time() > 0 {
+			limit = limit.min(available.ref_time() / per_account_weight.ref_time());
+		}
+		if per_account_weight.proof_size() > 0 {
+			limit = limit.min(available.proof_size() / per_account_weight.proof_size());
+		}
+
+		let counted = match CountMigrationPager::<T>::process(limit as u32, |who, currency_id, _| {
+			if pending.provider_references {
+				ProviderReferences::<T>::mutate(who, |count| *count = count.saturating_add(1));
+			}
+			if pending.accounts {
+				AccountsCount::<T>::mutate(currency_id, |count| *count = count.saturating_add(1));
+			}
+		}) {
+			Ok(outcome) => {
+				if outcome.finished {
+					PendingCountMigration::<T>::kill();
+				}
+				outcome.processed
+			}
+			// starting over would count entries twice
+			Err(PagerError::CursorOverflow { processed }) => {
+				log::error!("The raw key of the last counted account entry does not fit in CountMigrationCursor, aborting the migration.");
+				PendingCountMigration::<T>::kill();
+				processed
+			}
+			Err(PagerError::InvalidCursor) => {
+				log::error!("CountMigrationCursor can not be decoded, aborting the migration.");
+				PendingCountMigration::<T>::kill();
+				CountMigrationPager::<T>::reset();
+				0
+			}
+		};
+
+		base_weight.saturating_add(per_account_weight.saturating_mul(counted.into()))
+	}
+
 	pub(crate) fn deposit_consequence(
-		_who: &T::AccountId,
+		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		amount: T::Balance,
 		account: &AccountData<T::Balance>,
@@ -676,8 +1338,11 @@ impl<T: Config> Pallet<T> {
 			return DepositConsequence::BelowMinimum;
 		}
 
-		// NOTE: We assume that we are a provider, so don't need to do any checks in the
-		// case of account creation.
+		// NOTE: We assume that we are a provider, so only need to check the creation
+		// policy in the case of account creation.
+		if Self::ensure_can_create_account(who, currency_id).is_err() {
+			return DepositConsequence::CannotCreate;
+		}
 
 		DepositConsequence::Success
 	}
@@ -692,6 +1357,10 @@ impl<T: Config> Pallet<T> {
 			return WithdrawConsequence::Success;
 		}
 
+		if Self::is_account_frozen(who, currency_id) {
+			return WithdrawConsequence::Frozen;
+		}
+
 		if TotalIssuance::<T>::get(currency_id).checked_sub(&amount).is_none() {
 			return WithdrawConsequence::Underflow;
 		}
@@ -730,6 +1399,11 @@ impl<T: Config> Pallet<T> {
 		success
 	}
 
+	/// Whether `who` is frozen for `currency_id` and cannot send funds of it.
+	pub fn is_account_frozen(who: &T::AccountId, currency_id: T::CurrencyId) -> bool {
+		FrozenAccounts::<T>::contains_key(who, currency_id)
+	}
+
 	// Ensure that an account can withdraw from their free balance given any
 	// existing withdrawal restrictions like locks and vesting balance.
 	// Is a no-op if amount to be withdrawn is zero.
@@ -741,6 +1415,7 @@ impl<T: Config> Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
+		ensure!(!Self::is_account_frozen(who, currency_id), Error::<T>::AccountFrozen);
 
 		let new_balance = Self::free_balance(currency_id, who)
 			.checked_sub(&amount)
@@ -788,10 +1463,12 @@ impl<T: Config> Pallet<T> {
 				// Ignore the result, because if it failed then there are remaining consumers,
 				// and the account storage in frame_system shouldn't be reaped.
 				let _ = frame_system::Pallet::<T>::dec_providers(who);
+				Self::dec_account_counts(who, currency_id);
 				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnKilledTokenAccount::happened(&(who.clone(), currency_id));
 			} else if !existed && exists {
 				// if new, increase account provider
 				frame_system::Pallet::<T>::inc_providers(who);
+				Self::inc_account_counts(who, currency_id);
 				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnNewTokenAccount::happened(&(who.clone(), currency_id));
 			}
 
@@ -803,6 +1480,8 @@ impl<T: Config> Pallet<T> {
 				});
 			}
 
+			// deferred dust is reported when it's actually handled
+			let maybe_dust = maybe_dust.filter(|_| !Self::defer_dust(who, currency_id));
 			if let Some(dust_amount) = maybe_dust {
 				// `OnDust` maybe get/set storage `Accounts` of `who`, trigger handler here
 				// to avoid some unexpected errors.
@@ -819,6 +1498,154 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Handle queued dust accounts, at most `MaxDustPerBlock` and as many as
+	/// fit in `remaining_weight`.
+	fn handle_dust_queue(remaining_weight: Weight) -> Weight {
+		let base_weight = T::DbWeight::get().reads_writes(1, 1);
+		let per_dust_weight = T::WeightInfo::handle_dust();
+		if remaining_weight.any_lt(base_weight) || DustQueue::<T>::decode_len().unwrap_or(0) == 0 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let available = remaining_weight.saturating_sub(base_weight);
+		let mut limit = T::MaxDustPerBlock::get() as u64;
+		if per_dust_weight.ref_time() > 0 {
+			limit = limit.min(available.ref_time() / per_dust_weight.ref_time());
+		}
+		if per_dust_weight.proof_size() > 0 {
+			limit = limit.min(available.proof_size() / per_dust_weight.proof_size());
+		}
+		if limit == 0 {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let dusted = DustQueue::<T>::mutate(|queue| {
+			let count = queue.len().min(limit as usize);
+			let mut remaining = sp_std::mem::take(queue).into_inner();
+			let dusted = remaining.drain(..count).collect::<Vec<_>>();
+			*queue = BoundedVec::truncate_from(remaining);
+			dusted
+		});
+
+		let handled = dusted.len() as u64;
+		for (who, currency_id) in dusted {
+			Self::handle_queued_dust(&who, currency_id);
+		}
+
+		base_weight.saturating_add(per_dust_weight.saturating_mul(handled))
+	}
+
+	/// Check account entries for being empty and reap them, continuing from
+	/// where the previous sweep stopped. At most `MaxReapPerBlock` entries and
+	/// as many as fit in `remaining_weight` are checked.
+	fn sweep_dead_accounts(remaining_weight: Weight) -> Weight {
+		let base_weight = T::DbWeight::get().reads_writes(1, 1);
+		let per_account_weight = T::WeightInfo::reap_dead_accounts(1);
+		if remaining_weight.any_lt(base_weight) {
+			return Weight::zero();
+		}
+
+		let available = remaining_weight.saturating_sub(base_weight);
+		let mut limit = T::MaxReapPerBlock::get() as u64;
+		if per_account_weight.ref_time() > 0 {
+			limit = limit.min(available.ref_time() / per_account_weight.ref_time());
+		}
+		if per_account_weight.proof_size() > 0 {
+			limit = limit.min(available.proof_size() / per_account_weight.proof_size());
+		}
+		if limit == 0 {
+			return Weight::zero();
+		}
+
+		let checked = match ReapPager::<T>::process(limit as u32, |who, currency_id, account| {
+			if Self::is_dead_account(&who, currency_id, &account) {
+				Self::do_reap_dead_account(&who, currency_id);
+			}
+		}) {
+			Ok(outcome) => outcome.processed,
+			Err(PagerError::CursorOverflow { processed }) => {
+				log::error!("The raw key of the last swept account entry does not fit in ReapCursor, starting over.");
+				processed
+			}
+			Err(PagerError::InvalidCursor) => {
+				log::error!("ReapCursor can not be decoded, starting over.");
+				ReapPager::<T>::reset();
+				0
+			}
+		};
+
+		base_weight.saturating_add(per_account_weight.saturating_mul(checked.into()))
+	}
+
+	/// Whether the account entry holds no balance, locks, reserves or
+	/// freezes.
+	fn is_dead_account(who: &T::AccountId, currency_id: T::CurrencyId, account: &AccountData<T::Balance>) -> bool {
+		account.free.is_zero()
+			&& account.reserved.is_zero()
+			&& account.frozen.is_zero()
+			&& !Locks::<T>::contains_key(who, currency_id)
+			&& !Reserves::<T>::contains_key(who, currency_id)
+			&& !Freezes::<T>::contains_key(who, currency_id)
+	}
+
+	/// Remove an empty account entry, releasing the provider reference it
+	/// was holding.
+	fn do_reap_dead_account(who: &T::AccountId, currency_id: T::CurrencyId) {
+		Accounts::<T>::remove(who, currency_id);
+		// Ignore the result, because if it failed then there are remaining consumers,
+		// and the account storage in frame_system shouldn't be reaped.
+		let _ = frame_system::Pallet::<T>::dec_providers(who);
+		Self::dec_account_counts(who, currency_id);
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnKilledTokenAccount::happened(
+			&(who.clone(), currency_id),
+		);
+		Self::deposit_event(Event::DeadAccountReaped {
+			currency_id,
+			who: who.clone(),
+		});
+	}
+
+	/// Queue the dust of `who` to be handled in `on_idle`.
+	///
+	/// Return false if dust should be handled inline, either because inline
+	/// mode is configured or because the queue is full.
+	fn defer_dust(who: &T::AccountId, currency_id: T::CurrencyId) -> bool {
+		if T::DustHandlingMode::get() != DustHandling::Deferred {
+			return false;
+		}
+		DustQueue::<T>::mutate(|queue| {
+			// already queued, the current dust will be handled with it
+			if queue.iter().any(|(a, c)| a == who && *c == currency_id) {
+				return true;
+			}
+			queue.try_push((who.clone(), currency_id)).is_ok()
+		})
+	}
+
+	/// Handle the dust of a queued account, if it is still below the
+	/// existential deposit.
+	fn handle_queued_dust(who: &T::AccountId, currency_id: T::CurrencyId) {
+		let total = Self::accounts(who, currency_id).total();
+		if total.is_zero()
+			|| total >= T::ExistentialDeposits::get(&currency_id)
+			|| T::DustRemovalWhitelist::contains(who)
+		{
+			return;
+		}
+
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnDust::on_dust(
+			who,
+			currency_id,
+			total,
+		);
+
+		Self::deposit_event(Event::DustLost {
+			currency_id,
+			who: who.clone(),
+			amount: total,
+		});
+	}
+
 	pub(crate) fn mutate_account<R>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
@@ -882,10 +1709,7 @@ impl<T: Config> Pallet<T> {
 		// update account data
 		Self::mutate_account(who, currency_id, |account, _| {
 			total_frozen_prev = account.frozen;
-			account.frozen = Zero::zero();
-			for lock in locks.iter() {
-				account.frozen = account.frozen.max(lock.amount);
-			}
+			account.frozen = Self::max_frozen(locks, &Self::freezes(who, currency_id));
 			total_frozen_after = account.frozen;
 		});
 
@@ -934,6 +1758,78 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// The amount the free balance may not drop below given the locks and
+	/// freezes of an account.
+	fn max_frozen(
+		locks: &[BalanceLock<T::Balance>],
+		freezes: &[FreezeData<T::FreezeIdentifier, T::Balance>],
+	) -> T::Balance {
+		let max_lock = locks
+			.iter()
+			.fold(Zero::zero(), |max: T::Balance, lock| max.max(lock.amount));
+		freezes
+			.iter()
+			.fold(max_lock, |max: T::Balance, freeze| max.max(freeze.amount))
+	}
+
+	/// Update the account entry for `who` under `currency_id`, given the
+	/// freezes.
+	pub(crate) fn update_freezes(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		freezes: BoundedVec<FreezeData<T::FreezeIdentifier, T::Balance>, T::MaxFreezes>,
+	) -> DispatchResult {
+		// track freeze delta
+		let mut total_frozen_prev = Zero::zero();
+		let mut total_frozen_after = Zero::zero();
+
+		// update account data
+		Self::mutate_account(who, currency_id, |account, _| {
+			total_frozen_prev = account.frozen;
+			account.frozen = Self::max_frozen(&Self::locks(who, currency_id), &freezes);
+			total_frozen_after = account.frozen;
+		});
+
+		// update freezes
+		let existed = Freezes::<T>::contains_key(who, currency_id);
+		if freezes.is_empty() {
+			Freezes::<T>::remove(who, currency_id);
+			if existed {
+				// decrease account ref count when destruct freeze
+				frame_system::Pallet::<T>::dec_consumers(who);
+			}
+		} else {
+			Freezes::<T>::insert(who, currency_id, freezes);
+			if !existed && frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+				// No providers for the freezes. This is impossible under normal
+				// circumstances since the funds that are frozen will themselves be
+				// stored in the account and therefore will need a reference.
+				log::warn!(
+					"Warning: Attempt to introduce freeze consumer reference, yet no providers. \
+					This is unexpected but should be safe."
+				);
+			}
+		}
+
+		if total_frozen_prev < total_frozen_after {
+			let amount = total_frozen_after.saturating_sub(total_frozen_prev);
+			Self::deposit_event(Event::Frozen {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		} else if total_frozen_prev > total_frozen_after {
+			let amount = total_frozen_prev.saturating_sub(total_frozen_after);
+			Self::deposit_event(Event::Thawed {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		}
+
+		Ok(())
+	}
+
 	/// Transfer some free balance from `from` to `to`. Ensure from_account
 	/// allow death or new balance will not be reaped, and ensure
 	/// to_account will not be removed dust.
@@ -957,7 +1853,11 @@ impl<T: Config> Pallet<T> {
 			to,
 			amount,
 		)?;
-		Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
+		Self::try_mutate_account(to, currency_id, |to_account, to_existed| -> DispatchResult {
+			ensure!(
+				to_existed || Self::can_create_account(to),
+				Error::<T>::CannotCreateAccount
+			);
 			Self::try_mutate_account(from, currency_id, |from_account, _existed| -> DispatchResult {
 				from_account.free = from_account
 					.free
@@ -1002,6 +1902,7 @@ impl<T: Config> Pallet<T> {
 			to,
 			amount,
 		)?;
+		Self::on_balance_movement(currency_id, Some(from), Some(to), amount);
 		Self::deposit_event(Event::Transfer {
 			currency_id,
 			from: from.clone(),
@@ -1064,6 +1965,7 @@ impl<T: Config> Pallet<T> {
 			});
 			Ok(())
 		})?;
+		Self::on_balance_movement(currency_id, Some(who), None, amount);
 
 		Ok(())
 	}
@@ -1106,6 +2008,10 @@ impl<T: Config> Pallet<T> {
 					amount >= ed || existed || T::DustRemovalWhitelist::contains(who),
 					Error::<T>::ExistentialDeposit
 				);
+				ensure!(
+					existed || Self::can_create_account(who),
+					Error::<T>::CannotCreateAccount
+				);
 			}
 
 			let new_total_issuance = Self::total_issuance(currency_id)
@@ -1122,6 +2028,7 @@ impl<T: Config> Pallet<T> {
 			who,
 			amount,
 		)?;
+		Self::on_balance_movement(currency_id, None, Some(who), amount);
 		Self::deposit_event(Event::Deposited {
 			currency_id,
 			who: who.clone(),
@@ -1243,8 +2150,38 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			free_amount: free_slashed_amount,
 			reserved_amount: reserved_slashed_amount,
 		});
+		Self::on_balance_movement(
+			currency_id,
+			Some(who),
+			None,
+			amount.defensive_saturating_sub(remaining_slash),
+		);
 		remaining_slash
 	}
+
+	/// Total issuance is checked and updated once for the whole batch
+	/// rather than once per deposit.
+	#[transactional]
+	fn deposit_batch(currency_id: Self::CurrencyId, deposits: &[(T::AccountId, Self::Balance)]) -> DispatchResult {
+		let batch_total = deposits
+			.iter()
+			.try_fold(T::Balance::zero(), |acc, (_, amount)| acc.checked_add(amount))
+			.ok_or(ArithmeticError::Overflow)?;
+		if batch_total.is_zero() {
+			return Ok(());
+		}
+		let new_total_issuance = Self::total_issuance(currency_id)
+			.checked_add(&batch_total)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		for (who, amount) in deposits {
+			// do not require existing, total issuance is updated below
+			Self::do_deposit(currency_id, who, *amount, false, false)?;
+		}
+
+		TotalIssuance::<T>::insert(currency_id, new_total_issuance);
+		Ok(())
+	}
 }
 
 impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
@@ -1412,6 +2349,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			free_amount: Zero::zero(),
 			reserved_amount: actual,
 		});
+		Self::on_balance_movement(currency_id, Some(who), None, actual);
 		value.defensive_saturating_sub(actual)
 	}
 
@@ -1493,6 +2431,11 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
+		ensure!(
+			!Self::is_account_frozen(slashed, currency_id),
+			Error::<T>::AccountFrozen
+		);
+		Self::ensure_can_create_account(beneficiary, currency_id)?;
 		let from_account = Self::accounts(slashed, currency_id);
 		let to_account = Self::accounts(beneficiary, currency_id);
 		let actual = from_account.reserved.min(value);
@@ -1525,6 +2468,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			amount: actual,
 			status,
 		});
+		Self::on_balance_movement(currency_id, Some(slashed), Some(beneficiary), actual);
 		Ok(value.defensive_saturating_sub(actual))
 	}
 }
@@ -1690,6 +2634,11 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
+		ensure!(
+			!Self::is_account_frozen(slashed, currency_id),
+			Error::<T>::AccountFrozen
+		);
+
 		Reserves::<T>::try_mutate(
 			slashed,
 			currency_id,
@@ -1803,6 +2752,9 @@ impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 		preservation: Preservation,
 		_force: Fortitude,
 	) -> Self::Balance {
+		if Self::is_account_frozen(who, asset_id) {
+			return Zero::zero();
+		}
 		let a = Self::accounts(who, asset_id);
 		// Liquid balance is what is neither reserved nor locked/frozen.
 		let liquid = a.free.saturating_sub(a.frozen);
@@ -1899,22 +2851,33 @@ impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 		);
 
 		// Balance is the same type and will not overflow
-		let (_, dust_amount) = Self::try_mutate_account(who, asset_id, |account, _| -> Result<(), DispatchError> {
-			// Make sure the reduction (if there is one) is no more than the maximum
-			// allowed.
-			let reduction = account.free.saturating_sub(amount);
-			ensure!(reduction <= max_reduction, Error::<T>::BalanceTooLow);
+		let (old_free, dust_amount) =
+			Self::try_mutate_account(who, asset_id, |account, existed| -> Result<T::Balance, DispatchError> {
+				// Make sure the reduction (if there is one) is no more than the maximum
+				// allowed.
+				let reduction = account.free.saturating_sub(amount);
+				ensure!(reduction <= max_reduction, Error::<T>::BalanceTooLow);
+				ensure!(
+					existed || amount.is_zero() || Self::can_create_account(who),
+					Error::<T>::CannotCreateAccount
+				);
 
-			account.free = amount;
-			Self::deposit_event(Event::BalanceSet {
-				currency_id: asset_id,
-				who: who.clone(),
-				free: account.free,
-				reserved: account.reserved,
-			});
+				let old_free = account.free;
+				account.free = amount;
+				Self::deposit_event(Event::BalanceSet {
+					currency_id: asset_id,
+					who: who.clone(),
+					free: account.free,
+					reserved: account.reserved,
+				});
 
-			Ok(())
-		})?;
+				Ok(old_free)
+			})?;
+		if amount > old_free {
+			Self::on_balance_movement(asset_id, None, Some(who), amount.saturating_sub(old_free));
+		} else {
+			Self::on_balance_movement(asset_id, Some(who), None, old_free.saturating_sub(amount));
+		}
 
 		Ok(dust_amount)
 	}
@@ -1976,6 +2939,65 @@ impl<T: Config> fungibles::Balanced<T::AccountId> for Pallet<T> {
 	}
 }
 
+impl<T: Config> fungibles::InspectFreeze<T::AccountId> for Pallet<T> {
+	type Id = T::FreezeIdentifier;
+
+	fn balance_frozen(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		Self::freezes(who, asset_id)
+			.iter()
+			.find(|freeze| freeze.id == *id)
+			.map_or_else(Zero::zero, |freeze| freeze.amount)
+	}
+
+	fn can_freeze(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Self::freezes(who, asset_id);
+		!freezes.is_full() || freezes.iter().any(|freeze| freeze.id == *id)
+	}
+}
+
+impl<T: Config> fungibles::MutateFreeze<T::AccountId> for Pallet<T> {
+	fn set_freeze(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return <Self as fungibles::MutateFreeze<T::AccountId>>::thaw(asset_id, id, who);
+		}
+		let mut freezes = Self::freezes(who, asset_id);
+		if let Some(freeze) = freezes.iter_mut().find(|freeze| freeze.id == *id) {
+			freeze.amount = amount;
+		} else {
+			freezes
+				.try_push(FreezeData { id: *id, amount })
+				.map_err(|_| Error::<T>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset_id, who, freezes)
+	}
+
+	fn extend_freeze(
+		asset_id: Self::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let mut freezes = Self::freezes(who, asset_id);
+		if let Some(freeze) = freezes.iter_mut().find(|freeze| freeze.id == *id) {
+			freeze.amount = freeze.amount.max(amount);
+		} else {
+			freezes
+				.try_push(FreezeData { id: *id, amount })
+				.map_err(|_| Error::<T>::TooManyFreezes)?;
+		}
+		Self::update_freezes(asset_id, who, freezes)
+	}
+
+	fn thaw(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Self::freezes(who, asset_id);
+		freezes.retain(|freeze| freeze.id != *id);
+		Self::update_freezes(asset_id, who, freezes)
+	}
+}
+
 type ReasonOf<P, T> = <P as fungibles::InspectHold<<T as frame_system::Config>::AccountId>>::Reason;
 impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
 	type Reason = ();
@@ -2237,6 +3259,12 @@ where
 				free_amount: free_slashed_amount,
 				reserved_amount: reserved_slashed_amount,
 			});
+			Pallet::<T>::on_balance_movement(
+				currency_id,
+				Some(who),
+				None,
+				free_slashed_amount.saturating_add(reserved_slashed_amount),
+			);
 			(
 				Self::NegativeImbalance::new(free_slashed_amount.saturating_add(reserved_slashed_amount)),
 				remaining_slash,
@@ -2248,6 +3276,7 @@ where
 				free_amount: value,
 				reserved_amount: Zero::zero(),
 			});
+			Pallet::<T>::on_balance_movement(currency_id, Some(who), None, value);
 			(Self::NegativeImbalance::new(value), remaining_slash)
 		}
 	}
@@ -2299,6 +3328,7 @@ where
 				// underflow the issuance and our arithmetic will be off.
 				let ed = T::ExistentialDeposits::get(&currency_id);
 				ensure!(value.saturating_add(account.reserved) >= ed || existed, ());
+				ensure!(existed || Pallet::<T>::can_create_account(who), ());
 
 				let imbalance = if account.free <= value {
 					SignedImbalance::Positive(PositiveImbalance::new(value.saturating_sub(account.free)))
@@ -0,0 +1,30 @@
+use crate::{Config, CountMigrationCursor, Pallet, PendingCountMigration, PendingCounts};
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+/// Populates the storage added after the pallet was deployed.
+///
+/// The existing account entries are counted in pages by `on_idle`, in one pass
+/// for every counter added since the on-chain storage version. Until the pass
+/// is finished, `ProviderReferences` and `AccountsCount` only hold the entries
+/// counted so far.
+pub struct Migration<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		let pending = PendingCounts {
+			provider_references: onchain_version < 1,
+			accounts: onchain_version < 2,
+		};
+		if pending != PendingCounts::default() {
+			CountMigrationCursor::<T>::kill();
+			PendingCountMigration::<T>::put(pending);
+			weight.saturating_accrue(T::DbWeight::get().writes(2));
+		}
+		if onchain_version < Pallet::<T>::current_storage_version() {
+			Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+		weight
+	}
+}
@@ -288,6 +288,39 @@ where
 	}
 }
 
+thread_local! {
+	pub static BALANCE_MOVEMENTS: RefCell<Vec<(CurrencyId, Option<AccountId>, Option<AccountId>, Balance)>> = RefCell::new(vec![]);
+}
+
+pub struct TrackBalanceMovements<T>(marker::PhantomData<T>);
+impl<T: Config> TrackBalanceMovements<T> {
+	pub fn take() -> Vec<(CurrencyId, Option<AccountId>, Option<AccountId>, Balance)> {
+		BALANCE_MOVEMENTS.with(|movements| movements.take())
+	}
+}
+impl<T: Config> OnBalanceMovement<T::AccountId, T::CurrencyId, T::Balance> for TrackBalanceMovements<T>
+where
+	T::AccountId: Into<AccountId32>,
+	T::CurrencyId: Into<u32>,
+	T::Balance: Into<Balance>,
+{
+	fn on_balance_movement(
+		currency_id: T::CurrencyId,
+		from: Option<&T::AccountId>,
+		to: Option<&T::AccountId>,
+		amount: T::Balance,
+	) {
+		BALANCE_MOVEMENTS.with(|movements| {
+			movements.borrow_mut().push((
+				currency_id.into(),
+				from.cloned().map(Into::into),
+				to.cloned().map(Into::into),
+				amount.into(),
+			));
+		});
+	}
+}
+
 thread_local! {
 	pub static ON_SLASH_CALLS: RefCell<u32> = RefCell::new(0);
 	pub static ON_DEPOSIT_PREHOOK_CALLS: RefCell<u32> = RefCell::new(0);
@@ -384,6 +417,8 @@ impl<T: Config> PostTransfer<T> {
 
 parameter_types! {
 	pub DustReceiver: AccountId = PalletId(*b"orml/dst").into_account_truncating();
+	pub static DustMode: DustHandling = DustHandling::Inline;
+	pub static AccountCreationMode: AccountCreation = AccountCreation::Permissionless;
 }
 
 pub struct CurrencyHooks<T>(marker::PhantomData<T>);
@@ -391,6 +426,7 @@ impl<T: Config> MutationHooks<T::AccountId, T::CurrencyId, T::Balance> for Curre
 where
 	T::AccountId: From<AccountId32> + Into<AccountId32>,
 	T::CurrencyId: From<u32> + Into<u32>,
+	T::Balance: Into<Balance>,
 {
 	type OnDust = TransferDust<T, DustReceiver>;
 	type OnSlash = OnSlashHook<T>;
@@ -410,10 +446,22 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = CurrencyHooks<Runtime>;
+	type OnBalanceMovement = TrackBalanceMovements<Runtime>;
 	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type DustHandlingMode = DustMode;
+	type MaxDustQueueLen = ConstU32<2>;
+	type MaxDustPerBlock = ConstU32<1>;
+	type MaxReapPerBlock = ConstU32<2>;
+	type AccountCreation = AccountCreationMode;
+	type AccountCreationWhitelist = MockDustRemovalWhitelist;
+	type AssetAdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<2>;
+	type MaxIssuanceSnapshots = ConstU32<2>;
+	type MaxSnapshotCurrencies = ConstU32<2>;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
@@ -431,6 +479,8 @@ construct_runtime!(
 #[derive(Default)]
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
+	reserves: Vec<(AccountId, CurrencyId, Balance)>,
+	locks: Vec<(AccountId, CurrencyId, LockIdentifier, Balance)>,
 	treasury_genesis: bool,
 }
 
@@ -440,6 +490,16 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn reserves(mut self, mut reserves: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
+		self.reserves.append(&mut reserves);
+		self
+	}
+
+	pub fn locks(mut self, mut locks: Vec<(AccountId, CurrencyId, LockIdentifier, Balance)>) -> Self {
+		self.locks.append(&mut locks);
+		self
+	}
+
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Runtime>::default()
 			.build_storage()
@@ -447,6 +507,8 @@ impl ExtBuilder {
 
 		tokens::GenesisConfig::<Runtime> {
 			balances: self.balances,
+			reserves: self.reserves,
+			locks: self.locks,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -25,6 +25,27 @@ fn genesis_issuance_should_work() {
 		});
 }
 
+#[test]
+fn genesis_reserves_and_locks_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.reserves(vec![(ALICE, DOT, 30)])
+		.locks(vec![(ALICE, DOT, ID_1, 50), (BOB, DOT, ID_1, 20), (BOB, DOT, ID_2, 40)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_eq!(Tokens::accounts(&BOB, DOT).frozen, 40);
+			assert_eq!(Tokens::locks(&BOB, DOT).len(), 2);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 21),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+		});
+}
+
 // *************************************************
 // tests for call
 // *************************************************
@@ -156,6 +177,64 @@ fn transfer_all_allow_death_should_work() {
 		});
 }
 
+#[test]
+fn transfer_everything_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 200), (ALICE, ETH, 50)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, ETH, &ALICE, 20));
+			assert_noop!(
+				Tokens::transfer_everything(Some(ALICE).into(), BOB, true, true, 2),
+				Error::<Runtime>::TooManyCurrencies
+			);
+
+			// keep alive and skip the currency with a lock
+			assert_ok!(Tokens::transfer_everything(Some(ALICE).into(), BOB, true, true, 3));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 98,
+			}));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: BTC,
+				from: ALICE,
+				to: BOB,
+				amount: 199,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 2);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 1);
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(ETH, &BOB), 0);
+
+			// allow death and transfer the unfrozen balance
+			assert_ok!(Tokens::transfer_everything(
+				Some(ALICE).into(),
+				CHARLIE,
+				false,
+				false,
+				3
+			));
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 2);
+			assert_eq!(Tokens::free_balance(BTC, &CHARLIE), 1);
+			assert_eq!(Tokens::free_balance(ETH, &CHARLIE), 30);
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, BTC));
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 20);
+
+			// nothing left to transfer
+			assert_ok!(Tokens::transfer_everything(
+				Some(ALICE).into(),
+				CHARLIE,
+				false,
+				false,
+				1
+			));
+			assert_eq!(Tokens::free_balance(ETH, &ALICE), 20);
+		});
+}
+
 #[test]
 fn force_transfer_should_work() {
 	ExtBuilder::default()
@@ -1111,6 +1190,250 @@ fn dust_removal_work() {
 		});
 }
 
+#[test]
+fn deferred_dust_removal_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (CHARLIE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			DustMode::set(DustHandling::Deferred);
+
+			Tokens::set_free_balance(DOT, &ALICE, 1);
+			assert!(!System::events()
+				.iter()
+				.any(|r| matches!(r.event, RuntimeEvent::Tokens(crate::Event::DustLost { .. }))));
+			assert!(Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::dust_queue().into_inner(), vec![(ALICE, DOT)]);
+
+			// already queued
+			Tokens::set_free_balance(DOT, &ALICE, 1);
+			assert_eq!(Tokens::dust_queue().into_inner(), vec![(ALICE, DOT)]);
+
+			Tokens::set_free_balance(DOT, &BOB, 1);
+			assert_eq!(Tokens::dust_queue().into_inner(), vec![(ALICE, DOT), (BOB, DOT)]);
+
+			// queue is full, handled inline
+			Tokens::set_free_balance(DOT, &CHARLIE, 1);
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::DustLost {
+				currency_id: DOT,
+				who: CHARLIE,
+				amount: 1,
+			}));
+			assert!(!Accounts::<Runtime>::contains_key(CHARLIE, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 1);
+
+			// not enough weight
+			assert_eq!(Tokens::on_idle(1, Weight::zero()), Weight::zero());
+			assert_eq!(Tokens::dust_queue().len(), 2);
+
+			// at most `MaxDustPerBlock` accounts per block
+			Tokens::on_idle(1, Weight::MAX);
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::DustLost {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 1,
+			}));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 2);
+			assert_eq!(Tokens::dust_queue().into_inner(), vec![(BOB, DOT)]);
+
+			// no longer dust when handled
+			Tokens::set_free_balance(DOT, &BOB, 10);
+			Tokens::on_idle(2, Weight::MAX);
+			assert!(Tokens::dust_queue().is_empty());
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 2);
+		});
+}
+
+#[test]
+fn reap_dead_accounts_works() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			let insert_empty_entry = |who: &AccountId| {
+				Accounts::<Runtime>::insert(who, DOT, AccountData::default());
+				frame_system::Pallet::<Runtime>::inc_providers(who);
+			};
+			insert_empty_entry(&BOB);
+			insert_empty_entry(&CHARLIE);
+			Reserves::<Runtime>::insert(
+				CHARLIE,
+				DOT,
+				BoundedVec::try_from(vec![ReserveData {
+					id: [1u8; 8],
+					amount: 0,
+				}])
+				.unwrap(),
+			);
+
+			assert_noop!(
+				Tokens::reap_dead_accounts(Some(DAVE).into(), DOT, vec![BOB, ALICE]),
+				Error::<Runtime>::AccountNotDead
+			);
+			assert_noop!(
+				Tokens::reap_dead_accounts(Some(DAVE).into(), DOT, vec![CHARLIE]),
+				Error::<Runtime>::AccountNotDead
+			);
+
+			assert_ok!(Tokens::reap_dead_accounts(Some(DAVE).into(), DOT, vec![DAVE, BOB]));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::DeadAccountReaped {
+				currency_id: DOT,
+				who: BOB,
+			}));
+			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
+			assert_eq!(System::providers(&BOB), 0);
+			assert!(Accounts::<Runtime>::contains_key(CHARLIE, DOT));
+		});
+}
+
+#[test]
+fn on_idle_sweeps_dead_accounts() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			Accounts::<Runtime>::insert(BOB, DOT, AccountData::default());
+			Accounts::<Runtime>::insert(CHARLIE, DOT, AccountData::default());
+			frame_system::Pallet::<Runtime>::inc_providers(&BOB);
+			frame_system::Pallet::<Runtime>::inc_providers(&CHARLIE);
+
+			// not enough weight
+			assert_eq!(Tokens::on_idle(1, Weight::zero()), Weight::zero());
+			assert_eq!(Accounts::<Runtime>::iter().count(), 4);
+
+			// at most `MaxReapPerBlock` entries are checked per block
+			Tokens::on_idle(1, Weight::MAX);
+			assert!(ReapCursor::<Runtime>::get().is_some());
+			Tokens::on_idle(2, Weight::MAX);
+			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
+			assert!(!Accounts::<Runtime>::contains_key(CHARLIE, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 100);
+			assert_eq!(Accounts::<Runtime>::iter().count(), 2);
+
+			// starts over once all entries are checked
+			Tokens::on_idle(3, Weight::MAX);
+			assert!(ReapCursor::<Runtime>::get().is_none());
+
+			// a cursor that can not be decoded is dropped
+			frame_support::storage::unhashed::put_raw(&ReapCursor::<Runtime>::hashed_key(), &[0xff]);
+			Tokens::on_idle(4, Weight::MAX);
+			assert!(!ReapCursor::<Runtime>::exists());
+			Tokens::on_idle(5, Weight::MAX);
+			assert!(ReapCursor::<Runtime>::get().is_some());
+		});
+}
+
+#[test]
+fn account_creation_requires_provider() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			AccountCreationMode::set(AccountCreation::RequireProvider);
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::CannotCreateAccount
+			);
+			assert_noop!(Tokens::deposit(BTC, &BOB, 10), Error::<Runtime>::CannotCreateAccount);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(BTC, &BOB, 10, Provenance::Minted),
+				DepositConsequence::CannotCreate
+			);
+
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Error::<Runtime>::CannotCreateAccount
+			);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::write_balance(BTC, &BOB, 10),
+				Error::<Runtime>::CannotCreateAccount
+			);
+
+			// the provider held by the DOT account of ALICE doesn't count
+			assert_eq!(Tokens::provider_references(ALICE), 1);
+			assert_noop!(Tokens::deposit(BTC, &ALICE, 10), Error::<Runtime>::CannotCreateAccount);
+			frame_system::Pallet::<Runtime>::inc_providers(&ALICE);
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 10));
+			assert_eq!(Tokens::provider_references(ALICE), 2);
+			// DAVE is whitelisted
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), DAVE, DOT, 10));
+
+			frame_system::Pallet::<Runtime>::inc_providers(&BOB);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(BTC, &BOB, 10, Provenance::Minted),
+				DepositConsequence::Success
+			);
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 20);
+		});
+}
+
+#[test]
+fn migration_works() {
+	use frame_support::traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// provider references and accounts held before they were counted
+			let _ = ProviderReferences::<Runtime>::clear(u32::MAX, None);
+			let _ = AccountsCount::<Runtime>::clear(u32::MAX, None);
+			StorageVersion::new(0).put::<Tokens>();
+			crate::Migration::<Runtime>::on_runtime_upgrade();
+			assert_eq!(Tokens::on_chain_storage_version(), Tokens::current_storage_version());
+			assert_eq!(
+				Tokens::pending_counts(),
+				Some(PendingCounts {
+					provider_references: true,
+					accounts: true,
+				})
+			);
+
+			// the entries are counted in `on_idle`, one at a time here
+			let one_entry = <() as WeightInfo>::count_accounts(1);
+			assert_eq!(Tokens::on_idle(1, one_entry), one_entry);
+			assert_eq!(Tokens::accounts_count(DOT) + Tokens::accounts_count(BTC), 1);
+
+			// entries created and removed while the migration is in progress are
+			// counted once, whether they were already counted or not
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 10));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), DAVE, BTC, 10));
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), CHARLIE, DOT, false));
+
+			while Tokens::pending_counts().is_some() {
+				Tokens::on_idle(2, one_entry);
+			}
+			assert_eq!(Tokens::provider_references(ALICE), 2);
+			assert_eq!(Tokens::provider_references(BOB), 0);
+			assert_eq!(Tokens::provider_references(CHARLIE), 1);
+			assert_eq!(Tokens::provider_references(DAVE), 1);
+			assert_eq!(Tokens::accounts_count(DOT), 2);
+			assert_eq!(Tokens::accounts_count(BTC), 2);
+
+			// accounts held before they were counted, provider references already counted
+			let _ = AccountsCount::<Runtime>::clear(u32::MAX, None);
+			StorageVersion::new(1).put::<Tokens>();
+			crate::Migration::<Runtime>::on_runtime_upgrade();
+			Tokens::on_idle(3, Weight::MAX);
+			assert_eq!(Tokens::on_chain_storage_version(), Tokens::current_storage_version());
+			assert_eq!(Tokens::pending_counts(), None);
+			assert_eq!(Tokens::provider_references(ALICE), 2);
+			assert_eq!(Tokens::accounts_count(DOT), 2);
+			assert_eq!(Tokens::accounts_count(BTC), 2);
+		});
+}
+
 #[test]
 fn account_survive_due_to_dust_transfer_failure() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1250,6 +1573,57 @@ fn transfer_hooks_work() {
 		});
 }
 
+#[test]
+fn balance_movement_hook_works() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TrackBalanceMovements::<Runtime>::take();
+
+			assert_ok!(Tokens::do_transfer(
+				DOT,
+				&ALICE,
+				&BOB,
+				10,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_ok!(Tokens::do_deposit(DOT, &CHARLIE, 10, false, true));
+			assert_ok!(Tokens::do_withdraw(
+				DOT,
+				&ALICE,
+				5,
+				ExistenceRequirement::KeepAlive,
+				true
+			));
+			// reserving does not move balance
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 5), 0);
+			assert_ok!(Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 5, BalanceStatus::Free));
+
+			assert_eq!(
+				TrackBalanceMovements::<Runtime>::take(),
+				vec![
+					(DOT, Some(ALICE), Some(BOB), 10),
+					(DOT, None, Some(CHARLIE), 10),
+					(DOT, Some(ALICE), None, 5),
+					(DOT, Some(ALICE), None, 5),
+					(DOT, Some(ALICE), Some(BOB), 5),
+				]
+			);
+
+			// zero amounts are not reported
+			assert_ok!(Tokens::do_transfer(
+				DOT,
+				&ALICE,
+				&BOB,
+				0,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(TrackBalanceMovements::<Runtime>::take(), vec![]);
+		});
+}
+
 #[test]
 fn post_transfer_can_use_new_balance() {
 	ExtBuilder::default()
@@ -1269,3 +1643,170 @@ fn post_transfer_can_use_new_balance() {
 			));
 		});
 }
+
+// *************************************************
+// tests for try-state
+// *************************************************
+
+#[test]
+fn try_state_checks_invariants() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 50)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &BOB, 20));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+			assert_ok!(Tokens::do_try_state());
+
+			TotalIssuance::<Runtime>::mutate(BTC, |total| *total += 1);
+			assert!(Tokens::do_try_state().is_err());
+			TotalIssuance::<Runtime>::mutate(BTC, |total| *total -= 1);
+
+			Accounts::<Runtime>::mutate(ALICE, DOT, |account| account.frozen = 10);
+			assert!(Tokens::do_try_state().is_err());
+			Accounts::<Runtime>::mutate(ALICE, DOT, |account| account.frozen = 30);
+
+			Reserves::<Runtime>::mutate(BOB, DOT, |reserves| reserves[0].amount = 21);
+			assert!(Tokens::do_try_state().is_err());
+			Reserves::<Runtime>::mutate(BOB, DOT, |reserves| reserves[0].amount = 20);
+
+			assert_ok!(Tokens::do_try_state());
+		});
+}
+
+#[test]
+fn accounts_count_tracks_entries() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(Tokens::accounts_count(DOT), 2);
+			assert_eq!(Tokens::accounts_count(BTC), 0);
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 2));
+			assert_eq!(Tokens::accounts_count(DOT), 3);
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 98));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert_eq!(Tokens::accounts_count(DOT), 2);
+
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 10));
+			assert_eq!(Tokens::accounts_count(BTC), 1);
+		});
+}
+
+#[test]
+fn freeze_account_blocks_outgoing_transfers() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_account(Some(BOB).into(), DOT, ALICE), BadOrigin);
+			assert_noop!(
+				Tokens::thaw_account(RuntimeOrigin::root(), DOT, ALICE),
+				Error::<Runtime>::AccountNotFrozen
+			);
+
+			assert_ok!(Tokens::freeze_account(RuntimeOrigin::root(), DOT, ALICE));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountFrozen {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert_noop!(
+				Tokens::freeze_account(RuntimeOrigin::root(), DOT, ALICE),
+				Error::<Runtime>::AlreadyFrozen
+			);
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				<Tokens as MultiReservableCurrency<_>>::reserve(DOT, &ALICE, 10),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved_named(&RID_1, DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(
+					DOT,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				0
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_withdraw(DOT, &ALICE, 10),
+				WithdrawConsequence::Frozen
+			);
+
+			// incoming transfers and other currencies are not affected
+			assert_ok!(Tokens::transfer(Some(BOB).into(), ALICE, DOT, 10));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 10));
+
+			assert_ok!(Tokens::thaw_account(RuntimeOrigin::root(), DOT, ALICE));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountThawed {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
+
+#[test]
+fn total_issuance_snapshots_are_kept_in_a_ring_buffer() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			Tokens::snapshot_total_issuance(1);
+			assert_eq!(Tokens::issuance_snapshots(DOT).into_inner(), vec![(1, 100)]);
+			assert_eq!(Tokens::total_issuance_at(DOT, 1), Some(100));
+
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 50));
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 10));
+			Tokens::snapshot_total_issuance(2);
+			// sessions already recorded are ignored
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 50));
+			Tokens::snapshot_total_issuance(2);
+			assert_eq!(Tokens::total_issuance_at(DOT, 2), Some(150));
+
+			Tokens::snapshot_total_issuance(3);
+			assert_eq!(Tokens::issuance_snapshots(DOT).into_inner(), vec![(2, 150), (3, 200)]);
+			assert_eq!(Tokens::issuance_snapshots(BTC).into_inner(), vec![(2, 10), (3, 10)]);
+			assert_eq!(Tokens::total_issuance_at(DOT, 1), None);
+			assert_eq!(Tokens::total_issuance_at(DOT, 3), Some(200));
+			assert_eq!(Tokens::total_issuance_at(BTC, 4), None);
+		});
+}
+
+#[test]
+fn total_issuance_snapshots_are_recorded_on_session_start() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 10), (ALICE, ETH, 1)])
+		.build()
+		.execute_with(|| {
+			<SnapshotIssuanceOnSessionStart<Runtime, ()> as pallet_session::SessionManager<AccountId>>::start_session(
+				1,
+			);
+			// at most `MaxSnapshotCurrencies` currencies are recorded
+			assert_eq!(IssuanceSnapshots::<Runtime>::iter().count(), 2);
+			assert_eq!(
+				System::block_weight().get(DispatchClass::Mandatory),
+				&<Runtime as Config>::WeightInfo::snapshot_total_issuance(2)
+			);
+			assert_eq!(
+				Tokens::snapshot_total_issuance(2),
+				<Runtime as Config>::WeightInfo::snapshot_total_issuance(2)
+			);
+		});
+}
@@ -722,3 +722,68 @@ fn fungibles_mutate_convert_should_work() {
 			);
 		});
 }
+
+#[test]
+fn fungibles_freeze_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT, &ID_1, &ALICE, 30
+			));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Frozen {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 30);
+
+			// freezes overlap with locks
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 20));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 30);
+
+			// extending never lowers a freeze
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::extend_freeze(
+				DOT, &ID_1, &ALICE, 10
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &ID_1, &ALICE),
+				30
+			);
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT, &ID_2, &ALICE, 60
+			));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 60);
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			// at most `MaxFreezes` freezes per account
+			let id_3 = *b"3       ";
+			assert!(<Tokens as fungibles::InspectFreeze<_>>::can_freeze(DOT, &ID_1, &ALICE));
+			assert!(!<Tokens as fungibles::InspectFreeze<_>>::can_freeze(DOT, &id_3, &ALICE));
+			assert_noop!(
+				<Tokens as fungibles::MutateFreeze<_>>::set_freeze(DOT, &id_3, &ALICE, 10),
+				Error::<Runtime>::TooManyFreezes
+			);
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::thaw(DOT, &ID_2, &ALICE));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Thawed {
+				currency_id: DOT,
+				who: ALICE,
+				amount: 30,
+			}));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 30);
+
+			// setting a zero freeze thaws it, leaving the lock in place
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT, &ID_1, &ALICE, 0
+			));
+			assert_eq!(Tokens::freezes(&ALICE, DOT).len(), 0);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 20);
+			assert_ok!(Tokens::do_try_state());
+		});
+}
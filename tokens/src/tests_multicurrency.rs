@@ -800,3 +800,49 @@ fn slash_hook_works_for_reserved_named() {
 			assert_eq!(OnSlashHook::<Runtime>::calls(), initial_slash_hook_calls + 1);
 		});
 }
+
+#[test]
+fn multicurrency_deposit_batch_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			// a failing deposit reverts the whole batch
+			assert_noop!(
+				Tokens::deposit_batch(DOT, &[(BOB, 10), (CHARLIE, 1)]),
+				Error::<Runtime>::ExistentialDeposit
+			);
+
+			assert_ok!(Tokens::deposit_batch(DOT, &[(BOB, 10), (CHARLIE, 20), (ALICE, 30)]));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 130);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 20);
+			assert_eq!(Tokens::total_issuance(DOT), 160);
+
+			// overflow of the batch total
+			assert_noop!(
+				Tokens::deposit_batch(DOT, &[(BOB, Balance::max_value()), (CHARLIE, 1)]),
+				ArithmeticError::Overflow
+			);
+		});
+}
+
+#[test]
+fn multicurrency_transfer_batch_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer_batch(DOT, &ALICE, &[(BOB, 10), (CHARLIE, 20)]));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 10);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 20);
+			assert_eq!(Tokens::total_issuance(DOT), 100);
+
+			// a failing transfer reverts the whole batch
+			assert_noop!(
+				Tokens::transfer_batch(DOT, &ALICE, &[(BOB, 10), (CHARLIE, 100)]),
+				Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
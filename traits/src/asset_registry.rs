@@ -1,6 +1,7 @@
-use frame_support::pallet_prelude::*;
-use sp_runtime::DispatchResult;
-use sp_std::fmt::Debug;
+use crate::CurrencyMetadata;
+use frame_support::{pallet_prelude::*, weights::constants::WEIGHT_REF_TIME_PER_SECOND};
+use sp_runtime::{traits::Convert, DispatchResult, FixedPointNumber, FixedU128};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::Vec};
 use xcm::v3::prelude::*;
 use xcm::VersionedMultiLocation;
 
@@ -12,6 +13,58 @@ pub trait FixedConversionRateProvider {
 	fn get_fee_per_second(location: &MultiLocation) -> Option<u128>;
 }
 
+/// A segment of a `FeeCurve`, charging `fee_per_second` for the ref time
+/// above `from_ref_time`, up to the start of the next segment.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct FeeCurveSegment {
+	pub from_ref_time: u64,
+	pub fee_per_second: u128,
+}
+
+/// A piecewise linear mapping of weight to fee, so heavy messages can be
+/// priced superlinearly.
+///
+/// Segments must be sorted by strictly increasing `from_ref_time`, ref time
+/// below the first segment is free.
+#[derive(TypeInfo, Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, MaxEncodedLen)]
+#[codec(mel_bound(skip_type_params(MaxSegments)))]
+#[scale_info(skip_type_params(MaxSegments))]
+pub struct FeeCurve<MaxSegments: Get<u32>> {
+	pub segments: BoundedVec<FeeCurveSegment, MaxSegments>,
+}
+
+impl<MaxSegments: Get<u32>> FeeCurve<MaxSegments> {
+	/// Returns the fee of `weight`, `None` if the segments are not sorted or
+	/// the calculation overflows.
+	pub fn fee(&self, weight: Weight) -> Option<u128> {
+		let ref_time = weight.ref_time();
+		let mut fee: u128 = 0;
+		for (index, segment) in self.segments.iter().enumerate() {
+			let end = match self.segments.get(index + 1) {
+				Some(next) if next.from_ref_time <= segment.from_ref_time => return None,
+				Some(next) => next.from_ref_time.min(ref_time),
+				None => ref_time,
+			};
+			let charged = FixedU128::saturating_from_rational(
+				end.saturating_sub(segment.from_ref_time),
+				WEIGHT_REF_TIME_PER_SECOND,
+			);
+			fee = fee.checked_add(charged.saturating_mul_int(segment.fee_per_second))?;
+		}
+		Some(fee)
+	}
+}
+
+/// Provides the fee curve of an asset identified by its location.
+pub trait FeeCurveProvider {
+	/// The maximum number of segments of a fee curve.
+	type MaxSegments: Get<u32>;
+
+	/// Return the fee curve of the asset at `location`, or `None` if it is
+	/// priced at a fixed rate.
+	fn get_fee_curve(location: &MultiLocation) -> Option<FeeCurve<Self::MaxSegments>>;
+}
+
 pub trait AssetProcessor<AssetId, Metadata> {
 	fn pre_register(id: Option<AssetId>, asset_metadata: Metadata) -> Result<(AssetId, Metadata), DispatchError>;
 	fn post_register(_id: AssetId, _asset_metadata: Metadata) -> Result<(), DispatchError> {
@@ -73,3 +126,55 @@ pub trait Mutate: Inspect {
 		additional: Option<Self::CustomMetadata>,
 	) -> DispatchResult;
 }
+
+/// Lifecycle status of a registered asset.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AssetStatus {
+	/// The asset can be freely deposited and used to pay for execution.
+	#[default]
+	Live,
+	/// Deposits of the asset and fee payment with it are refused.
+	Frozen,
+	/// The asset is still accepted, but is scheduled for removal.
+	Deprecated,
+}
+
+/// Provides the status of an asset identified by its location.
+pub trait AssetStatusProvider {
+	/// Return the status of the asset at `location`, or `None` if the asset
+	/// is unknown.
+	fn asset_status(location: &MultiLocation) -> Option<AssetStatus>;
+}
+
+impl AssetStatusProvider for () {
+	fn asset_status(_location: &MultiLocation) -> Option<AssetStatus> {
+		None
+	}
+}
+
+/// `CurrencyMetadata` implementation reading the metadata of the registered
+/// asset a currency is converted to by `CurrencyIdConvert`.
+pub struct RegistryCurrencyMetadata<Registry, CurrencyIdConvert>(PhantomData<(Registry, CurrencyIdConvert)>);
+
+impl<CurrencyId, Registry, CurrencyIdConvert> CurrencyMetadata<CurrencyId>
+	for RegistryCurrencyMetadata<Registry, CurrencyIdConvert>
+where
+	Registry: Inspect,
+	CurrencyIdConvert: Convert<CurrencyId, Option<Registry::AssetId>>,
+	CurrencyId: Clone,
+{
+	fn name(currency_id: &CurrencyId) -> Option<Vec<u8>> {
+		let asset_id = CurrencyIdConvert::convert(currency_id.clone())?;
+		Registry::metadata(&asset_id).map(|metadata| metadata.name.into_inner())
+	}
+
+	fn symbol(currency_id: &CurrencyId) -> Option<Vec<u8>> {
+		let asset_id = CurrencyIdConvert::convert(currency_id.clone())?;
+		Registry::metadata(&asset_id).map(|metadata| metadata.symbol.into_inner())
+	}
+
+	fn decimals(currency_id: &CurrencyId) -> Option<u32> {
+		let asset_id = CurrencyIdConvert::convert(currency_id.clone())?;
+		Registry::metadata(&asset_id).map(|metadata| metadata.decimals)
+	}
+}
@@ -15,13 +15,18 @@ use sp_std::{
 /// Auction info.
 #[cfg_attr(feature = "std", derive(PartialEq, Eq))]
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct AuctionInfo<AccountId, Balance, BlockNumber> {
+pub struct AuctionInfo<AccountId, Balance, BlockNumber, AuctionCategory = (), AuctionSubject = ()> {
 	/// Current bidder and bid price.
 	pub bid: Option<(AccountId, Balance)>,
 	/// Define which block this auction will be started.
 	pub start: BlockNumber,
 	/// Define which block this auction will be ended.
 	pub end: Option<BlockNumber>,
+	/// The category attached at creation, passed to the auction handler.
+	pub category: Option<AuctionCategory>,
+	/// The subject attached at creation, e.g. the encoded id of the auctioned
+	/// item.
+	pub subject: Option<AuctionSubject>,
 }
 
 /// Abstraction over a simple auction system.
@@ -42,6 +47,48 @@ pub trait Auction<AccountId, BlockNumber> {
 	fn remove_auction(id: Self::AuctionId);
 }
 
+/// An auction system attaching a category to auctions, which is passed to
+/// the `AuctionHandler`.
+pub trait AuctionWithCategory<AccountId, BlockNumber>: Auction<AccountId, BlockNumber> {
+	/// The category of an auction.
+	type AuctionCategory: FullCodec + Clone + Debug;
+
+	/// Create new auction with specific startblock, endblock and category,
+	/// return the id of the auction
+	fn new_auction_with_category(
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+		category: Option<Self::AuctionCategory>,
+	) -> result::Result<Self::AuctionId, DispatchError>;
+	/// Create new auction on behalf of `creator`, reserving the creation
+	/// deposit of the implementation, if any, until the auction is concluded
+	/// or removed. Return the id of the auction
+	fn new_auction_with_deposit(
+		_creator: &AccountId,
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+		category: Option<Self::AuctionCategory>,
+	) -> result::Result<Self::AuctionId, DispatchError> {
+		Self::new_auction_with_category(start, end, category)
+	}
+}
+
+/// An auction system attaching an encoded subject to auctions, e.g. the id of
+/// the auctioned item.
+pub trait AuctionWithSubject<AccountId, BlockNumber>: AuctionWithCategory<AccountId, BlockNumber> {
+	/// The subject of an auction.
+	type AuctionSubject: FullCodec + Clone + Debug;
+
+	/// Create new auction with specific startblock, endblock, category and
+	/// subject, return the id of the auction
+	fn new_auction_with_subject(
+		start: BlockNumber,
+		end: Option<BlockNumber>,
+		category: Option<Self::AuctionCategory>,
+		subject: Self::AuctionSubject,
+	) -> result::Result<Self::AuctionId, DispatchError>;
+}
+
 /// The result of bid handling.
 pub struct OnNewBidResult<BlockNumber> {
 	/// Indicates if the bid was accepted
@@ -51,7 +98,12 @@ pub struct OnNewBidResult<BlockNumber> {
 }
 
 /// Hooks for auction to handle bids.
-pub trait AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> {
+///
+/// Auctions are handled with the `*_with_category` hooks, which receive the
+/// `category` of the auction so that a single handler can route auctions of
+/// different kinds to distinct logic. They call the hooks without a category
+/// by default.
+pub trait AuctionHandler<AccountId, Balance, BlockNumber, AuctionId, AuctionCategory = ()> {
 	/// Called when new bid is received.
 	/// The return value determines if the bid should be accepted and update
 	/// auction end time. Implementation should reserve money from current
@@ -64,4 +116,29 @@ pub trait AuctionHandler<AccountId, Balance, BlockNumber, AuctionId> {
 	) -> OnNewBidResult<BlockNumber>;
 	/// End an auction with `winner`
 	fn on_auction_ended(id: AuctionId, winner: Option<(AccountId, Balance)>);
+	/// Called when new bid is received for an auction of `category`.
+	fn on_new_bid_with_category(
+		now: BlockNumber,
+		id: AuctionId,
+		_category: Option<AuctionCategory>,
+		new_bid: (AccountId, Balance),
+		last_bid: Option<(AccountId, Balance)>,
+	) -> OnNewBidResult<BlockNumber> {
+		Self::on_new_bid(now, id, new_bid, last_bid)
+	}
+	/// End an auction of `category` with `winner`
+	fn on_auction_ended_with_category(
+		id: AuctionId,
+		_category: Option<AuctionCategory>,
+		winner: Option<(AccountId, Balance)>,
+	) {
+		Self::on_auction_ended(id, winner)
+	}
+}
+
+/// Hook for auctions with a hidden reserve price.
+pub trait ReserveNotMetHandler<AccountId, Balance, AuctionId, AuctionCategory = ()> {
+	/// End an auction whose hidden reserve was not met or not revealed.
+	/// Implementation should refund `last_bid`.
+	fn on_reserve_not_met(id: AuctionId, category: Option<AuctionCategory>, last_bid: (AccountId, Balance));
 }
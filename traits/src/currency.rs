@@ -11,6 +11,7 @@ use sp_runtime::{
 use sp_std::{
 	cmp::{Eq, Ordering, PartialEq},
 	fmt::Debug,
+	prelude::Vec,
 	result,
 };
 
@@ -82,6 +83,33 @@ pub trait MultiCurrency<AccountId> {
 	/// As much funds up to `amount` will be deducted as possible. If this is
 	/// less than `amount`, then a non-zero excess value will be returned.
 	fn slash(currency_id: Self::CurrencyId, who: &AccountId, amount: Self::Balance) -> Self::Balance;
+
+	/// Transfer some amount from `from` to each of the given accounts.
+	///
+	/// Either all transfers succeed or none of them are applied.
+	#[transactional]
+	fn transfer_batch(
+		currency_id: Self::CurrencyId,
+		from: &AccountId,
+		transfers: &[(AccountId, Self::Balance)],
+	) -> DispatchResult {
+		for (to, amount) in transfers {
+			Self::transfer(currency_id, from, to, *amount)?;
+		}
+		Ok(())
+	}
+
+	/// Deposit some amount into each of the given accounts under
+	/// `currency_id` and increase total issuance accordingly.
+	///
+	/// Either all deposits succeed or none of them are applied.
+	#[transactional]
+	fn deposit_batch(currency_id: Self::CurrencyId, deposits: &[(AccountId, Self::Balance)]) -> DispatchResult {
+		for (who, amount) in deposits {
+			Self::deposit(currency_id, who, *amount)?;
+		}
+		Ok(())
+	}
 }
 
 /// Extended `MultiCurrency` with additional helper types and methods.
@@ -636,6 +664,32 @@ pub trait NamedBasicReservableCurrency<AccountId, ReserveIdentifier>: BasicReser
 	}
 }
 
+/// Name, symbol and decimals of currencies.
+pub trait CurrencyMetadata<CurrencyId> {
+	/// The name of `currency_id`, `None` if the currency is unknown.
+	fn name(currency_id: &CurrencyId) -> Option<Vec<u8>>;
+
+	/// The symbol of `currency_id`, `None` if the currency is unknown.
+	fn symbol(currency_id: &CurrencyId) -> Option<Vec<u8>>;
+
+	/// The decimals of `currency_id`, `None` if the currency is unknown.
+	fn decimals(currency_id: &CurrencyId) -> Option<u32>;
+}
+
+impl<CurrencyId> CurrencyMetadata<CurrencyId> for () {
+	fn name(_: &CurrencyId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn symbol(_: &CurrencyId) -> Option<Vec<u8>> {
+		None
+	}
+
+	fn decimals(_: &CurrencyId) -> Option<u32> {
+		None
+	}
+}
+
 /// Handler for account which has dust, need to burn or recycle it
 pub trait OnDust<AccountId, CurrencyId, Balance> {
 	fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance);
@@ -691,6 +745,20 @@ impl<AccountId, CurrencyId, Balance> OnTransfer<AccountId, CurrencyId, Balance>
 	}
 }
 
+/// Hook to run after balance moves from an account to another, into an
+/// account (`from` is `None`) or out of an account (`to` is `None`). Reserving
+/// and unreserving do not move balance.
+///
+/// Can be used to mirror balance changes, for example as ERC20 `Transfer` logs
+/// of bridged token representations.
+pub trait OnBalanceMovement<AccountId, CurrencyId, Balance> {
+	fn on_balance_movement(currency_id: CurrencyId, from: Option<&AccountId>, to: Option<&AccountId>, amount: Balance);
+}
+
+impl<AccountId, CurrencyId, Balance> OnBalanceMovement<AccountId, CurrencyId, Balance> for () {
+	fn on_balance_movement(_: CurrencyId, _: Option<&AccountId>, _: Option<&AccountId>, _: Balance) {}
+}
+
 pub trait MutationHooks<AccountId, CurrencyId, Balance> {
 	/// Handler to burn or transfer account's dust.
 	type OnDust: OnDust<AccountId, CurrencyId, Balance>;
@@ -22,6 +22,28 @@ pub trait DataProviderExtended<Key, TimestampedValue> {
 	fn get_all_values() -> Vec<(Key, Option<TimestampedValue>)>;
 }
 
+/// A value along with the moment it was provided at.
+pub trait Timestamped {
+	type Value;
+	type Moment;
+
+	/// The provided value
+	fn value(&self) -> Self::Value;
+	/// The moment the value was provided at
+	fn timestamp(&self) -> Self::Moment;
+}
+
+/// Called when a priority data provider served a read.
+pub trait OnPriorityRead<Key> {
+	/// `source` is the position of the source which served the read of `key`,
+	/// in priority order.
+	fn on_read(key: &Key, source: u32);
+}
+
+impl<Key> OnPriorityRead<Key> for () {
+	fn on_read(_key: &Key, _source: u32) {}
+}
+
 #[allow(dead_code)] // rust cannot detect usage in macro_rules
 pub fn median<T: Ord + Clone>(mut items: Vec<T>) -> Option<T> {
 	if items.is_empty() {
@@ -73,9 +95,64 @@ macro_rules! create_median_value_data_provider {
 	}
 }
 
+/// Create a data provider that reads from several sources in priority order.
+///
+/// Each source is paired with the maximum age of its values. A source whose
+/// value is missing or older than its maximum age, compared to the moment
+/// returned by `$now`, is skipped in favour of the next one. `$on_read` is
+/// told which source served every `DataProvider::get` read.
+#[macro_export]
+macro_rules! create_priority_data_provider {
+	($name:ident, $key:ty, $value:ty, $timestamped_value:ty, $now:ty, $on_read:ty, [$( ($provider:ty, $max_age:ty) ),*]) => {
+		pub struct $name;
+		impl $name {
+			/// Get the first fresh timestamped value for `key` and the
+			/// position of the source which provided it.
+			#[allow(unused_assignments)]
+			pub fn get_with_source(key: &$key) -> Option<($timestamped_value, u32)> {
+				let now = <$now as frame_support::traits::Get<_>>::get();
+				let mut source: u32 = 0;
+				$(
+					if let Some(v) = <$provider as $crate::DataProviderExtended<$key, $timestamped_value>>::get_no_op(&key) {
+						let timestamp = <$timestamped_value as $crate::data_provider::Timestamped>::timestamp(&v);
+						let max_age = <$max_age as frame_support::traits::Get<_>>::get();
+						if sp_runtime::traits::Saturating::saturating_sub(now, timestamp) <= max_age {
+							return Some((v, source));
+						}
+					}
+					source += 1;
+				)*
+				None
+			}
+		}
+		impl $crate::DataProvider<$key, $value> for $name {
+			fn get(key: &$key) -> Option<$value> {
+				let (v, source) = Self::get_with_source(key)?;
+				<$on_read as $crate::data_provider::OnPriorityRead<$key>>::on_read(key, source);
+				Some(<$timestamped_value as $crate::data_provider::Timestamped>::value(&v))
+			}
+		}
+		impl $crate::DataProviderExtended<$key, $timestamped_value> for $name {
+			fn get_no_op(key: &$key) -> Option<$timestamped_value> {
+				Self::get_with_source(key).map(|(v, _)| v)
+			}
+			fn get_all_values() -> Vec<($key, Option<$timestamped_value>)> {
+				let mut keys = sp_std::collections::btree_set::BTreeSet::new();
+				$(
+					<$provider as $crate::DataProviderExtended<$key, $timestamped_value>>::get_all_values()
+						.into_iter()
+						.for_each(|(k, _)| { keys.insert(k); });
+				)*
+				keys.into_iter().map(|k| (k, Self::get_no_op(&k))).collect()
+			}
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use frame_support::parameter_types;
 	use sp_std::cell::RefCell;
 
 	thread_local! {
@@ -136,4 +213,101 @@ mod tests {
 			assert_eq!(<Providers as DataProvider<_, _>>::get(&0), target);
 		}
 	}
+
+	#[derive(Clone, Debug, PartialEq)]
+	pub struct Stamped(u8, u64);
+
+	impl Timestamped for Stamped {
+		type Value = u8;
+		type Moment = u64;
+
+		fn value(&self) -> u8 {
+			self.0
+		}
+		fn timestamp(&self) -> u64 {
+			self.1
+		}
+	}
+
+	thread_local! {
+		static PRIMARY: RefCell<Option<Stamped>> = RefCell::new(None);
+		static BACKUP: RefCell<Option<Stamped>> = RefCell::new(None);
+		static LAST_SOURCE: RefCell<Option<u32>> = RefCell::new(None);
+	}
+
+	macro_rules! mock_timestamped_provider {
+		($provider:ident, $value:ident) => {
+			pub struct $provider;
+			impl DataProviderExtended<u8, Stamped> for $provider {
+				fn get_no_op(_: &u8) -> Option<Stamped> {
+					$value.with(|v| v.borrow().clone())
+				}
+				fn get_all_values() -> Vec<(u8, Option<Stamped>)> {
+					vec![(0, Self::get_no_op(&0))]
+				}
+			}
+		};
+	}
+
+	mock_timestamped_provider!(Primary, PRIMARY);
+	mock_timestamped_provider!(Backup, BACKUP);
+
+	pub struct RecordSource;
+	impl OnPriorityRead<u8> for RecordSource {
+		fn on_read(_: &u8, source: u32) {
+			LAST_SOURCE.with(|v| *v.borrow_mut() = Some(source));
+		}
+	}
+
+	parameter_types! {
+		pub const Now: u64 = 100;
+		pub const PrimaryMaxAge: u64 = 10;
+		pub const BackupMaxAge: u64 = 50;
+	}
+
+	create_priority_data_provider!(
+		Prioritized,
+		u8,
+		u8,
+		Stamped,
+		Now,
+		RecordSource,
+		[(Primary, PrimaryMaxAge), (Backup, BackupMaxAge)]
+	);
+
+	fn set(primary: Option<Stamped>, backup: Option<Stamped>) {
+		PRIMARY.with(|v| *v.borrow_mut() = primary);
+		BACKUP.with(|v| *v.borrow_mut() = backup);
+		LAST_SOURCE.with(|v| *v.borrow_mut() = None);
+	}
+
+	fn last_source() -> Option<u32> {
+		LAST_SOURCE.with(|v| *v.borrow())
+	}
+
+	#[test]
+	fn priority_data_provider_works() {
+		set(None, None);
+		assert_eq!(<Prioritized as DataProvider<_, _>>::get(&0), None);
+		assert_eq!(last_source(), None);
+
+		// the primary source wins while it is fresh
+		set(Some(Stamped(1, 95)), Some(Stamped(2, 99)));
+		assert_eq!(<Prioritized as DataProvider<_, _>>::get(&0), Some(1));
+		assert_eq!(last_source(), Some(0));
+
+		// a stale primary falls back to the backup
+		set(Some(Stamped(1, 80)), Some(Stamped(2, 60)));
+		assert_eq!(<Prioritized as DataProvider<_, _>>::get(&0), Some(2));
+		assert_eq!(last_source(), Some(1));
+		assert_eq!(Prioritized::get_with_source(&0), Some((Stamped(2, 60), 1)));
+
+		// nothing is served when every source is stale
+		set(Some(Stamped(1, 80)), Some(Stamped(2, 40)));
+		assert_eq!(<Prioritized as DataProviderExtended<_, _>>::get_no_op(&0), None);
+		assert_eq!(
+			<Prioritized as DataProviderExtended<_, _>>::get_all_values(),
+			vec![(0, None)]
+		);
+	}
 }
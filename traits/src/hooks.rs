@@ -0,0 +1,241 @@
+use crate::{
+	currency::{OnBalanceMovement, OnDeposit, OnDust, OnSlash, OnTransfer},
+	Handler, Happened, OnNewData,
+};
+use sp_runtime::DispatchResult;
+use sp_std::marker::PhantomData;
+
+/// Runs a tuple of hooks in order.
+///
+/// Fallible hooks stop at the first error and return it, so later hooks are
+/// not run.
+pub struct HookChain<Hooks>(PhantomData<Hooks>);
+
+/// Runs a tuple of fallible hooks in order until one succeeds.
+///
+/// Returns the error of the last hook if none succeeds.
+pub struct FirstOkHook<Hooks>(PhantomData<Hooks>);
+
+macro_rules! impl_hook_chain {
+	($first:ident $(, $rest:ident)*) => {
+		impl<T, $first: Happened<T>, $($rest: Happened<T>),*> Happened<T> for HookChain<($first, $($rest,)*)> {
+			fn happened(t: &T) {
+				$first::happened(t);
+				$( $rest::happened(t); )*
+			}
+		}
+
+		impl<
+			AccountId, Key, Value,
+			$first: OnNewData<AccountId, Key, Value>,
+			$($rest: OnNewData<AccountId, Key, Value>),*
+		> OnNewData<AccountId, Key, Value> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_new_data(who: &AccountId, key: &Key, value: &Value) {
+				$first::on_new_data(who, key, value);
+				$( $rest::on_new_data(who, key, value); )*
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnDust<AccountId, CurrencyId, Balance>,
+			$($rest: OnDust<AccountId, CurrencyId, Balance>),*
+		> OnDust<AccountId, CurrencyId, Balance> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_dust(who: &AccountId, currency_id: CurrencyId, amount: Balance) {
+				$first::on_dust(who, currency_id.clone(), amount.clone());
+				$( $rest::on_dust(who, currency_id.clone(), amount.clone()); )*
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnSlash<AccountId, CurrencyId, Balance>,
+			$($rest: OnSlash<AccountId, CurrencyId, Balance>),*
+		> OnSlash<AccountId, CurrencyId, Balance> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+				$first::on_slash(currency_id.clone(), who, amount.clone());
+				$( $rest::on_slash(currency_id.clone(), who, amount.clone()); )*
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnBalanceMovement<AccountId, CurrencyId, Balance>,
+			$($rest: OnBalanceMovement<AccountId, CurrencyId, Balance>),*
+		> OnBalanceMovement<AccountId, CurrencyId, Balance> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_balance_movement(
+				currency_id: CurrencyId,
+				from: Option<&AccountId>,
+				to: Option<&AccountId>,
+				amount: Balance,
+			) {
+				$first::on_balance_movement(currency_id.clone(), from, to, amount.clone());
+				$( $rest::on_balance_movement(currency_id.clone(), from, to, amount.clone()); )*
+			}
+		}
+
+		impl<T, $first: Handler<T>, $($rest: Handler<T>),*> Handler<T> for HookChain<($first, $($rest,)*)> {
+			fn handle(t: &T) -> DispatchResult {
+				$first::handle(t)?;
+				$( $rest::handle(t)?; )*
+				Ok(())
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnDeposit<AccountId, CurrencyId, Balance>,
+			$($rest: OnDeposit<AccountId, CurrencyId, Balance>),*
+		> OnDeposit<AccountId, CurrencyId, Balance> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+				$first::on_deposit(currency_id.clone(), who, amount.clone())?;
+				$( $rest::on_deposit(currency_id.clone(), who, amount.clone())?; )*
+				Ok(())
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnTransfer<AccountId, CurrencyId, Balance>,
+			$($rest: OnTransfer<AccountId, CurrencyId, Balance>),*
+		> OnTransfer<AccountId, CurrencyId, Balance> for HookChain<($first, $($rest,)*)>
+		{
+			fn on_transfer(
+				currency_id: CurrencyId,
+				from: &AccountId,
+				to: &AccountId,
+				amount: Balance,
+			) -> DispatchResult {
+				$first::on_transfer(currency_id.clone(), from, to, amount.clone())?;
+				$( $rest::on_transfer(currency_id.clone(), from, to, amount.clone())?; )*
+				Ok(())
+			}
+		}
+
+		impl<T, $first: Handler<T>, $($rest: Handler<T>),*> Handler<T> for FirstOkHook<($first, $($rest,)*)> {
+			fn handle(t: &T) -> DispatchResult {
+				$first::handle(t)
+					$( .or_else(|_| $rest::handle(t)) )*
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnDeposit<AccountId, CurrencyId, Balance>,
+			$($rest: OnDeposit<AccountId, CurrencyId, Balance>),*
+		> OnDeposit<AccountId, CurrencyId, Balance> for FirstOkHook<($first, $($rest,)*)>
+		{
+			fn on_deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+				$first::on_deposit(currency_id.clone(), who, amount.clone())
+					$( .or_else(|_| $rest::on_deposit(currency_id.clone(), who, amount.clone())) )*
+			}
+		}
+
+		impl<
+			AccountId, CurrencyId: Clone, Balance: Clone,
+			$first: OnTransfer<AccountId, CurrencyId, Balance>,
+			$($rest: OnTransfer<AccountId, CurrencyId, Balance>),*
+		> OnTransfer<AccountId, CurrencyId, Balance> for FirstOkHook<($first, $($rest,)*)>
+		{
+			fn on_transfer(
+				currency_id: CurrencyId,
+				from: &AccountId,
+				to: &AccountId,
+				amount: Balance,
+			) -> DispatchResult {
+				$first::on_transfer(currency_id.clone(), from, to, amount.clone())
+					$( .or_else(|_| $rest::on_transfer(currency_id.clone(), from, to, amount.clone())) )*
+			}
+		}
+	};
+}
+
+impl_hook_chain!(A);
+impl_hook_chain!(A, B);
+impl_hook_chain!(A, B, C);
+impl_hook_chain!(A, B, C, D);
+impl_hook_chain!(A, B, C, D, E);
+impl_hook_chain!(A, B, C, D, E, F);
+impl_hook_chain!(A, B, C, D, E, F, G);
+impl_hook_chain!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::DispatchError;
+	use std::cell::RefCell;
+
+	thread_local! {
+		static CALLED: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+	}
+
+	fn called() -> Vec<u32> {
+		CALLED.with(|v| v.take())
+	}
+
+	pub struct Hook<const ID: u32, const OK: bool>;
+	impl<const ID: u32, const OK: bool> Happened<u32> for Hook<ID, OK> {
+		fn happened(_: &u32) {
+			CALLED.with(|v| v.borrow_mut().push(ID));
+		}
+	}
+	impl<const ID: u32, const OK: bool> Handler<u32> for Hook<ID, OK> {
+		fn handle(_: &u32) -> DispatchResult {
+			CALLED.with(|v| v.borrow_mut().push(ID));
+			if OK {
+				Ok(())
+			} else {
+				Err(DispatchError::Other("hook failed"))
+			}
+		}
+	}
+	impl<const ID: u32, const OK: bool> OnDust<u32, u32, u64> for Hook<ID, OK> {
+		fn on_dust(_: &u32, _: u32, _: u64) {
+			CALLED.with(|v| v.borrow_mut().push(ID));
+		}
+	}
+
+	#[test]
+	fn hook_chain_runs_all_hooks() {
+		<HookChain<(Hook<1, true>, Hook<2, true>, Hook<3, true>)> as Happened<u32>>::happened(&0);
+		assert_eq!(called(), vec![1, 2, 3]);
+
+		<HookChain<(Hook<1, true>, Hook<2, true>)> as OnDust<u32, u32, u64>>::on_dust(&0, 0, 10);
+		assert_eq!(called(), vec![1, 2]);
+	}
+
+	#[test]
+	fn hook_chain_stops_at_first_error() {
+		assert_eq!(
+			<HookChain<(Hook<1, true>, Hook<2, false>, Hook<3, true>)> as Handler<u32>>::handle(&0),
+			Err(DispatchError::Other("hook failed"))
+		);
+		assert_eq!(called(), vec![1, 2]);
+
+		assert_eq!(
+			<HookChain<(Hook<1, true>, Hook<2, true>)> as Handler<u32>>::handle(&0),
+			Ok(())
+		);
+		assert_eq!(called(), vec![1, 2]);
+	}
+
+	#[test]
+	fn first_ok_hook_stops_at_first_success() {
+		assert_eq!(
+			<FirstOkHook<(Hook<1, false>, Hook<2, true>, Hook<3, true>)> as Handler<u32>>::handle(&0),
+			Ok(())
+		);
+		assert_eq!(called(), vec![1, 2]);
+
+		assert_eq!(
+			<FirstOkHook<(Hook<1, false>, Hook<2, false>)> as Handler<u32>>::handle(&0),
+			Err(DispatchError::Other("hook failed"))
+		);
+		assert_eq!(called(), vec![1, 2]);
+	}
+}
@@ -8,19 +8,25 @@ use sp_std::{
 	prelude::Vec,
 };
 
-pub use asset_registry::{FixedConversionRateProvider, WeightToFeeConverter};
-pub use auction::{Auction, AuctionHandler, AuctionInfo, OnNewBidResult};
+pub use asset_registry::{FeeCurveProvider, FixedConversionRateProvider, WeightToFeeConverter};
+pub use auction::{
+	Auction, AuctionHandler, AuctionInfo, AuctionWithCategory, AuctionWithSubject, OnNewBidResult, ReserveNotMetHandler,
+};
 pub use currency::{
 	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
-	NamedBasicReservableCurrency, NamedMultiReservableCurrency,
+	CurrencyMetadata, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReservableCurrency, NamedBasicReservableCurrency, NamedMultiReservableCurrency,
 };
-pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended};
+pub use data_provider::{DataFeeder, DataProvider, DataProviderExtended, OnPriorityRead, Timestamped};
 pub use get_by_key::GetByKey;
+pub use hooks::{FirstOkHook, HookChain};
 pub use multi_asset::ConcreteFungibleAsset;
-pub use nft::InspectExtended;
-pub use price::{DefaultPriceProvider, PriceProvider};
-pub use rewards::RewardHandler;
+pub use nft::{InspectExtended, NftUser};
+pub use price::{
+	DataHistoryProvider, DefaultPriceProvider, DefaultTwapProvider, InversePriceProvider, PriceProvider,
+	RoutedPriceProvider, TwapProvider,
+};
+pub use rewards::{PenaltyDestination, RewardHandler, RewardPoolPause, WithdrawPenalty, YieldSource};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -32,6 +38,7 @@ pub mod auction;
 pub mod currency;
 pub mod data_provider;
 pub mod get_by_key;
+pub mod hooks;
 pub mod location;
 pub mod multi_asset;
 pub mod nft;
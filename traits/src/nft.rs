@@ -14,3 +14,15 @@ pub trait InspectExtended<AccountId>: Inspect<AccountId> {
 	/// Get the next token ID to be minted for a Class
 	fn next_token_id(class: Self::CollectionId) -> Self::ItemId;
 }
+
+/// Time-limited users of NFTs, distinct from their owners (ERC-4907).
+///
+/// The user of a token can use it, e.g. a rented game item, without owning it.
+pub trait NftUser<AccountId, ClassId, TokenId, BlockNumber> {
+	/// The current user of a token, `None` if it has none or the user has
+	/// expired.
+	fn user_of(class: ClassId, token: TokenId) -> Option<AccountId>;
+
+	/// The block the user of a token expires at, `None` if it has no user.
+	fn user_expires(class: ClassId, token: TokenId) -> Option<BlockNumber>;
+}
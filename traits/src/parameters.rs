@@ -7,6 +7,7 @@ use frame_support::Parameter;
 pub use paste;
 #[doc(hidden)]
 pub use scale_info;
+use sp_runtime::DispatchResult;
 
 pub trait RuntimeParameterStore {
 	type AggregratedKeyValue: AggregratedKeyValue;
@@ -34,6 +35,17 @@ pub trait AggregratedKeyValue: Parameter {
 	fn into_parts(self) -> (Self::AggregratedKey, Option<Self::AggregratedValue>);
 }
 
+/// Validate a parameter value before it is stored.
+pub trait ValidateParameter<KV: AggregratedKeyValue> {
+	fn validate(key_value: &KV) -> DispatchResult;
+}
+
+impl<KV: AggregratedKeyValue> ValidateParameter<KV> for () {
+	fn validate(_key_value: &KV) -> DispatchResult {
+		Ok(())
+	}
+}
+
 pub trait ParameterStore<KV: AggregratedKeyValue> {
 	fn get<K>(key: K) -> Option<K::Value>
 	where
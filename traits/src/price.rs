@@ -1,13 +1,31 @@
 use crate::DataProvider;
-use frame_support::Parameter;
-use sp_runtime::traits::{CheckedDiv, MaybeSerializeDeserialize, Member};
-use sp_std::marker::PhantomData;
+use frame_support::{
+	traits::{Get, Time},
+	Parameter,
+};
+use sp_runtime::{
+	traits::{CheckedDiv, MaybeSerializeDeserialize, Member, One, Saturating, Zero},
+	FixedPointNumber,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
 
 /// A trait to provide relative price for two currencies
 pub trait PriceProvider<CurrencyId, Price> {
 	fn get_price(base: CurrencyId, quote: CurrencyId) -> Option<Price>;
 }
 
+/// A trait to provide the time weighted average price for two currencies
+/// over the last `period`
+pub trait TwapProvider<CurrencyId, Price, Period> {
+	fn get_twap(base: CurrencyId, quote: CurrencyId, period: Period) -> Option<Price>;
+}
+
+/// A trait to provide the recent values of a key and the moments they became
+/// effective at, oldest first
+pub trait DataHistoryProvider<Key, Value, Moment> {
+	fn get_history(key: &Key) -> Vec<(Moment, Value)>;
+}
+
 /// A `PriceProvider` implementation based on price data from a `DataProvider`
 pub struct DefaultPriceProvider<CurrencyId, Source>(PhantomData<(CurrencyId, Source)>);
 
@@ -25,6 +43,105 @@ where
 	}
 }
 
+/// A `PriceProvider` implementation that provides the price of a pair as the
+/// reciprocal of the price of the inverse pair from `Source`
+pub struct InversePriceProvider<Source>(PhantomData<Source>);
+
+impl<CurrencyId, Source, Price> PriceProvider<CurrencyId, Price> for InversePriceProvider<Source>
+where
+	Source: PriceProvider<CurrencyId, Price>,
+	Price: FixedPointNumber,
+{
+	fn get_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		Source::get_price(quote_currency_id, base_currency_id)?.reciprocal()
+	}
+}
+
+/// A `PriceProvider` implementation that routes the price of a pair through
+/// a common quote currency, using the prices of both currencies against
+/// `CommonQuote` from `Source`
+pub struct RoutedPriceProvider<CommonQuote, Source>(PhantomData<(CommonQuote, Source)>);
+
+impl<CurrencyId, CommonQuote, Source, Price> PriceProvider<CurrencyId, Price>
+	for RoutedPriceProvider<CommonQuote, Source>
+where
+	CurrencyId: PartialEq,
+	CommonQuote: Get<CurrencyId>,
+	Source: PriceProvider<CurrencyId, Price>,
+	Price: CheckedDiv + One,
+{
+	fn get_price(base_currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		let price_in_common_quote = |currency_id: CurrencyId| {
+			let common_quote = CommonQuote::get();
+			if currency_id == common_quote {
+				Some(Price::one())
+			} else {
+				Source::get_price(currency_id, common_quote)
+			}
+		};
+
+		let base_price = price_in_common_quote(base_currency_id)?;
+		let quote_price = price_in_common_quote(quote_currency_id)?;
+
+		base_price.checked_div(&quote_price)
+	}
+}
+
+/// A `TwapProvider` implementation based on the price history from a
+/// `DataHistoryProvider`, over the `period` up to `Now`
+///
+/// Each price is weighted by the time it was effective during the period.
+/// Time before the first price of the period is not accounted for, `None` is
+/// returned if no price was effective during the period.
+pub struct DefaultTwapProvider<Source, Now>(PhantomData<(Source, Now)>);
+
+impl<Source, Now> DefaultTwapProvider<Source, Now> {
+	fn get_twap_of<CurrencyId, Price>(currency_id: &CurrencyId, period: Now::Moment) -> Option<Price>
+	where
+		Source: DataHistoryProvider<CurrencyId, Price, Now::Moment>,
+		Now: Time,
+		Now::Moment: Into<u128>,
+		Price: FixedPointNumber,
+	{
+		let now = Now::now();
+		let start = now.saturating_sub(period);
+		let history = Source::get_history(currency_id);
+
+		// the first price effective during the period, which may precede it
+		let first = history.iter().rposition(|(at, _)| *at <= start).unwrap_or(0);
+		let history = &history[first..];
+		let covered_from = history.first().map(|(at, _)| (*at).max(start))?;
+		let covered: u128 = now.saturating_sub(covered_from).into();
+		if covered.is_zero() {
+			return None;
+		}
+
+		let mut weighted_sum = Price::zero();
+		for (i, (at, price)) in history.iter().enumerate() {
+			let from = (*at).max(start).min(now);
+			let to = history.get(i + 1).map_or(now, |(next, _)| (*next).min(now));
+			let duration: u128 = to.saturating_sub(from).into();
+			weighted_sum = weighted_sum.saturating_add(price.saturating_mul(Price::saturating_from_integer(duration)));
+		}
+		weighted_sum.checked_div(&Price::saturating_from_integer(covered))
+	}
+}
+
+impl<CurrencyId, Source, Now, Price> TwapProvider<CurrencyId, Price, Now::Moment> for DefaultTwapProvider<Source, Now>
+where
+	Source: DataHistoryProvider<CurrencyId, Price, Now::Moment>,
+	Now: Time,
+	Now::Moment: Into<u128>,
+	Price: FixedPointNumber,
+{
+	fn get_twap(base_currency_id: CurrencyId, quote_currency_id: CurrencyId, period: Now::Moment) -> Option<Price> {
+		let base_twap = Self::get_twap_of(&base_currency_id, period)?;
+		let quote_twap = Self::get_twap_of(&quote_currency_id, period)?;
+
+		base_twap.checked_div(&quote_twap)
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -46,6 +163,23 @@ mod test {
 
 	type TestPriceProvider = DefaultPriceProvider<u32, MockDataProvider>;
 
+	frame_support::parameter_types! {
+		pub const CommonQuote: u32 = 1;
+	}
+
+	/// Only provides prices quoted in currency 1
+	pub struct MockQuotedPriceProvider;
+	impl PriceProvider<u32, Price> for MockQuotedPriceProvider {
+		fn get_price(base: u32, quote: u32) -> Option<Price> {
+			match (base, quote) {
+				(2, 1) => Some(Price::saturating_from_integer(2)),
+				(4, 1) => Some(Price::saturating_from_integer(8)),
+				(0, 1) => Some(Price::from_inner(0)),
+				_ => None,
+			}
+		}
+	}
+
 	#[test]
 	fn get_price_should_work() {
 		assert_eq!(
@@ -71,4 +205,76 @@ mod test {
 		assert_eq!(TestPriceProvider::get_price(1, 0), None);
 		assert_eq!(TestPriceProvider::get_price(0, 1), Some(Price::from_inner(0)));
 	}
+
+	#[test]
+	fn inverse_price_should_work() {
+		type Inverse = InversePriceProvider<MockQuotedPriceProvider>;
+
+		assert_eq!(Inverse::get_price(1, 2), Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(Inverse::get_price(1, 4), Some(Price::saturating_from_rational(1, 8)));
+		assert_eq!(Inverse::get_price(2, 1), None);
+		// zero price has no reciprocal
+		assert_eq!(Inverse::get_price(1, 0), None);
+	}
+
+	frame_support::parameter_types! {
+		pub static Now: u64 = 0;
+	}
+
+	pub struct MockTime;
+	impl Time for MockTime {
+		type Moment = u64;
+
+		fn now() -> u64 {
+			Now::get()
+		}
+	}
+
+	pub struct MockHistoryProvider;
+	impl DataHistoryProvider<u32, Price, u64> for MockHistoryProvider {
+		fn get_history(currency: &u32) -> Vec<(u64, Price)> {
+			match currency {
+				1 => vec![(10, Price::saturating_from_integer(1))],
+				2 => vec![
+					(10, Price::saturating_from_integer(1)),
+					(20, Price::saturating_from_integer(2)),
+					(30, Price::saturating_from_integer(6)),
+				],
+				_ => vec![],
+			}
+		}
+	}
+
+	#[test]
+	fn twap_should_work() {
+		type Twap = DefaultTwapProvider<MockHistoryProvider, MockTime>;
+
+		Now::set(40);
+		// 10 moments of each price
+		assert_eq!(Twap::get_twap(2, 1, 30), Some(Price::saturating_from_integer(3)));
+		assert_eq!(Twap::get_twap(2, 1, 20), Some(Price::saturating_from_integer(4)));
+		// the price before the period is effective at its start
+		assert_eq!(Twap::get_twap(2, 1, 25), Some(Price::saturating_from_rational(17, 5)));
+		// only the time since the first price is accounted for
+		assert_eq!(Twap::get_twap(2, 1, 100), Some(Price::saturating_from_integer(3)));
+		assert_eq!(Twap::get_twap(1, 2, 20), Some(Price::saturating_from_rational(1, 4)));
+		assert_eq!(Twap::get_twap(2, 3, 30), None);
+
+		// no price was effective during the period
+		Now::set(10);
+		assert_eq!(Twap::get_twap(2, 1, 10), None);
+	}
+
+	#[test]
+	fn routed_price_should_work() {
+		type Routed = RoutedPriceProvider<CommonQuote, MockQuotedPriceProvider>;
+
+		assert_eq!(Routed::get_price(4, 2), Some(Price::saturating_from_integer(4)));
+		assert_eq!(Routed::get_price(2, 4), Some(Price::saturating_from_rational(1, 4)));
+		assert_eq!(Routed::get_price(2, 1), Some(Price::saturating_from_integer(2)));
+		assert_eq!(Routed::get_price(1, 2), Some(Price::saturating_from_rational(1, 2)));
+		assert_eq!(Routed::get_price(1, 1), Some(Price::one()));
+		assert_eq!(Routed::get_price(3, 1), None);
+		assert_eq!(Routed::get_price(2, 0), None);
+	}
 }
@@ -1,6 +1,7 @@
 use codec::FullCodec;
-use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize};
-use sp_std::fmt::Debug;
+use frame_support::weights::Weight;
+use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Zero};
+use sp_std::{fmt::Debug, vec::Vec};
 
 /// Hooks to manage reward pool
 pub trait RewardHandler<AccountId, CurrencyId> {
@@ -13,3 +14,77 @@ pub trait RewardHandler<AccountId, CurrencyId> {
 	/// Payout the reward to `who`
 	fn payout(who: &AccountId, pool: &Self::PoolId, currency_id: CurrencyId, amount: Self::Balance);
 }
+
+/// Pause switches for share changes of reward pools, e.g. for an admin pallet
+/// during incident response. Rewards keep accumulating while paused.
+pub trait RewardPoolPause<PoolId> {
+	/// Pause or unpause adding shares to `pool`
+	fn set_share_additions_paused(pool: &PoolId, paused: bool);
+
+	/// Pause or unpause removing shares from `pool`
+	fn set_share_removals_paused(pool: &PoolId, paused: bool);
+
+	/// Whether adding shares to `pool` is paused
+	fn share_additions_paused(pool: &PoolId) -> bool;
+
+	/// Whether removing shares from `pool` is paused
+	fn share_removals_paused(pool: &PoolId) -> bool;
+}
+
+/// Source of rewards accrued by reward pools outside of explicit
+/// accumulation, e.g. staking token appreciation or fee revenue
+pub trait YieldSource<PoolId, CurrencyId, Balance> {
+	/// The rewards accrued by `pool` since it was last queried, to be
+	/// accumulated into the pool
+	fn take_yield(pool: &PoolId) -> Vec<(CurrencyId, Balance)>;
+
+	/// The weight of one `take_yield` call
+	fn take_yield_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+impl<PoolId, CurrencyId, Balance> YieldSource<PoolId, CurrencyId, Balance> for () {
+	fn take_yield(_pool: &PoolId) -> Vec<(CurrencyId, Balance)> {
+		Vec::new()
+	}
+}
+
+/// Where a withdraw penalty is routed to
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PenaltyDestination<AccountId> {
+	/// Added to the pool rewards, paid out to the remaining stakers
+	Stakers,
+	/// Paid out to the given account, e.g. a treasury
+	Account(AccountId),
+}
+
+/// Hooks to penalize claiming rewards or removing shares before a minimum
+/// staking duration
+pub trait WithdrawPenalty<AccountId, PoolId, CurrencyId, Balance, BlockNumber> {
+	/// The number of blocks shares of `pool` must be held for before their
+	/// rewards can be claimed or they can be removed without penalty
+	fn minimum_duration(pool: &PoolId) -> BlockNumber;
+
+	/// The part of `reward` deducted when `who` claims it early
+	fn penalty(who: &AccountId, pool: &PoolId, currency_id: CurrencyId, reward: Balance) -> Balance;
+
+	/// Where the penalties of `pool` are routed to
+	fn destination(pool: &PoolId) -> PenaltyDestination<AccountId>;
+}
+
+impl<AccountId, PoolId, CurrencyId, Balance: Zero, BlockNumber: Zero>
+	WithdrawPenalty<AccountId, PoolId, CurrencyId, Balance, BlockNumber> for ()
+{
+	fn minimum_duration(_pool: &PoolId) -> BlockNumber {
+		Zero::zero()
+	}
+
+	fn penalty(_who: &AccountId, _pool: &PoolId, _currency_id: CurrencyId, _reward: Balance) -> Balance {
+		Zero::zero()
+	}
+
+	fn destination(_pool: &PoolId) -> PenaltyDestination<AccountId> {
+		PenaltyDestination::Stakers
+	}
+}
@@ -0,0 +1,123 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::{U256, U512};
+use sp_runtime::RuntimeDebug;
+
+/// The direction a division that is not exact is rounded in.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Rounding {
+	/// Round towards zero.
+	Down,
+	/// Round away from zero.
+	Up,
+}
+
+/// Compute `a * b / c` without overflowing in the multiplication, rounded in
+/// the given direction.
+///
+/// Returns `None` if `c` is zero or the result does not fit in a `u128`.
+pub fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Option<u128> {
+	if c == 0 {
+		return None;
+	}
+	let product = U256::from(a).full_mul(U256::from(b));
+	let (mut quotient, remainder) = product.div_mod(U512::from(c));
+	if rounding == Rounding::Up && !remainder.is_zero() {
+		quotient = quotient.checked_add(U512::one())?;
+	}
+	(quotient <= U512::from(u128::MAX)).then(|| quotient.low_u128())
+}
+
+/// A non-negative fixed-point value with `DECIMALS` decimal places, stored as
+/// a `u128` mantissa scaled by `10^DECIMALS`.
+///
+/// Meant for values accumulated over time, e.g. the reward per share of a pool
+/// or an interest index. `DECIMALS` must not exceed 38.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct FixedAccumulator<const DECIMALS: u32>(u128);
+
+impl<const DECIMALS: u32> FixedAccumulator<DECIMALS> {
+	/// The accumulator with a mantissa of `mantissa`.
+	pub const fn from_mantissa(mantissa: u128) -> Self {
+		Self(mantissa)
+	}
+
+	/// The mantissa, the value scaled by `10^DECIMALS`.
+	pub const fn mantissa(&self) -> u128 {
+		self.0
+	}
+
+	/// The scale of the mantissa, `10^DECIMALS`.
+	pub fn accuracy() -> u128 {
+		10u128.pow(DECIMALS)
+	}
+
+	/// The value `n / d`, `None` if `d` is zero or the value overflows.
+	pub fn from_rational(n: u128, d: u128, rounding: Rounding) -> Option<Self> {
+		mul_div(n, Self::accuracy(), d, rounding).map(Self)
+	}
+
+	/// Add `n / d` rounded down, e.g. a reward spread over the total shares.
+	///
+	/// Nothing is added if `d` is zero, and the mantissa saturates at
+	/// `u128::MAX`.
+	pub fn saturating_accumulate(&mut self, n: u128, d: u128) {
+		if d == 0 {
+			return;
+		}
+		let increment = mul_div(n, Self::accuracy(), d, Rounding::Down).unwrap_or(u128::MAX);
+		self.0 = self.0.saturating_add(increment);
+	}
+
+	/// `amount` multiplied by the value, e.g. the reward of a share. `None` if
+	/// the result overflows.
+	pub fn mul_int(&self, amount: u128, rounding: Rounding) -> Option<u128> {
+		mul_div(amount, self.0, Self::accuracy(), rounding)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mul_div_rounds_in_the_given_direction() {
+		assert_eq!(mul_div(10, 10, 3, Rounding::Down), Some(33));
+		assert_eq!(mul_div(10, 10, 3, Rounding::Up), Some(34));
+		assert_eq!(mul_div(10, 10, 4, Rounding::Up), Some(25));
+		assert_eq!(mul_div(10, 10, 0, Rounding::Down), None);
+
+		// the product may exceed `u128`
+		assert_eq!(
+			mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down),
+			Some(u128::MAX)
+		);
+		assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Down), None);
+		assert_eq!(mul_div(u128::MAX, 3, 2, Rounding::Up), None);
+	}
+
+	#[test]
+	fn fixed_accumulator_works() {
+		type Accumulator = FixedAccumulator<12>;
+
+		assert_eq!(Accumulator::accuracy(), 1_000_000_000_000);
+		assert_eq!(
+			Accumulator::from_rational(1, 3, Rounding::Up),
+			Some(Accumulator::from_mantissa(333_333_333_334))
+		);
+		assert_eq!(Accumulator::from_rational(1, 0, Rounding::Down), None);
+
+		let mut per_share = Accumulator::default();
+		per_share.saturating_accumulate(100, 3);
+		per_share.saturating_accumulate(100, 0);
+		assert_eq!(per_share.mantissa(), 33_333_333_333_333);
+		assert_eq!(per_share.mul_int(3, Rounding::Down), Some(99));
+		assert_eq!(per_share.mul_int(3, Rounding::Up), Some(100));
+
+		per_share.saturating_accumulate(u128::MAX, 1);
+		assert_eq!(per_share.mantissa(), u128::MAX);
+		assert_eq!(per_share.mul_int(u128::MAX, Rounding::Down), None);
+	}
+}
@@ -4,11 +4,15 @@ use frame_support::storage::{with_transaction, TransactionOutcome};
 use sp_runtime::DispatchError;
 use sp_std::result::Result;
 
+pub mod fixed_math;
 pub mod offchain_worker;
 pub mod ordered_set;
+pub mod storage_pager;
 
+pub use fixed_math::{mul_div, FixedAccumulator, Rounding};
 pub use offchain_worker::OffchainErr;
 pub use ordered_set::OrderedSet;
+pub use storage_pager::{PageOutcome, PagerError, StorageDoubleMapPager, StoragePager};
 
 /// Execute the supplied function in a new storage transaction.
 ///
@@ -63,6 +67,16 @@ mod tests {
 
 		#[pallet::storage]
 		pub type Map<T: Config> = StorageMap<_, Twox64Concat, [u8; 4], u32, ValueQuery>;
+
+		#[pallet::storage]
+		pub type DoubleMap<T: Config> = StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, u32, ValueQuery>;
+
+		#[pallet::storage]
+		#[pallet::unbounded]
+		pub type Cursor<T: Config> = StorageValue<_, Vec<u8>, OptionQuery>;
+
+		#[pallet::storage]
+		pub type BoundedCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<4>>, OptionQuery>;
 	}
 
 	use module::*;
@@ -176,4 +190,144 @@ mod tests {
 			assert_eq!(Map::<Runtime>::get(b"val0"), 0);
 		});
 	}
+
+	#[test]
+	fn storage_pager_works() {
+		TestExternalities::default().execute_with(|| {
+			type Pager = StoragePager<[u8; 4], u32, Map<Runtime>, Cursor<Runtime>>;
+
+			for i in 0..5u32 {
+				Map::<Runtime>::insert(i.to_le_bytes(), i);
+			}
+
+			let mut seen = vec![];
+			assert_eq!(
+				Pager::process(0, |_, v| seen.push(v)),
+				Ok(PageOutcome {
+					processed: 0,
+					finished: false
+				})
+			);
+			assert!(!Pager::in_progress());
+
+			assert_eq!(
+				Pager::process(2, |_, v| seen.push(v)),
+				Ok(PageOutcome {
+					processed: 2,
+					finished: false
+				})
+			);
+			assert!(Pager::in_progress());
+
+			// items can be drained while paging
+			assert_eq!(
+				Pager::process(2, |k, v| {
+					Map::<Runtime>::remove(k);
+					seen.push(v)
+				}),
+				Ok(PageOutcome {
+					processed: 2,
+					finished: false
+				})
+			);
+			assert_eq!(
+				Pager::process(2, |_, v| seen.push(v)),
+				Ok(PageOutcome {
+					processed: 1,
+					finished: true
+				})
+			);
+			assert!(!Pager::in_progress());
+
+			seen.sort();
+			assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+			assert_eq!(Map::<Runtime>::iter().count(), 3);
+
+			// starts over once finished
+			assert_eq!(
+				Pager::process(10, |_, _| {}),
+				Ok(PageOutcome {
+					processed: 3,
+					finished: true
+				})
+			);
+
+			assert_eq!(
+				Pager::process(1, |_, _| {}),
+				Ok(PageOutcome {
+					processed: 1,
+					finished: false
+				})
+			);
+			Pager::reset();
+			assert!(!Pager::in_progress());
+		});
+	}
+
+	#[test]
+	fn storage_double_map_pager_works() {
+		TestExternalities::default().execute_with(|| {
+			type Pager = StorageDoubleMapPager<u32, u32, u32, DoubleMap<Runtime>, Cursor<Runtime>>;
+
+			for i in 0..3u32 {
+				DoubleMap::<Runtime>::insert(i, i, i);
+				DoubleMap::<Runtime>::insert(i, i + 10, i + 10);
+			}
+
+			let mut seen = vec![];
+			assert_eq!(
+				Pager::process(4, |_, _, v| seen.push(v)),
+				Ok(PageOutcome {
+					processed: 4,
+					finished: false
+				})
+			);
+			assert!(Pager::in_progress());
+			assert_eq!(
+				Pager::process(4, |_, _, v| seen.push(v)),
+				Ok(PageOutcome {
+					processed: 2,
+					finished: true
+				})
+			);
+			assert!(!Pager::in_progress());
+
+			seen.sort();
+			assert_eq!(seen, vec![0, 1, 2, 10, 11, 12]);
+		});
+	}
+
+	#[test]
+	fn storage_pager_rejects_bad_cursors() {
+		TestExternalities::default().execute_with(|| {
+			type Pager = StoragePager<[u8; 4], u32, Map<Runtime>, Cursor<Runtime>>;
+			type BoundedPager =
+				StoragePager<[u8; 4], u32, Map<Runtime>, BoundedCursor<Runtime>, BoundedVec<u8, ConstU32<4>>>;
+
+			for i in 0..3u32 {
+				Map::<Runtime>::insert(i.to_le_bytes(), i);
+			}
+
+			// the raw keys of the map are longer than the cursor
+			assert_eq!(
+				BoundedPager::process(1, |_, _| {}),
+				Err(PagerError::CursorOverflow { processed: 1 })
+			);
+			assert!(!BoundedPager::in_progress());
+
+			// a cursor that can not be decoded is not treated as missing
+			frame_support::storage::unhashed::put_raw(&Cursor::<Runtime>::hashed_key(), &[0xff]);
+			assert_eq!(Pager::process(1, |_, _| {}), Err(PagerError::InvalidCursor));
+			assert!(Pager::in_progress());
+
+			Pager::reset();
+			assert_eq!(
+				Pager::process(3, |_, _| {}),
+				Ok(PageOutcome {
+					processed: 3,
+					finished: true
+				})
+			);
+		});
+	}
 }
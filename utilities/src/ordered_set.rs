@@ -64,6 +64,74 @@ impl<T: Ord + Encode + Decode + MaxEncodedLen + Clone + Eq + PartialEq, S: Get<u
 	pub fn clear(&mut self) {
 		self.0 = BoundedVec::default();
 	}
+
+	/// Return the number of elements in the set
+	pub fn len(&self) -> usize {
+		self.0.len()
+	}
+
+	/// Return if the set is empty
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Return the maximum number of elements the set can hold
+	pub fn capacity() -> usize {
+		S::get() as usize
+	}
+
+	/// Return if the set reached its capacity
+	pub fn is_full(&self) -> bool {
+		self.len() >= Self::capacity()
+	}
+
+	/// Insert an element, evicting the smallest element if the set is full.
+	///
+	/// If the set is full, `value` is only inserted if it is greater than the
+	/// current smallest element, which is then removed and returned.
+	/// Return `Err(value)` if `value` was not inserted, either because it is
+	/// already in the set or because it is not greater than the smallest
+	/// element of a full set.
+	pub fn insert_or_replace_min(&mut self, value: T) -> Result<Option<T>, T> {
+		let loc = match self.0.binary_search(&value) {
+			Ok(_) => return Err(value),
+			Err(loc) => loc,
+		};
+		if !self.is_full() {
+			return self.0.try_insert(loc, value).map(|_| None);
+		}
+		if loc == 0 {
+			return Err(value);
+		}
+		let min = self.0.remove(0);
+		self.0
+			.try_insert(loc - 1, value)
+			.map_err(|_| ())
+			.expect("removed one element above; qed");
+		Ok(Some(min))
+	}
+
+	/// Return the rank of `value` counted from the greatest element, i.e.
+	/// the greatest element has rank 0. Return `None` if `value` is not in
+	/// the set.
+	pub fn rank(&self, value: &T) -> Option<usize> {
+		self.0.binary_search(value).ok().map(|loc| self.len() - 1 - loc)
+	}
+
+	/// Return up to `n` greatest elements, in descending order
+	pub fn top(&self, n: usize) -> impl Iterator<Item = &T> {
+		self.0.iter().rev().take(n)
+	}
+
+	/// Return the smallest element
+	pub fn min(&self) -> Option<&T> {
+		self.0.first()
+	}
+
+	/// Return the greatest element
+	pub fn max(&self) -> Option<&T> {
+		self.0.last()
+	}
 }
 
 impl<T: Ord + Encode + Decode + MaxEncodedLen + Clone + Eq + PartialEq, S: Get<u32>> From<BoundedVec<T, S>>
@@ -173,6 +241,52 @@ mod tests {
 		assert_eq!(set, OrderedSet::new());
 	}
 
+	#[test]
+	fn insert_or_replace_min() {
+		let mut set: OrderedSet<i32, Five> = OrderedSet::from(vec![2, 4, 6].try_into().unwrap());
+
+		assert_eq!(set.insert_or_replace_min(4), Err(4));
+		assert_eq!(set.insert_or_replace_min(3), Ok(None));
+		assert_eq!(set.insert_or_replace_min(8), Ok(None));
+		assert!(set.is_full());
+		assert_eq!(
+			set,
+			OrderedSet::<i32, Five>::from(vec![2, 3, 4, 6, 8].try_into().unwrap())
+		);
+
+		// not greater than the smallest element
+		assert_eq!(set.insert_or_replace_min(1), Err(1));
+		assert_eq!(set.insert_or_replace_min(2), Err(2));
+
+		assert_eq!(set.insert_or_replace_min(5), Ok(Some(2)));
+		assert_eq!(
+			set,
+			OrderedSet::<i32, Five>::from(vec![3, 4, 5, 6, 8].try_into().unwrap())
+		);
+
+		assert_eq!(set.insert_or_replace_min(9), Ok(Some(3)));
+		assert_eq!(
+			set,
+			OrderedSet::<i32, Five>::from(vec![4, 5, 6, 8, 9].try_into().unwrap())
+		);
+		assert_eq!(set.len(), OrderedSet::<i32, Five>::capacity());
+	}
+
+	#[test]
+	fn rank_and_top() {
+		let set: OrderedSet<i32, Eight> = OrderedSet::from(vec![1, 2, 3, 4].try_into().unwrap());
+
+		assert_eq!(set.rank(&4), Some(0));
+		assert_eq!(set.rank(&1), Some(3));
+		assert_eq!(set.rank(&5), None);
+
+		assert_eq!(set.top(2).copied().collect::<Vec<_>>(), vec![4, 3]);
+		assert_eq!(set.top(10).copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+		assert_eq!(set.min(), Some(&1));
+		assert_eq!(set.max(), Some(&4));
+		assert!(OrderedSet::<i32, Eight>::new().top(1).next().is_none());
+	}
+
 	#[test]
 	fn exceeding_max_size_should_fail() {
 		let mut set: OrderedSet<i32, Five> = OrderedSet::from(vec![1, 2, 3, 4, 5].try_into().unwrap());
@@ -0,0 +1,179 @@
+use codec::{FullCodec, FullEncode};
+use frame_support::storage::{IterableStorageDoubleMap, IterableStorageMap, StorageValue};
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// The outcome of processing a page of a storage map.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct PageOutcome {
+	/// The number of items that were processed.
+	pub processed: u32,
+	/// Whether the end of the map was reached. The cursor is cleared if so
+	/// and the next page starts from the beginning of the map again.
+	pub finished: bool,
+}
+
+/// Errors of processing a page of a storage map.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum PagerError {
+	/// The persisted cursor can not be decoded. It is left as is, call
+	/// `reset` to start over from the beginning of the map.
+	InvalidCursor,
+	/// The raw key of the last processed item does not fit in the cursor.
+	/// The cursor is cleared, so the next page starts from the beginning of
+	/// the map again.
+	CursorOverflow {
+		/// The number of items that were processed.
+		processed: u32,
+	},
+}
+
+/// Process an `IterableStorageMap` at most `limit` items at a time, persisting
+/// the raw key of the last processed item in `Cursor` so the next call resumes
+/// where the previous one stopped.
+///
+/// Useful to bound the work done in hooks like `on_initialize` or `on_idle`.
+/// Items may be removed from the map while it is being processed. The cursor
+/// can be any value convertible from and into the raw key, e.g. a
+/// `BoundedVec<u8, _>` for pallets with storage info.
+pub struct StoragePager<K, V, Map, Cursor, CursorValue = Vec<u8>>(PhantomData<(K, V, Map, Cursor, CursorValue)>);
+
+impl<K, V, Map, Cursor, CursorValue> StoragePager<K, V, Map, Cursor, CursorValue>
+where
+	K: FullEncode + FullCodec,
+	V: FullCodec,
+	Map: IterableStorageMap<K, V>,
+	Cursor: StorageValue<CursorValue, Query = Option<CursorValue>>,
+	CursorValue: FullCodec + TryFrom<Vec<u8>> + Into<Vec<u8>>,
+{
+	/// Process at most `limit` items with `f`, starting after the persisted
+	/// cursor.
+	pub fn process(limit: u32, mut f: impl FnMut(K, V)) -> Result<PageOutcome, PagerError> {
+		process_page::<_, _, Cursor, CursorValue>(
+			limit,
+			|cursor| match cursor {
+				Some(raw_key) => Map::iter_from(raw_key),
+				None => Map::iter(),
+			},
+			|(key, value)| {
+				let raw_key = Map::hashed_key_for(&key);
+				f(key, value);
+				raw_key
+			},
+		)
+	}
+
+	/// Return if there is a page in progress.
+	pub fn in_progress() -> bool {
+		Cursor::exists()
+	}
+
+	/// Drop the persisted cursor so the next page starts from the beginning
+	/// of the map.
+	pub fn reset() {
+		Cursor::kill();
+	}
+}
+
+/// Process an `IterableStorageDoubleMap` like `StoragePager`.
+pub struct StorageDoubleMapPager<K1, K2, V, Map, Cursor, CursorValue = Vec<u8>>(
+	PhantomData<(K1, K2, V, Map, Cursor, CursorValue)>,
+);
+
+impl<K1, K2, V, Map, Cursor, CursorValue> StorageDoubleMapPager<K1, K2, V, Map, Cursor, CursorValue>
+where
+	K1: FullEncode + FullCodec,
+	K2: FullEncode + FullCodec,
+	V: FullCodec,
+	Map: IterableStorageDoubleMap<K1, K2, V>,
+	Cursor: StorageValue<CursorValue, Query = Option<CursorValue>>,
+	CursorValue: FullCodec + TryFrom<Vec<u8>> + Into<Vec<u8>>,
+{
+	/// Process at most `limit` items with `f`, starting after the persisted
+	/// cursor.
+	pub fn process(limit: u32, mut f: impl FnMut(K1, K2, V)) -> Result<PageOutcome, PagerError> {
+		process_page::<_, _, Cursor, CursorValue>(
+			limit,
+			|cursor| match cursor {
+				Some(raw_key) => Map::iter_from(raw_key),
+				None => Map::iter(),
+			},
+			|(key1, key2, value)| {
+				let raw_key = Map::hashed_key_for(&key1, &key2);
+				f(key1, key2, value);
+				raw_key
+			},
+		)
+	}
+
+	/// Return if there is a page in progress.
+	pub fn in_progress() -> bool {
+		Cursor::exists()
+	}
+
+	/// Drop the persisted cursor so the next page starts from the beginning
+	/// of the map.
+	pub fn reset() {
+		Cursor::kill();
+	}
+}
+
+/// Process at most `limit` items of the iterator returned by `iter` for the
+/// persisted raw key, with `f` returning the raw key of each item.
+fn process_page<I, Item, Cursor, CursorValue>(
+	limit: u32,
+	iter: impl FnOnce(Option<Vec<u8>>) -> I,
+	mut f: impl FnMut(Item) -> Vec<u8>,
+) -> Result<PageOutcome, PagerError>
+where
+	I: Iterator<Item = Item>,
+	Cursor: StorageValue<CursorValue, Query = Option<CursorValue>>,
+	CursorValue: FullCodec + TryFrom<Vec<u8>> + Into<Vec<u8>>,
+{
+	if limit == 0 {
+		return Ok(PageOutcome {
+			processed: 0,
+			finished: false,
+		});
+	}
+
+	let cursor = if Cursor::exists() {
+		// `get` would treat a cursor that can not be decoded as missing
+		Some(Cursor::try_get().map_err(|_| PagerError::InvalidCursor)?.into())
+	} else {
+		None
+	};
+	let mut iter = iter(cursor);
+
+	let mut processed = 0;
+	let mut last_raw_key = None;
+	while processed < limit {
+		match iter.next() {
+			Some(item) => {
+				last_raw_key = Some(f(item));
+				processed += 1;
+			}
+			None => {
+				Cursor::kill();
+				return Ok(PageOutcome {
+					processed,
+					finished: true,
+				});
+			}
+		}
+	}
+
+	if let Some(raw_key) = last_raw_key {
+		match CursorValue::try_from(raw_key) {
+			Ok(cursor) => Cursor::put(cursor),
+			Err(_) => {
+				Cursor::kill();
+				return Err(PagerError::CursorOverflow { processed });
+			}
+		}
+	}
+	Ok(PageOutcome {
+		processed,
+		finished: false,
+	})
+}
@@ -20,9 +20,45 @@
 //! ### Dispatchable Functions
 //!
 //! - `vested_transfer` - Add a new vesting schedule for an account.
+//! - `vested_transfer_many` - Add a vesting schedule scaled from a template to
+//!   each of many accounts.
 //! - `claim` - Claim unlocked balances.
 //! - `update_vesting_schedules` - Update all vesting schedules under an
 //!   account, `root` origin required.
+//! - `assign_vested` - Assign a fraction of a vesting schedule, with its locked
+//!   balance, to another account.
+//! - `set_vested_transfer_acceptance` - Require vested transfers to the caller
+//!   to be accepted.
+//! - `accept_vested_transfer` - Accept a pending vested transfer.
+//! - `refund_vested_transfer` - Decline a pending vested transfer, or refund it
+//!   after it expired.
+//!
+//! ### Accepting Vested Transfers
+//!
+//! Accounts can require vested transfers to them to be accepted, so others
+//! can not fill their `MaxVestingSchedules` with dust schedules. A vested
+//! transfer to such an account holds the balance in the pallet account as a
+//! pending offer, until the recipient accepts it within `AcceptancePeriod`
+//! blocks. Unaccepted offers are refunded to the sender.
+//!
+//! ### Dust Locks
+//!
+//! A claim can leave less than the existential deposit locked, which can not be
+//! transferred until the schedules end. For accounts without free balance
+//! besides their vesting schedules, `DustLockHandling` configures whether such
+//! a remainder is kept locked, unlocking is deferred until the remainder is
+//! unlocked too, or the remainder is unlocked together with the next unlocked
+//! period.
+//!
+//! ### Lock Layer
+//!
+//! The locked balance is kept from being transferred by `VestingLock`.
+//! `CurrencyLock` uses `LockableCurrency` locks, while `FungibleFreeze` uses
+//! named `fungible` freezes for chains that migrated to the freeze and hold
+//! model. Chains switching from `CurrencyLock` to `FungibleFreeze` convert
+//! the existing locks with the `MigrateLocksToFreezes` runtime upgrade, which
+//! migrates the accounts in pages in `on_idle` while `MigratingLock` is
+//! configured as `VestingLock`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -31,24 +67,31 @@ use codec::{HasCompact, MaxEncodedLen};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
-	BoundedVec,
+	traits::{
+		fungible, Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, WithdrawReasons,
+	},
+	BoundedVec, PalletId,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use orml_utilities::{PageOutcome, PagerError, StoragePager};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32Bit, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, Zero},
-	ArithmeticError, DispatchResult, RuntimeDebug,
+	traits::{AccountIdConversion, AtLeast32Bit, BlockNumberProvider, CheckedAdd, One, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchResult, Perbill, RuntimeDebug,
 };
 use sp_std::{
 	cmp::{Eq, PartialEq},
+	marker::PhantomData,
+	vec,
 	vec::Vec,
 };
 
+mod migrations;
 mod mock;
 mod tests;
 mod weights;
 
+pub use migrations::{MigrateLocksToFreezes, MigratingLock};
 pub use module::*;
 pub use weights::WeightInfo;
 
@@ -87,6 +130,20 @@ impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + MaxEncodedLen + C
 		self.per_period.checked_mul(&self.period_count.into())
 	}
 
+	/// Returns the first block after `time` unlocking a period, `None` if
+	/// all periods are unlocked at `time` or calculation overflows.
+	///
+	/// Note this func assumes schedule is a valid one(non-zero period).
+	pub fn next_unlock(&self, time: BlockNumber) -> Option<BlockNumber> {
+		if self.locked_amount(time).is_zero() {
+			return None;
+		}
+		let full = time.saturating_sub(self.start).checked_div(&self.period)?;
+		full.checked_add(&One::one())?
+			.checked_mul(&self.period)?
+			.checked_add(&self.start)
+	}
+
 	/// Returns locked amount for a given `time`.
 	///
 	/// Note this func assumes schedule is a valid one(non-zero period and
@@ -106,6 +163,75 @@ impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + MaxEncodedLen + C
 	}
 }
 
+/// A vested transfer waiting for the recipient to accept it.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct VestedTransferOffer<AccountId, BlockNumber, Balance: MaxEncodedLen + HasCompact> {
+	/// The sender, refunded if the offer is not accepted
+	pub from: AccountId,
+	/// The recipient
+	pub to: AccountId,
+	/// The offered vesting schedule
+	pub schedule: VestingSchedule<BlockNumber, Balance>,
+	/// The last block the offer can be accepted at
+	pub expires_at: BlockNumber,
+}
+
+/// How claims leaving less than the existential deposit locked are handled.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum DustLockHandling {
+	/// Lock the remainder as any other amount.
+	#[default]
+	Keep,
+	/// Keep the current lock until the remainder is unlocked too.
+	Defer,
+	/// Unlock the remainder with the next unlocked period of any schedule.
+	Merge,
+}
+
+/// Keeps the locked balance of vesting schedules from being transferred.
+pub trait VestingLock<AccountId, Balance> {
+	/// Lock `amount` of the balance of `who`, replacing the previous lock.
+	fn set_lock(who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Remove the lock of `who`.
+	fn remove_lock(who: &AccountId);
+}
+
+/// Locks vested balances with a `LockableCurrency` lock under
+/// `VESTING_LOCK_ID`.
+pub struct CurrencyLock<Currency>(PhantomData<Currency>);
+
+impl<AccountId, C: LockableCurrency<AccountId>> VestingLock<AccountId, <C as Currency<AccountId>>::Balance>
+	for CurrencyLock<C>
+{
+	fn set_lock(who: &AccountId, amount: <C as Currency<AccountId>>::Balance) -> DispatchResult {
+		C::set_lock(VESTING_LOCK_ID, who, amount, WithdrawReasons::all());
+		Ok(())
+	}
+
+	fn remove_lock(who: &AccountId) {
+		C::remove_lock(VESTING_LOCK_ID, who);
+	}
+}
+
+/// Freezes vested balances with a `fungible` freeze under `FreezeId`.
+pub struct FungibleFreeze<Currency, FreezeId>(PhantomData<(Currency, FreezeId)>);
+
+impl<AccountId, F, FreezeId> VestingLock<AccountId, <F as fungible::Inspect<AccountId>>::Balance>
+	for FungibleFreeze<F, FreezeId>
+where
+	F: fungible::MutateFreeze<AccountId>,
+	FreezeId: Get<<F as fungible::InspectFreeze<AccountId>>::Id>,
+{
+	fn set_lock(who: &AccountId, amount: <F as fungible::Inspect<AccountId>>::Balance) -> DispatchResult {
+		F::set_freeze(&FreezeId::get(), who, amount)
+	}
+
+	fn remove_lock(who: &AccountId) {
+		let _ = F::thaw(&FreezeId::get(), who);
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -113,6 +239,8 @@ pub mod module {
 	pub(crate) type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 	pub(crate) type VestingScheduleOf<T> = VestingSchedule<BlockNumberFor<T>, BalanceOf<T>>;
+	pub(crate) type VestedTransferOfferOf<T> =
+		VestedTransferOffer<<T as frame_system::Config>::AccountId, BlockNumberFor<T>, BalanceOf<T>>;
 	pub type ScheduledItem<T> = (
 		<T as frame_system::Config>::AccountId,
 		BlockNumberFor<T>,
@@ -125,7 +253,11 @@ pub mod module {
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		type Currency: LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
+		type Currency: Currency<Self::AccountId>;
+
+		/// The lock layer keeping vested balances from being transferred,
+		/// `CurrencyLock` or `FungibleFreeze`.
+		type VestingLock: VestingLock<Self::AccountId, BalanceOf<Self>>;
 
 		#[pallet::constant]
 		/// The minimum amount transferred to call `vested_transfer`.
@@ -142,6 +274,27 @@ pub mod module {
 
 		// The block number provider
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// How claims leaving less than the existential deposit locked are
+		/// handled.
+		#[pallet::constant]
+		type DustLockHandling: Get<DustLockHandling>;
+
+		/// The pallet id, used for deriving the account holding pending
+		/// vested transfers.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of blocks a recipient has to accept a vested transfer.
+		#[pallet::constant]
+		type AcceptancePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of grants in one `vested_transfer_many` call.
+		#[pallet::constant]
+		type MaxVestedTransferGrants: Get<u32>;
+
+		/// The origin allowed to move vesting schedules to a new beneficiary.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 	}
 
 	#[pallet::error]
@@ -158,6 +311,24 @@ pub mod module {
 		AmountLow,
 		/// Failed because the maximum vesting schedules was exceeded
 		MaxVestingSchedulesExceeded,
+		/// The vesting schedule does not exist
+		VestingScheduleNotFound,
+		/// Nothing is locked by the assigned fraction of the vesting schedule
+		NothingToAssign,
+		/// The vested transfer offer does not exist
+		VestedTransferOfferNotFound,
+		/// The vested transfer offer is expired
+		VestedTransferOfferExpired,
+		/// The vested transfer offer can only be refunded by its recipient
+		/// before it expires
+		VestedTransferOfferNotExpired,
+		/// The caller is not the recipient of the vested transfer offer
+		NotVestedTransferRecipient,
+		/// No vested transfer offer ID available
+		NoAvailableOfferId,
+		/// The granted amount is not a multiple of the `period_count` of the
+		/// template
+		IndivisibleGrant,
 	}
 
 	#[pallet::event]
@@ -171,8 +342,45 @@ pub mod module {
 		},
 		/// Claimed vesting.
 		Claimed { who: T::AccountId, amount: BalanceOf<T> },
+		/// Claiming was deferred as it would leave a remainder below the
+		/// existential deposit locked, the lock is unchanged.
+		ClaimDeferred { who: T::AccountId },
 		/// Updated vesting schedules.
 		VestingSchedulesUpdated { who: T::AccountId },
+		/// Assigned a part of a vesting schedule to another account.
+		VestingScheduleAssigned {
+			from: T::AccountId,
+			to: T::AccountId,
+			vesting_schedule: VestingScheduleOf<T>,
+		},
+		/// Set whether vested transfers to an account must be accepted.
+		VestedTransferAcceptanceSet { who: T::AccountId, required: bool },
+		/// Offered a vested transfer, waiting for acceptance.
+		VestedTransferOffered {
+			offer_id: u32,
+			from: T::AccountId,
+			to: T::AccountId,
+			vesting_schedule: VestingScheduleOf<T>,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// Refunded a vested transfer offer to its sender.
+		VestedTransferRefunded {
+			offer_id: u32,
+			from: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Moved a vesting schedule to a new beneficiary.
+		VestingBeneficiaryUpdated {
+			from: T::AccountId,
+			to: T::AccountId,
+			vesting_schedule: VestingScheduleOf<T>,
+		},
+		/// The vested balance of an account could not be locked with the new
+		/// lock by `MigrateLocksToFreezes`, its old lock is kept.
+		LockMigrationFailed { who: T::AccountId },
+		/// `MigrateLocksToFreezes` processed every account with vesting
+		/// schedules.
+		LocksMigrated,
 	}
 
 	/// Vesting schedules of an account.
@@ -188,6 +396,39 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Whether `MigrateLocksToFreezes` is in progress.
+	///
+	/// LockMigrationPending: bool
+	#[pallet::storage]
+	#[pallet::getter(fn lock_migration_pending)]
+	pub type LockMigrationPending<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The raw key of the last account migrated by `MigrateLocksToFreezes`.
+	///
+	/// LockMigrationCursor: BoundedVec<u8, 256>
+	#[pallet::storage]
+	pub type LockMigrationCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// Accounts requiring vested transfers to them to be accepted.
+	///
+	/// VestedTransferAcceptanceRequired: map AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn vested_transfer_acceptance_required)]
+	pub type VestedTransferAcceptanceRequired<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Pending vested transfers.
+	///
+	/// VestedTransferOffers: map u32 => Option<VestedTransferOffer>
+	#[pallet::storage]
+	#[pallet::getter(fn vested_transfer_offers)]
+	pub type VestedTransferOffers<T: Config> = StorageMap<_, Twox64Concat, u32, VestedTransferOfferOf<T>, OptionQuery>;
+
+	/// The ID of the next vested transfer offer.
+	#[pallet::storage]
+	#[pallet::getter(fn next_offer_id)]
+	pub type NextOfferId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub vesting: Vec<ScheduledItem<T>>,
@@ -231,7 +472,7 @@ pub mod module {
 						"Account do not have enough balance"
 					);
 
-					T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
+					T::VestingLock::set_lock(who, total_amount).expect("Failed to lock vested balance");
 					VestingSchedules::<T>::insert(who, bounded_schedules);
 				});
 		}
@@ -241,7 +482,11 @@ pub mod module {
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::idle_migrate_locks(remaining_weight)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -249,13 +494,7 @@ pub mod module {
 		#[pallet::weight(T::WeightInfo::claim(<T as Config>::MaxVestingSchedules::get() / 2))]
 		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let locked_amount = Self::do_claim(&who);
-
-			Self::deposit_event(Event::Claimed {
-				who,
-				amount: locked_amount,
-			});
-			Ok(())
+			Self::claim_avoiding_dust(who)
 		}
 
 		#[pallet::call_index(1)]
@@ -268,21 +507,7 @@ pub mod module {
 			let from = T::VestedTransferOrigin::ensure_origin(origin)?;
 			let to = T::Lookup::lookup(dest)?;
 
-			if to == from {
-				ensure!(
-					T::Currency::free_balance(&from) >= schedule.total_amount().ok_or(ArithmeticError::Overflow)?,
-					Error::<T>::InsufficientBalanceToLock,
-				);
-			}
-
-			Self::do_vested_transfer(&from, &to, schedule.clone())?;
-
-			Self::deposit_event(Event::VestingScheduleAdded {
-				from,
-				to,
-				vesting_schedule: schedule,
-			});
-			Ok(())
+			Self::vested_transfer_or_offer(from, to, schedule)
 		}
 
 		#[pallet::call_index(2)]
@@ -306,28 +531,307 @@ pub mod module {
 		pub fn claim_for(origin: OriginFor<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
 			let who = T::Lookup::lookup(dest)?;
-			let locked_amount = Self::do_claim(&who);
+			Self::claim_avoiding_dust(who)
+		}
 
-			Self::deposit_event(Event::Claimed {
-				who,
-				amount: locked_amount,
+		/// Assign `fraction` of the vesting schedule at index `schedule_id` to
+		/// `dest`, along with the balance it still locks.
+		///
+		/// The schedule is split in two with the same `start`, `period` and
+		/// `period_count`. `per_period` of the assigned schedule is rounded
+		/// down, the remainder stays with the caller.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::assign_vested())]
+		pub fn assign_vested(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			schedule_id: u32,
+			fraction: Perbill,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+
+			let schedule = Self::do_assign_vested(&from, &to, schedule_id, fraction)?;
+
+			Self::deposit_event(Event::VestingScheduleAssigned {
+				from,
+				to,
+				vesting_schedule: schedule,
+			});
+			Ok(())
+		}
+
+		/// Set whether vested transfers to the caller must be accepted with
+		/// `accept_vested_transfer`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_vested_transfer_acceptance())]
+		pub fn set_vested_transfer_acceptance(origin: OriginFor<T>, required: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if required {
+				VestedTransferAcceptanceRequired::<T>::insert(&who, true);
+			} else {
+				VestedTransferAcceptanceRequired::<T>::remove(&who);
+			}
+
+			Self::deposit_event(Event::VestedTransferAcceptanceSet { who, required });
+			Ok(())
+		}
+
+		/// Accept the pending vested transfer `offer_id` to the caller, adding
+		/// its vesting schedule.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::accept_vested_transfer())]
+		pub fn accept_vested_transfer(origin: OriginFor<T>, offer_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let offer = Self::do_accept_vested_transfer(&who, offer_id)?;
+
+			Self::deposit_event(Event::VestingScheduleAdded {
+				from: offer.from,
+				to: offer.to,
+				vesting_schedule: offer.schedule,
+			});
+			Ok(())
+		}
+
+		/// Refund the pending vested transfer `offer_id` to its sender.
+		///
+		/// The recipient can decline an offer at any time, anyone else can
+		/// refund it once it expired.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::refund_vested_transfer())]
+		pub fn refund_vested_transfer(origin: OriginFor<T>, offer_id: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (from, amount) = Self::do_refund_vested_transfer(&who, offer_id)?;
+
+			Self::deposit_event(Event::VestedTransferRefunded { offer_id, from, amount });
+			Ok(())
+		}
+
+		/// Vested transfer to each of `grants`, with `template` scaled to the
+		/// granted amount.
+		///
+		/// Each schedule has the `start`, `period` and `period_count` of
+		/// `template`, and `per_period` of the granted amount divided by
+		/// `period_count`, which must divide the granted amount. The
+		/// `per_period` of `template` is ignored. Any failing grant fails the
+		/// whole call.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::vested_transfer_many(grants.len() as u32))]
+		pub fn vested_transfer_many(
+			origin: OriginFor<T>,
+			template: VestingScheduleOf<T>,
+			grants: BoundedVec<(<T::Lookup as StaticLookup>::Source, BalanceOf<T>), T::MaxVestedTransferGrants>,
+		) -> DispatchResult {
+			let from = T::VestedTransferOrigin::ensure_origin(origin)?;
+			ensure!(!template.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+
+			for (dest, amount) in grants {
+				let to = T::Lookup::lookup(dest)?;
+				let period_count: BalanceOf<T> = template.period_count.into();
+				ensure!((amount % period_count).is_zero(), Error::<T>::IndivisibleGrant);
+				let schedule = VestingSchedule {
+					per_period: amount / period_count,
+					..template.clone()
+				};
+				Self::vested_transfer_or_offer(from.clone(), to, schedule)?;
+			}
+			Ok(())
+		}
+
+		/// Move the vesting schedule at index `schedule_id` of `old` to `new`,
+		/// along with the balance it still locks.
+		///
+		/// The vesting locks of both accounts are recomputed. Balance already
+		/// vested stays with `old`.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::force_update_beneficiary())]
+		pub fn force_update_beneficiary(
+			origin: OriginFor<T>,
+			old: <T::Lookup as StaticLookup>::Source,
+			new: <T::Lookup as StaticLookup>::Source,
+			schedule_id: u32,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let from = T::Lookup::lookup(old)?;
+			let to = T::Lookup::lookup(new)?;
+
+			let schedule = Self::do_assign_vested(&from, &to, schedule_id, Perbill::one())?;
+
+			Self::deposit_event(Event::VestingBeneficiaryUpdated {
+				from,
+				to,
+				vesting_schedule: schedule,
 			});
 			Ok(())
 		}
 	}
 }
 
+type LockMigrationPager<T> = StoragePager<
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<VestingScheduleOf<T>, <T as Config>::MaxVestingSchedules>,
+	VestingSchedules<T>,
+	LockMigrationCursor<T>,
+	BoundedVec<u8, ConstU32<256>>,
+>;
+
 impl<T: Config> Pallet<T> {
-	fn do_claim(who: &T::AccountId) -> BalanceOf<T> {
+	/// Migrate the locks of a page of accounts while `MigrateLocksToFreezes`
+	/// is in progress.
+	fn idle_migrate_locks(remaining_weight: Weight) -> Weight {
+		// read the migration flag and the cursor, and update them
+		let base_weight = T::DbWeight::get().reads_writes(2, 2);
+		// claim for the account and remove its old lock
+		let per_account_weight =
+			T::WeightInfo::claim(T::MaxVestingSchedules::get()).saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		if remaining_weight.any_lt(base_weight) {
+			return Weight::zero();
+		}
+		if !LockMigrationPending::<T>::get() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let available = remaining_weight.saturating_sub(base_weight);
+		let mut limit = u32::MAX as u64;
+		if per_account_weight.ref_time() > 0 {
+			limit = limit.min(available.ref_time() / per_account_weight.ref_time());
+		}
+		if per_account_weight.proof_size() > 0 {
+			limit = limit.min(available.proof_size() / per_account_weight.proof_size());
+		}
+
+		let outcome = LockMigrationPager::<T>::process(limit as u32, |who, _| {
+			// the new lock is set before the old one is removed, nothing changes if
+			// it fails
+			if frame_support::storage::with_storage_layer(|| Self::do_claim(&who)).is_err() {
+				Self::deposit_event(Event::LockMigrationFailed { who });
+			}
+		});
+		let processed = match outcome {
+			Ok(PageOutcome { processed, finished }) => {
+				if finished {
+					LockMigrationPending::<T>::kill();
+					Self::deposit_event(Event::LocksMigrated);
+				}
+				processed
+			}
+			// the accounts are migrated again from the beginning, which has no
+			// further effect on the migrated ones
+			Err(PagerError::CursorOverflow { processed }) => processed,
+			Err(PagerError::InvalidCursor) => {
+				LockMigrationPager::<T>::reset();
+				0
+			}
+		};
+		base_weight.saturating_add(per_account_weight.saturating_mul(processed.into()))
+	}
+
+	/// Add `schedule` to `to`, or offer it if `to` requires vested transfers
+	/// to be accepted.
+	fn vested_transfer_or_offer(
+		from: T::AccountId,
+		to: T::AccountId,
+		schedule: VestingScheduleOf<T>,
+	) -> DispatchResult {
+		if to == from {
+			ensure!(
+				T::Currency::free_balance(&from) >= schedule.total_amount().ok_or(ArithmeticError::Overflow)?,
+				Error::<T>::InsufficientBalanceToLock,
+			);
+		} else if Self::vested_transfer_acceptance_required(&to) {
+			let (offer_id, expires_at) = Self::do_offer_vested_transfer(&from, &to, schedule.clone())?;
+
+			Self::deposit_event(Event::VestedTransferOffered {
+				offer_id,
+				from,
+				to,
+				vesting_schedule: schedule,
+				expires_at,
+			});
+			return Ok(());
+		}
+
+		Self::do_vested_transfer(&from, &to, schedule.clone())?;
+
+		Self::deposit_event(Event::VestingScheduleAdded {
+			from,
+			to,
+			vesting_schedule: schedule,
+		});
+		Ok(())
+	}
+
+	fn claim_avoiding_dust(who: T::AccountId) -> DispatchResult {
+		match Self::do_claim_avoiding_dust(&who)? {
+			Some(locked_amount) => Self::deposit_event(Event::Claimed {
+				who,
+				amount: locked_amount,
+			}),
+			None => Self::deposit_event(Event::ClaimDeferred { who }),
+		}
+		Ok(())
+	}
+
+	fn do_claim(who: &T::AccountId) -> Result<BalanceOf<T>, DispatchError> {
 		let locked = Self::locked_balance(who);
+		Self::set_vesting_lock(who, locked)
+	}
+
+	fn set_vesting_lock(who: &T::AccountId, locked: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
 		if locked.is_zero() {
 			// cleanup the storage and unlock the fund
 			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+			T::VestingLock::remove_lock(who);
 		} else {
-			T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+			T::VestingLock::set_lock(who, locked)?;
+		}
+		Ok(locked)
+	}
+
+	/// Claim like `do_claim`, handling a remainder below the existential
+	/// deposit as configured by `DustLockHandling` if the account has no
+	/// free balance besides its vesting schedules.
+	///
+	/// Returns the locked balance, or `None` if the lock was left unchanged.
+	fn do_claim_avoiding_dust(who: &T::AccountId) -> Result<Option<BalanceOf<T>>, DispatchError> {
+		let locked = Self::locked_balance(who);
+		if locked.is_zero() || locked >= T::Currency::minimum_balance() {
+			return Self::set_vesting_lock(who, locked).map(Some);
+		}
+
+		let schedules = <VestingSchedules<T>>::get(who);
+		let vesting_total = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, s| {
+			total.saturating_add(s.per_period.saturating_mul(s.period_count.into()))
+		});
+		if T::Currency::free_balance(who) > vesting_total {
+			return Self::set_vesting_lock(who, locked).map(Some);
+		}
+
+		match T::DustLockHandling::get() {
+			DustLockHandling::Keep => Self::set_vesting_lock(who, locked).map(Some),
+			// the current lock is kept as is
+			DustLockHandling::Defer => Ok(None),
+			DustLockHandling::Merge => {
+				// replace the schedules by one unlocking the remainder at the next
+				// unlocked period
+				let now = T::BlockNumberProvider::current_block_number();
+				if let Some(next_unlock) = schedules.iter().filter_map(|s| s.next_unlock(now)).min() {
+					let merged = VestingSchedule {
+						start: now,
+						period: next_unlock.saturating_sub(now),
+						period_count: 1,
+						per_period: locked,
+					};
+					<VestingSchedules<T>>::insert(who, BoundedVec::truncate_from(vec![merged]));
+				}
+				Self::set_vesting_lock(who, locked).map(Some)
+			}
 		}
-		locked
 	}
 
 	/// Returns locked balance based on current block number.
@@ -360,11 +864,123 @@ impl<T: Config> Pallet<T> {
 			.ok_or(ArithmeticError::Overflow)?;
 
 		T::Currency::transfer(from, to, schedule_amount, ExistenceRequirement::AllowDeath)?;
-		T::Currency::set_lock(VESTING_LOCK_ID, to, total_amount, WithdrawReasons::all());
+		T::VestingLock::set_lock(to, total_amount)?;
 		<VestingSchedules<T>>::try_append(to, schedule).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
 		Ok(())
 	}
 
+	/// The account holding the balance of pending vested transfers.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	fn do_offer_vested_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		schedule: VestingScheduleOf<T>,
+	) -> Result<(u32, BlockNumberFor<T>), DispatchError> {
+		let schedule_amount = ensure_valid_vesting_schedule::<T>(&schedule)?;
+
+		let offer_id = NextOfferId::<T>::try_mutate(|next_id| -> Result<u32, DispatchError> {
+			let current_id = *next_id;
+			*next_id = next_id.checked_add(1).ok_or(Error::<T>::NoAvailableOfferId)?;
+			Ok(current_id)
+		})?;
+		let expires_at = T::BlockNumberProvider::current_block_number().saturating_add(T::AcceptancePeriod::get());
+
+		T::Currency::transfer(
+			from,
+			&Self::account_id(),
+			schedule_amount,
+			ExistenceRequirement::AllowDeath,
+		)?;
+		VestedTransferOffers::<T>::insert(
+			offer_id,
+			VestedTransferOffer {
+				from: from.clone(),
+				to: to.clone(),
+				schedule,
+				expires_at,
+			},
+		);
+		Ok((offer_id, expires_at))
+	}
+
+	fn do_accept_vested_transfer(who: &T::AccountId, offer_id: u32) -> Result<VestedTransferOfferOf<T>, DispatchError> {
+		let offer = Self::vested_transfer_offers(offer_id).ok_or(Error::<T>::VestedTransferOfferNotFound)?;
+		ensure!(offer.to == *who, Error::<T>::NotVestedTransferRecipient);
+		ensure!(
+			T::BlockNumberProvider::current_block_number() <= offer.expires_at,
+			Error::<T>::VestedTransferOfferExpired
+		);
+
+		Self::do_vested_transfer(&Self::account_id(), who, offer.schedule.clone())?;
+		VestedTransferOffers::<T>::remove(offer_id);
+		Ok(offer)
+	}
+
+	fn do_refund_vested_transfer(
+		who: &T::AccountId,
+		offer_id: u32,
+	) -> Result<(T::AccountId, BalanceOf<T>), DispatchError> {
+		let offer = Self::vested_transfer_offers(offer_id).ok_or(Error::<T>::VestedTransferOfferNotFound)?;
+		ensure!(
+			offer.to == *who || T::BlockNumberProvider::current_block_number() > offer.expires_at,
+			Error::<T>::VestedTransferOfferNotExpired
+		);
+
+		let amount = offer.schedule.total_amount().ok_or(ArithmeticError::Overflow)?;
+		T::Currency::transfer(
+			&Self::account_id(),
+			&offer.from,
+			amount,
+			ExistenceRequirement::AllowDeath,
+		)?;
+		VestedTransferOffers::<T>::remove(offer_id);
+		Ok((offer.from, amount))
+	}
+
+	fn do_assign_vested(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		schedule_id: u32,
+		fraction: Perbill,
+	) -> Result<VestingScheduleOf<T>, DispatchError> {
+		let mut schedules = <VestingSchedules<T>>::get(from);
+		let index = schedule_id as usize;
+		let schedule = schedules.get(index).ok_or(Error::<T>::VestingScheduleNotFound)?.clone();
+
+		let assigned_per_period = fraction.mul_floor(schedule.per_period);
+		let assigned = VestingSchedule {
+			per_period: assigned_per_period,
+			..schedule.clone()
+		};
+		let assigned_locked = assigned.locked_amount(T::BlockNumberProvider::current_block_number());
+		ensure!(!assigned_locked.is_zero(), Error::<T>::NothingToAssign);
+		ensure_valid_vesting_schedule::<T>(&assigned)?;
+
+		let remaining_per_period = schedule.per_period.saturating_sub(assigned_per_period);
+		match schedules.get_mut(index) {
+			Some(remaining) if !remaining_per_period.is_zero() => remaining.per_period = remaining_per_period,
+			_ => {
+				schedules.remove(index);
+			}
+		}
+		<VestingSchedules<T>>::insert(from, schedules);
+		// release the lock on the assigned balance
+		Self::do_claim(from)?;
+
+		let total_amount = Self::locked_balance(to)
+			.checked_add(&assigned_locked)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		T::Currency::transfer(from, to, assigned_locked, ExistenceRequirement::AllowDeath)?;
+		T::VestingLock::set_lock(to, total_amount)?;
+		<VestingSchedules<T>>::try_append(to, assigned.clone()).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+
+		Ok(assigned)
+	}
+
 	fn do_update_vesting_schedules(who: &T::AccountId, schedules: Vec<VestingScheduleOf<T>>) -> DispatchResult {
 		let bounded_schedules: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> = schedules
 			.try_into()
@@ -373,7 +989,7 @@ impl<T: Config> Pallet<T> {
 		// empty vesting schedules cleanup the storage and unlock the fund
 		if bounded_schedules.len().is_zero() {
 			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+			T::VestingLock::remove_lock(who);
 			return Ok(());
 		}
 
@@ -390,7 +1006,7 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InsufficientBalanceToLock,
 		);
 
-		T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
+		T::VestingLock::set_lock(who, total_amount)?;
 		<VestingSchedules<T>>::insert(who, bounded_schedules);
 
 		Ok(())
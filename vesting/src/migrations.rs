@@ -0,0 +1,47 @@
+use crate::{Config, LockMigrationCursor, LockMigrationPending, VestingLock, VESTING_LOCK_ID};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, LockableCurrency, OnRuntimeUpgrade},
+};
+
+/// Starts moving the vested balances locked with `CurrencyLock` to a new
+/// `VestingLock`, e.g. when switching to `FungibleFreeze`.
+///
+/// `MigratingLock` must be configured as `VestingLock` while the migration is
+/// in progress. The accounts with vesting schedules are then migrated in pages
+/// by `on_idle`, locking their currently locked balance with the new lock and
+/// dropping the schedules that have ended. An account whose new lock can not
+/// be set keeps its old lock and `LockMigrationFailed` is emitted.
+/// `LocksMigrated` is emitted once every account was processed, after which
+/// the new lock can be configured as `VestingLock` directly.
+pub struct MigrateLocksToFreezes<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for MigrateLocksToFreezes<T> {
+	fn on_runtime_upgrade() -> Weight {
+		LockMigrationCursor::<T>::kill();
+		LockMigrationPending::<T>::put(true);
+		T::DbWeight::get().writes(2)
+	}
+}
+
+/// Locks vested balances with `NewLock`, removing the `VESTING_LOCK_ID` lock
+/// of `OldCurrency` once that succeeded. Used while `MigrateLocksToFreezes` is
+/// in progress.
+pub struct MigratingLock<OldCurrency, NewLock>(PhantomData<(OldCurrency, NewLock)>);
+
+impl<AccountId, OldCurrency, NewLock> VestingLock<AccountId, <OldCurrency as Currency<AccountId>>::Balance>
+	for MigratingLock<OldCurrency, NewLock>
+where
+	OldCurrency: LockableCurrency<AccountId>,
+	NewLock: VestingLock<AccountId, <OldCurrency as Currency<AccountId>>::Balance>,
+{
+	fn set_lock(who: &AccountId, amount: <OldCurrency as Currency<AccountId>>::Balance) -> DispatchResult {
+		NewLock::set_lock(who, amount)?;
+		OldCurrency::remove_lock(VESTING_LOCK_ID, who);
+		Ok(())
+	}
+
+	fn remove_lock(who: &AccountId) {
+		NewLock::remove_lock(who);
+		OldCurrency::remove_lock(VESTING_LOCK_ID, who);
+	}
+}
@@ -7,7 +7,7 @@ use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{ConstU32, ConstU64, EnsureOrigin, Everything},
 };
-use frame_system::RawOrigin;
+use frame_system::{EnsureRoot, RawOrigin};
 use sp_core::H256;
 use sp_runtime::{traits::IdentityLookup, BuildStorage};
 
@@ -46,7 +46,7 @@ impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
 	type DustRemoval = ();
 	type RuntimeEvent = RuntimeEvent;
-	type ExistentialDeposit = ConstU64<1>;
+	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = frame_system::Pallet<Runtime>;
 	type MaxLocks = ();
 	type MaxReserves = ();
@@ -55,7 +55,7 @@ impl pallet_balances::Config for Runtime {
 	type RuntimeHoldReason = RuntimeHoldReason;
 	type FreezeIdentifier = [u8; 8];
 	type MaxHolds = ();
-	type MaxFreezes = ();
+	type MaxFreezes = ConstU32<1>;
 }
 
 pub struct EnsureAliceOrBob;
@@ -80,6 +80,32 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureAliceOrBob {
 
 parameter_types! {
 	pub static MockBlockNumberProvider: u64 = 0;
+	pub static ExistentialDeposit: u64 = 1;
+	pub static MockDustLockHandling: DustLockHandling = DustLockHandling::Keep;
+	pub const VestingPalletId: PalletId = PalletId(*b"orml/vst");
+	pub static UseFreeze: bool = false;
+	pub const VestingFreezeId: [u8; 8] = VESTING_LOCK_ID;
+}
+
+/// Locks vested balances with a freeze instead of a lock if `UseFreeze` is
+/// set, removing the lock if any.
+pub struct MockVestingLock;
+impl VestingLock<AccountId, Balance> for MockVestingLock {
+	fn set_lock(who: &AccountId, amount: Balance) -> DispatchResult {
+		if UseFreeze::get() {
+			MigratingLock::<PalletBalances, FungibleFreeze<PalletBalances, VestingFreezeId>>::set_lock(who, amount)
+		} else {
+			CurrencyLock::<PalletBalances>::set_lock(who, amount)
+		}
+	}
+
+	fn remove_lock(who: &AccountId) {
+		if UseFreeze::get() {
+			MigratingLock::<PalletBalances, FungibleFreeze<PalletBalances, VestingFreezeId>>::remove_lock(who)
+		} else {
+			CurrencyLock::<PalletBalances>::remove_lock(who)
+		}
+	}
 }
 
 impl BlockNumberProvider for MockBlockNumberProvider {
@@ -93,11 +119,17 @@ impl BlockNumberProvider for MockBlockNumberProvider {
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = PalletBalances;
+	type VestingLock = MockVestingLock;
 	type MinVestedTransfer = ConstU64<5>;
 	type VestedTransferOrigin = EnsureAliceOrBob;
 	type WeightInfo = ();
 	type MaxVestingSchedules = ConstU32<2>;
 	type BlockNumberProvider = MockBlockNumberProvider;
+	type DustLockHandling = MockDustLockHandling;
+	type PalletId = VestingPalletId;
+	type AcceptancePeriod = ConstU64<10>;
+	type MaxVestedTransferGrants = ConstU32<3>;
+	type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -533,3 +533,606 @@ fn cliff_vesting_works() {
 		));
 	});
 }
+
+#[test]
+fn assign_vested_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 4u32,
+			per_period: 11u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+
+		// one period vested, 33 locked
+		MockBlockNumberProvider::set(11);
+
+		assert_ok!(Vesting::assign_vested(
+			RuntimeOrigin::signed(BOB),
+			CHARLIE,
+			0,
+			Perbill::from_percent(50)
+		));
+
+		// per_period is rounded down for the assignee
+		let assigned = VestingSchedule {
+			per_period: 5u64,
+			..schedule.clone()
+		};
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAssigned {
+			from: BOB,
+			to: CHARLIE,
+			vesting_schedule: assigned.clone(),
+		}));
+
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![VestingSchedule {
+				per_period: 6u64,
+				..schedule
+			}]
+		);
+		assert_eq!(PalletBalances::free_balance(BOB), 44 - 15);
+		assert_eq!(
+			PalletBalances::locks(&BOB).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 18u64,
+				reasons: Reasons::All,
+			})
+		);
+
+		// CHARLIE still has 5 locked by genesis schedules
+		assert_eq!(Vesting::vesting_schedules(&CHARLIE).len(), 2);
+		assert_eq!(Vesting::vesting_schedules(&CHARLIE).last(), Some(&assigned));
+		assert_eq!(PalletBalances::free_balance(CHARLIE), CHARLIE_BALANCE + 15);
+		assert_eq!(
+			PalletBalances::locks(&CHARLIE).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 5u64 + 15u64,
+				reasons: Reasons::All,
+			})
+		);
+
+		// assigning the whole schedule removes it
+		assert_ok!(Vesting::assign_vested(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			0,
+			Perbill::one()
+		));
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
+		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert_eq!(PalletBalances::free_balance(BOB), 11);
+	});
+}
+
+#[test]
+fn assign_vested_fails_if_invalid() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+
+		assert_noop!(
+			Vesting::assign_vested(RuntimeOrigin::signed(BOB), ALICE, 1, Perbill::one()),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::assign_vested(RuntimeOrigin::signed(BOB), ALICE, 0, Perbill::from_percent(1)),
+			Error::<Runtime>::NothingToAssign
+		);
+		assert_noop!(
+			Vesting::assign_vested(RuntimeOrigin::signed(BOB), ALICE, 0, Perbill::from_percent(20)),
+			Error::<Runtime>::AmountLow
+		);
+		// CHARLIE already has the maximum number of schedules
+		assert_noop!(
+			Vesting::assign_vested(RuntimeOrigin::signed(BOB), CHARLIE, 0, Perbill::from_percent(50)),
+			Error::<Runtime>::MaxVestingSchedulesExceeded
+		);
+	});
+}
+
+#[test]
+fn claim_handles_locks_below_existential_deposit() {
+	let vesting_lock = |amount| {
+		Some(BalanceLock {
+			id: VESTING_LOCK_ID,
+			amount,
+			reasons: Reasons::All,
+		})
+	};
+	let setup = |handling| {
+		System::set_block_number(1);
+		ExistentialDeposit::set(3);
+		MockDustLockHandling::set(handling);
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 5u32,
+			per_period: 2u64,
+		};
+		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		MockBlockNumberProvider::set(31);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(4));
+		MockBlockNumberProvider::set(41);
+	};
+
+	ExtBuilder::build().execute_with(|| {
+		setup(DustLockHandling::Keep);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(2));
+	});
+
+	ExtBuilder::build().execute_with(|| {
+		setup(DustLockHandling::Defer);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(4));
+		assert_eq!(Vesting::vesting_schedules(&BOB).len(), 1);
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::ClaimDeferred { who: BOB }));
+
+		MockBlockNumberProvider::set(51);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::Claimed { who: BOB, amount: 0 }));
+	});
+
+	ExtBuilder::build().execute_with(|| {
+		setup(DustLockHandling::Merge);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(2));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB),
+			vec![VestingSchedule {
+				start: 41u64,
+				period: 9u64,
+				period_count: 1u32,
+				per_period: 2u64,
+			}]
+		);
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::Claimed { who: BOB, amount: 2 }));
+	});
+}
+
+#[test]
+fn merged_locks_below_existential_deposit_unlock_at_next_period() {
+	let vesting_lock = |amount| {
+		Some(BalanceLock {
+			id: VESTING_LOCK_ID,
+			amount,
+			reasons: Reasons::All,
+		})
+	};
+	let setup = || {
+		System::set_block_number(1);
+		ExistentialDeposit::set(3);
+		MockDustLockHandling::set(DustLockHandling::Merge);
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 10u32,
+			per_period: 1u64,
+		};
+		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		MockBlockNumberProvider::set(81);
+	};
+
+	ExtBuilder::build().execute_with(|| {
+		setup();
+		// the remainder of two periods is unlocked with the next one
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(2));
+
+		MockBlockNumberProvider::set(89);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(2));
+
+		MockBlockNumberProvider::set(90);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+	});
+
+	ExtBuilder::build().execute_with(|| {
+		setup();
+		// not handled with free balance besides the schedules
+		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(ALICE), BOB, 5));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(2));
+
+		MockBlockNumberProvider::set(90);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(&BOB).get(0).cloned(), vesting_lock(1));
+	});
+}
+
+#[test]
+fn vested_transfer_requiring_acceptance_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Vesting::set_vested_transfer_acceptance(
+			RuntimeOrigin::signed(BOB),
+			true
+		));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestedTransferAcceptanceSet {
+			who: BOB,
+			required: true,
+		}));
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 1u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestedTransferOffered {
+			offer_id: 0,
+			from: ALICE,
+			to: BOB,
+			vesting_schedule: schedule.clone(),
+			expires_at: 10,
+		}));
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+		assert_eq!(PalletBalances::free_balance(BOB), 0);
+		assert_eq!(PalletBalances::free_balance(Vesting::account_id()), 10);
+
+		assert_noop!(
+			Vesting::accept_vested_transfer(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::NotVestedTransferRecipient
+		);
+		assert_noop!(
+			Vesting::refund_vested_transfer(RuntimeOrigin::signed(ALICE), 0),
+			Error::<Runtime>::VestedTransferOfferNotExpired
+		);
+
+		MockBlockNumberProvider::set(5);
+		assert_ok!(Vesting::accept_vested_transfer(RuntimeOrigin::signed(BOB), 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
+			from: ALICE,
+			to: BOB,
+			vesting_schedule: schedule.clone(),
+		}));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule.clone()]);
+		assert_eq!(PalletBalances::free_balance(BOB), 10);
+		assert_eq!(
+			PalletBalances::locks(&BOB).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 10u64,
+				reasons: Reasons::All,
+			})
+		);
+		assert_eq!(Vesting::vested_transfer_offers(0), None);
+		assert_noop!(
+			Vesting::accept_vested_transfer(RuntimeOrigin::signed(BOB), 0),
+			Error::<Runtime>::VestedTransferOfferNotFound
+		);
+	});
+}
+
+#[test]
+fn unaccepted_vested_transfer_is_refundable() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Vesting::set_vested_transfer_acceptance(
+			RuntimeOrigin::signed(BOB),
+			true
+		));
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 1u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+		assert_eq!(PalletBalances::free_balance(ALICE), ALICE_BALANCE - 20);
+
+		// the recipient can decline
+		assert_ok!(Vesting::refund_vested_transfer(RuntimeOrigin::signed(BOB), 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestedTransferRefunded {
+			offer_id: 0,
+			from: ALICE,
+			amount: 10,
+		}));
+
+		// anyone can refund once expired
+		MockBlockNumberProvider::set(11);
+		assert_noop!(
+			Vesting::accept_vested_transfer(RuntimeOrigin::signed(BOB), 1),
+			Error::<Runtime>::VestedTransferOfferExpired
+		);
+		assert_ok!(Vesting::refund_vested_transfer(RuntimeOrigin::signed(CHARLIE), 1));
+		assert_eq!(PalletBalances::free_balance(ALICE), ALICE_BALANCE);
+		assert!(Vesting::vesting_schedules(&BOB).is_empty());
+
+		// transfers go through directly again once acceptance is not required
+		assert_ok!(Vesting::set_vested_transfer_acceptance(
+			RuntimeOrigin::signed(BOB),
+			false
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule]);
+	});
+}
+
+#[test]
+fn vested_transfer_many_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let template = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 0u64,
+		};
+		assert_ok!(Vesting::vested_transfer_many(
+			RuntimeOrigin::signed(ALICE),
+			template.clone(),
+			vec![(BOB, 20), (4, 10)].try_into().unwrap()
+		));
+
+		let bob_schedule = VestingSchedule {
+			per_period: 10u64,
+			..template.clone()
+		};
+		let dave_schedule = VestingSchedule {
+			per_period: 5u64,
+			..template.clone()
+		};
+		System::assert_has_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
+			from: ALICE,
+			to: BOB,
+			vesting_schedule: bob_schedule.clone(),
+		}));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
+			from: ALICE,
+			to: 4,
+			vesting_schedule: dave_schedule.clone(),
+		}));
+		assert_eq!(Vesting::vesting_schedules(&BOB), vec![bob_schedule]);
+		assert_eq!(Vesting::vesting_schedules(&4), vec![dave_schedule]);
+		assert_eq!(PalletBalances::free_balance(BOB), 20);
+		assert_eq!(PalletBalances::free_balance(ALICE), ALICE_BALANCE - 30);
+	});
+}
+
+#[test]
+fn vested_transfer_many_fails_if_any_grant_fails() {
+	ExtBuilder::build().execute_with(|| {
+		let template = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 0u64,
+		};
+		assert_noop!(
+			Vesting::vested_transfer_many(
+				RuntimeOrigin::signed(ALICE),
+				template.clone(),
+				vec![(BOB, 10), (4, 21)].try_into().unwrap()
+			),
+			Error::<Runtime>::IndivisibleGrant
+		);
+		assert_noop!(
+			Vesting::vested_transfer_many(
+				RuntimeOrigin::signed(ALICE),
+				VestingSchedule {
+					period_count: 0u32,
+					..template.clone()
+				},
+				vec![(BOB, 10)].try_into().unwrap()
+			),
+			Error::<Runtime>::ZeroVestingPeriodCount
+		);
+		// CHARLIE already has the maximum vesting schedules
+		assert_noop!(
+			Vesting::vested_transfer_many(
+				RuntimeOrigin::signed(ALICE),
+				template,
+				vec![(BOB, 10), (CHARLIE, 10)].try_into().unwrap()
+			),
+			Error::<Runtime>::MaxVestingSchedulesExceeded
+		);
+	});
+}
+
+#[test]
+fn force_update_beneficiary_works() {
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			schedule.clone()
+		));
+
+		// one period vested, 10 locked
+		MockBlockNumberProvider::set(11);
+
+		assert_noop!(
+			Vesting::force_update_beneficiary(RuntimeOrigin::signed(BOB), BOB, ALICE, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			Vesting::force_update_beneficiary(RuntimeOrigin::root(), BOB, ALICE, 1),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+
+		assert_ok!(Vesting::force_update_beneficiary(RuntimeOrigin::root(), BOB, ALICE, 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingBeneficiaryUpdated {
+			from: BOB,
+			to: ALICE,
+			vesting_schedule: schedule.clone(),
+		}));
+
+		// the vested balance stays with BOB
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
+		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert_eq!(PalletBalances::free_balance(BOB), 10);
+
+		assert_eq!(Vesting::vesting_schedules(&ALICE), vec![schedule]);
+		assert_eq!(PalletBalances::free_balance(ALICE), ALICE_BALANCE - 10);
+		assert_eq!(
+			PalletBalances::locks(&ALICE).get(0),
+			Some(&BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 10u64,
+				reasons: Reasons::All,
+			})
+		);
+	});
+}
+
+#[test]
+fn vesting_with_fungible_freeze_works() {
+	ExtBuilder::build().execute_with(|| {
+		UseFreeze::set(true);
+		let frozen = |who| <PalletBalances as fungible::InspectFreeze<_>>::balance_frozen(&VESTING_LOCK_ID, who);
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_eq!(frozen(&BOB), 20);
+		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 1).is_err());
+
+		MockBlockNumberProvider::set(11);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(frozen(&BOB), 10);
+		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10));
+		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 1).is_err());
+
+		MockBlockNumberProvider::set(21);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_eq!(frozen(&BOB), 0);
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
+	});
+}
+
+#[test]
+fn migrate_locks_to_freezes_works() {
+	use frame_support::traits::{Hooks, OnRuntimeUpgrade};
+
+	ExtBuilder::build().execute_with(|| {
+		System::set_block_number(1);
+		let frozen = |who| <PalletBalances as fungible::InspectFreeze<_>>::balance_frozen(&VESTING_LOCK_ID, who);
+		let schedule = VestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_eq!(
+			PalletBalances::locks(&BOB),
+			vec![BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 20u64,
+				reasons: Reasons::All,
+			}]
+		);
+
+		UseFreeze::set(true);
+		MockBlockNumberProvider::set(11);
+		MigrateLocksToFreezes::<Runtime>::on_runtime_upgrade();
+		assert!(Vesting::lock_migration_pending());
+
+		// the accounts are only migrated in `on_idle`
+		assert_eq!(PalletBalances::locks(&BOB).len(), 1);
+		Vesting::on_idle(1, Weight::zero());
+		assert_eq!(PalletBalances::locks(&BOB).len(), 1);
+
+		// BOB can not be frozen and keeps the old lock
+		assert_ok!(<PalletBalances as fungible::MutateFreeze<_>>::set_freeze(
+			b"otherfrz",
+			&BOB,
+			1
+		));
+		Vesting::on_idle(1, Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Vesting(crate::Event::LockMigrationFailed { who: BOB }));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::LocksMigrated));
+		assert!(!Vesting::lock_migration_pending());
+		assert_eq!(
+			PalletBalances::locks(&BOB),
+			vec![BalanceLock {
+				id: VESTING_LOCK_ID,
+				amount: 20u64,
+				reasons: Reasons::All,
+			}]
+		);
+		assert_eq!(frozen(&BOB), 0);
+		assert_eq!(PalletBalances::locks(&CHARLIE), vec![]);
+		assert_eq!(frozen(&CHARLIE), 5);
+
+		// failing to lock fails the claim
+		assert_noop!(
+			Vesting::claim(RuntimeOrigin::signed(BOB)),
+			pallet_balances::Error::<Runtime>::TooManyFreezes
+		);
+
+		assert_ok!(<PalletBalances as fungible::MutateFreeze<_>>::thaw(b"otherfrz", &BOB));
+		MigrateLocksToFreezes::<Runtime>::on_runtime_upgrade();
+		Vesting::on_idle(2, Weight::MAX);
+		assert_eq!(PalletBalances::locks(&BOB), vec![]);
+		assert_eq!(frozen(&BOB), 10);
+		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10));
+		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 1).is_err());
+
+		// migrating again has no further effect
+		MigrateLocksToFreezes::<Runtime>::on_runtime_upgrade();
+		Vesting::on_idle(3, Weight::MAX);
+		assert_eq!(frozen(&BOB), 10);
+		assert_eq!(frozen(&CHARLIE), 5);
+
+		MockBlockNumberProvider::set(21);
+		MigrateLocksToFreezes::<Runtime>::on_runtime_upgrade();
+		Vesting::on_idle(4, Weight::MAX);
+		assert_eq!(frozen(&BOB), 0);
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
+	});
+}
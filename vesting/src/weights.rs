@@ -33,6 +33,12 @@ pub trait WeightInfo {
 	fn vested_transfer() -> Weight;
 	fn claim(i: u32, ) -> Weight;
 	fn update_vesting_schedules(i: u32, ) -> Weight;
+	fn assign_vested() -> Weight;
+	fn set_vested_transfer_acceptance() -> Weight;
+	fn accept_vested_transfer() -> Weight;
+	fn refund_vested_transfer() -> Weight;
+	fn vested_transfer_many(i: u32, ) -> Weight;
+	fn force_update_beneficiary() -> Weight;
 }
 
 /// Default weights.
@@ -56,4 +62,35 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	fn assign_vested() -> Weight {
+		Weight::from_parts(98_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn set_vested_transfer_acceptance() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn accept_vested_transfer() -> Weight {
+		Weight::from_parts(75_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn refund_vested_transfer() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn vested_transfer_many(i: u32, ) -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(70_000_000, 0).saturating_mul(i as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(i as u64)))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(i as u64)))
+	}
+	fn force_update_beneficiary() -> Weight {
+		Weight::from_parts(98_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
 }
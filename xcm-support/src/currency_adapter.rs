@@ -68,28 +68,146 @@ impl<CurrencyId, AccountId, Balance> OnDepositFail<CurrencyId, AccountId, Balanc
 	}
 }
 
+/// Fallback chain of deposit errors handlers. Handlers are tried in order,
+/// and the error of the last one is returned if all of them fail.
+#[impl_trait_for_tuples::impl_for_tuples(1, 5)]
+impl<CurrencyId: Copy, AccountId, Balance: Clone> OnDepositFail<CurrencyId, AccountId, Balance> for Tuple {
+	fn on_deposit_currency_fail(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> Result {
+		let mut last_err = XcmError::FailedToTransactAsset(err.into());
+		for_tuples!( #(
+			match Tuple::on_deposit_currency_fail(err, currency_id, who, amount.clone()) {
+				Ok(()) => return Ok(()),
+				Err(e) => last_err = e,
+			}
+		)* );
+		Err(last_err)
+	}
+
+	fn on_deposit_unknown_asset_fail(err: DispatchError, asset: &MultiAsset, location: &MultiLocation) -> Result {
+		let mut last_err = XcmError::FailedToTransactAsset(err.into());
+		for_tuples!( #(
+			match Tuple::on_deposit_unknown_asset_fail(err, asset, location) {
+				Ok(()) => return Ok(()),
+				Err(e) => last_err = e,
+			}
+		)* );
+		Err(last_err)
+	}
+}
+
+/// Observer of the failed deposits redirected by `OnDepositFail` impls,
+/// notified with the context of the failed deposit so it could be recorded,
+/// e.g. in an event.
+pub trait OnDepositRedirected<CurrencyId, AccountId, Balance> {
+	/// Called after `amount` of `currency_id`, which failed to be deposited
+	/// to `who` with `err`, was deposited to `to` instead. `to` is `who` if
+	/// the deposit is held as unknown assets of `who`.
+	fn on_deposit_redirected(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+	);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<CurrencyId: Copy, AccountId, Balance: Clone> OnDepositRedirected<CurrencyId, AccountId, Balance> for Tuple {
+	fn on_deposit_redirected(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+	) {
+		for_tuples!( #( Tuple::on_deposit_redirected(err, currency_id, who, to, amount.clone()); )* );
+	}
+}
+
 /// `OnDepositFail` impl, will deposit known currencies to an alternative
-/// account.
-pub struct DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>(
-	PhantomData<(Alternative, MultiCurrency, CurrencyId, AccountId, Balance)>,
+/// account. `Observer` is notified of every redirected deposit.
+pub struct DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance, Observer = ()>(
+	PhantomData<(Alternative, MultiCurrency, CurrencyId, AccountId, Balance, Observer)>,
 );
 impl<
 		Alternative: Get<AccountId>,
 		MultiCurrency: orml_traits::MultiCurrency<AccountId, CurrencyId = CurrencyId, Balance = Balance>,
 		AccountId: sp_std::fmt::Debug + Clone,
 		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
-		Balance,
+		Balance: Clone,
+		Observer: OnDepositRedirected<CurrencyId, AccountId, Balance>,
 	> OnDepositFail<CurrencyId, AccountId, Balance>
-	for DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance>
+	for DepositToAlternative<Alternative, MultiCurrency, CurrencyId, AccountId, Balance, Observer>
 {
 	fn on_deposit_currency_fail(
-		_err: DispatchError,
+		err: DispatchError,
 		currency_id: CurrencyId,
-		_who: &AccountId,
+		who: &AccountId,
 		amount: Balance,
 	) -> Result {
-		MultiCurrency::deposit(currency_id, &Alternative::get(), amount)
-			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))
+		let alternative = Alternative::get();
+		MultiCurrency::deposit(currency_id, &alternative, amount.clone())
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		Observer::on_deposit_redirected(err, currency_id, who, &alternative, amount);
+		Ok(())
+	}
+}
+
+/// `OnDepositFail` impl, will deposit known currencies as unknown assets of
+/// the beneficiary, so they could be recovered later. `Observer` is
+/// notified of every redirected deposit.
+pub struct DepositToUnknownAsset<UnknownAsset, CurrencyIdConvert, AccountIdConvert, Observer = ()>(
+	PhantomData<(UnknownAsset, CurrencyIdConvert, AccountIdConvert, Observer)>,
+);
+impl<
+		UnknownAsset: UnknownAssetT,
+		CurrencyIdConvert: Convert<CurrencyId, Option<MultiLocation>>,
+		AccountIdConvert: Convert<AccountId, MultiLocation>,
+		Observer: OnDepositRedirected<CurrencyId, AccountId, Balance>,
+		CurrencyId: Copy,
+		AccountId: Clone,
+		Balance: Clone + Into<u128>,
+	> OnDepositFail<CurrencyId, AccountId, Balance>
+	for DepositToUnknownAsset<UnknownAsset, CurrencyIdConvert, AccountIdConvert, Observer>
+{
+	fn on_deposit_currency_fail(
+		err: DispatchError,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> Result {
+		let id = CurrencyIdConvert::convert(currency_id).ok_or_else(|| XcmError::FailedToTransactAsset(err.into()))?;
+		let asset: MultiAsset = (id, amount.clone().into()).into();
+		UnknownAsset::deposit(&asset, &AccountIdConvert::convert(who.clone()))
+			.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+		Observer::on_deposit_redirected(err, currency_id, who, who, amount);
+		Ok(())
+	}
+}
+
+/// Observer of the known currencies `MultiCurrencyAdapter` deposits and
+/// withdraws, called after each successful deposit or withdrawal.
+pub trait OnAssetTransact<Balance> {
+	/// Called after `amount` of `asset` was deposited to `location`.
+	fn on_deposit(location: &MultiLocation, asset: &MultiAsset, amount: Balance);
+
+	/// Called after `amount` of `asset` was withdrawn from `location`.
+	fn on_withdraw(location: &MultiLocation, asset: &MultiAsset, amount: Balance);
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl<Balance: Clone> OnAssetTransact<Balance> for Tuple {
+	fn on_deposit(location: &MultiLocation, asset: &MultiAsset, amount: Balance) {
+		for_tuples!( #( Tuple::on_deposit(location, asset, amount.clone()); )* );
+	}
+
+	fn on_withdraw(location: &MultiLocation, asset: &MultiAsset, amount: Balance) {
+		for_tuples!( #( Tuple::on_withdraw(location, asset, amount.clone()); )* );
 	}
 }
 
@@ -100,7 +218,8 @@ impl<
 /// errors.
 ///
 /// If the asset is known, deposit/withdraw will be handled by `MultiCurrency`,
-/// else by `UnknownAsset` if unknown.
+/// else by `UnknownAsset` if unknown. `Observer` is notified of every
+/// successful deposit/withdraw handled by `MultiCurrency`.
 #[allow(clippy::type_complexity)]
 pub struct MultiCurrencyAdapter<
 	MultiCurrency,
@@ -111,6 +230,7 @@ pub struct MultiCurrencyAdapter<
 	CurrencyId,
 	CurrencyIdConvert,
 	DepositFailureHandler,
+	Observer = (),
 >(
 	PhantomData<(
 		MultiCurrency,
@@ -121,6 +241,7 @@ pub struct MultiCurrencyAdapter<
 		CurrencyId,
 		CurrencyIdConvert,
 		DepositFailureHandler,
+		Observer,
 	)>,
 );
 
@@ -133,6 +254,7 @@ impl<
 		CurrencyId: FullCodec + Eq + PartialEq + Copy + MaybeSerializeDeserialize + Debug,
 		CurrencyIdConvert: Convert<MultiAsset, Option<CurrencyId>>,
 		DepositFailureHandler: OnDepositFail<CurrencyId, AccountId, MultiCurrency::Balance>,
+		Observer: OnAssetTransact<MultiCurrency::Balance>,
 	> TransactAsset
 	for MultiCurrencyAdapter<
 		MultiCurrency,
@@ -143,6 +265,7 @@ impl<
 		CurrencyId,
 		CurrencyIdConvert,
 		DepositFailureHandler,
+		Observer,
 	>
 {
 	fn deposit_asset(asset: &MultiAsset, location: &MultiLocation, _context: &XcmContext) -> Result {
@@ -152,8 +275,13 @@ impl<
 			Match::matches_fungible(asset),
 		) {
 			// known asset
-			(Some(who), Some(currency_id), Some(amount)) => MultiCurrency::deposit(currency_id, &who, amount)
-				.or_else(|err| DepositFailureHandler::on_deposit_currency_fail(err, currency_id, &who, amount)),
+			(Some(who), Some(currency_id), Some(amount)) => match MultiCurrency::deposit(currency_id, &who, amount) {
+				Ok(()) => {
+					Observer::on_deposit(location, asset, amount);
+					Ok(())
+				}
+				Err(err) => DepositFailureHandler::on_deposit_currency_fail(err, currency_id, &who, amount),
+			},
 			// unknown asset
 			_ => UnknownAsset::deposit(asset, location)
 				.or_else(|err| DepositFailureHandler::on_deposit_unknown_asset_fail(err, asset, location)),
@@ -173,7 +301,10 @@ impl<
 			let amount: MultiCurrency::Balance = Match::matches_fungible(asset)
 				.ok_or_else(|| XcmError::from(Error::FailedToMatchFungible))?
 				.saturated_into();
-			MultiCurrency::withdraw(currency_id, &who, amount).map_err(|e| XcmError::FailedToTransactAsset(e.into()))
+			MultiCurrency::withdraw(currency_id, &who, amount)
+				.map_err(|e| XcmError::FailedToTransactAsset(e.into()))?;
+			Observer::on_withdraw(location, asset, amount);
+			Ok::<_, XcmError>(())
 		})?;
 
 		Ok(asset.clone().into())
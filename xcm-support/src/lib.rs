@@ -21,9 +21,17 @@ use xcm_executor::traits::MatchesFungible;
 
 use orml_traits::{location::Reserve, GetByKey};
 
-pub use currency_adapter::{DepositToAlternative, MultiCurrencyAdapter, OnDepositFail};
+pub use currency_adapter::{
+	DepositToAlternative, DepositToUnknownAsset, MultiCurrencyAdapter, OnAssetTransact, OnDepositFail,
+	OnDepositRedirected,
+};
+pub use matchers::{
+	ConcreteFungibleMatcher, IsConvertible, IsPalletInstanceAsset, IsRegisteredAsset, NormalizeLocation, StartsWith,
+	ToAbsoluteView, ToRelativeView,
+};
 
 mod currency_adapter;
+mod matchers;
 
 mod tests;
 
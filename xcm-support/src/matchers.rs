@@ -0,0 +1,127 @@
+//! Composable building blocks for `MatchesFungible` implementations.
+//!
+//! `ConcreteFungibleMatcher` matches concrete fungible assets by their
+//! location. The location is normalized first and then checked against a
+//! `Contains<MultiLocation>` filter. Filters can be combined with tuples, which
+//! match if any of their filters match, and with
+//! `frame_support::traits::InsideBoth`.
+
+use frame_support::traits::{Contains, Get};
+use sp_runtime::traits::{CheckedConversion, Convert};
+use sp_std::marker::PhantomData;
+
+use xcm::v3::prelude::*;
+use xcm_executor::traits::MatchesFungible;
+
+use orml_traits::asset_registry::Inspect;
+
+/// Normalizes the location of an asset before it is matched.
+pub trait NormalizeLocation {
+	/// Returns the normalized location, or `None` if it can not be normalized.
+	fn normalize(location: MultiLocation) -> Option<MultiLocation>;
+}
+
+impl NormalizeLocation for () {
+	fn normalize(location: MultiLocation) -> Option<MultiLocation> {
+		Some(location)
+	}
+}
+
+/// Views locations under `SelfLocation` from this chain, e.g.
+/// `(1, Parachain(id), X)` becomes `(0, X)` if `SelfLocation` is
+/// `(1, Parachain(id))`. Other locations are kept.
+pub struct ToRelativeView<SelfLocation>(PhantomData<SelfLocation>);
+impl<SelfLocation: Get<MultiLocation>> NormalizeLocation for ToRelativeView<SelfLocation> {
+	fn normalize(location: MultiLocation) -> Option<MultiLocation> {
+		let self_location = SelfLocation::get();
+		if !location.starts_with(&self_location) {
+			return Some(location);
+		}
+
+		let mut relative = MultiLocation::here();
+		for junction in location.interior.iter().skip(self_location.interior.len()) {
+			relative.push_interior(*junction).ok()?;
+		}
+		Some(relative)
+	}
+}
+
+/// Views the locations of this chain from outside, e.g. `(0, X)` becomes
+/// `(1, Parachain(id), X)` if `SelfLocation` is `(1, Parachain(id))`. Other
+/// locations are kept.
+pub struct ToAbsoluteView<SelfLocation>(PhantomData<SelfLocation>);
+impl<SelfLocation: Get<MultiLocation>> NormalizeLocation for ToAbsoluteView<SelfLocation> {
+	fn normalize(location: MultiLocation) -> Option<MultiLocation> {
+		if location.parents > 0 {
+			return Some(location);
+		}
+
+		let mut absolute = SelfLocation::get();
+		for junction in location.interior.iter() {
+			absolute.push_interior(*junction).ok()?;
+		}
+		Some(absolute)
+	}
+}
+
+/// Matches locations which can be converted into `CurrencyId`.
+pub struct IsConvertible<CurrencyId, CurrencyIdConvert>(PhantomData<(CurrencyId, CurrencyIdConvert)>);
+impl<CurrencyId, CurrencyIdConvert> Contains<MultiLocation> for IsConvertible<CurrencyId, CurrencyIdConvert>
+where
+	CurrencyIdConvert: Convert<MultiLocation, Option<CurrencyId>>,
+{
+	fn contains(location: &MultiLocation) -> bool {
+		CurrencyIdConvert::convert(*location).is_some()
+	}
+}
+
+/// Matches locations of assets registered in the asset registry `Registry`.
+pub struct IsRegisteredAsset<Registry>(PhantomData<Registry>);
+impl<Registry: Inspect> Contains<MultiLocation> for IsRegisteredAsset<Registry> {
+	fn contains(location: &MultiLocation) -> bool {
+		Registry::asset_id(location).is_some()
+	}
+}
+
+/// Matches locations starting with `Prefix`.
+pub struct StartsWith<Prefix>(PhantomData<Prefix>);
+impl<Prefix: Get<MultiLocation>> Contains<MultiLocation> for StartsWith<Prefix> {
+	fn contains(location: &MultiLocation) -> bool {
+		location.starts_with(&Prefix::get())
+	}
+}
+
+/// Matches locations of assets of the pallet instance `Instance` under
+/// `Prefix`, e.g. `(1, Parachain(id), PalletInstance(50), X)` if `Prefix` is
+/// `(1, Parachain(id))` and `Instance` is `50`.
+pub struct IsPalletInstanceAsset<Prefix, Instance>(PhantomData<(Prefix, Instance)>);
+impl<Prefix, Instance> Contains<MultiLocation> for IsPalletInstanceAsset<Prefix, Instance>
+where
+	Prefix: Get<MultiLocation>,
+	Instance: Get<u8>,
+{
+	fn contains(location: &MultiLocation) -> bool {
+		let prefix = Prefix::get();
+		location.starts_with(&prefix)
+			&& location.interior.at(prefix.interior.len()) == Some(&PalletInstance(Instance::get()))
+	}
+}
+
+/// A `MatchesFungible` implementation. It matches concrete fungible assets
+/// whose location, normalized by `Normalize`, is contained in `Filter`.
+pub struct ConcreteFungibleMatcher<Filter, Normalize = ()>(PhantomData<(Filter, Normalize)>);
+impl<Filter, Normalize, Amount> MatchesFungible<Amount> for ConcreteFungibleMatcher<Filter, Normalize>
+where
+	Filter: Contains<MultiLocation>,
+	Normalize: NormalizeLocation,
+	Amount: TryFrom<u128>,
+{
+	fn matches_fungible(a: &MultiAsset) -> Option<Amount> {
+		if let (Fungible(ref amount), Concrete(ref location)) = (&a.fun, &a.id) {
+			if Normalize::normalize(*location).map_or(false, |location| Filter::contains(&location)) {
+				return CheckedConversion::checked_from(*amount);
+			}
+		}
+		None
+	}
+}
@@ -4,6 +4,7 @@
 
 use super::*;
 
+use frame_support::traits::Contains;
 use orml_traits::{location::AbsoluteReserveProvider, location::RelativeLocations, ConcreteFungibleAsset};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -109,3 +110,190 @@ fn multi_native_asset() {
 		&MultiLocation::parent(),
 	));
 }
+
+frame_support::parameter_types! {
+	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(2)));
+	pub const AssetsPalletIndex: u8 = 50;
+}
+
+type ComposedMatcher = ConcreteFungibleMatcher<
+	(
+		IsConvertible<TestCurrencyId, CurrencyIdConvert>,
+		IsPalletInstanceAsset<SelfLocation, AssetsPalletIndex>,
+	),
+	ToAbsoluteView<SelfLocation>,
+>;
+
+#[test]
+fn concrete_fungible_matcher_combines_filters() {
+	let local_asset = |instance: u8| MultiAsset {
+		fun: Fungible(100),
+		id: Concrete(MultiLocation::new(0, X2(PalletInstance(instance), GeneralIndex(1)))),
+	};
+
+	assert_eq!(
+		<ComposedMatcher as MatchesFungible<u128>>::matches_fungible(&MultiAsset::parent_asset(100)),
+		Some(100)
+	);
+	assert_eq!(
+		<ComposedMatcher as MatchesFungible<u128>>::matches_fungible(&local_asset(50)),
+		Some(100)
+	);
+	assert_eq!(
+		<ComposedMatcher as MatchesFungible<u128>>::matches_fungible(&local_asset(51)),
+		None
+	);
+	assert_eq!(
+		<ComposedMatcher as MatchesFungible<u128>>::matches_fungible(&MultiAsset {
+			fun: NonFungible(Index(1)),
+			id: Concrete(MultiLocation::parent()),
+		}),
+		None
+	);
+}
+
+#[test]
+fn location_normalization_works() {
+	let local = MultiLocation::new(0, X2(PalletInstance(50), GeneralIndex(1)));
+	let absolute = MultiLocation::new(1, X3(Parachain(2), PalletInstance(50), GeneralIndex(1)));
+
+	assert_eq!(ToAbsoluteView::<SelfLocation>::normalize(local), Some(absolute));
+	assert_eq!(ToRelativeView::<SelfLocation>::normalize(absolute), Some(local));
+	assert_eq!(
+		ToRelativeView::<SelfLocation>::normalize(MultiLocation::parent()),
+		Some(MultiLocation::parent())
+	);
+	assert_eq!(
+		ToAbsoluteView::<SelfLocation>::normalize(MultiLocation::parent()),
+		Some(MultiLocation::parent())
+	);
+
+	assert!(StartsWith::<SelfLocation>::contains(&absolute));
+	assert!(!StartsWith::<SelfLocation>::contains(&local));
+}
+
+struct FailingHandler;
+impl OnDepositFail<u32, u32, u128> for FailingHandler {
+	fn on_deposit_currency_fail(_err: DispatchError, _currency_id: u32, _who: &u32, _amount: u128) -> xcm::v3::Result {
+		Err(XcmError::FailedToTransactAsset("FailingHandler"))
+	}
+}
+
+frame_support::parameter_types! {
+	static Redirected: Vec<(u32, u32, u128)> = vec![];
+}
+
+struct RedirectTo<const ACCOUNT: u32>;
+impl<const ACCOUNT: u32> OnDepositFail<u32, u32, u128> for RedirectTo<ACCOUNT> {
+	fn on_deposit_currency_fail(_err: DispatchError, currency_id: u32, _who: &u32, amount: u128) -> xcm::v3::Result {
+		Redirected::mutate(|v| v.push((currency_id, ACCOUNT, amount)));
+		Ok(())
+	}
+}
+
+#[test]
+fn deposit_fail_handlers_are_tried_in_order() {
+	let err = DispatchError::Other("DepositFailed");
+
+	assert_eq!(
+		<(FailingHandler, RedirectTo<10>, RedirectTo<20>)>::on_deposit_currency_fail(err, 1, &2, 100),
+		Ok(())
+	);
+	assert_eq!(Redirected::get(), vec![(1, 10, 100)]);
+
+	assert_eq!(
+		<(FailingHandler, FailingHandler)>::on_deposit_currency_fail(err, 1, &2, 100),
+		Err(XcmError::FailedToTransactAsset("FailingHandler"))
+	);
+	assert_eq!(
+		<(FailingHandler,)>::on_deposit_unknown_asset_fail(err, &MultiAsset::parent_asset(100), &MultiLocation::here()),
+		Err(XcmError::FailedToTransactAsset("DepositFailed"))
+	);
+}
+
+frame_support::parameter_types! {
+	static Observed: Vec<(u32, bool, u128)> = vec![];
+}
+
+struct RecordAs<const ID: u32>;
+impl<const ID: u32> OnAssetTransact<u128> for RecordAs<ID> {
+	fn on_deposit(_location: &MultiLocation, _asset: &MultiAsset, amount: u128) {
+		Observed::mutate(|v| v.push((ID, true, amount)));
+	}
+
+	fn on_withdraw(_location: &MultiLocation, _asset: &MultiAsset, amount: u128) {
+		Observed::mutate(|v| v.push((ID, false, amount)));
+	}
+}
+
+#[test]
+fn asset_transact_observers_are_called_in_order() {
+	let asset = MultiAsset::parent_asset(100);
+	let location = MultiLocation::here();
+
+	<(RecordAs<1>, RecordAs<2>)>::on_deposit(&location, &asset, 100);
+	<(RecordAs<1>, RecordAs<2>)>::on_withdraw(&location, &asset, 50);
+	<()>::on_deposit(&location, &asset, 10);
+	assert_eq!(
+		Observed::get(),
+		vec![(1, true, 100), (2, true, 100), (1, false, 50), (2, false, 50)]
+	);
+}
+
+frame_support::parameter_types! {
+	static HeldUnknownAssets: Vec<(MultiAsset, MultiLocation)> = vec![];
+	static RedirectedDeposits: Vec<(DispatchError, u32, u32, u32, u128)> = vec![];
+}
+
+struct MockUnknownAsset;
+impl UnknownAsset for MockUnknownAsset {
+	fn deposit(asset: &MultiAsset, to: &MultiLocation) -> DispatchResult {
+		HeldUnknownAssets::mutate(|v| v.push((asset.clone(), *to)));
+		Ok(())
+	}
+
+	fn withdraw(_asset: &MultiAsset, _from: &MultiLocation) -> DispatchResult {
+		Err(DispatchError::Other("NotSupported"))
+	}
+}
+
+struct CurrencyIdToLocation;
+impl Convert<u32, Option<MultiLocation>> for CurrencyIdToLocation {
+	fn convert(id: u32) -> Option<MultiLocation> {
+		(id == 1).then(MultiLocation::parent)
+	}
+}
+
+struct AccountIdToLocation;
+impl Convert<u32, MultiLocation> for AccountIdToLocation {
+	fn convert(id: u32) -> MultiLocation {
+		Junction::GeneralIndex(id.into()).into()
+	}
+}
+
+struct RecordRedirected;
+impl OnDepositRedirected<u32, u32, u128> for RecordRedirected {
+	fn on_deposit_redirected(err: DispatchError, currency_id: u32, who: &u32, to: &u32, amount: u128) {
+		RedirectedDeposits::mutate(|v| v.push((err, currency_id, *who, *to, amount)));
+	}
+}
+
+#[test]
+fn redirected_deposits_are_observed_with_context() {
+	type Handler = DepositToUnknownAsset<MockUnknownAsset, CurrencyIdToLocation, AccountIdToLocation, RecordRedirected>;
+	let err = DispatchError::Other("DepositFailed");
+
+	assert_eq!(Handler::on_deposit_currency_fail(err, 1, &2, 100), Ok(()));
+	assert_eq!(
+		HeldUnknownAssets::get(),
+		vec![(MultiAsset::parent_asset(100), Junction::GeneralIndex(2).into())]
+	);
+	assert_eq!(RedirectedDeposits::get(), vec![(err, 1, 2, 2, 100)]);
+
+	// not redirected, not observed
+	assert_eq!(
+		Handler::on_deposit_currency_fail(err, 2, &2, 100),
+		Err(XcmError::FailedToTransactAsset("DepositFailed"))
+	);
+	assert_eq!(RedirectedDeposits::get().len(), 1);
+}
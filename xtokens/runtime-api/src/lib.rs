@@ -0,0 +1,18 @@
+//! Runtime API definition for xtokens module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait XtokensApi<QueryId, TransferStatus> where
+		QueryId: Codec,
+		TransferStatus: Codec,
+	{
+		fn transfer_status(query_id: QueryId) -> Option<TransferStatus>;
+	}
+}
@@ -24,6 +24,37 @@
 //!   item to be used as fee.
 //! - `transfer_multiassets`: Transfer several `MultiAsset` specifying the item
 //!   to be used as fee.
+//! - `clear_transfer_status`: Remove the delivery status of a settled tracked
+//!   transfer.
+//! - `set_destination_fee_mode`: Set which fee assets a destination chain
+//!   accepts, so transfers paying fees in other assets fail locally.
+//! - `transfer_nonfungible`: Transfer an NFT instance, mapped to a non-fungible
+//!   `MultiAsset` by `NftLocationMapper`, paying the fee in a currency.
+//! - `set_reserve_view`: Set whether self assets are located in the absolute or
+//!   relative view, for `SelectableReserveProvider` and
+//!   `SelectableSelfLocation`.
+//!
+//! ### Native token
+//!
+//! Transfers of `NativeCurrencyId` are located by `NativeAssetLocation`
+//! within `SelfLocation`, so `CurrencyIdConvert` does not need to map the
+//! native token.
+//!
+//! ### Delivery tracking
+//!
+//! Up to `MaxTrackedTransfers` transfers at a time, and up to
+//! `MaxTrackedTransfersPerAccount` per sender, ask the destination chain to
+//! report the outcome of the deposit. The pallet implements `OnResponse` so it
+//! can be used as (part of) the XCM executor's `ResponseHandler`, and exposes
+//! the status through `transfer_status`. The status is kept for another
+//! `TransferStatusTimeout` blocks after the transfer times out, and is removed
+//! afterwards unless cleared earlier with `clear_transfer_status`.
+//!
+//! The query ids of tracked transfers have `TRACKED_TRANSFER_QUERY_ID_FLAG`
+//! set, so they don't collide with the query ids of other response handlers
+//! like `pallet-xcm`, which count up from zero.
+//! `TrackedTransferResponseRouter` hands the responses to tracked transfers
+//! to the pallet and all others to another handler.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::from_over_into)]
@@ -41,7 +72,7 @@ use frame_support::{
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Bounded, Convert, MaybeSerializeDeserialize, Member, Zero},
+	traits::{AtLeast32BitUnsigned, Bounded, Convert, MaybeSerializeDeserialize, Member, Saturating, Zero},
 	DispatchError,
 };
 use sp_std::{prelude::*, result::Result};
@@ -50,11 +81,11 @@ use xcm::{
 	v3::{prelude::*, Weight},
 	VersionedMultiAsset, VersionedMultiAssets, VersionedMultiLocation,
 };
-use xcm_executor::traits::WeightBounds;
+use xcm_executor::traits::{OnResponse, WeightBounds};
 
 pub use module::*;
 use orml_traits::{
-	location::{Parse, Reserve},
+	location::{AbsoluteReserveProvider, Parse, RelativeReserveProvider, Reserve},
 	xcm_transfer::{Transferred, XtokensWeightInfo},
 	GetByKey, XcmTransfer,
 };
@@ -72,6 +103,68 @@ enum TransferKind {
 }
 use TransferKind::*;
 
+/// The fee assets accepted by a destination chain.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FeeMode {
+	/// The fee must be paid in an asset reserved by the destination, i.e. its
+	/// native asset.
+	DestinationNative,
+	/// The fee can be paid in any asset with a known reserve.
+	ReserveAsset,
+	/// The fee can be paid in any asset specified by the sender.
+	SenderSpecified,
+}
+
+/// How the locations of this chain's own assets are represented.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReserveView {
+	/// Self assets are located from the relay chain, as with
+	/// `AbsoluteReserveProvider`.
+	Absolute,
+	/// Self assets are located relative to `Here`, as with
+	/// `RelativeReserveProvider`.
+	Relative,
+}
+
+/// Where the native token of this chain is located within `SelfLocation`.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NativeAssetLocation {
+	/// At `SelfLocation` itself.
+	#[default]
+	Here,
+	/// At the given pallet instance within `SelfLocation`, e.g. of the
+	/// balances pallet.
+	PalletInstance(u8),
+}
+
+/// Delivery status of a tracked transfer.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TransferStatus {
+	/// Waiting for the destination to report the outcome.
+	Pending,
+	/// The assets were deposited on the destination.
+	Delivered,
+	/// Execution on the destination failed at instruction `index`.
+	Failed { index: u32 },
+	/// The destination did not report the outcome in time. This is also the
+	/// case if buying execution on the destination failed, as the report is
+	/// only requested afterwards.
+	TimedOut,
+}
+
+/// A transfer whose delivery status is tracked.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TrackedTransfer<AccountId, BlockNumber> {
+	/// The sender of the transfer.
+	pub sender: AccountId,
+	/// The chain expected to report the outcome.
+	pub responder: MultiLocation,
+	/// The transfer times out if no outcome is reported before this block.
+	pub timeout: BlockNumber,
+	/// The reported status.
+	pub status: TransferStatus,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -98,8 +191,8 @@ pub mod module {
 		/// Convert `T::AccountId` to `MultiLocation`.
 		type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
 
-		/// Self chain location.
-		#[pallet::constant]
+		/// Self chain location. Use `SelectableSelfLocation` to follow the
+		/// reserve view set with `set_reserve_view`.
 		type SelfLocation: Get<MultiLocation>;
 
 		/// Minimum xcm execution fee paid on destination chain.
@@ -111,6 +204,11 @@ pub mod module {
 		/// MultiLocation filter
 		type MultiLocationsFilter: Contains<MultiLocation>;
 
+		/// Filter of the `(sender, asset, dest)` triples allowed to be sent,
+		/// checked for every asset of a transfer, including the fee, before
+		/// the XCM message is built.
+		type OutboundAssetFilter: Contains<(Self::AccountId, MultiAsset, MultiLocation)>;
+
 		/// Means of measuring the weight consumed by an XCM message locally.
 		type Weigher: WeightBounds<Self::RuntimeCall>;
 
@@ -129,8 +227,54 @@ pub mod module {
 		type MaxAssetsForTransfer: Get<usize>;
 
 		/// The way to retreave the reserve of a MultiAsset. This can be
-		/// configured to accept absolute or relative paths for self tokens
+		/// configured to accept absolute or relative paths for self tokens, or
+		/// to follow the reserve view set with `set_reserve_view` by using
+		/// `SelectableReserveProvider`.
 		type ReserveProvider: Reserve;
+
+		/// The maximum encoded size of a transfer message, and of each message
+		/// forwarded from it to the reserve or destination chain. Larger
+		/// messages are rejected before execution instead of being dropped by
+		/// the transport layer.
+		#[pallet::constant]
+		type MaxXcmEncodedSize: Get<u32>;
+
+		/// The maximum number of transfers whose delivery status is tracked.
+		/// Transfers are not tracked while the limit is reached, zero disables
+		/// tracking.
+		#[pallet::constant]
+		type MaxTrackedTransfers: Get<u32>;
+
+		/// The maximum number of transfers of a single sender whose delivery
+		/// status is tracked.
+		#[pallet::constant]
+		type MaxTrackedTransfersPerAccount: Get<u32>;
+
+		/// The number of blocks to wait for the destination to report the
+		/// outcome of a tracked transfer. The status is removed after twice
+		/// this number of blocks.
+		#[pallet::constant]
+		type TransferStatusTimeout: Get<BlockNumberFor<Self>>;
+
+		/// The origin which may set the fee mode of destinations.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// NFT class id.
+		type NftClassId: Parameter + Member + Clone;
+
+		/// NFT instance id.
+		type NftInstanceId: Parameter + Member + Clone;
+
+		/// Convert an NFT class and instance to the location of the class and
+		/// the XCM asset instance.
+		type NftLocationMapper: Convert<(Self::NftClassId, Self::NftInstanceId), Option<(MultiLocation, AssetInstance)>>;
+
+		/// The currency id of the native token, located by
+		/// `NativeAssetLocation` instead of `CurrencyIdConvert`.
+		type NativeCurrencyId: Get<Option<Self::CurrencyId>>;
+
+		/// Where the native token is located within `SelfLocation`.
+		type NativeAssetLocation: Get<NativeAssetLocation>;
 	}
 
 	#[pallet::event]
@@ -143,6 +287,18 @@ pub mod module {
 			fee: MultiAsset,
 			dest: MultiLocation,
 		},
+		/// The delivery status of a transfer is tracked under `query_id`.
+		TransferTracked {
+			sender: T::AccountId,
+			query_id: QueryId,
+			dest: MultiLocation,
+		},
+		/// The destination reported the outcome of a tracked transfer.
+		TransferStatusUpdated { query_id: QueryId, status: TransferStatus },
+		/// The fee mode of a destination is updated.
+		DestinationFeeModeUpdated { dest: MultiLocation, mode: Option<FeeMode> },
+		/// The reserve view of self assets is updated.
+		ReserveViewUpdated { view: Option<ReserveView> },
 	}
 
 	#[pallet::error]
@@ -188,10 +344,81 @@ pub mod module {
 		NotSupportedMultiLocation,
 		/// MinXcmFee not registered for certain reserve location
 		MinXcmFeeNotDefined,
+		/// No transfer is tracked under the given query id.
+		TransferNotFound,
+		/// Only the sender can clear the status of a transfer.
+		NotTransferSender,
+		/// The outcome of the transfer has not been reported yet.
+		TransferPending,
+		/// The fee asset is not accepted by the destination.
+		FeeNotAllowedByDestination,
+		/// NFT is not cross-chain transferable.
+		NotCrossChainTransferableNft,
+		/// The encoded transfer message is larger than `MaxXcmEncodedSize`.
+		XcmMessageTooLarge,
+		/// The asset is not allowed to be sent to the destination by
+		/// `OutboundAssetFilter`.
+		AssetFilteredOut,
 	}
 
+	/// The fee assets accepted by destination chains. Destinations without a
+	/// fee mode accept any fee asset.
+	///
+	/// DestinationFeeModes: map MultiLocation => Option<FeeMode>
+	#[pallet::storage]
+	#[pallet::getter(fn destination_fee_mode)]
+	pub type DestinationFeeModes<T: Config> = StorageMap<_, Blake2_128Concat, MultiLocation, FeeMode, OptionQuery>;
+
+	/// The reserve view of self assets, used by `SelectableReserveProvider` and
+	/// `SelectableSelfLocation` instead of their default view.
+	///
+	/// SelectedReserveView: Option<ReserveView>
+	#[pallet::storage]
+	#[pallet::getter(fn reserve_view)]
+	pub type SelectedReserveView<T: Config> = StorageValue<_, ReserveView, OptionQuery>;
+
+	/// The query id of the next tracked transfer.
+	#[pallet::storage]
+	pub type NextQueryId<T: Config> = StorageValue<_, QueryId, ValueQuery>;
+
+	/// Transfers whose delivery status is tracked.
+	///
+	/// TrackedTransfers: map QueryId => Option<TrackedTransfer>
+	#[pallet::storage]
+	#[pallet::getter(fn tracked_transfers)]
+	pub type TrackedTransfers<T: Config> =
+		CountedStorageMap<_, Twox64Concat, QueryId, TrackedTransfer<T::AccountId, BlockNumberFor<T>>, OptionQuery>;
+
+	/// The number of tracked transfers of each sender.
+	///
+	/// TrackedTransfersPerAccount: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn tracked_transfers_per_account)]
+	pub type TrackedTransfersPerAccount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The tracked transfers to remove at a block.
+	///
+	/// TransferStatusExpiries: double_map BlockNumber, QueryId => ()
+	#[pallet::storage]
+	pub type TransferStatusExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, QueryId, (), OptionQuery>;
+
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// The number of entries is bounded by `MaxTrackedTransfers`.
+			let mut removed: u64 = 0;
+			for (query_id, _) in TransferStatusExpiries::<T>::drain_prefix(now) {
+				if let Some(transfer) = TrackedTransfers::<T>::take(query_id) {
+					Self::untrack_sender(&transfer.sender);
+				}
+				removed = removed.saturating_add(1);
+			}
+			T::DbWeight::get()
+				.reads(1)
+				.saturating_add(T::DbWeight::get().reads_writes(2, 4).saturating_mul(removed))
+		}
+	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
@@ -388,9 +615,130 @@ pub mod module {
 
 			Self::do_transfer_multiassets(who, assets.clone(), fee.clone(), dest, dest_weight_limit).map(|_| ())
 		}
+
+		/// Remove the delivery status of a tracked transfer once its outcome
+		/// is known or it timed out.
+		///
+		/// The dispatch origin for this call must be `Signed` by the sender of
+		/// the transfer.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 4))]
+		pub fn clear_transfer_status(origin: OriginFor<T>, query_id: QueryId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let transfer = Self::tracked_transfers(query_id).ok_or(Error::<T>::TransferNotFound)?;
+			ensure!(transfer.sender == who, Error::<T>::NotTransferSender);
+			ensure!(
+				Self::transfer_status(query_id) != Some(TransferStatus::Pending),
+				Error::<T>::TransferPending
+			);
+			TrackedTransfers::<T>::remove(query_id);
+			TransferStatusExpiries::<T>::remove(Self::status_expiry(transfer.timeout), query_id);
+			Self::untrack_sender(&who);
+			Ok(())
+		}
+
+		/// Set or remove the fee mode of a destination chain.
+		///
+		/// The dispatch origin for this call must be `UpdateOrigin`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_destination_fee_mode(
+			origin: OriginFor<T>,
+			dest: Box<VersionedMultiLocation>,
+			mode: Option<FeeMode>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			DestinationFeeModes::<T>::set(dest, mode);
+			Self::deposit_event(Event::<T>::DestinationFeeModeUpdated { dest, mode });
+			Ok(())
+		}
+
+		/// Set or remove the reserve view of self assets. Without a set view,
+		/// the default view of `SelectableReserveProvider` and
+		/// `SelectableSelfLocation` is used.
+		///
+		/// Transfers only depend on the view when they are executed, so no
+		/// pending state has to be migrated when switching. Assets registered
+		/// with locations of the previous view have to be updated separately.
+		///
+		/// The dispatch origin for this call must be `UpdateOrigin`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_reserve_view(origin: OriginFor<T>, view: Option<ReserveView>) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			SelectedReserveView::<T>::set(view);
+			Self::deposit_event(Event::<T>::ReserveViewUpdated { view });
+			Ok(())
+		}
+
+		/// Transfer an NFT instance, paying the execution on the destination
+		/// chain with `fee` of `fee_currency_id`.
+		///
+		/// The class and instance are mapped to a non-fungible `MultiAsset` by
+		/// `NftLocationMapper`, and the asset transactor of this chain has to
+		/// be able to withdraw it from the caller. The fee currency must share
+		/// the reserve of the NFT class, or be the relay chain asset when
+		/// sending the NFT back to its reserve.
+		///
+		/// `dest_weight_limit` is the weight for XCM execution on the dest
+		/// chain, and it would be charged from the fee.
+		///
+		/// It's a no-op if any error on local XCM execution or message sending.
+		/// Note sending assets out per se doesn't guarantee they would be
+		/// received. Receiving depends on if the XCM message could be delivered
+		/// by the network, and if the receiving chain would handle
+		/// messages correctly.
+		#[pallet::call_index(8)]
+		#[pallet::weight(XtokensWeight::<T>::weight_of_transfer_nonfungible(
+			class_id.clone(),
+			instance_id.clone(),
+			fee_currency_id.clone(),
+			*fee,
+			dest
+		))]
+		pub fn transfer_nonfungible(
+			origin: OriginFor<T>,
+			class_id: T::NftClassId,
+			instance_id: T::NftInstanceId,
+			fee_currency_id: T::CurrencyId,
+			fee: T::Balance,
+			dest: Box<VersionedMultiLocation>,
+			dest_weight_limit: WeightLimit,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest: MultiLocation = (*dest).try_into().map_err(|()| Error::<T>::BadVersion)?;
+
+			Self::do_transfer_nonfungible(
+				who,
+				class_id,
+				instance_id,
+				fee_currency_id,
+				fee,
+				dest,
+				dest_weight_limit,
+			)
+			.map(|_| ())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// The location of `currency_id`, resolved from `NativeAssetLocation`
+		/// for the native token and by `CurrencyIdConvert` otherwise.
+		pub fn currency_location(currency_id: T::CurrencyId) -> Option<MultiLocation> {
+			if T::NativeCurrencyId::get().as_ref() != Some(&currency_id) {
+				return T::CurrencyIdConvert::convert(currency_id);
+			}
+			let mut location = T::SelfLocation::get();
+			match T::NativeAssetLocation::get() {
+				NativeAssetLocation::Here => {}
+				NativeAssetLocation::PalletInstance(index) => location.push_interior(PalletInstance(index)).ok()?,
+			}
+			Some(location)
+		}
+
 		fn do_transfer(
 			who: T::AccountId,
 			currency_id: T::CurrencyId,
@@ -399,7 +747,7 @@ pub mod module {
 			dest_weight_limit: WeightLimit,
 		) -> Result<Transferred<T::AccountId>, DispatchError> {
 			let location: MultiLocation =
-				T::CurrencyIdConvert::convert(currency_id).ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+				Self::currency_location(currency_id).ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
 
 			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
 			ensure!(
@@ -411,6 +759,42 @@ pub mod module {
 			Self::do_transfer_multiassets(who, vec![asset.clone()].into(), asset, dest, dest_weight_limit)
 		}
 
+		fn do_transfer_nonfungible(
+			who: T::AccountId,
+			class_id: T::NftClassId,
+			instance_id: T::NftInstanceId,
+			fee_currency_id: T::CurrencyId,
+			fee: T::Balance,
+			dest: MultiLocation,
+			dest_weight_limit: WeightLimit,
+		) -> Result<Transferred<T::AccountId>, DispatchError> {
+			let (asset, fee_asset) = Self::nonfungible_assets(class_id, instance_id, fee_currency_id, fee)?;
+			ensure!(!fee.is_zero(), Error::<T>::ZeroFee);
+
+			// Push contains saturated addition, so we should be able to use it safely
+			let mut assets = MultiAssets::new();
+			assets.push(asset);
+			assets.push(fee_asset.clone());
+
+			Self::do_transfer_multiassets(who, assets, fee_asset, dest, dest_weight_limit)
+		}
+
+		/// The non-fungible asset of an NFT instance, and the fee asset paying
+		/// for its transfer.
+		pub(crate) fn nonfungible_assets(
+			class_id: T::NftClassId,
+			instance_id: T::NftInstanceId,
+			fee_currency_id: T::CurrencyId,
+			fee: T::Balance,
+		) -> Result<(MultiAsset, MultiAsset), DispatchError> {
+			let (class_location, instance) = T::NftLocationMapper::convert((class_id, instance_id))
+				.ok_or(Error::<T>::NotCrossChainTransferableNft)?;
+			let fee_location: MultiLocation =
+				Self::currency_location(fee_currency_id).ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+
+			Ok(((class_location, instance).into(), (fee_location, fee.into()).into()))
+		}
+
 		fn do_transfer_with_fee(
 			who: T::AccountId,
 			currency_id: T::CurrencyId,
@@ -420,7 +804,7 @@ pub mod module {
 			dest_weight_limit: WeightLimit,
 		) -> Result<Transferred<T::AccountId>, DispatchError> {
 			let location: MultiLocation =
-				T::CurrencyIdConvert::convert(currency_id).ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
+				Self::currency_location(currency_id).ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
 
 			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
 			ensure!(!fee.is_zero(), Error::<T>::ZeroFee);
@@ -488,7 +872,7 @@ pub mod module {
 				.ok_or(Error::<T>::AssetIndexNonExistent)?;
 
 			for (currency_id, amount) in &currencies {
-				let location: MultiLocation = T::CurrencyIdConvert::convert(currency_id.clone())
+				let location: MultiLocation = Self::currency_location(currency_id.clone())
 					.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
 				ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
 
@@ -498,7 +882,7 @@ pub mod module {
 
 			// We construct the fee now, since getting it from assets wont work as assets
 			// sorts it
-			let fee_location: MultiLocation = T::CurrencyIdConvert::convert(fee_currency_id.clone())
+			let fee_location: MultiLocation = Self::currency_location(fee_currency_id.clone())
 				.ok_or(Error::<T>::NotCrossChainTransferableCurrency)?;
 
 			let fee: MultiAsset = (fee_location, (*fee_amount).into()).into();
@@ -522,15 +906,27 @@ pub mod module {
 				Error::<T>::NotSupportedMultiLocation
 			);
 			let origin_location = T::AccountIdToMultiLocation::convert(who.clone());
+			let (maybe_query_id, maybe_report) = match Self::track_transfer(&who, &dest) {
+				Some((query_id, report)) => (Some(query_id), Some(report)),
+				None => (None, None),
+			};
 
 			let mut non_fee_reserve: Option<MultiLocation> = None;
 			let asset_len = assets.len();
 			for i in 0..asset_len {
 				let asset = assets.get(i).ok_or(Error::<T>::AssetIndexNonExistent)?;
+				// only the fee has to be fungible, other assets may be NFT instances
 				ensure!(
-					matches!(asset.fun, Fungibility::Fungible(x) if !x.is_zero()),
+					match asset.fun {
+						Fungibility::Fungible(x) => !x.is_zero(),
+						Fungibility::NonFungible(_) => fee != *asset,
+					},
 					Error::<T>::InvalidAsset
 				);
+				ensure!(
+					T::OutboundAssetFilter::contains(&(who.clone(), asset.clone(), dest)),
+					Error::<T>::AssetFilteredOut
+				);
 				// `assets` includes fee, the reserve location is decided by non fee asset
 				if (fee != *asset && non_fee_reserve.is_none()) || asset_len == 1 {
 					non_fee_reserve = T::ReserveProvider::reserve(asset);
@@ -545,6 +941,7 @@ pub mod module {
 			}
 
 			let fee_reserve = T::ReserveProvider::reserve(&fee);
+			Self::ensure_fee_allowed(&dest, fee_reserve)?;
 			if fee_reserve != non_fee_reserve {
 				// Current only support `ToReserve` with relay-chain asset as fee. other case
 				// like `NonReserve` or `SelfReserve` with relay-chain fee is not support.
@@ -593,6 +990,7 @@ pub mod module {
 					Some(override_recipient),
 					dest_weight_limit.clone(),
 					true,
+					None,
 				)?;
 
 				// Second xcm send to dest chain.
@@ -605,6 +1003,7 @@ pub mod module {
 					None,
 					dest_weight_limit,
 					false,
+					maybe_report,
 				)?;
 			} else {
 				Self::execute_and_send_reserve_kind_xcm(
@@ -616,6 +1015,7 @@ pub mod module {
 					None,
 					dest_weight_limit,
 					false,
+					maybe_report,
 				)?;
 			}
 
@@ -625,6 +1025,13 @@ pub mod module {
 				fee: fee.clone(),
 				dest,
 			});
+			if let Some(query_id) = maybe_query_id {
+				Self::deposit_event(Event::<T>::TransferTracked {
+					sender: who.clone(),
+					query_id,
+					dest,
+				});
+			}
 
 			Ok(Transferred {
 				sender: who,
@@ -634,8 +1041,102 @@ pub mod module {
 			})
 		}
 
+		/// Ensure the fee mode of the destination chain of `dest` accepts a fee
+		/// asset reserved by `fee_reserve`.
+		fn ensure_fee_allowed(dest: &MultiLocation, fee_reserve: Option<MultiLocation>) -> DispatchResult {
+			let dest_chain = match dest.chain_part() {
+				Some(dest_chain) => dest_chain,
+				None => return Ok(()),
+			};
+			let allowed = match Self::destination_fee_mode(dest_chain) {
+				Some(FeeMode::DestinationNative) => fee_reserve == Some(dest_chain),
+				Some(FeeMode::ReserveAsset) => fee_reserve.is_some(),
+				Some(FeeMode::SenderSpecified) | None => true,
+			};
+			ensure!(allowed, Error::<T>::FeeNotAllowedByDestination);
+			Ok(())
+		}
+
+		/// Start tracking the delivery status of a transfer from `who` to
+		/// `dest`.
+		///
+		/// Returns the query id and the instruction asking the destination to
+		/// report the outcome, or `None` if the transfer can't be tracked.
+		fn track_transfer(who: &T::AccountId, dest: &MultiLocation) -> Option<(QueryId, Instruction<()>)> {
+			if TrackedTransfers::<T>::count() >= T::MaxTrackedTransfers::get()
+				|| TrackedTransfersPerAccount::<T>::get(who) >= T::MaxTrackedTransfersPerAccount::get()
+			{
+				return None;
+			}
+			let responder = dest.chain_part()?;
+			let destination = T::UniversalLocation::get().invert_target(&responder).ok()?;
+
+			let query_id = NextQueryId::<T>::mutate(|id| {
+				let current = *id;
+				*id = id.wrapping_add(1) & !TRACKED_TRANSFER_QUERY_ID_FLAG;
+				current | TRACKED_TRANSFER_QUERY_ID_FLAG
+			});
+			let timeout = frame_system::Pallet::<T>::block_number().saturating_add(T::TransferStatusTimeout::get());
+			TrackedTransfers::<T>::insert(
+				query_id,
+				TrackedTransfer {
+					sender: who.clone(),
+					responder,
+					timeout,
+					status: TransferStatus::Pending,
+				},
+			);
+			TransferStatusExpiries::<T>::insert(Self::status_expiry(timeout), query_id, ());
+			TrackedTransfersPerAccount::<T>::mutate(who, |count| *count = count.saturating_add(1));
+
+			let report = SetAppendix(Xcm(vec![ReportError(QueryResponseInfo {
+				destination,
+				query_id,
+				max_weight: T::DbWeight::get().reads_writes(1, 1),
+			})]));
+			Some((query_id, report))
+		}
+
+		/// The weight of tracking the delivery status of a transfer.
+		fn track_transfer_weight() -> Weight {
+			// the number of tracked transfers, those of the sender and the next
+			// query id, and the tracked transfer with its counter and expiry
+			T::DbWeight::get().reads_writes(3, 5)
+		}
+
+		/// The block at which the status of a transfer timing out at `timeout`
+		/// is removed.
+		fn status_expiry(timeout: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			timeout.saturating_add(T::TransferStatusTimeout::get())
+		}
+
+		/// Decrease the number of tracked transfers of `who`.
+		fn untrack_sender(who: &T::AccountId) {
+			TrackedTransfersPerAccount::<T>::mutate_exists(who, |maybe_count| {
+				*maybe_count = maybe_count
+					.and_then(|count| count.checked_sub(1))
+					.filter(|count| *count > 0);
+			});
+		}
+
+		/// The delivery status of the transfer tracked under `query_id`.
+		pub fn transfer_status(query_id: QueryId) -> Option<TransferStatus> {
+			Self::tracked_transfers(query_id).map(|transfer| {
+				if transfer.status == TransferStatus::Pending
+					&& frame_system::Pallet::<T>::block_number() >= transfer.timeout
+				{
+					TransferStatus::TimedOut
+				} else {
+					transfer.status
+				}
+			})
+		}
+
 		/// Execute and send xcm with given assets and fee to dest chain or
 		/// reserve chain.
+		///
+		/// `maybe_report` is executed on the destination after buying
+		/// execution, to report the outcome of the deposit.
 		fn execute_and_send_reserve_kind_xcm(
 			origin_location: MultiLocation,
 			assets: MultiAssets,
@@ -645,6 +1146,7 @@ pub mod module {
 			maybe_recipient_override: Option<MultiLocation>,
 			dest_weight_limit: WeightLimit,
 			use_teleport: bool,
+			maybe_report: Option<Instruction<()>>,
 		) -> DispatchResult {
 			let (transfer_kind, dest, reserve, recipient) = Self::transfer_kind(reserve, dest)?;
 			let recipient = match maybe_recipient_override {
@@ -652,18 +1154,30 @@ pub mod module {
 				None => recipient,
 			};
 			let mut msg = match transfer_kind {
-				SelfReserveAsset => Self::transfer_self_reserve_asset(assets, fee, dest, recipient, dest_weight_limit)?,
-				ToReserve => Self::transfer_to_reserve(assets, fee, dest, recipient, dest_weight_limit)?,
+				SelfReserveAsset => Self::transfer_self_reserve_asset(
+					assets.clone(),
+					fee,
+					dest,
+					recipient,
+					dest_weight_limit,
+					maybe_report,
+				)?,
+				ToReserve => {
+					Self::transfer_to_reserve(assets.clone(), fee, dest, recipient, dest_weight_limit, maybe_report)?
+				}
 				ToNonReserve => Self::transfer_to_non_reserve(
-					assets,
+					assets.clone(),
 					fee,
 					reserve,
 					dest,
 					recipient,
 					dest_weight_limit,
 					use_teleport,
+					maybe_report,
 				)?,
 			};
+			Self::ensure_xcm_size(&msg)?;
+			Self::ensure_forwarded_xcm_size(&msg.0, &assets, T::UniversalLocation::get())?;
 			let hash = msg.using_encoded(sp_io::hashing::blake2_256);
 
 			let weight = T::Weigher::weight(&mut msg).map_err(|()| Error::<T>::UnweighableMessage)?;
@@ -677,20 +1191,70 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Ensure the encoded `msg`, as a `VersionedXcm`, is not larger than
+		/// `MaxXcmEncodedSize`.
+		fn ensure_xcm_size(msg: &impl Encode) -> DispatchResult {
+			// one more byte for the version of `VersionedXcm`
+			let encoded_size = msg.encoded_size().saturating_add(1);
+			ensure!(
+				encoded_size <= T::MaxXcmEncodedSize::get() as usize,
+				Error::<T>::XcmMessageTooLarge
+			);
+			Ok(())
+		}
+
+		/// Ensure the messages forwarded by `instructions`, and the messages
+		/// forwarded by them in turn, are not larger than
+		/// `MaxXcmEncodedSize`.
+		///
+		/// The executor prefixes a forwarded message with an instruction
+		/// holding the transferred `assets`, reanchored from `context` to the
+		/// receiving chain, and `ClearOrigin`.
+		fn ensure_forwarded_xcm_size<Call>(
+			instructions: &[Instruction<Call>],
+			assets: &MultiAssets,
+			context: InteriorMultiLocation,
+		) -> DispatchResult {
+			for instruction in instructions {
+				let (xcm, dest) = match instruction {
+					TransferReserveAsset { xcm, dest, .. }
+					| InitiateReserveWithdraw { xcm, reserve: dest, .. }
+					| DepositReserveAsset { xcm, dest, .. }
+					| InitiateTeleport { xcm, dest, .. } => (xcm, dest),
+					_ => continue,
+				};
+				let mut assets = assets.clone();
+				assets.reanchor(dest, context).map_err(|_| Error::<T>::CannotReanchor)?;
+				let mut forwarded = vec![ReserveAssetDeposited(assets.clone()), ClearOrigin];
+				forwarded.extend(xcm.0.iter().cloned());
+				Self::ensure_xcm_size(&Xcm(forwarded))?;
+
+				let mut dest_context: MultiLocation = context.into();
+				dest_context
+					.append_with(*dest)
+					.map_err(|_| Error::<T>::CannotReanchor)?;
+				Self::ensure_forwarded_xcm_size(&xcm.0, &assets, dest_context.interior)?;
+			}
+			Ok(())
+		}
+
 		fn transfer_self_reserve_asset(
 			assets: MultiAssets,
 			fee: MultiAsset,
 			dest: MultiLocation,
 			recipient: MultiLocation,
 			dest_weight_limit: WeightLimit,
+			maybe_report: Option<Instruction<()>>,
 		) -> Result<Xcm<T::RuntimeCall>, DispatchError> {
 			Ok(Xcm(vec![TransferReserveAsset {
 				assets: assets.clone(),
 				dest,
-				xcm: Xcm(vec![
+				xcm: Self::deposit_xcm(
 					Self::buy_execution(fee, &dest, dest_weight_limit)?,
-					Self::deposit_asset(recipient, assets.len() as u32),
-				]),
+					maybe_report,
+					recipient,
+					assets.len() as u32,
+				),
 			}]))
 		}
 
@@ -700,16 +1264,19 @@ pub mod module {
 			reserve: MultiLocation,
 			recipient: MultiLocation,
 			dest_weight_limit: WeightLimit,
+			maybe_report: Option<Instruction<()>>,
 		) -> Result<Xcm<T::RuntimeCall>, DispatchError> {
 			Ok(Xcm(vec![
 				WithdrawAsset(assets.clone()),
 				InitiateReserveWithdraw {
 					assets: All.into(),
 					reserve,
-					xcm: Xcm(vec![
+					xcm: Self::deposit_xcm(
 						Self::buy_execution(fee, &reserve, dest_weight_limit)?,
-						Self::deposit_asset(recipient, assets.len() as u32),
-					]),
+						maybe_report,
+						recipient,
+						assets.len() as u32,
+					),
 				},
 			]))
 		}
@@ -722,6 +1289,7 @@ pub mod module {
 			recipient: MultiLocation,
 			dest_weight_limit: WeightLimit,
 			use_teleport: bool,
+			maybe_report: Option<Instruction<()>>,
 		) -> Result<Xcm<T::RuntimeCall>, DispatchError> {
 			let mut reanchored_dest = dest;
 			if reserve == MultiLocation::parent() {
@@ -746,10 +1314,12 @@ pub mod module {
 							DepositReserveAsset {
 								assets: AllCounted(max_assets).into(),
 								dest: reanchored_dest,
-								xcm: Xcm(vec![
+								xcm: Self::deposit_xcm(
 									Self::buy_execution(half(&fee), &dest, dest_weight_limit)?,
-									Self::deposit_asset(recipient, max_assets),
-								]),
+									maybe_report,
+									recipient,
+									max_assets,
+								),
 							},
 						]),
 					},
@@ -765,10 +1335,12 @@ pub mod module {
 							InitiateTeleport {
 								assets: All.into(),
 								dest: reanchored_dest,
-								xcm: Xcm(vec![
+								xcm: Self::deposit_xcm(
 									Self::buy_execution(half(&fee), &dest, dest_weight_limit)?,
-									Self::deposit_asset(recipient, max_assets),
-								]),
+									maybe_report,
+									recipient,
+									max_assets,
+								),
 							},
 						]),
 					},
@@ -776,6 +1348,20 @@ pub mod module {
 			}
 		}
 
+		/// The xcm executed on the destination: buy execution, optionally ask
+		/// for a report of the outcome, and deposit the assets.
+		fn deposit_xcm(
+			buy_execution: Instruction<()>,
+			maybe_report: Option<Instruction<()>>,
+			recipient: MultiLocation,
+			max_assets: u32,
+		) -> Xcm<()> {
+			let mut xcm = vec![buy_execution];
+			xcm.extend(maybe_report);
+			xcm.push(Self::deposit_asset(recipient, max_assets));
+			Xcm(xcm)
+		}
+
 		fn deposit_asset(recipient: MultiLocation, max_assets: u32) -> Instruction<()> {
 			DepositAsset {
 				assets: AllCounted(max_assets).into(),
@@ -871,8 +1457,11 @@ pub mod module {
 							},
 						]),
 					};
-					return T::Weigher::weight(&mut msg)
-						.map_or(Weight::max_value(), |w| T::BaseXcmWeight::get().saturating_add(w));
+					return T::Weigher::weight(&mut msg).map_or(Weight::max_value(), |w| {
+						T::BaseXcmWeight::get()
+							.saturating_add(w)
+							.saturating_add(Pallet::<T>::track_transfer_weight())
+					});
 				}
 			}
 			Weight::zero()
@@ -880,7 +1469,7 @@ pub mod module {
 
 		/// Returns weight of `transfer` call.
 		fn weight_of_transfer(currency_id: T::CurrencyId, amount: T::Balance, dest: &VersionedMultiLocation) -> Weight {
-			if let Some(location) = T::CurrencyIdConvert::convert(currency_id) {
+			if let Some(location) = Self::currency_location(currency_id) {
 				let asset = (location, amount.into()).into();
 				Self::weight_of_transfer_multiasset(&asset, dest)
 			} else {
@@ -896,7 +1485,7 @@ pub mod module {
 		) -> Weight {
 			let mut assets: Vec<MultiAsset> = Vec::new();
 			for (currency_id, amount) in currencies {
-				if let Some(location) = T::CurrencyIdConvert::convert(currency_id.clone()) {
+				if let Some(location) = Self::currency_location(currency_id.clone()) {
 					let asset: MultiAsset = (location, (*amount).into()).into();
 					assets.push(asset);
 				} else {
@@ -934,14 +1523,37 @@ pub mod module {
 							},
 						]),
 					};
-					return T::Weigher::weight(&mut msg)
-						.map_or(Weight::max_value(), |w| T::BaseXcmWeight::get().saturating_add(w));
+					return T::Weigher::weight(&mut msg).map_or(Weight::max_value(), |w| {
+						T::BaseXcmWeight::get()
+							.saturating_add(w)
+							.saturating_add(Pallet::<T>::track_transfer_weight())
+					});
 				}
 			}
 			Weight::zero()
 		}
 	}
 
+	impl<T: Config> XtokensWeight<T> {
+		/// Returns weight of `transfer_nonfungible` call.
+		pub fn weight_of_transfer_nonfungible(
+			class_id: T::NftClassId,
+			instance_id: T::NftInstanceId,
+			fee_currency_id: T::CurrencyId,
+			fee: T::Balance,
+			dest: &VersionedMultiLocation,
+		) -> Weight {
+			if let Ok((asset, fee_asset)) = Pallet::<T>::nonfungible_assets(class_id, instance_id, fee_currency_id, fee)
+			{
+				let assets = MultiAssets::from(vec![asset, fee_asset.clone()]);
+				let fee_item = assets.inner().iter().position(|a| *a == fee_asset).unwrap_or_default() as u32;
+				Self::weight_of_transfer_multiassets(&VersionedMultiAssets::from(assets), &fee_item, dest)
+			} else {
+				Weight::zero()
+			}
+		}
+	}
+
 	impl<T: Config> XcmTransfer<T::AccountId, T::Balance, T::CurrencyId> for Pallet<T> {
 		#[require_transactional]
 		fn transfer(
@@ -1009,6 +1621,111 @@ pub mod module {
 			Self::do_transfer_multiassets(who, assets, fee, dest, dest_weight_limit)
 		}
 	}
+
+	impl<T: Config> OnResponse for Pallet<T> {
+		fn expecting_response(origin: &MultiLocation, query_id: QueryId, _querier: Option<&MultiLocation>) -> bool {
+			if !is_tracked_transfer_query(query_id) {
+				return false;
+			}
+			Self::tracked_transfers(query_id).map_or(false, |transfer| {
+				transfer.status == TransferStatus::Pending
+					&& transfer.responder == *origin
+					&& frame_system::Pallet::<T>::block_number() < transfer.timeout
+			})
+		}
+
+		fn on_response(
+			origin: &MultiLocation,
+			query_id: QueryId,
+			querier: Option<&MultiLocation>,
+			response: Response,
+			_max_weight: Weight,
+			_context: &XcmContext,
+		) -> Weight {
+			if !Self::expecting_response(origin, query_id, querier) {
+				return T::DbWeight::get().reads(1);
+			}
+			let status = match response {
+				Response::ExecutionResult(None) => TransferStatus::Delivered,
+				Response::ExecutionResult(Some((index, _))) => TransferStatus::Failed { index },
+				_ => return T::DbWeight::get().reads(1),
+			};
+
+			TrackedTransfers::<T>::mutate(query_id, |maybe_transfer| {
+				if let Some(transfer) = maybe_transfer {
+					transfer.status = status;
+				}
+			});
+			Self::deposit_event(Event::<T>::TransferStatusUpdated { query_id, status });
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+	}
+}
+
+/// The bit set in the query ids of tracked transfers.
+pub const TRACKED_TRANSFER_QUERY_ID_FLAG: QueryId = 1 << 63;
+
+/// Whether `query_id` is the query id of a tracked transfer.
+pub fn is_tracked_transfer_query(query_id: QueryId) -> bool {
+	query_id & TRACKED_TRANSFER_QUERY_ID_FLAG != 0
+}
+
+/// A response handler handing the responses to tracked transfers to the
+/// pallet, and all other responses to `Other`, e.g. `pallet-xcm`.
+pub struct TrackedTransferResponseRouter<T, Other>(PhantomData<(T, Other)>);
+
+impl<T: Config, Other: OnResponse> OnResponse for TrackedTransferResponseRouter<T, Other> {
+	fn expecting_response(origin: &MultiLocation, query_id: QueryId, querier: Option<&MultiLocation>) -> bool {
+		if is_tracked_transfer_query(query_id) {
+			Pallet::<T>::expecting_response(origin, query_id, querier)
+		} else {
+			Other::expecting_response(origin, query_id, querier)
+		}
+	}
+
+	fn on_response(
+		origin: &MultiLocation,
+		query_id: QueryId,
+		querier: Option<&MultiLocation>,
+		response: Response,
+		max_weight: Weight,
+		context: &XcmContext,
+	) -> Weight {
+		if is_tracked_transfer_query(query_id) {
+			Pallet::<T>::on_response(origin, query_id, querier, response, max_weight, context)
+		} else {
+			Other::on_response(origin, query_id, querier, response, max_weight, context)
+		}
+	}
+}
+
+/// A `Reserve` provider following the reserve view set with
+/// `set_reserve_view`, or `DefaultView` if none is set.
+pub struct SelectableReserveProvider<T, DefaultView>(PhantomData<(T, DefaultView)>);
+
+impl<T: Config, DefaultView: Get<ReserveView>> Reserve for SelectableReserveProvider<T, DefaultView> {
+	fn reserve(asset: &MultiAsset) -> Option<MultiLocation> {
+		match Pallet::<T>::reserve_view().unwrap_or_else(DefaultView::get) {
+			ReserveView::Absolute => AbsoluteReserveProvider::reserve(asset),
+			ReserveView::Relative => RelativeReserveProvider::reserve(asset),
+		}
+	}
+}
+
+/// The self location in the reserve view set with `set_reserve_view`, or
+/// `DefaultView` if none is set. It is `AbsoluteLocation` in the absolute view
+/// and `Here` in the relative view.
+pub struct SelectableSelfLocation<T, DefaultView, AbsoluteLocation>(PhantomData<(T, DefaultView, AbsoluteLocation)>);
+
+impl<T: Config, DefaultView: Get<ReserveView>, AbsoluteLocation: Get<MultiLocation>> Get<MultiLocation>
+	for SelectableSelfLocation<T, DefaultView, AbsoluteLocation>
+{
+	fn get() -> MultiLocation {
+		match Pallet::<T>::reserve_view().unwrap_or_else(DefaultView::get) {
+			ReserveView::Absolute => AbsoluteLocation::get(),
+			ReserveView::Relative => MultiLocation::here(),
+		}
+	}
 }
 
 /// Returns amount if `asset` is fungible, or zero.
@@ -7,7 +7,7 @@ use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_io::TestExternalities;
 use sp_runtime::{AccountId32, BoundedVec, BuildStorage};
-use xcm_executor::traits::WeightTrader;
+use xcm_executor::traits::{TransactAsset, WeightTrader};
 use xcm_executor::Assets;
 
 use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
@@ -40,6 +40,8 @@ pub enum CurrencyId {
 	C,
 	/// Parachain D token
 	D,
+	/// Native token of a parachain, located by `NativeAssetLocation`
+	N,
 }
 
 pub struct CurrencyIdConvert;
@@ -103,6 +105,7 @@ impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
 				)
 					.into(),
 			),
+			CurrencyId::N => None,
 		}
 	}
 }
@@ -253,6 +256,7 @@ pub fn para_ext(para_id: u32) -> TestExternalities {
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -277,6 +281,7 @@ pub fn para_teleport_ext(para_id: u32) -> TestExternalities {
 
 	orml_tokens::GenesisConfig::<Runtime> {
 		balances: vec![(ALICE, CurrencyId::R, 1_000)],
+		..Default::default()
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
@@ -304,6 +309,77 @@ pub fn relay_ext() -> sp_io::TestExternalities {
 	ext
 }
 
+pub type NftClassId = u32;
+pub type NftInstanceId = u64;
+
+/// Classes below this id are ParaA NFTs, others can't be transferred.
+pub const MAX_NFT_CLASS_ID: NftClassId = 10;
+
+pub struct NftLocationMapper;
+impl Convert<(NftClassId, NftInstanceId), Option<(MultiLocation, AssetInstance)>> for NftLocationMapper {
+	fn convert((class_id, instance_id): (NftClassId, NftInstanceId)) -> Option<(MultiLocation, AssetInstance)> {
+		if class_id >= MAX_NFT_CLASS_ID {
+			return None;
+		}
+		Some((
+			(Parent, Parachain(1), GeneralIndex(class_id.into())).into(),
+			AssetInstance::Index(instance_id.into()),
+		))
+	}
+}
+
+/// Mocks an NFT backed asset transactor, keeping the owner of every instance
+/// in the storage of the chain.
+pub struct MockNftTransactor;
+impl MockNftTransactor {
+	fn key(class: &MultiLocation, instance: &AssetInstance) -> Vec<u8> {
+		(b"MockNft", class, instance).encode()
+	}
+
+	pub fn owner(class: &MultiLocation, instance: &AssetInstance) -> Option<MultiLocation> {
+		frame_support::storage::unhashed::get(&Self::key(class, instance))
+	}
+
+	pub fn mint(class: &MultiLocation, instance: &AssetInstance, owner: &MultiLocation) {
+		frame_support::storage::unhashed::put(&Self::key(class, instance), owner)
+	}
+}
+
+impl TransactAsset for MockNftTransactor {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation, _context: &XcmContext) -> XcmResult {
+		match what {
+			MultiAsset {
+				id: Concrete(class),
+				fun: NonFungible(instance),
+			} => {
+				Self::mint(class, instance, who);
+				Ok(())
+			}
+			_ => Err(XcmError::AssetNotFound),
+		}
+	}
+
+	fn withdraw_asset(
+		what: &MultiAsset,
+		who: &MultiLocation,
+		_maybe_context: Option<&XcmContext>,
+	) -> Result<Assets, XcmError> {
+		match what {
+			MultiAsset {
+				id: Concrete(class),
+				fun: NonFungible(instance),
+			} => {
+				if Self::owner(class, instance) != Some(*who) {
+					return Err(XcmError::FailedToTransactAsset("NotNftOwner"));
+				}
+				frame_support::storage::unhashed::kill(&Self::key(class, instance));
+				Ok(what.clone().into())
+			}
+			_ => Err(XcmError::AssetNotFound),
+		}
+	}
+}
+
 /// A trader who believes all tokens are created equal to "weight" of any chain,
 /// which is not true, but good enough to mock the fee payment of XCM execution.
 ///
@@ -3,7 +3,7 @@ use crate as orml_xtokens;
 
 use frame_support::{
 	construct_runtime, match_types, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU64, Everything, Get, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Contains, Everything, Get, GetDefault, Nothing},
 	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 };
 use frame_system::EnsureRoot;
@@ -18,8 +18,8 @@ use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
 use xcm::v3::{prelude::*, Weight};
 use xcm_builder::{
-	AccountId32Aliases, AllowTopLevelPaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds, NativeAsset,
-	ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
+	AccountId32Aliases, AllowKnownQueryResponses, AllowTopLevelPaidExecutionFrom, EnsureXcmOrigin, FixedWeightBounds,
+	NativeAsset, ParentIsPreset, RelayChainAsNative, SiblingParachainAsNative, SiblingParachainConvertsVia,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit,
 };
 use xcm_executor::{Config, XcmExecutor};
@@ -27,6 +27,9 @@ use xcm_executor::{Config, XcmExecutor};
 use crate::mock::AllTokensAreCreatedEqualToWeight;
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key};
 use orml_xcm_support::{IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
+use orml_xtokens::{
+	NativeAssetLocation, ReserveView, SelectableReserveProvider, SelectableSelfLocation, TrackedTransferResponseRouter,
+};
 
 pub type AccountId = AccountId32;
 
@@ -86,10 +89,22 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
+	type OnBalanceMovement = ();
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type DustHandlingMode = GetDefault;
+	type MaxDustQueueLen = ();
+	type MaxDustPerBlock = ();
+	type MaxReapPerBlock = ();
+	type MaxIssuanceSnapshots = ();
+	type MaxSnapshotCurrencies = ();
+	type AccountCreation = GetDefault;
+	type AccountCreationWhitelist = Nothing;
+	type AssetAdminOrigin = EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<50>;
 }
 
 parameter_types! {
@@ -133,7 +148,11 @@ pub type LocalAssetTransactor = MultiCurrencyAdapter<
 >;
 
 pub type XcmRouter = ParachainXcmRouter<ParachainInfo>;
-pub type Barrier = (TakeWeightCredit, AllowTopLevelPaidExecutionFrom<Everything>);
+pub type Barrier = (
+	TakeWeightCredit,
+	AllowTopLevelPaidExecutionFrom<Everything>,
+	AllowKnownQueryResponses<TrackedTransferResponseRouter<Runtime, ()>>,
+);
 
 parameter_types! {
 	pub const UnitWeightCost: Weight = Weight::from_parts(10, 10);
@@ -146,7 +165,7 @@ pub struct XcmConfig;
 impl Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
 	type XcmSender = XcmRouter;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor = (super::MockNftTransactor, LocalAssetTransactor);
 	type OriginConverter = XcmOriginToCallOrigin;
 	type IsReserve = MultiNativeAsset<AbsoluteReserveProvider>;
 	type IsTeleporter = NativeAsset;
@@ -154,7 +173,7 @@ impl Config for XcmConfig {
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type Trader = AllTokensAreCreatedEqualToWeight;
-	type ResponseHandler = ();
+	type ResponseHandler = TrackedTransferResponseRouter<Runtime, ()>;
 	type AssetTrap = PolkadotXcm;
 	type AssetClaims = PolkadotXcm;
 	type SubscriptionService = PolkadotXcm;
@@ -251,7 +270,21 @@ impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
 
 parameter_types! {
 	pub SelfLocation: MultiLocation = MultiLocation::new(1, X1(Parachain(ParachainInfo::get().into())));
+	pub const NativeCurrencyId: Option<CurrencyId> = Some(CurrencyId::N);
+	pub const NativeLocation: NativeAssetLocation = NativeAssetLocation::PalletInstance(1);
+	pub const AbsoluteView: ReserveView = ReserveView::Absolute;
 	pub const MaxAssetsForTransfer: usize = 3;
+	pub static MaxTrackedTransfers: u32 = 0;
+	pub static MaxTrackedTransfersPerAccount: u32 = 2;
+	pub static MaxXcmEncodedSize: u32 = 64 * 1024;
+	pub static BlockedOutboundDests: Vec<MultiLocation> = vec![];
+}
+
+pub struct OutboundAssetFilter;
+impl Contains<(AccountId, MultiAsset, MultiLocation)> for OutboundAssetFilter {
+	fn contains((_, _, dest): &(AccountId, MultiAsset, MultiLocation)) -> bool {
+		!BlockedOutboundDests::get().contains(dest)
+	}
 }
 
 match_types! {
@@ -282,15 +315,26 @@ impl orml_xtokens::Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type CurrencyIdConvert = CurrencyIdConvert;
 	type AccountIdToMultiLocation = AccountIdToMultiLocation;
-	type SelfLocation = SelfLocation;
+	type SelfLocation = SelectableSelfLocation<Runtime, AbsoluteView, SelfLocation>;
 	type MultiLocationsFilter = ParentOrParachains;
+	type OutboundAssetFilter = OutboundAssetFilter;
 	type MinXcmFee = ParachainMinFee;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type BaseXcmWeight = BaseXcmWeight;
 	type UniversalLocation = UniversalLocation;
 	type MaxAssetsForTransfer = MaxAssetsForTransfer;
-	type ReserveProvider = AbsoluteReserveProvider;
+	type ReserveProvider = SelectableReserveProvider<Runtime, AbsoluteView>;
+	type MaxXcmEncodedSize = MaxXcmEncodedSize;
+	type MaxTrackedTransfers = MaxTrackedTransfers;
+	type MaxTrackedTransfersPerAccount = MaxTrackedTransfersPerAccount;
+	type TransferStatusTimeout = ConstU64<10>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type NftClassId = super::NftClassId;
+	type NftInstanceId = super::NftInstanceId;
+	type NftLocationMapper = super::NftLocationMapper;
+	type NativeCurrencyId = NativeCurrencyId;
+	type NativeAssetLocation = NativeLocation;
 }
 
 impl orml_xcm::Config for Runtime {
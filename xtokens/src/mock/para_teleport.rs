@@ -3,7 +3,7 @@ use crate as orml_xtokens;
 
 use frame_support::{
 	construct_runtime, match_types, parameter_types,
-	traits::{ConstU128, ConstU32, ConstU64, Everything, Get, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Get, GetDefault, Nothing},
 	weights::constants::WEIGHT_REF_TIME_PER_SECOND,
 };
 use frame_system::EnsureRoot;
@@ -87,10 +87,22 @@ impl orml_tokens::Config for Runtime {
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
 	type CurrencyHooks = ();
+	type OnBalanceMovement = ();
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type DustHandlingMode = GetDefault;
+	type MaxDustQueueLen = ();
+	type MaxDustPerBlock = ();
+	type MaxReapPerBlock = ();
+	type MaxIssuanceSnapshots = ();
+	type MaxSnapshotCurrencies = ();
+	type AccountCreation = GetDefault;
+	type AccountCreationWhitelist = Nothing;
+	type AssetAdminOrigin = EnsureRoot<AccountId>;
+	type FreezeIdentifier = [u8; 8];
+	type MaxFreezes = ConstU32<50>;
 }
 
 parameter_types! {
@@ -275,6 +287,7 @@ impl orml_xtokens::Config for Runtime {
 	type AccountIdToMultiLocation = AccountIdToMultiLocation;
 	type SelfLocation = SelfLocation;
 	type MultiLocationsFilter = ParentOrParachains;
+	type OutboundAssetFilter = Everything;
 	type MinXcmFee = DisabledParachainFee;
 	type XcmExecutor = XcmExecutor<XcmConfig>;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
@@ -282,6 +295,16 @@ impl orml_xtokens::Config for Runtime {
 	type UniversalLocation = UniversalLocation;
 	type MaxAssetsForTransfer = MaxAssetsForTransfer;
 	type ReserveProvider = AbsoluteReserveProvider;
+	type MaxXcmEncodedSize = ConstU32<{ 64 * 1024 }>;
+	type MaxTrackedTransfers = ConstU32<0>;
+	type MaxTrackedTransfersPerAccount = ConstU32<0>;
+	type TransferStatusTimeout = ConstU64<10>;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type NftClassId = super::NftClassId;
+	type NftInstanceId = super::NftInstanceId;
+	type NftLocationMapper = super::NftLocationMapper;
+	type NativeCurrencyId = ();
+	type NativeAssetLocation = ();
 }
 
 impl orml_xcm::Config for Runtime {
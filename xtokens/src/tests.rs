@@ -1721,3 +1721,502 @@ fn send_with_insufficient_weight_limit() {
 		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 0);
 	});
 }
+
+fn transfer_a_to_bob_on_para_b() {
+	assert_ok!(ParaXTokens::transfer(
+		Some(ALICE).into(),
+		CurrencyId::A,
+		500,
+		Box::new(
+			MultiLocation::new(
+				1,
+				X2(
+					Parachain(2),
+					Junction::AccountId32 {
+						network: None,
+						id: BOB.into(),
+					}
+				)
+			)
+			.into()
+		),
+		WeightLimit::Unlimited
+	));
+}
+
+/// The query id of the `n`th tracked transfer.
+fn tracked_query_id(n: QueryId) -> QueryId {
+	TRACKED_TRANSFER_QUERY_ID_FLAG | n
+}
+
+#[test]
+fn tracked_transfer_is_reported_delivered() {
+	TestNet::reset();
+	para::MaxTrackedTransfers::set(1);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+		transfer_a_to_bob_on_para_b();
+
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::Pending)
+		);
+		assert_noop!(
+			ParaXTokens::clear_transfer_status(Some(ALICE).into(), tracked_query_id(0)),
+			Error::<para::Runtime>::TransferPending
+		);
+
+		// the limit is reached, so further transfers are not tracked
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(ParaXTokens::transfer_status(tracked_query_id(1)), None);
+	});
+
+	// the report is executed as part of the appendix, 60 is used for weight
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 440 + 460);
+	});
+
+	ParaA::execute_with(|| {
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::Delivered)
+		);
+		assert_noop!(
+			ParaXTokens::clear_transfer_status(Some(BOB).into(), tracked_query_id(0)),
+			Error::<para::Runtime>::NotTransferSender
+		);
+		assert_ok!(ParaXTokens::clear_transfer_status(
+			Some(ALICE).into(),
+			tracked_query_id(0)
+		));
+		assert_eq!(ParaXTokens::transfer_status(tracked_query_id(0)), None);
+		assert_noop!(
+			ParaXTokens::clear_transfer_status(Some(ALICE).into(), tracked_query_id(0)),
+			Error::<para::Runtime>::TransferNotFound
+		);
+	});
+
+	para::MaxTrackedTransfers::set(0);
+}
+
+#[test]
+fn tracked_transfer_query_ids_are_namespaced() {
+	assert!(is_tracked_transfer_query(tracked_query_id(0)));
+	assert!(!is_tracked_transfer_query(0));
+
+	TestNet::reset();
+	para::MaxTrackedTransfers::set(1);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+		transfer_a_to_bob_on_para_b();
+
+		let responder = MultiLocation::new(1, X1(Parachain(2)));
+		assert!(ParaXTokens::expecting_response(&responder, tracked_query_id(0), None));
+		// the query ids of other response handlers are never taken as the query id
+		// of a tracked transfer
+		assert!(!ParaXTokens::expecting_response(&responder, 0, None));
+		assert!(!TrackedTransferResponseRouter::<para::Runtime, ()>::expecting_response(
+			&responder, 0, None
+		));
+		assert!(TrackedTransferResponseRouter::<para::Runtime, ()>::expecting_response(
+			&responder,
+			tracked_query_id(0),
+			None
+		));
+
+		// the counter wraps around within the namespace
+		para::MaxTrackedTransfers::set(2);
+		NextQueryId::<para::Runtime>::put(!TRACKED_TRANSFER_QUERY_ID_FLAG);
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(ParaXTokens::transfer_status(u64::MAX), Some(TransferStatus::Pending));
+		assert_eq!(NextQueryId::<para::Runtime>::get(), 0);
+	});
+
+	para::MaxTrackedTransfers::set(0);
+}
+
+#[test]
+fn tracked_transfer_times_out() {
+	TestNet::reset();
+	para::MaxTrackedTransfers::set(1);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+		transfer_a_to_bob_on_para_b();
+
+		para::System::set_block_number(11);
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::TimedOut)
+		);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 440);
+	});
+
+	// the late report is not accepted
+	ParaA::execute_with(|| {
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::TimedOut)
+		);
+		assert_ok!(ParaXTokens::clear_transfer_status(
+			Some(ALICE).into(),
+			tracked_query_id(0)
+		));
+		assert_eq!(ParaXTokens::tracked_transfers_per_account(ALICE), 0);
+	});
+
+	para::MaxTrackedTransfers::set(0);
+}
+
+#[test]
+fn tracked_transfers_are_capped_per_account_and_expire() {
+	TestNet::reset();
+	para::MaxTrackedTransfers::set(3);
+	para::MaxTrackedTransfersPerAccount::set(1);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_500));
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::Pending)
+		);
+		assert_eq!(ParaXTokens::tracked_transfers_per_account(ALICE), 1);
+
+		// the limit of ALICE is reached, the global limit is not reached
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(ParaXTokens::transfer_status(tracked_query_id(1)), None);
+		assert_eq!(ParaXTokens::tracked_transfers_per_account(ALICE), 1);
+
+		para::System::set_block_number(11);
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::TimedOut)
+		);
+
+		// the status is kept for another timeout period
+		<ParaXTokens as frame_support::traits::Hooks<_>>::on_initialize(20);
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(0)),
+			Some(TransferStatus::TimedOut)
+		);
+		<ParaXTokens as frame_support::traits::Hooks<_>>::on_initialize(21);
+		assert_eq!(ParaXTokens::transfer_status(tracked_query_id(0)), None);
+		assert_eq!(ParaXTokens::tracked_transfers_per_account(ALICE), 0);
+		assert_noop!(
+			ParaXTokens::clear_transfer_status(Some(ALICE).into(), tracked_query_id(0)),
+			Error::<para::Runtime>::TransferNotFound
+		);
+
+		// ALICE can be tracked again
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(
+			ParaXTokens::transfer_status(tracked_query_id(1)),
+			Some(TransferStatus::Pending)
+		);
+	});
+
+	para::MaxTrackedTransfers::set(0);
+	para::MaxTrackedTransfersPerAccount::set(2);
+}
+
+#[test]
+fn destination_fee_mode_is_checked_locally() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		let para_b: MultiLocation = (Parent, Parachain(2)).into();
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+
+		assert_noop!(
+			ParaXTokens::set_destination_fee_mode(
+				Some(ALICE).into(),
+				Box::new(para_b.into()),
+				Some(FeeMode::DestinationNative)
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ParaXTokens::set_destination_fee_mode(
+			para::RuntimeOrigin::root(),
+			Box::new(para_b.into()),
+			Some(FeeMode::DestinationNative)
+		));
+		assert_eq!(
+			ParaXTokens::destination_fee_mode(para_b),
+			Some(FeeMode::DestinationNative)
+		);
+
+		// A is reserved by ParaA, not by ParaB
+		assert_noop!(
+			ParaXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::A,
+				500,
+				Box::new(
+					MultiLocation::new(
+						1,
+						X2(
+							Parachain(2),
+							Junction::AccountId32 {
+								network: None,
+								id: BOB.into(),
+							}
+						)
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::FeeNotAllowedByDestination
+		);
+
+		assert_ok!(ParaXTokens::set_destination_fee_mode(
+			para::RuntimeOrigin::root(),
+			Box::new(para_b.into()),
+			Some(FeeMode::ReserveAsset)
+		));
+		transfer_a_to_bob_on_para_b();
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 500);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 460);
+	});
+}
+
+#[test]
+fn send_nonfungible_to_sibling() {
+	TestNet::reset();
+
+	let class: MultiLocation = (Parent, Parachain(1), GeneralIndex(1)).into();
+	let instance = AssetInstance::Index(7);
+	let alice: MultiLocation = X1(Junction::AccountId32 {
+		network: None,
+		id: ALICE.into(),
+	})
+	.into();
+	let bob: MultiLocation = X1(Junction::AccountId32 {
+		network: None,
+		id: BOB.into(),
+	})
+	.into();
+	let dest: Box<VersionedMultiLocation> = Box::new(
+		MultiLocation::new(
+			1,
+			X2(
+				Parachain(2),
+				Junction::AccountId32 {
+					network: None,
+					id: BOB.into(),
+				},
+			),
+		)
+		.into(),
+	);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::A, &ALICE, 1_000));
+		MockNftTransactor::mint(&class, &instance, &alice);
+
+		assert_noop!(
+			ParaXTokens::transfer_nonfungible(
+				Some(ALICE).into(),
+				MAX_NFT_CLASS_ID,
+				7,
+				CurrencyId::A,
+				100,
+				dest.clone(),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::NotCrossChainTransferableNft
+		);
+		assert_noop!(
+			ParaXTokens::transfer_nonfungible(
+				Some(ALICE).into(),
+				1,
+				7,
+				CurrencyId::A,
+				0,
+				dest.clone(),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::ZeroFee
+		);
+
+		assert_ok!(ParaXTokens::transfer_nonfungible(
+			Some(ALICE).into(),
+			1,
+			7,
+			CurrencyId::A,
+			100,
+			dest,
+			WeightLimit::Unlimited
+		));
+
+		// the NFT is held by the sovereign account of ParaB on its reserve
+		assert_eq!(
+			MockNftTransactor::owner(&class, &instance),
+			Some(MultiLocation::new(1, X1(Parachain(2))))
+		);
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &ALICE), 900);
+	});
+
+	ParaB::execute_with(|| {
+		assert_eq!(MockNftTransactor::owner(&class, &instance), Some(bob));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::A, &BOB), 60);
+	});
+}
+
+#[test]
+fn transfer_message_over_max_encoded_size_is_rejected() {
+	TestNet::reset();
+	para::MaxXcmEncodedSize::set(16);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &ALICE, 1_000));
+		assert_noop!(
+			ParaXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::R,
+				500,
+				Box::new(
+					MultiLocation::new(
+						1,
+						X1(Junction::AccountId32 {
+							network: None,
+							id: BOB.into(),
+						})
+					)
+					.into()
+				),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::XcmMessageTooLarge
+		);
+	});
+
+	para::MaxXcmEncodedSize::set(64 * 1024);
+}
+
+#[test]
+fn transfer_rejected_by_outbound_asset_filter() {
+	TestNet::reset();
+	let blocked = MultiLocation::new(
+		1,
+		X1(Junction::AccountId32 {
+			network: None,
+			id: BOB.into(),
+		}),
+	);
+	para::BlockedOutboundDests::set(vec![blocked]);
+
+	ParaA::execute_with(|| {
+		assert_ok!(ParaTokens::deposit(CurrencyId::R, &ALICE, 1_000));
+		assert_noop!(
+			ParaXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::R,
+				500,
+				Box::new(blocked.into()),
+				WeightLimit::Unlimited
+			),
+			Error::<para::Runtime>::AssetFilteredOut
+		);
+
+		assert_ok!(ParaXTokens::transfer(
+			Some(ALICE).into(),
+			CurrencyId::R,
+			500,
+			Box::new(
+				MultiLocation::new(
+					1,
+					X1(Junction::AccountId32 {
+						network: None,
+						id: ALICE.into(),
+					})
+				)
+				.into()
+			),
+			WeightLimit::Unlimited
+		));
+		assert_eq!(ParaTokens::free_balance(CurrencyId::R, &ALICE), 500);
+	});
+
+	para::BlockedOutboundDests::set(vec![]);
+}
+
+#[test]
+fn reserve_view_can_be_switched() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		type Provider = SelectableReserveProvider<para::Runtime, para::AbsoluteView>;
+		type Location = SelectableSelfLocation<para::Runtime, para::AbsoluteView, para::SelfLocation>;
+		let self_asset: MultiAsset = (MultiLocation::new(0, X1(GeneralIndex(1))), 1).into();
+
+		// the default view is absolute
+		assert_eq!(Provider::reserve(&self_asset), None);
+		assert_eq!(Location::get(), MultiLocation::new(1, X1(Parachain(1))));
+
+		assert_noop!(
+			ParaXTokens::set_reserve_view(Some(ALICE).into(), Some(ReserveView::Relative)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ParaXTokens::set_reserve_view(
+			para::RuntimeOrigin::root(),
+			Some(ReserveView::Relative)
+		));
+		para::System::assert_last_event(para::RuntimeEvent::XTokens(Event::ReserveViewUpdated {
+			view: Some(ReserveView::Relative),
+		}));
+		assert_eq!(Provider::reserve(&self_asset), Some(MultiLocation::here()));
+		assert_eq!(Location::get(), MultiLocation::here());
+
+		assert_ok!(ParaXTokens::set_reserve_view(para::RuntimeOrigin::root(), None));
+		assert_eq!(Provider::reserve(&self_asset), None);
+	});
+}
+
+#[test]
+fn native_currency_is_located_by_native_asset_location() {
+	TestNet::reset();
+
+	ParaA::execute_with(|| {
+		assert_eq!(
+			ParaXTokens::currency_location(CurrencyId::N),
+			Some(MultiLocation::new(1, X2(Parachain(1), PalletInstance(1))))
+		);
+		assert_eq!(
+			ParaXTokens::currency_location(CurrencyId::R),
+			Some(MultiLocation::parent())
+		);
+	});
+
+	ParaD::execute_with(|| {
+		assert_eq!(
+			ParaRelativeXTokens::currency_location(CurrencyId::N),
+			Some(MultiLocation::here())
+		);
+	});
+
+	// without a native currency id the location is left to `CurrencyIdConvert`
+	ParaC::execute_with(|| {
+		type ParaTeleportXTokens = Pallet<para_teleport::Runtime>;
+		assert_eq!(ParaTeleportXTokens::currency_location(CurrencyId::N), None);
+		assert_noop!(
+			ParaTeleportXTokens::transfer(
+				Some(ALICE).into(),
+				CurrencyId::N,
+				100,
+				Box::new(MultiLocation::parent().into()),
+				WeightLimit::Unlimited,
+			),
+			Error::<para_teleport::Runtime>::NotCrossChainTransferableCurrency
+		);
+	});
+}